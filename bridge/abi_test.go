@@ -0,0 +1,142 @@
+//go:build !windows
+// +build !windows
+
+package bridge
+
+import (
+	"os"
+	"reflect"
+	"regexp"
+	"strings"
+	"testing"
+)
+
+// purego 绑定的函数类型必须与 rust/include/polars_bridge.h 中的声明一致（参数个数与各参数的宽度）
+func TestBindingsMatchHeader(t *testing.T) {
+	header, err := os.ReadFile("../rust/include/polars_bridge.h")
+	if err != nil {
+		t.Fatalf("Failed to read C header: %v", err)
+	}
+	loader, err := os.ReadFile("loader_unix.go")
+	if err != nil {
+		t.Fatalf("Failed to read loader: %v", err)
+	}
+
+	declared := headerFunctions(string(header))
+	bindings := regexp.MustCompile(`purego\.RegisterLibFunc\(&b\.(\w+), lib, prefix\+"(\w+)"\)`).
+		FindAllStringSubmatch(string(loader), -1)
+	if len(bindings) == 0 {
+		t.Fatal("No purego bindings found in loader_unix.go")
+	}
+
+	bridgeType := reflect.TypeOf(Bridge{})
+	for _, binding := range bindings {
+		field, symbol := binding[1], binding[2]
+		decl, ok := declared[symbol]
+		if !ok {
+			t.Errorf("%s is not declared in polars_bridge.h", symbol)
+			continue
+		}
+		f, ok := bridgeType.FieldByName(field)
+		if !ok || f.Type.Kind() != reflect.Func {
+			t.Errorf("Bridge.%s is not a function field", field)
+			continue
+		}
+
+		fn := f.Type
+		if fn.NumIn() != len(decl.params) {
+			t.Errorf("%s: Go binding has %d parameters, header declares %d", symbol, fn.NumIn(), len(decl.params))
+			continue
+		}
+		for i, param := range decl.params {
+			if !compatible(fn.In(i), param) {
+				t.Errorf("%s: parameter %d is %s in Go but %q in the header", symbol, i, fn.In(i), param)
+			}
+		}
+		switch {
+		case decl.ret == "void" && fn.NumOut() != 0:
+			t.Errorf("%s: header returns void but Go binding returns %d values", symbol, fn.NumOut())
+		case decl.ret != "void" && (fn.NumOut() != 1 || !compatible(fn.Out(0), decl.ret)):
+			t.Errorf("%s: header returns %q but Go binding returns %v", symbol, decl.ret, fn)
+		}
+	}
+}
+
+type cFunction struct {
+	params []string
+	ret    string
+}
+
+// 解析头文件中的 POLARS_BRIDGE_API 声明，类型去掉参数名并压缩空白
+func headerFunctions(header string) map[string]cFunction {
+	header = regexp.MustCompile(`(?s)/\*.*?\*/`).ReplaceAllString(header, "")
+	if i := strings.Index(header, `extern "C" {`); i >= 0 {
+		header = header[i:]
+	}
+
+	declRe := regexp.MustCompile(`(?s)POLARS_BRIDGE_API\s+(.*?)\b(\w+)\((.*?)\);`)
+	fnPtrRe := regexp.MustCompile(`\(\*\s*\w+\)`)
+	nameRe := regexp.MustCompile(`\w+$`)
+
+	functions := make(map[string]cFunction)
+	for _, m := range declRe.FindAllStringSubmatch(header, -1) {
+		var params []string
+		for _, param := range splitParams(m[3]) {
+			if param == "void" {
+				continue
+			}
+			if fnPtrRe.MatchString(param) {
+				param = "void (*)()" // 函数指针按指针宽度比较
+			} else {
+				param = strings.TrimSpace(nameRe.ReplaceAllString(param, ""))
+			}
+			params = append(params, strings.Join(strings.Fields(param), " "))
+		}
+		functions[m[2]] = cFunction{params: params, ret: strings.Join(strings.Fields(m[1]), " ")}
+	}
+	return functions
+}
+
+// 按不在括号内的逗号拆分参数列表
+func splitParams(list string) []string {
+	var params []string
+	depth, start := 0, 0
+	for i, c := range list {
+		switch c {
+		case '(':
+			depth++
+		case ')':
+			depth--
+		case ',':
+			if depth == 0 {
+				params = append(params, strings.TrimSpace(list[start:i]))
+				start = i + 1
+			}
+		}
+	}
+	if last := strings.TrimSpace(list[start:]); last != "" {
+		params = append(params, last)
+	}
+	return params
+}
+
+// Go 参数类型能否按 C 类型的宽度传递：指针可以用任意指针或 uintptr 传递
+func compatible(goType reflect.Type, cType string) bool {
+	if strings.Contains(cType, "*") {
+		return goType.Kind() == reflect.Ptr || goType.Kind() == reflect.Uintptr ||
+			goType.Kind() == reflect.UnsafePointer
+	}
+	switch cType {
+	case "uint8_t":
+		return goType.Kind() == reflect.Uint8
+	case "uint32_t":
+		return goType.Kind() == reflect.Uint32
+	case "uint64_t":
+		return goType.Kind() == reflect.Uint64
+	case "int":
+		return goType.Kind() == reflect.Int32
+	case "size_t":
+		return goType.Kind() == reflect.Uintptr
+	}
+	return false
+}
@@ -1,8 +1,16 @@
 package bridge
 
 import (
+	"encoding/json"
+	"fmt"
+	"net"
 	"os"
+	"path/filepath"
+	"reflect"
 	"testing"
+
+	pb "github.com/isesword/polars-go-bridge/proto"
+	"google.golang.org/protobuf/proto"
 )
 
 func TestLoadBridge(t *testing.T) {
@@ -19,8 +27,8 @@ func TestLoadBridge(t *testing.T) {
 
 	// 测试 ABI 版本
 	abiVer := brg.AbiVersion()
-	if abiVer != 1 {
-		t.Errorf("Expected ABI version 1, got %d", abiVer)
+	if abiVer != ABIVersion {
+		t.Errorf("Expected ABI version %d, got %d", ABIVersion, abiVer)
 	}
 
 	t.Logf("✅ ABI Version: %d", abiVer)
@@ -123,3 +131,388 @@ func TestInvalidPlanCompile(t *testing.T) {
 
 	t.Logf("✅ Invalid plan correctly rejected: %v", err)
 }
+
+func TestConformanceCorpus(t *testing.T) {
+	libPath := os.Getenv("POLARS_BRIDGE_LIB")
+	if libPath == "" {
+		t.Skip("POLARS_BRIDGE_LIB not set, skipping test")
+	}
+
+	// 可以用 POLARS_BRIDGE_CONFORMANCE_DIR 指向其他用例目录
+	dir := os.Getenv("POLARS_BRIDGE_CONFORMANCE_DIR")
+	if dir == "" {
+		dir = "../testdata/conformance"
+	}
+	if _, err := os.Stat(dir); os.IsNotExist(err) {
+		t.Skipf("conformance corpus %s not found, skipping test", dir)
+	}
+
+	brg, err := LoadBridge(libPath)
+	if err != nil {
+		t.Fatalf("Failed to load bridge: %v", err)
+	}
+
+	report, err := brg.RunConformance(dir)
+	if err != nil {
+		t.Fatalf("Failed to run conformance corpus: %v", err)
+	}
+	if report.Total == 0 {
+		t.Fatalf("Conformance corpus %s has no cases", dir)
+	}
+
+	for _, c := range report.FailedCases() {
+		t.Errorf("❌ %s: %s", c.Name, c.Message)
+	}
+	t.Logf("✅ Conformance: %d/%d passed", report.Passed, report.Total)
+}
+
+// dataFrameRows 把 DataFrame 句柄导出为 JSON 行（数字解码为 float64）
+func dataFrameRows(t *testing.T, brg *Bridge, handle uint64) []map[string]interface{} {
+	t.Helper()
+	output, err := brg.DataFrameToJSON(handle)
+	if err != nil {
+		t.Fatalf("Failed to export DataFrame: %v", err)
+	}
+	var rows []map[string]interface{}
+	if err := json.Unmarshal(output, &rows); err != nil {
+		t.Fatalf("Failed to decode rows: %v", err)
+	}
+	return rows
+}
+
+func compilePlan(t *testing.T, brg *Bridge, root *pb.Node) uint64 {
+	t.Helper()
+	planBytes, err := proto.Marshal(&pb.Plan{PlanVersion: 1, Root: root})
+	if err != nil {
+		t.Fatalf("Failed to marshal plan: %v", err)
+	}
+	handle, err := brg.CompilePlan(planBytes)
+	if err != nil {
+		t.Fatalf("Failed to compile plan: %v", err)
+	}
+	return handle
+}
+
+func memoryScanNode() *pb.Node {
+	return &pb.Node{Id: 1, Kind: &pb.Node_MemoryScan{MemoryScan: &pb.MemoryScan{}}}
+}
+
+func TestHandshake(t *testing.T) {
+	libPath := os.Getenv("POLARS_BRIDGE_LIB")
+	if libPath == "" {
+		t.Skip("POLARS_BRIDGE_LIB not set, skipping test")
+	}
+
+	brg, err := LoadBridge(libPath)
+	if err != nil {
+		t.Fatalf("Failed to load bridge: %v", err)
+	}
+
+	report, err := brg.Handshake(1)
+	if err != nil {
+		t.Fatalf("Handshake failed: %v", err)
+	}
+	if !report.GetCompatible() || report.GetBridgeAbiVersion() != ABIVersion || report.GetNegotiatedPlanVersion() != 1 {
+		t.Errorf("Unexpected handshake report: %v", report)
+	}
+
+	// 更新的客户端被降到 bridge 支持的最高版本
+	newer, err := brg.Handshake(report.GetMaxPlanVersion() + 1)
+	if err != nil {
+		t.Fatalf("Handshake failed: %v", err)
+	}
+	if newer.GetNegotiatedPlanVersion() != report.GetMaxPlanVersion() {
+		t.Errorf("Expected plan version %d, got %d", report.GetMaxPlanVersion(), newer.GetNegotiatedPlanVersion())
+	}
+
+	t.Logf("✅ Handshake: plan versions %d-%d", report.GetMinPlanVersion(), report.GetMaxPlanVersion())
+}
+
+func TestRetainDataFrame(t *testing.T) {
+	libPath := os.Getenv("POLARS_BRIDGE_LIB")
+	if libPath == "" {
+		t.Skip("POLARS_BRIDGE_LIB not set, skipping test")
+	}
+
+	brg, err := LoadBridge(libPath)
+	if err != nil {
+		t.Fatalf("Failed to load bridge: %v", err)
+	}
+
+	handle, err := brg.CreateDataFrameFromColumns([]byte(`[{"name": "id", "values": [1, 2]}]`))
+	if err != nil {
+		t.Fatalf("Failed to create DataFrame: %v", err)
+	}
+	if err := brg.RetainDataFrame(handle); err != nil {
+		t.Fatalf("Failed to retain DataFrame: %v", err)
+	}
+
+	// 两个引用：第一次释放后句柄仍然有效
+	brg.FreeDataFrame(handle)
+	if rows := dataFrameRows(t, brg, handle); len(rows) != 2 {
+		t.Errorf("Expected 2 rows after first free, got %v", rows)
+	}
+	brg.FreeDataFrame(handle)
+	if _, err := brg.DataFrameToJSON(handle); err == nil {
+		t.Error("Expected error after releasing every reference")
+	}
+	if err := brg.RetainDataFrame(handle); err == nil {
+		t.Error("Expected error retaining a released handle")
+	}
+
+	t.Log("✅ Retained DataFrame outlives the first free")
+}
+
+func TestDataFrameFromBuffer(t *testing.T) {
+	libPath := os.Getenv("POLARS_BRIDGE_LIB")
+	if libPath == "" {
+		t.Skip("POLARS_BRIDGE_LIB not set, skipping test")
+	}
+
+	brg, err := LoadBridge(libPath)
+	if err != nil {
+		t.Fatalf("Failed to load bridge: %v", err)
+	}
+
+	ints, err := brg.DataFrameFromInt64Buffer("n", []int64{1, -2, 3})
+	if err != nil {
+		t.Fatalf("Failed to import int64 buffer: %v", err)
+	}
+	floats, err := brg.DataFrameFromFloat64Buffer("x", []float64{0.5, 1.5})
+	if err != nil {
+		t.Fatalf("Failed to import float64 buffer: %v", err)
+	}
+	if pinnedBuffers.len() != 2 {
+		t.Errorf("Expected 2 pinned buffers, got %d", pinnedBuffers.len())
+	}
+
+	expected := []map[string]interface{}{{"n": 1.0}, {"n": -2.0}, {"n": 3.0}}
+	if rows := dataFrameRows(t, brg, ints); !reflect.DeepEqual(rows, expected) {
+		t.Errorf("Expected %v, got %v", expected, rows)
+	}
+	expected = []map[string]interface{}{{"x": 0.5}, {"x": 1.5}}
+	if rows := dataFrameRows(t, brg, floats); !reflect.DeepEqual(rows, expected) {
+		t.Errorf("Expected %v, got %v", expected, rows)
+	}
+
+	// 释放最后一个引用时 bridge 调用 dealloc 解除固定
+	brg.FreeDataFrame(ints)
+	brg.FreeDataFrame(floats)
+	if pinnedBuffers.len() != 0 {
+		t.Errorf("Expected every buffer to be released, %d still pinned", pinnedBuffers.len())
+	}
+
+	// 空缓冲区同样只回调一次
+	empty, err := brg.DataFrameFromInt64Buffer("n", nil)
+	if err != nil {
+		t.Fatalf("Failed to import empty buffer: %v", err)
+	}
+	if rows := dataFrameRows(t, brg, empty); len(rows) != 0 {
+		t.Errorf("Expected no rows, got %v", rows)
+	}
+	brg.FreeDataFrame(empty)
+	if pinnedBuffers.len() != 0 {
+		t.Errorf("Expected empty buffer to be released, %d still pinned", pinnedBuffers.len())
+	}
+
+	t.Log("✅ Zero-copy buffers are released with their DataFrames")
+}
+
+func TestSessionTables(t *testing.T) {
+	libPath := os.Getenv("POLARS_BRIDGE_LIB")
+	if libPath == "" {
+		t.Skip("POLARS_BRIDGE_LIB not set, skipping test")
+	}
+
+	brg, err := LoadBridge(libPath)
+	if err != nil {
+		t.Fatalf("Failed to load bridge: %v", err)
+	}
+
+	session, err := brg.NewSession()
+	if err != nil {
+		t.Fatalf("Failed to create session: %v", err)
+	}
+	defer brg.FreeSession(session)
+
+	plan := compilePlan(t, brg, &pb.Node{Id: 1, Kind: &pb.Node_TableScan{TableScan: &pb.TableScan{Name: "t"}}})
+	defer brg.FreePlan(plan)
+	hints, err := proto.Marshal(&pb.ExecutionHints{SessionHandle: session})
+	if err != nil {
+		t.Fatalf("Failed to marshal hints: %v", err)
+	}
+	collect := func() ([]map[string]interface{}, error) {
+		handle, err := brg.CollectPlanDFWithHints(plan, 0, hints)
+		if err != nil {
+			return nil, err
+		}
+		defer brg.FreeDataFrame(handle)
+		return dataFrameRows(t, brg, handle), nil
+	}
+
+	// DataFrame 表注册的是快照，之后释放句柄不影响会话
+	df, err := brg.CreateDataFrameFromColumns([]byte(`[{"name": "id", "values": [7, 8]}]`))
+	if err != nil {
+		t.Fatalf("Failed to create DataFrame: %v", err)
+	}
+	if err := brg.RegisterTable(session, "t", df); err != nil {
+		t.Fatalf("Failed to register table: %v", err)
+	}
+	brg.FreeDataFrame(df)
+	rows, err := collect()
+	if err != nil {
+		t.Fatalf("Failed to collect table: %v", err)
+	}
+	if expected := []map[string]interface{}{{"id": 7.0}, {"id": 8.0}}; !reflect.DeepEqual(rows, expected) {
+		t.Errorf("Expected %v, got %v", expected, rows)
+	}
+
+	// 用扫描节点替换同名表
+	node, err := proto.Marshal(&pb.Node{Id: 1, Kind: &pb.Node_CsvScan{CsvScan: &pb.CsvScan{Path: "../testdata/small.csv"}}})
+	if err != nil {
+		t.Fatalf("Failed to marshal node: %v", err)
+	}
+	if err := brg.RegisterTableNode(session, "t", node); err != nil {
+		t.Fatalf("Failed to register table node: %v", err)
+	}
+	rows, err = collect()
+	if err != nil {
+		t.Fatalf("Failed to collect table: %v", err)
+	}
+	if len(rows) != 3 || rows[2]["value"] != 300.0 {
+		t.Errorf("Unexpected rows from CSV table: %v", rows)
+	}
+
+	if err := brg.UnregisterTable(session, "t"); err != nil {
+		t.Fatalf("Failed to unregister table: %v", err)
+	}
+	if _, err := collect(); err == nil {
+		t.Error("Expected error scanning an unregistered table")
+	}
+
+	t.Log("✅ Session tables resolve, replace and unregister")
+}
+
+func TestRecorderReplay(t *testing.T) {
+	libPath := os.Getenv("POLARS_BRIDGE_LIB")
+	if libPath == "" {
+		t.Skip("POLARS_BRIDGE_LIB not set, skipping test")
+	}
+
+	brg, err := LoadBridge(libPath)
+	if err != nil {
+		t.Fatalf("Failed to load bridge: %v", err)
+	}
+
+	dir := t.TempDir()
+	options, err := proto.Marshal(&pb.RecorderOptions{Dir: dir, RecordInputs: true})
+	if err != nil {
+		t.Fatalf("Failed to marshal recorder options: %v", err)
+	}
+	if err := brg.ConfigureRecorder(options); err != nil {
+		t.Fatalf("Failed to configure recorder: %v", err)
+	}
+	// 录制是进程级的，测试结束时关闭
+	defer brg.ConfigureRecorder(nil)
+
+	input, err := brg.CreateDataFrameFromColumns([]byte(`[{"name": "id", "values": [1, 2, 3]}]`))
+	if err != nil {
+		t.Fatalf("Failed to create DataFrame: %v", err)
+	}
+	defer brg.FreeDataFrame(input)
+	plan := compilePlan(t, brg, memoryScanNode())
+	defer brg.FreePlan(plan)
+	result, err := brg.CollectPlanDF(plan, input)
+	if err != nil {
+		t.Fatalf("Failed to collect plan: %v", err)
+	}
+	expected := dataFrameRows(t, brg, result)
+	brg.FreeDataFrame(result)
+
+	if err := brg.ConfigureRecorder(nil); err != nil {
+		t.Fatalf("Failed to disable recorder: %v", err)
+	}
+	recordings, err := os.ReadDir(dir)
+	if err != nil || len(recordings) != 1 {
+		t.Fatalf("Expected one recording in %s, got %v (%v)", dir, recordings, err)
+	}
+
+	// 录制了输入，回放时不必再提供
+	replayed, err := brg.Replay(filepath.Join(dir, recordings[0].Name()), 0)
+	if err != nil {
+		t.Fatalf("Failed to replay recording: %v", err)
+	}
+	defer brg.FreeDataFrame(replayed)
+	if rows := dataFrameRows(t, brg, replayed); !reflect.DeepEqual(rows, expected) {
+		t.Errorf("Replay returned %v, expected %v", rows, expected)
+	}
+
+	t.Logf("✅ Replayed %s", recordings[0].Name())
+}
+
+func TestStream(t *testing.T) {
+	libPath := os.Getenv("POLARS_BRIDGE_LIB")
+	if libPath == "" {
+		t.Skip("POLARS_BRIDGE_LIB not set, skipping test")
+	}
+
+	brg, err := LoadBridge(libPath)
+	if err != nil {
+		t.Fatalf("Failed to load bridge: %v", err)
+	}
+
+	// NDJSON 数据源：写完 5 行后关闭连接，表示流结束
+	listener, err := net.Listen("tcp", "127.0.0.1:0")
+	if err != nil {
+		t.Fatalf("Failed to listen: %v", err)
+	}
+	defer listener.Close()
+	go func() {
+		conn, err := listener.Accept()
+		if err != nil {
+			return
+		}
+		defer conn.Close()
+		for i := 1; i <= 5; i++ {
+			fmt.Fprintf(conn, "{\"id\": %d}\n", i)
+		}
+	}()
+
+	plan := compilePlan(t, brg, memoryScanNode())
+	defer brg.FreePlan(plan)
+	options, err := proto.Marshal(&pb.StreamOptions{
+		Source:    &pb.StreamOptions_NdjsonAddr{NdjsonAddr: listener.Addr().String()},
+		Schema:    &pb.Schema{Fields: []*pb.Field{{Name: "id", DataType: pb.DataType_INT64}}},
+		BatchRows: proto.Uint32(2),
+	})
+	if err != nil {
+		t.Fatalf("Failed to marshal stream options: %v", err)
+	}
+
+	var batches []uint64
+	stream, err := brg.NewStream(plan, options, func(batchIndex uint64, schema *ArrowSchema, array *ArrowArray) error {
+		batches = append(batches, batchIndex)
+		return nil
+	})
+	if err != nil {
+		t.Fatalf("Failed to create stream: %v", err)
+	}
+
+	stats, err := brg.RunStream(stream, 0)
+	if err != nil {
+		t.Fatalf("Failed to run stream: %v", err)
+	}
+	if !stats.GetFinished() || stats.GetBatches() != 3 || stats.GetRowsIn() != 5 || stats.GetRowsOut() != 5 {
+		t.Errorf("Unexpected stream stats: %v", stats)
+	}
+	if expected := []uint64{0, 1, 2}; !reflect.DeepEqual(batches, expected) {
+		t.Errorf("Expected callbacks for batches %v, got %v", expected, batches)
+	}
+
+	brg.FreeStream(stream)
+	if streamCallbacks.len() != 0 {
+		t.Errorf("Expected stream callback to be released, %d registered", streamCallbacks.len())
+	}
+
+	t.Logf("✅ Stream: %d batches, %d rows", stats.GetBatches(), stats.GetRowsOut())
+}
@@ -0,0 +1,96 @@
+package bridge
+
+import (
+	"runtime"
+	"sync"
+	"unsafe"
+)
+
+// StreamCallback 接收微批流每一批的结果（Arrow C Data Interface）
+//
+// 数组在回调返回后由 bridge 释放；需要保留数据时在回调内按 move 语义导入（如 cdata.ImportCRecordBatch）。
+// 返回 error 会停止流，bridge_stream_run 随之报错。
+type StreamCallback func(batchIndex uint64, schema *ArrowSchema, array *ArrowArray) error
+
+// callbackRegistry 保存回调关联的 Go 对象：传给 bridge 的 ctx 是注册表中的编号而不是 Go 指针，
+// bridge 可以在调用返回后继续持有它
+type callbackRegistry[T any] struct {
+	mu      sync.Mutex
+	next    uintptr
+	entries map[uintptr]T
+}
+
+func (r *callbackRegistry[T]) add(value T) uintptr {
+	r.mu.Lock()
+	defer r.mu.Unlock()
+	if r.entries == nil {
+		r.entries = make(map[uintptr]T)
+	}
+	r.next++
+	r.entries[r.next] = value
+	return r.next
+}
+
+func (r *callbackRegistry[T]) get(id uintptr) (T, bool) {
+	r.mu.Lock()
+	defer r.mu.Unlock()
+	value, ok := r.entries[id]
+	return value, ok
+}
+
+func (r *callbackRegistry[T]) remove(id uintptr) (T, bool) {
+	r.mu.Lock()
+	defer r.mu.Unlock()
+	value, ok := r.entries[id]
+	delete(r.entries, id)
+	return value, ok
+}
+
+func (r *callbackRegistry[T]) len() int {
+	r.mu.Lock()
+	defer r.mu.Unlock()
+	return len(r.entries)
+}
+
+var (
+	// 被 bridge 直接引用的 Go 缓冲区（bridge_series_from_buffer），dealloc 回调时解除固定
+	pinnedBuffers callbackRegistry[*runtime.Pinner]
+
+	streamCallbacks callbackRegistry[StreamCallback]
+
+	// 流句柄 -> streamCallbacks 中的编号，FreeStream 时注销回调
+	streamContexts sync.Map
+)
+
+// pinBuffer 固定缓冲区的首元素，直到 bridge 通过 dealloc 回调释放它（空缓冲区不需要固定）
+func pinBuffer(data unsafe.Pointer) uintptr {
+	pinner := &runtime.Pinner{}
+	if data != nil {
+		pinner.Pin(data)
+	}
+	return pinnedBuffers.add(pinner)
+}
+
+// releaseBuffer 是 dealloc 回调的实现：可能在 bridge 的任意线程上调用
+func releaseBuffer(ctx uintptr) {
+	if pinner, ok := pinnedBuffers.remove(ctx); ok {
+		pinner.Unpin()
+	}
+}
+
+func dispatchStreamBatch(ctx uintptr, batchIndex uint64, schema *ArrowSchema, array *ArrowArray) int32 {
+	callback, ok := streamCallbacks.get(ctx)
+	if !ok {
+		return 1
+	}
+	if err := callback(batchIndex, schema, array); err != nil {
+		return 1
+	}
+	return 0
+}
+
+func forgetStream(streamHandle uint64) {
+	if ctx, ok := streamContexts.LoadAndDelete(streamHandle); ok {
+		streamCallbacks.remove(ctx.(uintptr))
+	}
+}
@@ -0,0 +1,41 @@
+package bridge
+
+import (
+	"encoding/json"
+	"fmt"
+)
+
+// ConformanceReport golden Plan 用例的运行报告（bridge_conformance_run 输出）
+type ConformanceReport struct {
+	Total  int               `json:"total"`
+	Passed int               `json:"passed"`
+	Failed int               `json:"failed"`
+	Cases  []ConformanceCase `json:"cases"`
+}
+
+// ConformanceCase 单个用例的结果
+type ConformanceCase struct {
+	Name       string `json:"name"`
+	Passed     bool   `json:"passed"`
+	Message    string `json:"message"` // 失败原因（通过时为空）
+	DurationUs uint64 `json:"duration_us"`
+}
+
+// FailedCases 返回失败的用例
+func (r *ConformanceReport) FailedCases() []ConformanceCase {
+	var failed []ConformanceCase
+	for _, c := range r.Cases {
+		if !c.Passed {
+			failed = append(failed, c)
+		}
+	}
+	return failed
+}
+
+func parseConformanceReport(data []byte) (*ConformanceReport, error) {
+	var report ConformanceReport
+	if err := json.Unmarshal(data, &report); err != nil {
+		return nil, fmt.Errorf("failed to decode conformance report: %w", err)
+	}
+	return &report, nil
+}
@@ -8,34 +8,70 @@ import (
 	"os"
 	"path/filepath"
 	"runtime"
+	"sync"
 	"unsafe"
 
 	"github.com/ebitengine/purego"
+	pb "github.com/isesword/polars-go-bridge/proto"
+	"google.golang.org/protobuf/proto"
 )
 
 // Bridge Rust FFI 接口
 type Bridge struct {
-	lib               uintptr
-	abiVersion        func() uint32
-	engineVersion     func(*uintptr, *uintptr) int32
-	capabilities      func(*uintptr, *uintptr) int32
-	lastError         func(*uintptr, *uintptr) int32
-	lastErrorFree     func(uintptr, uintptr)
-	planCompile       func(*byte, uintptr, *uint64) int32
-	planFree          func(uint64)
-	planExecuteSimple func(uint64, *byte, uintptr, *uintptr, *uintptr) int32
-	planExecutePrint  func(uint64) int32
-	planExecuteArrow  func(uint64, *ArrowSchema, *ArrowArray, *ArrowSchema, *ArrowArray) int32
-	planCollectDF     func(uint64, uint64, *uint64) int32
-	dfToIPC           func(uint64, *uintptr, *uintptr) int32
-	dfPrint           func(uint64) int32
-	dfFree            func(uint64)
-	dfFromColumns     func(*byte, uintptr, *uint64) int32
-	outputFree        func(uintptr, uintptr)
+	lib                    uintptr
+	abiVersion             func() uint32
+	handshake              func(uint32, uint32, *uintptr, *uintptr) int32
+	engineVersion          func(*uintptr, *uintptr) int32
+	capabilities           func(*uintptr, *uintptr) int32
+	lastError              func(*uintptr, *uintptr) int32
+	lastErrorFree          func(uintptr, uintptr)
+	planCompile            func(*byte, uintptr, *uint64) int32
+	planFree               func(uint64)
+	planExecuteSimple      func(uint64, *byte, uintptr, *uintptr, *uintptr) int32
+	planExecutePrint       func(uint64) int32
+	planExecuteArrow       func(uint64, *ArrowSchema, *ArrowArray, *ArrowSchema, *ArrowArray) int32
+	planCollectDF          func(uint64, uint64, *uint64) int32
+	planCollectDFWithHints func(uint64, uint64, *byte, uintptr, *uint64) int32
+	dfToIPC                func(uint64, *uintptr, *uintptr) int32
+	dfToJSON               func(uint64, *byte, uintptr, *uintptr, *uintptr) int32
+	dfPrint                func(uint64) int32
+	dfFree                 func(uint64)
+	dfRetain               func(uint64) int32
+	dfFromColumns          func(*byte, uintptr, *uint64) int32
+	dfWithColumns          func(uint64, *byte, uintptr, *uint64) int32
+	dfRenameColumns        func(uint64, *byte, uintptr, *uint64) int32
+	seriesFromBuffer       func(*byte, uintptr, int32, unsafe.Pointer, uintptr, uintptr, uintptr, *uint64) int32
+	sessionNew             func(*uint64) int32
+	tableRegister          func(uint64, *byte, uintptr, uint64, *byte, uintptr) int32
+	tableUnregister        func(uint64, *byte, uintptr) int32
+	sessionFree            func(uint64)
+	recorderConfigure      func(*byte, uintptr) int32
+	replay                 func(*byte, uintptr, uint64, *uint64) int32
+	streamNew              func(uint64, *byte, uintptr, uintptr, uintptr, *uint64) int32
+	streamRun              func(uint64, uint64, *uintptr, *uintptr) int32
+	streamFree             func(uint64)
+	conformanceRun         func(*byte, uintptr, *uintptr, *uintptr) int32
+	outputFree             func(uintptr, uintptr)
 }
 
+// purego 回调不会被回收，每个进程只创建一次
+var (
+	deallocCallbackPtr = sync.OnceValue(func() uintptr {
+		return purego.NewCallback(func(ctx uintptr) { releaseBuffer(ctx) })
+	})
+	streamCallbackPtr = sync.OnceValue(func() uintptr {
+		return purego.NewCallback(dispatchStreamBatch)
+	})
+)
+
 // LoadBridge 加载动态库
 func LoadBridge(libPath string) (*Bridge, error) {
+	return LoadBridgeWithPrefix(libPath, "")
+}
+
+// LoadBridgeWithPrefix 加载以 versioned-symbols 构建的动态库（导出符号带前缀，如 "pb1_"），
+// 不同前缀的 bridge 可以同时加载进同一个进程
+func LoadBridgeWithPrefix(libPath string, prefix string) (*Bridge, error) {
 	if libPath == "" {
 		// 优先级：环境变量 > 可执行文件目录
 		libPath = os.Getenv("POLARS_BRIDGE_LIB")
@@ -60,30 +96,46 @@ func LoadBridge(libPath string) (*Bridge, error) {
 
 	b := &Bridge{lib: lib}
 
-	// 加载所有函数
-	purego.RegisterLibFunc(&b.abiVersion, lib, "bridge_abi_version")
-	purego.RegisterLibFunc(&b.engineVersion, lib, "bridge_engine_version")
-	purego.RegisterLibFunc(&b.capabilities, lib, "bridge_capabilities")
-	purego.RegisterLibFunc(&b.lastError, lib, "bridge_last_error")
-	purego.RegisterLibFunc(&b.lastErrorFree, lib, "bridge_last_error_free")
-	purego.RegisterLibFunc(&b.planCompile, lib, "bridge_plan_compile")
-	purego.RegisterLibFunc(&b.planFree, lib, "bridge_plan_free")
-	purego.RegisterLibFunc(&b.planExecuteSimple, lib, "bridge_plan_execute_simple")
-	purego.RegisterLibFunc(&b.planExecutePrint, lib, "bridge_plan_execute_and_print")
-	purego.RegisterLibFunc(&b.planExecuteArrow, lib, "bridge_plan_execute_arrow")
-	purego.RegisterLibFunc(&b.planCollectDF, lib, "bridge_plan_collect_df")
-	purego.RegisterLibFunc(&b.dfToIPC, lib, "bridge_df_to_ipc")
-	purego.RegisterLibFunc(&b.dfPrint, lib, "bridge_df_print")
-	purego.RegisterLibFunc(&b.dfFree, lib, "bridge_df_free")
-	purego.RegisterLibFunc(&b.dfFromColumns, lib, "bridge_df_from_columns")
-	purego.RegisterLibFunc(&b.outputFree, lib, "bridge_output_free")
-
-	// 验证 ABI 版本
-	abiVer := b.AbiVersion()
-	if abiVer != 1 {
-		return nil, fmt.Errorf("ABI version mismatch: expected 1, got %d", abiVer)
+	// 先验证 ABI 版本：错误消息的释放方式取决于版本，旧版 bridge 也可能缺少后面的符号
+	purego.RegisterLibFunc(&b.abiVersion, lib, prefix+"bridge_abi_version")
+	if abiVer := b.AbiVersion(); abiVer != ABIVersion {
+		return nil, fmt.Errorf("ABI version mismatch: expected %d, got %d", ABIVersion, abiVer)
 	}
 
+	// 加载其余函数
+	purego.RegisterLibFunc(&b.handshake, lib, prefix+"bridge_handshake")
+	purego.RegisterLibFunc(&b.engineVersion, lib, prefix+"bridge_engine_version")
+	purego.RegisterLibFunc(&b.capabilities, lib, prefix+"bridge_capabilities")
+	purego.RegisterLibFunc(&b.lastError, lib, prefix+"bridge_last_error")
+	purego.RegisterLibFunc(&b.lastErrorFree, lib, prefix+"bridge_last_error_free")
+	purego.RegisterLibFunc(&b.planCompile, lib, prefix+"bridge_plan_compile")
+	purego.RegisterLibFunc(&b.planFree, lib, prefix+"bridge_plan_free")
+	purego.RegisterLibFunc(&b.planExecuteSimple, lib, prefix+"bridge_plan_execute_simple")
+	purego.RegisterLibFunc(&b.planExecutePrint, lib, prefix+"bridge_plan_execute_and_print")
+	purego.RegisterLibFunc(&b.planExecuteArrow, lib, prefix+"bridge_plan_execute_arrow")
+	purego.RegisterLibFunc(&b.planCollectDF, lib, prefix+"bridge_plan_collect_df")
+	purego.RegisterLibFunc(&b.planCollectDFWithHints, lib, prefix+"bridge_plan_collect_df_with_hints")
+	purego.RegisterLibFunc(&b.dfToIPC, lib, prefix+"bridge_df_to_ipc")
+	purego.RegisterLibFunc(&b.dfToJSON, lib, prefix+"bridge_df_to_json")
+	purego.RegisterLibFunc(&b.dfPrint, lib, prefix+"bridge_df_print")
+	purego.RegisterLibFunc(&b.dfFree, lib, prefix+"bridge_df_free")
+	purego.RegisterLibFunc(&b.dfRetain, lib, prefix+"bridge_df_retain")
+	purego.RegisterLibFunc(&b.dfFromColumns, lib, prefix+"bridge_df_from_columns")
+	purego.RegisterLibFunc(&b.dfWithColumns, lib, prefix+"bridge_df_with_columns")
+	purego.RegisterLibFunc(&b.dfRenameColumns, lib, prefix+"bridge_df_rename_columns")
+	purego.RegisterLibFunc(&b.seriesFromBuffer, lib, prefix+"bridge_series_from_buffer")
+	purego.RegisterLibFunc(&b.sessionNew, lib, prefix+"bridge_session_new")
+	purego.RegisterLibFunc(&b.tableRegister, lib, prefix+"bridge_table_register")
+	purego.RegisterLibFunc(&b.tableUnregister, lib, prefix+"bridge_table_unregister")
+	purego.RegisterLibFunc(&b.sessionFree, lib, prefix+"bridge_session_free")
+	purego.RegisterLibFunc(&b.recorderConfigure, lib, prefix+"bridge_recorder_configure")
+	purego.RegisterLibFunc(&b.replay, lib, prefix+"bridge_replay")
+	purego.RegisterLibFunc(&b.streamNew, lib, prefix+"bridge_stream_new")
+	purego.RegisterLibFunc(&b.streamRun, lib, prefix+"bridge_stream_run")
+	purego.RegisterLibFunc(&b.streamFree, lib, prefix+"bridge_stream_free")
+	purego.RegisterLibFunc(&b.conformanceRun, lib, prefix+"bridge_conformance_run")
+	purego.RegisterLibFunc(&b.outputFree, lib, prefix+"bridge_output_free")
+
 	return b, nil
 }
 
@@ -103,6 +155,25 @@ func (b *Bridge) AbiVersion() uint32 {
 	return b.abiVersion()
 }
 
+// Handshake 与 bridge 协商协议版本（客户端 ABI 取 ABIVersion），返回 HandshakeReport
+func (b *Bridge) Handshake(clientPlanVersion uint32) (*pb.HandshakeReport, error) {
+	var outputPtr uintptr
+	var outputLen uintptr
+	ret := b.handshake(ABIVersion, clientPlanVersion, &outputPtr, &outputLen)
+	if ret != 0 {
+		return nil, b.getLastError()
+	}
+
+	output := []byte(copyBridgeString(outputPtr, int(outputLen)))
+	b.outputFree(outputPtr, outputLen)
+
+	report := &pb.HandshakeReport{}
+	if err := proto.Unmarshal(output, report); err != nil {
+		return nil, fmt.Errorf("failed to decode handshake report: %w", err)
+	}
+	return report, nil
+}
+
 // EngineVersion 获取引擎版本
 func (b *Bridge) EngineVersion() (string, error) {
 	var ptr uintptr
@@ -222,6 +293,23 @@ func (b *Bridge) CollectPlanDF(planHandle uint64, inputDFHandle uint64) (uint64,
 	return dfHandle, nil
 }
 
+// CollectPlanDFWithHints 带执行提示执行计划并返回 DataFrame 句柄，hintsBytes 为序列化的 ExecutionHints（可为空）
+func (b *Bridge) CollectPlanDFWithHints(planHandle uint64, inputDFHandle uint64, hintsBytes []byte) (uint64, error) {
+	var hintsPtr *byte
+	if len(hintsBytes) > 0 {
+		hintsPtr = &hintsBytes[0]
+	}
+
+	var dfHandle uint64
+	ret := b.planCollectDFWithHints(planHandle, inputDFHandle, hintsPtr, uintptr(len(hintsBytes)), &dfHandle)
+	runtime.KeepAlive(hintsBytes)
+
+	if ret != 0 {
+		return 0, b.getLastError()
+	}
+	return dfHandle, nil
+}
+
 // CreateDataFrameFromColumns 从 JSON 格式的列数据创建 DataFrame
 // jsonData 格式: [{"name": "col1", "values": [1, 2, 3]}, {"name": "col2", "values": ["a", "b", "c"]}]
 func (b *Bridge) CreateDataFrameFromColumns(jsonData []byte) (uint64, error) {
@@ -240,6 +328,83 @@ func (b *Bridge) CreateDataFrameFromColumns(jsonData []byte) (uint64, error) {
 	return dfHandle, nil
 }
 
+// DataFrameFromInt64Buffer 直接引用 data 创建单列 DataFrame（不复制数据）
+//
+// data 在 DataFrame 及其派生结果全部释放前保持固定，调用方不能再修改它
+func (b *Bridge) DataFrameFromInt64Buffer(name string, data []int64) (uint64, error) {
+	return b.dataFrameFromBuffer(name, pb.DataType_INT64, unsafe.Pointer(unsafe.SliceData(data)), len(data))
+}
+
+// DataFrameFromFloat64Buffer 直接引用 data 创建单列 DataFrame（不复制数据），约束同 DataFrameFromInt64Buffer
+func (b *Bridge) DataFrameFromFloat64Buffer(name string, data []float64) (uint64, error) {
+	return b.dataFrameFromBuffer(name, pb.DataType_FLOAT64, unsafe.Pointer(unsafe.SliceData(data)), len(data))
+}
+
+func (b *Bridge) dataFrameFromBuffer(name string, dataType pb.DataType, data unsafe.Pointer, length int) (uint64, error) {
+	nameBytes := []byte(name)
+	var namePtr *byte
+	if len(nameBytes) > 0 {
+		namePtr = &nameBytes[0]
+	}
+
+	if length == 0 {
+		data = nil
+	}
+
+	// bridge 无论成功与否都会调用一次 dealloc，解除固定
+	ctx := pinBuffer(data)
+	var dfHandle uint64
+	ret := b.seriesFromBuffer(
+		namePtr,
+		uintptr(len(nameBytes)),
+		int32(dataType),
+		data,
+		uintptr(length),
+		deallocCallbackPtr(),
+		ctx,
+		&dfHandle,
+	)
+	runtime.KeepAlive(nameBytes)
+
+	if ret != 0 {
+		return 0, b.getLastError()
+	}
+	return dfHandle, nil
+}
+
+// DataFrameWithColumns 在 DataFrame 上添加 / 替换列，exprsBytes 为序列化的 ExprList，返回新的 DataFrame 句柄
+func (b *Bridge) DataFrameWithColumns(handle uint64, exprsBytes []byte) (uint64, error) {
+	if len(exprsBytes) == 0 {
+		return 0, fmt.Errorf("exprsBytes is empty")
+	}
+
+	var dfHandle uint64
+	ret := b.dfWithColumns(handle, &exprsBytes[0], uintptr(len(exprsBytes)), &dfHandle)
+	runtime.KeepAlive(exprsBytes)
+
+	if ret != 0 {
+		return 0, b.getLastError()
+	}
+	return dfHandle, nil
+}
+
+// DataFrameRenameColumns 批量改名，optionsBytes 为序列化的 RenameOptions，返回新的 DataFrame 句柄
+func (b *Bridge) DataFrameRenameColumns(handle uint64, optionsBytes []byte) (uint64, error) {
+	var optionsPtr *byte
+	if len(optionsBytes) > 0 {
+		optionsPtr = &optionsBytes[0]
+	}
+
+	var dfHandle uint64
+	ret := b.dfRenameColumns(handle, optionsPtr, uintptr(len(optionsBytes)), &dfHandle)
+	runtime.KeepAlive(optionsBytes)
+
+	if ret != 0 {
+		return 0, b.getLastError()
+	}
+	return dfHandle, nil
+}
+
 // DataFrameToIPC 将 DataFrame 导出为 Arrow IPC 二进制数据
 func (b *Bridge) DataFrameToIPC(handle uint64) ([]byte, error) {
 	var outputPtr uintptr
@@ -259,6 +424,22 @@ func (b *Bridge) DataFrameToIPC(handle uint64) ([]byte, error) {
 	return output, nil
 }
 
+// DataFrameToJSON 将 DataFrame 导出为 JSON 行数组（时间类型使用默认的字符串格式）
+func (b *Bridge) DataFrameToJSON(handle uint64) ([]byte, error) {
+	var outputPtr uintptr
+	var outputLen uintptr
+
+	ret := b.dfToJSON(handle, nil, 0, &outputPtr, &outputLen)
+	if ret != 0 {
+		return nil, b.getLastError()
+	}
+
+	output := []byte(copyBridgeString(outputPtr, int(outputLen)))
+	b.outputFree(outputPtr, outputLen)
+
+	return output, nil
+}
+
 // DataFramePrint 打印 DataFrame（使用 Polars 原生 Display）
 func (b *Bridge) DataFramePrint(handle uint64) error {
 	ret := b.dfPrint(handle)
@@ -273,6 +454,181 @@ func (b *Bridge) FreeDataFrame(handle uint64) {
 	b.dfFree(handle)
 }
 
+// RetainDataFrame 为 DataFrame 句柄增加一个引用：每次 retain 都需要对应一次 FreeDataFrame
+func (b *Bridge) RetainDataFrame(handle uint64) error {
+	ret := b.dfRetain(handle)
+	if ret != 0 {
+		return b.getLastError()
+	}
+	return nil
+}
+
+// NewSession 创建表注册会话（Plan 中的 TableScan 经 ExecutionHints.session_handle 解析）
+func (b *Bridge) NewSession() (uint64, error) {
+	var sessionHandle uint64
+	ret := b.sessionNew(&sessionHandle)
+	if ret != 0 {
+		return 0, b.getLastError()
+	}
+	return sessionHandle, nil
+}
+
+// RegisterTable 把 DataFrame 的快照注册（或替换）为逻辑表，之后释放 dfHandle 不影响会话
+func (b *Bridge) RegisterTable(sessionHandle uint64, name string, dfHandle uint64) error {
+	return b.registerTable(sessionHandle, name, dfHandle, nil)
+}
+
+// RegisterTableNode 把 Node（序列化的 Node protobuf，通常是扫描节点）注册（或替换）为逻辑表，执行时才构建
+func (b *Bridge) RegisterTableNode(sessionHandle uint64, name string, nodeBytes []byte) error {
+	if len(nodeBytes) == 0 {
+		return fmt.Errorf("nodeBytes is empty")
+	}
+	return b.registerTable(sessionHandle, name, 0, nodeBytes)
+}
+
+func (b *Bridge) registerTable(sessionHandle uint64, name string, dfHandle uint64, nodeBytes []byte) error {
+	nameBytes := []byte(name)
+	var namePtr, nodePtr *byte
+	if len(nameBytes) > 0 {
+		namePtr = &nameBytes[0]
+	}
+	if len(nodeBytes) > 0 {
+		nodePtr = &nodeBytes[0]
+	}
+
+	ret := b.tableRegister(sessionHandle, namePtr, uintptr(len(nameBytes)), dfHandle, nodePtr, uintptr(len(nodeBytes)))
+	runtime.KeepAlive(nameBytes)
+	runtime.KeepAlive(nodeBytes)
+
+	if ret != 0 {
+		return b.getLastError()
+	}
+	return nil
+}
+
+// UnregisterTable 注销一张表（不存在时为空操作）
+func (b *Bridge) UnregisterTable(sessionHandle uint64, name string) error {
+	nameBytes := []byte(name)
+	var namePtr *byte
+	if len(nameBytes) > 0 {
+		namePtr = &nameBytes[0]
+	}
+
+	ret := b.tableUnregister(sessionHandle, namePtr, uintptr(len(nameBytes)))
+	runtime.KeepAlive(nameBytes)
+
+	if ret != 0 {
+		return b.getLastError()
+	}
+	return nil
+}
+
+// FreeSession 释放会话（正在执行的 Plan 持有自己的引用，不受影响）
+func (b *Bridge) FreeSession(sessionHandle uint64) {
+	b.sessionFree(sessionHandle)
+}
+
+// ConfigureRecorder 开启或关闭查询录制，optionsBytes 为序列化的 RecorderOptions（dir 为空表示关闭）
+func (b *Bridge) ConfigureRecorder(optionsBytes []byte) error {
+	var optionsPtr *byte
+	if len(optionsBytes) > 0 {
+		optionsPtr = &optionsBytes[0]
+	}
+
+	ret := b.recorderConfigure(optionsPtr, uintptr(len(optionsBytes)))
+	runtime.KeepAlive(optionsBytes)
+
+	if ret != 0 {
+		return b.getLastError()
+	}
+	return nil
+}
+
+// Replay 回放一次录制（dir 为单次录制的目录），返回结果 DataFrame 句柄；inputDFHandle 非 0 时代替录制的输入
+func (b *Bridge) Replay(dir string, inputDFHandle uint64) (uint64, error) {
+	if dir == "" {
+		return 0, fmt.Errorf("recording directory is empty")
+	}
+	dirBytes := []byte(dir)
+
+	var dfHandle uint64
+	ret := b.replay(&dirBytes[0], uintptr(len(dirBytes)), inputDFHandle, &dfHandle)
+	runtime.KeepAlive(dirBytes)
+
+	if ret != 0 {
+		return 0, b.getLastError()
+	}
+	return dfHandle, nil
+}
+
+// NewStream 创建微批流，optionsBytes 为序列化的 StreamOptions；每批的结果同步推送给 callback（在 RunStream 中调用）
+func (b *Bridge) NewStream(planHandle uint64, optionsBytes []byte, callback StreamCallback) (uint64, error) {
+	if callback == nil {
+		return 0, fmt.Errorf("stream callback is nil")
+	}
+	var optionsPtr *byte
+	if len(optionsBytes) > 0 {
+		optionsPtr = &optionsBytes[0]
+	}
+
+	ctx := streamCallbacks.add(callback)
+	var streamHandle uint64
+	ret := b.streamNew(planHandle, optionsPtr, uintptr(len(optionsBytes)), streamCallbackPtr(), ctx, &streamHandle)
+	runtime.KeepAlive(optionsBytes)
+
+	if ret != 0 {
+		streamCallbacks.remove(ctx)
+		return 0, b.getLastError()
+	}
+	streamContexts.Store(streamHandle, ctx)
+	return streamHandle, nil
+}
+
+// RunStream 处理至多 maxBatches 个微批（0 表示直到数据源结束），返回累计的 StreamStats
+func (b *Bridge) RunStream(streamHandle uint64, maxBatches uint64) (*pb.StreamStats, error) {
+	var outputPtr uintptr
+	var outputLen uintptr
+	ret := b.streamRun(streamHandle, maxBatches, &outputPtr, &outputLen)
+	if ret != 0 {
+		return nil, b.getLastError()
+	}
+
+	output := []byte(copyBridgeString(outputPtr, int(outputLen)))
+	b.outputFree(outputPtr, outputLen)
+
+	stats := &pb.StreamStats{}
+	if err := proto.Unmarshal(output, stats); err != nil {
+		return nil, fmt.Errorf("failed to decode stream stats: %w", err)
+	}
+	return stats, nil
+}
+
+// FreeStream 释放微批流并注销其回调
+func (b *Bridge) FreeStream(streamHandle uint64) {
+	b.streamFree(streamHandle)
+	forgetStream(streamHandle)
+}
+
+// RunConformance 运行 golden Plan 用例目录（格式见 testdata/conformance/README.md），返回通过/失败报告
+func (b *Bridge) RunConformance(dir string) (*ConformanceReport, error) {
+	if dir == "" {
+		return nil, fmt.Errorf("conformance corpus directory is empty")
+	}
+	dirBytes := []byte(dir)
+	var outputPtr uintptr
+	var outputLen uintptr
+	ret := b.conformanceRun(&dirBytes[0], uintptr(len(dirBytes)), &outputPtr, &outputLen)
+	runtime.KeepAlive(dirBytes)
+
+	if ret != 0 {
+		return nil, b.getLastError()
+	}
+
+	output := []byte(ptrToString(outputPtr, int(outputLen)))
+	b.outputFree(outputPtr, outputLen)
+	return parseConformanceReport(output)
+}
+
 func (b *Bridge) getLastError() error {
 	var ptr uintptr
 	var length uintptr
@@ -282,7 +638,9 @@ func (b *Bridge) getLastError() error {
 		return fmt.Errorf("unknown error")
 	}
 
-	errMsg := ptrToString(ptr, int(length))
+	// 消息在 bridge 分配的缓冲区中，先复制再释放
+	errMsg := copyBridgeString(ptr, int(length))
+	b.lastErrorFree(ptr, length)
 	return fmt.Errorf("%s", errMsg)
 }
 
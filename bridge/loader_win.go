@@ -8,33 +8,80 @@ import (
 	"os"
 	"path/filepath"
 	"runtime"
+	"sync"
 	"syscall"
 	"unsafe"
+
+	pb "github.com/isesword/polars-go-bridge/proto"
+	"google.golang.org/protobuf/proto"
 )
 
 // Bridge Rust FFI 接口
 type Bridge struct {
-	lib               *syscall.DLL
-	abiVersion        *syscall.Proc
-	engineVersion     *syscall.Proc
-	capabilities      *syscall.Proc
-	lastError         *syscall.Proc
-	lastErrorFree     *syscall.Proc
-	planCompile       *syscall.Proc
-	planFree          *syscall.Proc
-	planExecuteSimple *syscall.Proc
-	planExecutePrint  *syscall.Proc
-	planExecuteArrow  *syscall.Proc
-	planCollectDF     *syscall.Proc
-	dfToIPC           *syscall.Proc
-	dfPrint           *syscall.Proc
-	dfFree            *syscall.Proc
-	dfFromColumns     *syscall.Proc
-	outputFree        *syscall.Proc
+	lib                    *syscall.DLL
+	abiVersion             *syscall.Proc
+	handshake              *syscall.Proc
+	engineVersion          *syscall.Proc
+	capabilities           *syscall.Proc
+	lastError              *syscall.Proc
+	lastErrorFree          *syscall.Proc
+	planCompile            *syscall.Proc
+	planFree               *syscall.Proc
+	planExecuteSimple      *syscall.Proc
+	planExecutePrint       *syscall.Proc
+	planExecuteArrow       *syscall.Proc
+	planCollectDF          *syscall.Proc
+	planCollectDFWithHints *syscall.Proc
+	dfToIPC                *syscall.Proc
+	dfToJSON               *syscall.Proc
+	dfPrint                *syscall.Proc
+	dfFree                 *syscall.Proc
+	dfRetain               *syscall.Proc
+	dfFromColumns          *syscall.Proc
+	dfWithColumns          *syscall.Proc
+	dfRenameColumns        *syscall.Proc
+	seriesFromBuffer       *syscall.Proc
+	sessionNew             *syscall.Proc
+	tableRegister          *syscall.Proc
+	tableUnregister        *syscall.Proc
+	sessionFree            *syscall.Proc
+	recorderConfigure      *syscall.Proc
+	replay                 *syscall.Proc
+	streamNew              *syscall.Proc
+	streamRun              *syscall.Proc
+	streamFree             *syscall.Proc
+	conformanceRun         *syscall.Proc
+	outputFree             *syscall.Proc
 }
 
+// syscall 回调不会被回收，每个进程只创建一次（参数与返回值都按 uintptr 传递）
+var (
+	deallocCallbackPtr = sync.OnceValue(func() uintptr {
+		return syscall.NewCallback(func(ctx uintptr) uintptr {
+			releaseBuffer(ctx)
+			return 0
+		})
+	})
+	streamCallbackPtr = sync.OnceValue(func() uintptr {
+		return syscall.NewCallback(func(ctx, batchIndex, schema, array uintptr) uintptr {
+			return uintptr(dispatchStreamBatch(
+				ctx,
+				uint64(batchIndex),
+				(*ArrowSchema)(unsafe.Pointer(schema)),
+				(*ArrowArray)(unsafe.Pointer(array)),
+			))
+		})
+	})
+)
+
 // LoadBridge 加载动态库
 func LoadBridge(libPath string) (*Bridge, error) {
+	return LoadBridgeWithPrefix(libPath, "")
+}
+
+// LoadBridgeWithPrefix 加载以 versioned-symbols 构建的动态库（导出符号带前缀，如 "pb1_"），
+// 不同前缀的 bridge 可以同时加载进同一个进程
+func LoadBridgeWithPrefix(libPath string, prefix string) (*Bridge, error) {
 	if libPath == "" {
 		// 优先级：环境变量 > 可执行文件目录
 		libPath = os.Getenv("POLARS_BRIDGE_LIB")
@@ -59,60 +106,110 @@ func LoadBridge(libPath string) (*Bridge, error) {
 
 	b := &Bridge{lib: lib}
 
-	// 加载所有函数
-	if b.abiVersion, err = lib.FindProc("bridge_abi_version"); err != nil {
+	// 先验证 ABI 版本：错误消息的释放方式取决于版本，旧版 bridge 也可能缺少后面的符号
+	if b.abiVersion, err = lib.FindProc(prefix+"bridge_abi_version"); err != nil {
 		return nil, fmt.Errorf("failed to find bridge_abi_version: %w", err)
 	}
-	if b.engineVersion, err = lib.FindProc("bridge_engine_version"); err != nil {
+	if abiVer := b.AbiVersion(); abiVer != ABIVersion {
+		return nil, fmt.Errorf("ABI version mismatch: expected %d, got %d", ABIVersion, abiVer)
+	}
+
+	// 加载其余函数
+	if b.handshake, err = lib.FindProc(prefix+"bridge_handshake"); err != nil {
+		return nil, fmt.Errorf("failed to find bridge_handshake: %w", err)
+	}
+	if b.engineVersion, err = lib.FindProc(prefix+"bridge_engine_version"); err != nil {
 		return nil, fmt.Errorf("failed to find bridge_engine_version: %w", err)
 	}
-	if b.capabilities, err = lib.FindProc("bridge_capabilities"); err != nil {
+	if b.capabilities, err = lib.FindProc(prefix+"bridge_capabilities"); err != nil {
 		return nil, fmt.Errorf("failed to find bridge_capabilities: %w", err)
 	}
-	if b.lastError, err = lib.FindProc("bridge_last_error"); err != nil {
+	if b.lastError, err = lib.FindProc(prefix+"bridge_last_error"); err != nil {
 		return nil, fmt.Errorf("failed to find bridge_last_error: %w", err)
 	}
-	if b.lastErrorFree, err = lib.FindProc("bridge_last_error_free"); err != nil {
+	if b.lastErrorFree, err = lib.FindProc(prefix+"bridge_last_error_free"); err != nil {
 		return nil, fmt.Errorf("failed to find bridge_last_error_free: %w", err)
 	}
-	if b.planCompile, err = lib.FindProc("bridge_plan_compile"); err != nil {
+	if b.planCompile, err = lib.FindProc(prefix+"bridge_plan_compile"); err != nil {
 		return nil, fmt.Errorf("failed to find bridge_plan_compile: %w", err)
 	}
-	if b.planFree, err = lib.FindProc("bridge_plan_free"); err != nil {
+	if b.planFree, err = lib.FindProc(prefix+"bridge_plan_free"); err != nil {
 		return nil, fmt.Errorf("failed to find bridge_plan_free: %w", err)
 	}
-	if b.planExecuteSimple, err = lib.FindProc("bridge_plan_execute_simple"); err != nil {
+	if b.planExecuteSimple, err = lib.FindProc(prefix+"bridge_plan_execute_simple"); err != nil {
 		return nil, fmt.Errorf("failed to find bridge_plan_execute_simple: %w", err)
 	}
-	if b.planExecutePrint, err = lib.FindProc("bridge_plan_execute_and_print"); err != nil {
+	if b.planExecutePrint, err = lib.FindProc(prefix+"bridge_plan_execute_and_print"); err != nil {
 		return nil, fmt.Errorf("failed to find bridge_plan_execute_and_print: %w", err)
 	}
-	if b.planExecuteArrow, err = lib.FindProc("bridge_plan_execute_arrow"); err != nil {
+	if b.planExecuteArrow, err = lib.FindProc(prefix+"bridge_plan_execute_arrow"); err != nil {
 		return nil, fmt.Errorf("failed to find bridge_plan_execute_arrow: %w", err)
 	}
-	if b.planCollectDF, err = lib.FindProc("bridge_plan_collect_df"); err != nil {
+	if b.planCollectDF, err = lib.FindProc(prefix+"bridge_plan_collect_df"); err != nil {
 		return nil, fmt.Errorf("failed to find bridge_plan_collect_df: %w", err)
 	}
-	if b.dfToIPC, err = lib.FindProc("bridge_df_to_ipc"); err != nil {
+	if b.planCollectDFWithHints, err = lib.FindProc(prefix+"bridge_plan_collect_df_with_hints"); err != nil {
+		return nil, fmt.Errorf("failed to find bridge_plan_collect_df_with_hints: %w", err)
+	}
+	if b.dfToIPC, err = lib.FindProc(prefix+"bridge_df_to_ipc"); err != nil {
 		return nil, fmt.Errorf("failed to find bridge_df_to_ipc: %w", err)
 	}
-	if b.dfPrint, err = lib.FindProc("bridge_df_print"); err != nil {
+	if b.dfToJSON, err = lib.FindProc(prefix+"bridge_df_to_json"); err != nil {
+		return nil, fmt.Errorf("failed to find bridge_df_to_json: %w", err)
+	}
+	if b.dfPrint, err = lib.FindProc(prefix+"bridge_df_print"); err != nil {
 		return nil, fmt.Errorf("failed to find bridge_df_print: %w", err)
 	}
-	if b.dfFree, err = lib.FindProc("bridge_df_free"); err != nil {
+	if b.dfFree, err = lib.FindProc(prefix+"bridge_df_free"); err != nil {
 		return nil, fmt.Errorf("failed to find bridge_df_free: %w", err)
 	}
-	if b.dfFromColumns, err = lib.FindProc("bridge_df_from_columns"); err != nil {
+	if b.dfRetain, err = lib.FindProc(prefix+"bridge_df_retain"); err != nil {
+		return nil, fmt.Errorf("failed to find bridge_df_retain: %w", err)
+	}
+	if b.dfFromColumns, err = lib.FindProc(prefix+"bridge_df_from_columns"); err != nil {
 		return nil, fmt.Errorf("failed to find bridge_df_from_columns: %w", err)
 	}
-	if b.outputFree, err = lib.FindProc("bridge_output_free"); err != nil {
-		return nil, fmt.Errorf("failed to find bridge_output_free: %w", err)
+	if b.dfWithColumns, err = lib.FindProc(prefix+"bridge_df_with_columns"); err != nil {
+		return nil, fmt.Errorf("failed to find bridge_df_with_columns: %w", err)
 	}
-
-	// 验证 ABI 版本
-	abiVer := b.AbiVersion()
-	if abiVer != 1 {
-		return nil, fmt.Errorf("ABI version mismatch: expected 1, got %d", abiVer)
+	if b.dfRenameColumns, err = lib.FindProc(prefix+"bridge_df_rename_columns"); err != nil {
+		return nil, fmt.Errorf("failed to find bridge_df_rename_columns: %w", err)
+	}
+	if b.seriesFromBuffer, err = lib.FindProc(prefix+"bridge_series_from_buffer"); err != nil {
+		return nil, fmt.Errorf("failed to find bridge_series_from_buffer: %w", err)
+	}
+	if b.sessionNew, err = lib.FindProc(prefix+"bridge_session_new"); err != nil {
+		return nil, fmt.Errorf("failed to find bridge_session_new: %w", err)
+	}
+	if b.tableRegister, err = lib.FindProc(prefix+"bridge_table_register"); err != nil {
+		return nil, fmt.Errorf("failed to find bridge_table_register: %w", err)
+	}
+	if b.tableUnregister, err = lib.FindProc(prefix+"bridge_table_unregister"); err != nil {
+		return nil, fmt.Errorf("failed to find bridge_table_unregister: %w", err)
+	}
+	if b.sessionFree, err = lib.FindProc(prefix+"bridge_session_free"); err != nil {
+		return nil, fmt.Errorf("failed to find bridge_session_free: %w", err)
+	}
+	if b.recorderConfigure, err = lib.FindProc(prefix+"bridge_recorder_configure"); err != nil {
+		return nil, fmt.Errorf("failed to find bridge_recorder_configure: %w", err)
+	}
+	if b.replay, err = lib.FindProc(prefix+"bridge_replay"); err != nil {
+		return nil, fmt.Errorf("failed to find bridge_replay: %w", err)
+	}
+	if b.streamNew, err = lib.FindProc(prefix+"bridge_stream_new"); err != nil {
+		return nil, fmt.Errorf("failed to find bridge_stream_new: %w", err)
+	}
+	if b.streamRun, err = lib.FindProc(prefix+"bridge_stream_run"); err != nil {
+		return nil, fmt.Errorf("failed to find bridge_stream_run: %w", err)
+	}
+	if b.streamFree, err = lib.FindProc(prefix+"bridge_stream_free"); err != nil {
+		return nil, fmt.Errorf("failed to find bridge_stream_free: %w", err)
+	}
+	if b.conformanceRun, err = lib.FindProc(prefix+"bridge_conformance_run"); err != nil {
+		return nil, fmt.Errorf("failed to find bridge_conformance_run: %w", err)
+	}
+	if b.outputFree, err = lib.FindProc(prefix+"bridge_output_free"); err != nil {
+		return nil, fmt.Errorf("failed to find bridge_output_free: %w", err)
 	}
 
 	return b, nil
@@ -135,6 +232,30 @@ func (b *Bridge) AbiVersion() uint32 {
 	return uint32(ret)
 }
 
+// Handshake 与 bridge 协商协议版本（客户端 ABI 取 ABIVersion），返回 HandshakeReport
+func (b *Bridge) Handshake(clientPlanVersion uint32) (*pb.HandshakeReport, error) {
+	var outputPtr uintptr
+	var outputLen uintptr
+	ret, _, _ := b.handshake.Call(
+		uintptr(ABIVersion),
+		uintptr(clientPlanVersion),
+		uintptr(unsafe.Pointer(&outputPtr)),
+		uintptr(unsafe.Pointer(&outputLen)),
+	)
+	if ret != 0 {
+		return nil, b.getLastError()
+	}
+
+	output := []byte(copyBridgeString(outputPtr, int(outputLen)))
+	b.outputFree.Call(outputPtr, outputLen)
+
+	report := &pb.HandshakeReport{}
+	if err := proto.Unmarshal(output, report); err != nil {
+		return nil, fmt.Errorf("failed to decode handshake report: %w", err)
+	}
+	return report, nil
+}
+
 // EngineVersion 获取引擎版本
 func (b *Bridge) EngineVersion() (string, error) {
 	var ptr uintptr
@@ -269,6 +390,116 @@ func (b *Bridge) CollectPlanDF(planHandle uint64, inputDFHandle uint64) (uint64,
 	return dfHandle, nil
 }
 
+// CollectPlanDFWithHints 带执行提示执行计划并返回 DataFrame 句柄，hintsBytes 为序列化的 ExecutionHints（可为空）
+func (b *Bridge) CollectPlanDFWithHints(planHandle uint64, inputDFHandle uint64, hintsBytes []byte) (uint64, error) {
+	var hintsPtr uintptr
+	if len(hintsBytes) > 0 {
+		hintsPtr = uintptr(unsafe.Pointer(&hintsBytes[0]))
+	}
+
+	var dfHandle uint64
+	ret, _, _ := b.planCollectDFWithHints.Call(
+		uintptr(planHandle),
+		uintptr(inputDFHandle),
+		hintsPtr,
+		uintptr(len(hintsBytes)),
+		uintptr(unsafe.Pointer(&dfHandle)),
+	)
+	runtime.KeepAlive(hintsBytes)
+
+	if ret != 0 {
+		return 0, b.getLastError()
+	}
+	return dfHandle, nil
+}
+
+// DataFrameFromInt64Buffer 直接引用 data 创建单列 DataFrame（不复制数据）
+//
+// data 在 DataFrame 及其派生结果全部释放前保持固定，调用方不能再修改它
+func (b *Bridge) DataFrameFromInt64Buffer(name string, data []int64) (uint64, error) {
+	return b.dataFrameFromBuffer(name, pb.DataType_INT64, unsafe.Pointer(unsafe.SliceData(data)), len(data))
+}
+
+// DataFrameFromFloat64Buffer 直接引用 data 创建单列 DataFrame（不复制数据），约束同 DataFrameFromInt64Buffer
+func (b *Bridge) DataFrameFromFloat64Buffer(name string, data []float64) (uint64, error) {
+	return b.dataFrameFromBuffer(name, pb.DataType_FLOAT64, unsafe.Pointer(unsafe.SliceData(data)), len(data))
+}
+
+func (b *Bridge) dataFrameFromBuffer(name string, dataType pb.DataType, data unsafe.Pointer, length int) (uint64, error) {
+	nameBytes := []byte(name)
+	var namePtr uintptr
+	if len(nameBytes) > 0 {
+		namePtr = uintptr(unsafe.Pointer(&nameBytes[0]))
+	}
+
+	if length == 0 {
+		data = nil
+	}
+
+	// bridge 无论成功与否都会调用一次 dealloc，解除固定
+	ctx := pinBuffer(data)
+	var dfHandle uint64
+	ret, _, _ := b.seriesFromBuffer.Call(
+		namePtr,
+		uintptr(len(nameBytes)),
+		uintptr(dataType),
+		uintptr(data),
+		uintptr(length),
+		deallocCallbackPtr(),
+		ctx,
+		uintptr(unsafe.Pointer(&dfHandle)),
+	)
+	runtime.KeepAlive(nameBytes)
+
+	if ret != 0 {
+		return 0, b.getLastError()
+	}
+	return dfHandle, nil
+}
+
+// DataFrameWithColumns 在 DataFrame 上添加 / 替换列，exprsBytes 为序列化的 ExprList，返回新的 DataFrame 句柄
+func (b *Bridge) DataFrameWithColumns(handle uint64, exprsBytes []byte) (uint64, error) {
+	if len(exprsBytes) == 0 {
+		return 0, fmt.Errorf("exprsBytes is empty")
+	}
+
+	var dfHandle uint64
+	ret, _, _ := b.dfWithColumns.Call(
+		uintptr(handle),
+		uintptr(unsafe.Pointer(&exprsBytes[0])),
+		uintptr(len(exprsBytes)),
+		uintptr(unsafe.Pointer(&dfHandle)),
+	)
+	runtime.KeepAlive(exprsBytes)
+
+	if ret != 0 {
+		return 0, b.getLastError()
+	}
+	return dfHandle, nil
+}
+
+// DataFrameRenameColumns 批量改名，optionsBytes 为序列化的 RenameOptions，返回新的 DataFrame 句柄
+func (b *Bridge) DataFrameRenameColumns(handle uint64, optionsBytes []byte) (uint64, error) {
+	var optionsPtr uintptr
+	if len(optionsBytes) > 0 {
+		optionsPtr = uintptr(unsafe.Pointer(&optionsBytes[0]))
+	}
+
+	var dfHandle uint64
+	ret, _, _ := b.dfRenameColumns.Call(
+		uintptr(handle),
+		optionsPtr,
+		uintptr(len(optionsBytes)),
+		uintptr(unsafe.Pointer(&dfHandle)),
+	)
+	runtime.KeepAlive(optionsBytes)
+
+	if ret != 0 {
+		return 0, b.getLastError()
+	}
+	return dfHandle, nil
+}
+
 // DataFrameToIPC 将 DataFrame 导出为 Arrow IPC 二进制数据
 func (b *Bridge) DataFrameToIPC(handle uint64) ([]byte, error) {
 	var outputPtr uintptr
@@ -291,6 +522,27 @@ func (b *Bridge) DataFrameToIPC(handle uint64) ([]byte, error) {
 	return output, nil
 }
 
+// DataFrameToJSON 将 DataFrame 导出为 JSON 行数组（时间类型使用默认的字符串格式）
+func (b *Bridge) DataFrameToJSON(handle uint64) ([]byte, error) {
+	var outputPtr uintptr
+	var outputLen uintptr
+	ret, _, _ := b.dfToJSON.Call(
+		uintptr(handle),
+		0,
+		0,
+		uintptr(unsafe.Pointer(&outputPtr)),
+		uintptr(unsafe.Pointer(&outputLen)),
+	)
+	if ret != 0 {
+		return nil, b.getLastError()
+	}
+
+	output := []byte(copyBridgeString(outputPtr, int(outputLen)))
+	b.outputFree.Call(outputPtr, outputLen)
+
+	return output, nil
+}
+
 // DataFramePrint 打印 DataFrame（使用 Polars 原生 Display）
 func (b *Bridge) DataFramePrint(handle uint64) error {
 	ret, _, _ := b.dfPrint.Call(uintptr(handle))
@@ -305,6 +557,185 @@ func (b *Bridge) FreeDataFrame(handle uint64) {
 	b.dfFree.Call(uintptr(handle))
 }
 
+// RetainDataFrame 为 DataFrame 句柄增加一个引用：每次 retain 都需要对应一次 FreeDataFrame
+func (b *Bridge) RetainDataFrame(handle uint64) error {
+	ret, _, _ := b.dfRetain.Call(uintptr(handle))
+	if ret != 0 {
+		return b.getLastError()
+	}
+	return nil
+}
+
+// NewSession 创建表注册会话（Plan 中的 TableScan 经 ExecutionHints.session_handle 解析）
+func (b *Bridge) NewSession() (uint64, error) {
+	var sessionHandle uint64
+	ret, _, _ := b.sessionNew.Call(uintptr(unsafe.Pointer(&sessionHandle)))
+	if ret != 0 {
+		return 0, b.getLastError()
+	}
+	return sessionHandle, nil
+}
+
+// RegisterTable 把 DataFrame 的快照注册（或替换）为逻辑表，之后释放 dfHandle 不影响会话
+func (b *Bridge) RegisterTable(sessionHandle uint64, name string, dfHandle uint64) error {
+	return b.registerTable(sessionHandle, name, dfHandle, nil)
+}
+
+// RegisterTableNode 把 Node（序列化的 Node protobuf，通常是扫描节点）注册（或替换）为逻辑表，执行时才构建
+func (b *Bridge) RegisterTableNode(sessionHandle uint64, name string, nodeBytes []byte) error {
+	if len(nodeBytes) == 0 {
+		return fmt.Errorf("nodeBytes is empty")
+	}
+	return b.registerTable(sessionHandle, name, 0, nodeBytes)
+}
+
+func (b *Bridge) registerTable(sessionHandle uint64, name string, dfHandle uint64, nodeBytes []byte) error {
+	nameBytes := []byte(name)
+	var namePtr, nodePtr uintptr
+	if len(nameBytes) > 0 {
+		namePtr = uintptr(unsafe.Pointer(&nameBytes[0]))
+	}
+	if len(nodeBytes) > 0 {
+		nodePtr = uintptr(unsafe.Pointer(&nodeBytes[0]))
+	}
+
+	ret, _, _ := b.tableRegister.Call(
+		uintptr(sessionHandle),
+		namePtr,
+		uintptr(len(nameBytes)),
+		uintptr(dfHandle),
+		nodePtr,
+		uintptr(len(nodeBytes)),
+	)
+	runtime.KeepAlive(nameBytes)
+	runtime.KeepAlive(nodeBytes)
+
+	if ret != 0 {
+		return b.getLastError()
+	}
+	return nil
+}
+
+// UnregisterTable 注销一张表（不存在时为空操作）
+func (b *Bridge) UnregisterTable(sessionHandle uint64, name string) error {
+	nameBytes := []byte(name)
+	var namePtr uintptr
+	if len(nameBytes) > 0 {
+		namePtr = uintptr(unsafe.Pointer(&nameBytes[0]))
+	}
+
+	ret, _, _ := b.tableUnregister.Call(uintptr(sessionHandle), namePtr, uintptr(len(nameBytes)))
+	runtime.KeepAlive(nameBytes)
+
+	if ret != 0 {
+		return b.getLastError()
+	}
+	return nil
+}
+
+// FreeSession 释放会话（正在执行的 Plan 持有自己的引用，不受影响）
+func (b *Bridge) FreeSession(sessionHandle uint64) {
+	b.sessionFree.Call(uintptr(sessionHandle))
+}
+
+// ConfigureRecorder 开启或关闭查询录制，optionsBytes 为序列化的 RecorderOptions（dir 为空表示关闭）
+func (b *Bridge) ConfigureRecorder(optionsBytes []byte) error {
+	var optionsPtr uintptr
+	if len(optionsBytes) > 0 {
+		optionsPtr = uintptr(unsafe.Pointer(&optionsBytes[0]))
+	}
+
+	ret, _, _ := b.recorderConfigure.Call(optionsPtr, uintptr(len(optionsBytes)))
+	runtime.KeepAlive(optionsBytes)
+
+	if ret != 0 {
+		return b.getLastError()
+	}
+	return nil
+}
+
+// Replay 回放一次录制（dir 为单次录制的目录），返回结果 DataFrame 句柄；inputDFHandle 非 0 时代替录制的输入
+func (b *Bridge) Replay(dir string, inputDFHandle uint64) (uint64, error) {
+	if dir == "" {
+		return 0, fmt.Errorf("recording directory is empty")
+	}
+	dirBytes := []byte(dir)
+
+	var dfHandle uint64
+	ret, _, _ := b.replay.Call(
+		uintptr(unsafe.Pointer(&dirBytes[0])),
+		uintptr(len(dirBytes)),
+		uintptr(inputDFHandle),
+		uintptr(unsafe.Pointer(&dfHandle)),
+	)
+	runtime.KeepAlive(dirBytes)
+
+	if ret != 0 {
+		return 0, b.getLastError()
+	}
+	return dfHandle, nil
+}
+
+// NewStream 创建微批流，optionsBytes 为序列化的 StreamOptions；每批的结果同步推送给 callback（在 RunStream 中调用）
+func (b *Bridge) NewStream(planHandle uint64, optionsBytes []byte, callback StreamCallback) (uint64, error) {
+	if callback == nil {
+		return 0, fmt.Errorf("stream callback is nil")
+	}
+	var optionsPtr uintptr
+	if len(optionsBytes) > 0 {
+		optionsPtr = uintptr(unsafe.Pointer(&optionsBytes[0]))
+	}
+
+	ctx := streamCallbacks.add(callback)
+	var streamHandle uint64
+	ret, _, _ := b.streamNew.Call(
+		uintptr(planHandle),
+		optionsPtr,
+		uintptr(len(optionsBytes)),
+		streamCallbackPtr(),
+		ctx,
+		uintptr(unsafe.Pointer(&streamHandle)),
+	)
+	runtime.KeepAlive(optionsBytes)
+
+	if ret != 0 {
+		streamCallbacks.remove(ctx)
+		return 0, b.getLastError()
+	}
+	streamContexts.Store(streamHandle, ctx)
+	return streamHandle, nil
+}
+
+// RunStream 处理至多 maxBatches 个微批（0 表示直到数据源结束），返回累计的 StreamStats
+func (b *Bridge) RunStream(streamHandle uint64, maxBatches uint64) (*pb.StreamStats, error) {
+	var outputPtr uintptr
+	var outputLen uintptr
+	ret, _, _ := b.streamRun.Call(
+		uintptr(streamHandle),
+		uintptr(maxBatches),
+		uintptr(unsafe.Pointer(&outputPtr)),
+		uintptr(unsafe.Pointer(&outputLen)),
+	)
+	if ret != 0 {
+		return nil, b.getLastError()
+	}
+
+	output := []byte(copyBridgeString(outputPtr, int(outputLen)))
+	b.outputFree.Call(outputPtr, outputLen)
+
+	stats := &pb.StreamStats{}
+	if err := proto.Unmarshal(output, stats); err != nil {
+		return nil, fmt.Errorf("failed to decode stream stats: %w", err)
+	}
+	return stats, nil
+}
+
+// FreeStream 释放微批流并注销其回调
+func (b *Bridge) FreeStream(streamHandle uint64) {
+	b.streamFree.Call(uintptr(streamHandle))
+	forgetStream(streamHandle)
+}
+
 // CreateDataFrameFromColumns 从列数据创建 DataFrame（JSON 格式）
 func (b *Bridge) CreateDataFrameFromColumns(jsonData []byte) (uint64, error) {
 	if len(jsonData) == 0 {
@@ -326,6 +757,31 @@ func (b *Bridge) CreateDataFrameFromColumns(jsonData []byte) (uint64, error) {
 	return dfHandle, nil
 }
 
+// RunConformance 运行 golden Plan 用例目录（格式见 testdata/conformance/README.md），返回通过/失败报告
+func (b *Bridge) RunConformance(dir string) (*ConformanceReport, error) {
+	if dir == "" {
+		return nil, fmt.Errorf("conformance corpus directory is empty")
+	}
+	dirBytes := []byte(dir)
+	var outputPtr uintptr
+	var outputLen uintptr
+	ret, _, _ := b.conformanceRun.Call(
+		uintptr(unsafe.Pointer(&dirBytes[0])),
+		uintptr(len(dirBytes)),
+		uintptr(unsafe.Pointer(&outputPtr)),
+		uintptr(unsafe.Pointer(&outputLen)),
+	)
+	runtime.KeepAlive(dirBytes)
+
+	if ret != 0 {
+		return nil, b.getLastError()
+	}
+
+	output := []byte(ptrToString(outputPtr, int(outputLen)))
+	b.outputFree.Call(outputPtr, outputLen)
+	return parseConformanceReport(output)
+}
+
 func (b *Bridge) getLastError() error {
 	var ptr uintptr
 	var length uintptr
@@ -335,7 +791,9 @@ func (b *Bridge) getLastError() error {
 		return fmt.Errorf("unknown error")
 	}
 
-	errMsg := ptrToString(ptr, int(length))
+	// 消息在 bridge 分配的缓冲区中，先复制再释放
+	errMsg := copyBridgeString(ptr, int(length))
+	b.lastErrorFree.Call(ptr, length)
 	return fmt.Errorf("%s", errMsg)
 }
 
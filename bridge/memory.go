@@ -0,0 +1,17 @@
+package bridge
+
+import "unsafe"
+
+// ABIVersion 是本 SDK 要求的 bridge ABI 版本
+//
+// ABI 2 起 bridge_last_error 返回 bridge 分配的缓冲区，必须调用 bridge_last_error_free 释放；
+// ABI 1 返回的是线程局部存储中的指针，释放它会导致 double free。加载时先检查版本，不匹配直接报错。
+const ABIVersion = 2
+
+// copyBridgeString 把 bridge 分配的缓冲区复制为 Go 字符串（返回后即可释放原缓冲区）
+func copyBridgeString(ptr uintptr, length int) string {
+	if ptr == 0 || length == 0 {
+		return ""
+	}
+	return string(unsafe.Slice((*byte)(unsafe.Pointer(ptr)), length))
+}
@@ -16,4 +16,6 @@ const (
 	ErrExecution              ErrorCode = 9
 	ErrUnsupported            ErrorCode = 10
 	ErrOom                    ErrorCode = 11
+	ErrSchemaMismatch         ErrorCode = 12
+	ErrAssertionFailed        ErrorCode = 13
 )
@@ -35,6 +35,9 @@ func (df *DataFrame) Free() {
 }
 
 // Rows exports the DataFrame to Arrow IPC and parses it into rows.
+//
+// Signed integers are int64, unsigned integers uint64 and floats float64 (NaN/±Inf preserved);
+// List columns become []interface{} and Struct columns map[string]interface{}.
 func (df *DataFrame) Rows() ([]map[string]interface{}, error) {
 	if df == nil || df.handle == 0 || df.brg == nil {
 		return nil, fmt.Errorf("dataframe is nil")
@@ -46,6 +49,21 @@ func (df *DataFrame) Rows() ([]map[string]interface{}, error) {
 	return parseArrowIPC(ipcBytes)
 }
 
+// JSONRows exports the DataFrame as JSON rows (bridge_df_to_json) and decodes them.
+//
+// Unlike Rows, temporal values come back as strings, integers that fit in int64 are int64
+// regardless of the column type, and NaN/±Inf become nil.
+func (df *DataFrame) JSONRows() ([]map[string]interface{}, error) {
+	if df == nil || df.handle == 0 || df.brg == nil {
+		return nil, fmt.Errorf("dataframe is nil")
+	}
+	jsonBytes, err := df.brg.DataFrameToJSON(df.handle)
+	if err != nil {
+		return nil, fmt.Errorf("failed to export dataframe: %w", err)
+	}
+	return parseJSONRows(jsonBytes)
+}
+
 // Print outputs the DataFrame using Polars' Display implementation.
 func (df *DataFrame) Print() error {
 	if df == nil || df.handle == 0 || df.brg == nil {
@@ -2,6 +2,7 @@ package polars
 
 import (
 	"fmt"
+	"math"
 	"testing"
 
 	"github.com/apache/arrow-go/v18/arrow"
@@ -943,4 +944,45 @@ func TestDataFrameFromMap(t *testing.T) {
 			t.Fatalf("Failed to print DataFrame: %v", err)
 		}
 	})
+	// 测试 6: Rows 保留 Arrow 类型，JSONRows 按 JSON 解码
+	t.Run("RowsKeepArrowTypes", func(t *testing.T) {
+		df, err := NewDataFrameFromMap(brg, map[string]interface{}{
+			"n": []int64{1, 0, 2},
+		})
+		if err != nil {
+			t.Fatalf("Failed to create DataFrame: %v", err)
+		}
+		defer df.Free()
+
+		result, err := df.Select(
+			Col("n").Cast(UInt32, true).Alias("u"),
+			Col("n").Cast(Float64, true).Div(Lit(0.0)).Alias("f"),
+		).Collect(brg)
+		if err != nil {
+			t.Fatalf("Collect failed: %v", err)
+		}
+		defer result.Free()
+
+		rows, err := result.Rows()
+		if err != nil {
+			t.Fatalf("Rows failed: %v", err)
+		}
+		if rows[0]["u"] != uint64(1) {
+			t.Fatalf("Expected u[0] = uint64(1), got %#v", rows[0]["u"])
+		}
+		if f, ok := rows[0]["f"].(float64); !ok || !math.IsInf(f, 1) {
+			t.Fatalf("Expected f[0] = +Inf, got %#v", rows[0]["f"])
+		}
+		if f, ok := rows[1]["f"].(float64); !ok || !math.IsNaN(f) {
+			t.Fatalf("Expected f[1] = NaN, got %#v", rows[1]["f"])
+		}
+
+		jsonRows, err := result.JSONRows()
+		if err != nil {
+			t.Fatalf("JSONRows failed: %v", err)
+		}
+		if jsonRows[0]["u"] != int64(1) || jsonRows[1]["f"] != nil {
+			t.Fatalf("Unexpected JSON rows: %v", jsonRows)
+		}
+	})
 }
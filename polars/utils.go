@@ -2,10 +2,13 @@ package polars
 
 import (
 	"bufio"
+	"bytes"
 	"encoding/json"
 	"fmt"
+	"strconv"
 	"strings"
 
+	"github.com/apache/arrow-go/v18/arrow"
 	"github.com/apache/arrow-go/v18/arrow/array"
 	"github.com/apache/arrow-go/v18/arrow/ipc"
 )
@@ -61,49 +64,12 @@ func parseArrowIPC(ipcBytes []byte) ([]map[string]interface{}, error) {
 			row := make(map[string]interface{}, nCols)
 			for colIdx := 0; colIdx < nCols; colIdx++ {
 				field := fields[colIdx]
-				col := rec.Column(colIdx)
-
-				if col.IsNull(i) {
-					row[field.Name] = nil
-					continue
-				}
-
-				switch c := col.(type) {
-				case *array.Int64:
-					row[field.Name] = c.Value(i)
-				case *array.Int32:
-					row[field.Name] = int64(c.Value(i))
-				case *array.Int16:
-					row[field.Name] = int64(c.Value(i))
-				case *array.Int8:
-					row[field.Name] = int64(c.Value(i))
-				case *array.Uint64:
-					row[field.Name] = c.Value(i)
-				case *array.Uint32:
-					row[field.Name] = uint64(c.Value(i))
-				case *array.Uint16:
-					row[field.Name] = uint64(c.Value(i))
-				case *array.Uint8:
-					row[field.Name] = uint64(c.Value(i))
-				case *array.Float64:
-					row[field.Name] = c.Value(i)
-				case *array.Float32:
-					row[field.Name] = float64(c.Value(i))
-				case *array.Boolean:
-					row[field.Name] = c.Value(i)
-				case *array.String:
-					row[field.Name] = c.Value(i)
-				case *array.LargeString:
-					row[field.Name] = c.Value(i)
-				case *array.BinaryView:
-					// StringView in Polars is represented as BinaryView in Arrow
-					row[field.Name] = string(c.Value(i))
-				case *array.StringView:
-					// Polars StringView type (optimized string representation)
-					row[field.Name] = c.Value(i)
-				default:
-					return nil, fmt.Errorf("unsupported Arrow type %T for field %s", col, field.Name)
+				value, err := arrowValue(rec.Column(colIdx), i)
+				if err != nil {
+					rec.Release()
+					return nil, fmt.Errorf("field %s: %w", field.Name, err)
 				}
+				row[field.Name] = value
 			}
 			rows = append(rows, row)
 		}
@@ -113,3 +79,123 @@ func parseArrowIPC(ipcBytes []byte) ([]map[string]interface{}, error) {
 
 	return rows, nil
 }
+
+// arrowValue 读取数组中第 i 个值：有符号整数为 int64，无符号整数为 uint64，浮点数为 float64（保留 NaN/±Inf），
+// List 为 []interface{}，Struct 为 map[string]interface{}，null 为 nil
+func arrowValue(col arrow.Array, i int) (interface{}, error) {
+	if col.IsNull(i) {
+		return nil, nil
+	}
+
+	switch c := col.(type) {
+	case *array.Int64:
+		return c.Value(i), nil
+	case *array.Int32:
+		return int64(c.Value(i)), nil
+	case *array.Int16:
+		return int64(c.Value(i)), nil
+	case *array.Int8:
+		return int64(c.Value(i)), nil
+	case *array.Uint64:
+		return c.Value(i), nil
+	case *array.Uint32:
+		return uint64(c.Value(i)), nil
+	case *array.Uint16:
+		return uint64(c.Value(i)), nil
+	case *array.Uint8:
+		return uint64(c.Value(i)), nil
+	case *array.Float64:
+		return c.Value(i), nil
+	case *array.Float32:
+		return float64(c.Value(i)), nil
+	case *array.Boolean:
+		return c.Value(i), nil
+	case *array.String:
+		return c.Value(i), nil
+	case *array.LargeString:
+		return c.Value(i), nil
+	case *array.BinaryView:
+		// StringView in Polars is represented as BinaryView in Arrow
+		return string(c.Value(i)), nil
+	case *array.StringView:
+		// Polars StringView type (optimized string representation)
+		return c.Value(i), nil
+	case *array.List:
+		start, end := c.ValueOffsets(i)
+		return listValues(c.ListValues(), start, end)
+	case *array.LargeList:
+		start, end := c.ValueOffsets(i)
+		return listValues(c.ListValues(), start, end)
+	case *array.Struct:
+		structType := c.DataType().(*arrow.StructType)
+		value := make(map[string]interface{}, c.NumField())
+		for j := 0; j < c.NumField(); j++ {
+			item, err := arrowValue(c.Field(j), i)
+			if err != nil {
+				return nil, err
+			}
+			value[structType.Field(j).Name] = item
+		}
+		return value, nil
+	default:
+		return nil, fmt.Errorf("unsupported Arrow type %T", col)
+	}
+}
+
+func listValues(values arrow.Array, start, end int64) ([]interface{}, error) {
+	items := make([]interface{}, 0, end-start)
+	for j := start; j < end; j++ {
+		item, err := arrowValue(values, int(j))
+		if err != nil {
+			return nil, err
+		}
+		items = append(items, item)
+	}
+	return items, nil
+}
+
+// parseJSONRows 解析 bridge_df_to_json 输出的 JSON 行数组
+//
+// 整数解码为 int64（超出范围的无符号整数为 uint64），其余数字为 float64，嵌套的 List/Struct 递归处理；
+// JSON 不能表示 NaN/±Inf，它们与 null 一样解码为 nil。
+func parseJSONRows(data []byte) ([]map[string]interface{}, error) {
+	decoder := json.NewDecoder(bytes.NewReader(data))
+	decoder.UseNumber()
+
+	var rows []map[string]interface{}
+	if err := decoder.Decode(&rows); err != nil {
+		return nil, fmt.Errorf("failed to parse JSON rows: %w", err)
+	}
+	for _, row := range rows {
+		for name, value := range row {
+			row[name] = normalizeJSONValue(value)
+		}
+	}
+	return rows, nil
+}
+
+func normalizeJSONValue(value interface{}) interface{} {
+	switch v := value.(type) {
+	case json.Number:
+		if i, err := v.Int64(); err == nil {
+			return i
+		}
+		if u, err := strconv.ParseUint(v.String(), 10, 64); err == nil {
+			return u
+		}
+		f, _ := v.Float64()
+		return f
+	case []interface{}:
+		for i, item := range v {
+			v[i] = normalizeJSONValue(item)
+		}
+		return v
+	case map[string]interface{}:
+		for key, item := range v {
+			v[key] = normalizeJSONValue(item)
+		}
+		return v
+	default:
+		return value
+	}
+}
@@ -1,4 +1,5 @@
 use std::path::PathBuf;
+use std::process::Command;
 
 fn main() {
     // 创建输出目录
@@ -10,9 +11,220 @@ fn main() {
     let proto_dir = PathBuf::from("..").join("proto");
 
     println!("cargo:rerun-if-changed={}", proto_file.display());
+    configure_protoc();
 
+    // map 字段使用 BTreeMap，保证重新编码的字节稳定（用于 Plan 指纹）
     prost_build::Config::new()
         .out_dir(&out_dir)
+        .btree_map(["."])
         .compile_protos(&[proto_file], &[proto_dir])
         .expect("Failed to compile protobuf");
+
+    emit_build_info();
+    let prefix = emit_symbol_prefix();
+    emit_c_header(&prefix);
+}
+
+// 导出符号前缀（lib.rs 中每个导出函数的符号名 = 前缀 + 函数名）
+//
+// 默认为空；启用 versioned-symbols feature 时按主版本生成（如 pb1_bridge_plan_compile，
+// 0.x 版本按 0.minor 生成，如 pb0_1_），使两个版本的 bridge 可以加载进同一个进程。
+// 也可以通过环境变量 BRIDGE_SYMBOL_PREFIX 显式指定。
+fn emit_symbol_prefix() -> String {
+    println!("cargo:rerun-if-env-changed=BRIDGE_SYMBOL_PREFIX");
+
+    let prefix = match std::env::var("BRIDGE_SYMBOL_PREFIX") {
+        Ok(prefix) => prefix,
+        Err(_) if std::env::var_os("CARGO_FEATURE_VERSIONED_SYMBOLS").is_some() => {
+            let major = std::env::var("CARGO_PKG_VERSION_MAJOR").unwrap_or_default();
+            let minor = std::env::var("CARGO_PKG_VERSION_MINOR").unwrap_or_default();
+            if major == "0" {
+                format!("pb0_{}_", minor)
+            } else {
+                format!("pb{}_", major)
+            }
+        }
+        Err(_) => String::new(),
+    };
+    if !prefix.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        panic!("BRIDGE_SYMBOL_PREFIX must contain only ASCII letters, digits and '_': {:?}", prefix);
+    }
+    println!("cargo:rustc-env=BRIDGE_SYMBOL_PREFIX={}", prefix);
+    prefix
+}
+
+// 用 cbindgen 从导出函数生成 C 头文件，与 src/abi.rs 的签名检查配合使用
+//
+// 默认前缀的头文件写入 include/polars_bridge.h 并提交到仓库（FFI 变更在 diff 中可见）；
+// 带前缀的构建只写入 OUT_DIR。cbindgen 只识别字面量形式的 export_name，这里先把 lib.rs 中
+// 带前缀宏的 export_name 展开为实际符号名，再交给 cbindgen 解析。
+// 生成失败只给出警告：头文件是给 C 调用方的产物，不影响动态库本身。
+fn emit_c_header(prefix: &str) {
+    const PREFIXED: &str = "#[export_name = concat!(env!(\"BRIDGE_SYMBOL_PREFIX\"), \"";
+
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-changed=src/lib.rs");
+
+    let lib = std::fs::read_to_string("src/lib.rs").expect("Failed to read src/lib.rs");
+    let mut expanded = String::with_capacity(lib.len());
+    for line in lib.lines() {
+        match line.trim_start().strip_prefix(PREFIXED).and_then(|rest| rest.strip_suffix("\")]")) {
+            Some(name) => expanded.push_str(&format!("#[export_name = \"{}{}\"]", prefix, name)),
+            // 其他写法的导出属性 cbindgen 无法识别，对应的函数会从头文件中缺失
+            None if line.contains("BRIDGE_SYMBOL_PREFIX") && line.contains("export_name") => {
+                println!("cargo:warning=Unrecognized export_name attribute in src/lib.rs: {}", line.trim());
+                expanded.push_str(line);
+            }
+            None => expanded.push_str(line),
+        }
+        expanded.push('\n');
+    }
+
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").expect("OUT_DIR not set"));
+    let ffi_surface = out_dir.join("ffi_surface.rs");
+    std::fs::write(&ffi_surface, expanded).expect("Failed to write FFI surface");
+
+    let config = match cbindgen::Config::from_file("cbindgen.toml") {
+        Ok(config) => config,
+        Err(e) => {
+            println!("cargo:warning=Skipping C header generation: {}", e);
+            return;
+        }
+    };
+    let bindings = match cbindgen::Builder::new().with_config(config).with_src(&ffi_surface).generate() {
+        Ok(bindings) => bindings,
+        Err(e) => {
+            println!("cargo:warning=Skipping C header generation: {}", e);
+            return;
+        }
+    };
+
+    bindings.write_to_file(out_dir.join("polars_bridge.h"));
+    if prefix.is_empty() {
+        bindings.write_to_file(PathBuf::from("include").join("polars_bridge.h"));
+    }
+}
+
+// 选择 protoc：PROTOC 环境变量 > PATH 中的 protoc > vendored-protoc feature 从源码构建
+fn configure_protoc() {
+    println!("cargo:rerun-if-env-changed=PROTOC");
+    if std::env::var_os("PROTOC").is_some() {
+        return;
+    }
+
+    let installed = Command::new("protoc")
+        .arg("--version")
+        .output()
+        .map(|out| out.status.success())
+        .unwrap_or(false);
+    if installed {
+        return;
+    }
+
+    #[cfg(feature = "vendored-protoc")]
+    std::env::set_var("PROTOC", protobuf_src::protoc());
+
+    #[cfg(not(feature = "vendored-protoc"))]
+    panic!(
+        "protoc not found: install the Protobuf compiler, set PROTOC, \
+         or build with `--features vendored-protoc`"
+    );
+}
+
+// 构建元数据（供 bridge_engine_info 使用）
+fn emit_build_info() {
+    println!("cargo:rerun-if-changed=Cargo.toml");
+    println!("cargo:rerun-if-changed=../.git/HEAD");
+
+    let target = std::env::var("TARGET").unwrap_or_else(|_| "unknown".into());
+    let profile = std::env::var("PROFILE").unwrap_or_else(|_| "unknown".into());
+    println!("cargo:rustc-env=BRIDGE_TARGET={}", target);
+    println!("cargo:rustc-env=BRIDGE_PROFILE={}", profile);
+
+    let git_commit = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .map(|s| s.trim().to_string())
+        .unwrap_or_else(|| "unknown".into());
+    println!("cargo:rustc-env=BRIDGE_GIT_COMMIT={}", git_commit);
+
+    // 本 crate 启用的 features
+    let mut features: Vec<String> = std::env::vars()
+        .filter_map(|(key, _)| key.strip_prefix("CARGO_FEATURE_").map(|f| f.to_lowercase()))
+        .collect();
+    features.sort();
+    println!("cargo:rustc-env=BRIDGE_FEATURES={}", features.join(","));
+
+    let manifest = std::fs::read_to_string("Cargo.toml").unwrap_or_default();
+    println!("cargo:rustc-env=BRIDGE_POLARS_FEATURES={}", polars_features(&manifest).join(","));
+
+    let lock = std::fs::read_to_string("Cargo.lock").unwrap_or_default();
+    let polars_version = locked_version(&lock, "polars").unwrap_or_else(|| "unknown".into());
+    println!("cargo:rustc-env=BRIDGE_POLARS_VERSION={}", polars_version);
+}
+
+// polars 实际启用的 features：依赖行中的基础列表 + 本 crate 已启用的 feature 中的 `polars/<name>`
+//
+// cargo 为每个启用的 feature（含被其它 feature 间接启用的）设置 CARGO_FEATURE_<NAME>，
+// 因此只需按 [features] 表逐项检查，不需要展开 feature 之间的依赖。
+fn polars_features(manifest: &str) -> Vec<String> {
+    let mut features: Vec<String> = manifest
+        .lines()
+        .find(|line| line.trim_start().starts_with("polars ="))
+        .and_then(|line| line.split_once("features"))
+        .and_then(|(_, rest)| toml_list(rest))
+        .unwrap_or_default();
+
+    let table = manifest
+        .lines()
+        .skip_while(|line| line.trim() != "[features]")
+        .skip(1)
+        .take_while(|line| !line.trim_start().starts_with('['));
+    for line in table {
+        let Some((name, rest)) = line.split_once('=') else {
+            continue;
+        };
+        let env = format!("CARGO_FEATURE_{}", name.trim().to_uppercase().replace('-', "_"));
+        if std::env::var_os(env).is_none() {
+            continue;
+        }
+        let enabled = toml_list(rest).unwrap_or_default();
+        features.extend(enabled.iter().filter_map(|f| f.strip_prefix("polars/")).map(String::from));
+    }
+
+    features.sort();
+    features.dedup();
+    features
+}
+
+// 解析单行 TOML 字符串数组：["a", "b"]
+fn toml_list(text: &str) -> Option<Vec<String>> {
+    let start = text.find('[')?;
+    let end = text.find(']')?;
+    Some(
+        text[start + 1..end]
+            .split(',')
+            .map(|f| f.trim().trim_matches('"').to_string())
+            .filter(|f| !f.is_empty())
+            .collect(),
+    )
+}
+
+// 从 Cargo.lock 中查找依赖的实际版本
+fn locked_version(lock: &str, name: &str) -> Option<String> {
+    let needle = format!("name = \"{}\"", name);
+    let mut lines = lock.lines();
+    while let Some(line) = lines.next() {
+        if line.trim() == needle {
+            let version = lines.next()?.trim();
+            return version
+                .strip_prefix("version = \"")
+                .and_then(|v| v.strip_suffix('"'))
+                .map(|v| v.to_string());
+        }
+    }
+    None
 }
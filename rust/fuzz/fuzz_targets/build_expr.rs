@@ -0,0 +1,9 @@
+#![no_main]
+
+// 结构化表达式 -> build_expr，并在样例数据上求值
+use libfuzzer_sys::fuzz_target;
+use polars_bridge_fuzz::FuzzExpr;
+
+fuzz_target!(|expr: FuzzExpr| {
+    polars_bridge::fuzz::build_expr(&expr.to_proto());
+});
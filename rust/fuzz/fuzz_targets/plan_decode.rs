@@ -0,0 +1,8 @@
+#![no_main]
+
+// 原始字节 -> bridge_plan_compile 的解码与版本校验
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    polars_bridge::fuzz::plan_compile(data);
+});
@@ -0,0 +1,9 @@
+#![no_main]
+
+// 结构化 Plan -> 有资源上限的执行（只使用 MemoryScan 与样例数据）
+use libfuzzer_sys::fuzz_target;
+use polars_bridge_fuzz::FuzzNode;
+
+fuzz_target!(|node: FuzzNode| {
+    polars_bridge::fuzz::execute_plan(&node.to_plan());
+});
@@ -0,0 +1,395 @@
+//! 结构化模糊输入：由 arbitrary 生成表达式 / Plan 语法树，再转换为 proto 消息
+//!
+//! 直接变异 protobuf 字节大多在解码阶段就被拒绝，结构化输入能更快覆盖构建与执行路径；
+//! 原始字节的解码由 plan_decode 目标单独覆盖。
+
+use arbitrary::Arbitrary;
+use polars_bridge::fuzz::{proto, SAMPLE_COLUMNS};
+
+use proto::expr::Kind;
+
+/// 嵌套深度上限（与 prost 解码的递归上限同一量级，超出后用列引用截断）
+const MAX_DEPTH: usize = 32;
+
+#[derive(Arbitrary, Debug)]
+pub struct ColumnRef(u8);
+
+impl ColumnRef {
+    // 大部分引用命中样例数据的列，少量指向不存在的列
+    fn name(&self) -> String {
+        match SAMPLE_COLUMNS.get(self.0 as usize % (SAMPLE_COLUMNS.len() + 1)) {
+            Some(name) => (*name).to_string(),
+            None => "missing".to_string(),
+        }
+    }
+}
+
+#[derive(Arbitrary, Debug)]
+pub enum FuzzLiteral {
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Str(String),
+    Null,
+    Decimal(String),
+}
+
+#[derive(Arbitrary, Debug, Clone, Copy)]
+pub enum AggKind {
+    Sum,
+    Mean,
+    Min,
+    Max,
+    Count,
+    Median,
+    NUnique,
+    NullCount,
+    First,
+    Last,
+    Implode,
+    ArgMin,
+    ArgMax,
+    ArgUnique,
+}
+
+#[derive(Arbitrary, Debug, Clone, Copy)]
+pub enum CumKind {
+    Sum,
+    Min,
+    Max,
+    Prod,
+    Count,
+}
+
+#[derive(Arbitrary, Debug, Clone, Copy)]
+pub enum RollingKind {
+    Mean,
+    Sum,
+    Min,
+    Max,
+    Std,
+}
+
+#[derive(Arbitrary, Debug)]
+pub enum FuzzExpr {
+    Col(ColumnRef),
+    Lit(FuzzLiteral),
+    Binary(Box<FuzzExpr>, u8, Box<FuzzExpr>),
+    Alias(Box<FuzzExpr>, ColumnRef),
+    IsNull(Box<FuzzExpr>),
+    Not(Box<FuzzExpr>),
+    Cast(Box<FuzzExpr>, u8, bool),
+    Agg(AggKind, Box<FuzzExpr>),
+    Std(Box<FuzzExpr>, Option<u8>),
+    Quantile(Box<FuzzExpr>, f64, u8),
+    FillNull(Box<FuzzExpr>, Box<FuzzExpr>),
+    Clip(Box<FuzzExpr>, Option<Box<FuzzExpr>>, Option<Box<FuzzExpr>>),
+    Cumulative(CumKind, Box<FuzzExpr>, bool),
+    Shift(Box<FuzzExpr>, i8, Option<Box<FuzzExpr>>),
+    Diff(Box<FuzzExpr>, Option<i8>, bool),
+    PctChange(Box<FuzzExpr>, Option<i8>),
+    Rolling(RollingKind, Box<FuzzExpr>, u8, Option<u8>, bool, Vec<f64>),
+    ArgSort(Box<FuzzExpr>, bool, bool),
+    StrContains(Box<FuzzExpr>, String, bool),
+    Extension(Vec<FuzzExpr>, u8),
+}
+
+impl FuzzExpr {
+    pub fn to_proto(&self) -> proto::Expr {
+        self.build(0)
+    }
+
+    fn build(&self, depth: usize) -> proto::Expr {
+        if depth >= MAX_DEPTH {
+            return column("a");
+        }
+        let inner = |e: &FuzzExpr| Some(Box::new(e.build(depth + 1)));
+        let agg = |e: &FuzzExpr| proto::AggFunction { expr: inner(e) };
+
+        let kind = match self {
+            FuzzExpr::Col(c) => return column(&c.name()),
+            FuzzExpr::Lit(value) => Kind::Lit(literal(value).into()),
+            FuzzExpr::Binary(left, op, right) => Kind::Binary(
+                proto::BinaryExpr {
+                    left: inner(left),
+                    op: *op as i32,
+                    right: inner(right),
+                }
+                .into(),
+            ),
+            FuzzExpr::Alias(e, name) => Kind::Alias(
+                proto::Alias {
+                    expr: inner(e),
+                    name: name.name(),
+                }
+                .into(),
+            ),
+            FuzzExpr::IsNull(e) => Kind::IsNull(proto::IsNull { expr: inner(e) }.into()),
+            FuzzExpr::Not(e) => Kind::Not(proto::Not { expr: inner(e) }.into()),
+            FuzzExpr::Cast(e, dtype, strict) => Kind::Cast(
+                proto::Cast {
+                    expr: inner(e),
+                    data_type: *dtype as i32,
+                    strict: *strict,
+                    ..Default::default()
+                }
+                .into(),
+            ),
+            FuzzExpr::Agg(kind, e) => {
+                let func = agg(e).into();
+                match kind {
+                    AggKind::Sum => Kind::Sum(func),
+                    AggKind::Mean => Kind::Mean(func),
+                    AggKind::Min => Kind::Min(func),
+                    AggKind::Max => Kind::Max(func),
+                    AggKind::Count => Kind::Count(func),
+                    AggKind::Median => Kind::Median(func),
+                    AggKind::NUnique => Kind::NUnique(func),
+                    AggKind::NullCount => Kind::NullCount(func),
+                    AggKind::First => Kind::First(func),
+                    AggKind::Last => Kind::Last(func),
+                    AggKind::Implode => Kind::Implode(func),
+                    AggKind::ArgMin => Kind::ArgMin(func),
+                    AggKind::ArgMax => Kind::ArgMax(func),
+                    AggKind::ArgUnique => Kind::ArgUnique(func),
+                }
+            }
+            FuzzExpr::Std(e, ddof) => Kind::Std(
+                proto::Dispersion {
+                    expr: inner(e),
+                    ddof: ddof.map(u32::from),
+                }
+                .into(),
+            ),
+            FuzzExpr::Quantile(e, quantile, method) => Kind::Quantile(
+                proto::Quantile {
+                    expr: inner(e),
+                    quantile: *quantile,
+                    method: *method as i32,
+                }
+                .into(),
+            ),
+            FuzzExpr::FillNull(e, value) => Kind::FillNull(
+                proto::FillNull {
+                    expr: inner(e),
+                    fill: Some(proto::fill_null::Fill::Value(value.build(depth + 1).into())),
+                    limit: None,
+                }
+                .into(),
+            ),
+            FuzzExpr::Clip(e, min, max) => Kind::Clip(
+                proto::Clip {
+                    expr: inner(e),
+                    min: min.as_deref().and_then(inner),
+                    max: max.as_deref().and_then(inner),
+                }
+                .into(),
+            ),
+            FuzzExpr::Cumulative(kind, e, reverse) => {
+                let cum = proto::Cumulative {
+                    expr: inner(e),
+                    reverse: *reverse,
+                }
+                .into();
+                match kind {
+                    CumKind::Sum => Kind::CumSum(cum),
+                    CumKind::Min => Kind::CumMin(cum),
+                    CumKind::Max => Kind::CumMax(cum),
+                    CumKind::Prod => Kind::CumProd(cum),
+                    CumKind::Count => Kind::CumCount(cum),
+                }
+            }
+            FuzzExpr::Shift(e, n, fill_value) => Kind::Shift(
+                proto::Shift {
+                    expr: inner(e),
+                    n: i64::from(*n),
+                    fill_value: fill_value.as_deref().and_then(inner),
+                }
+                .into(),
+            ),
+            FuzzExpr::Diff(e, n, drop) => Kind::Diff(
+                proto::Diff {
+                    expr: inner(e),
+                    n: n.map(i64::from),
+                    null_behavior: *drop as i32,
+                }
+                .into(),
+            ),
+            FuzzExpr::PctChange(e, n) => Kind::PctChange(
+                proto::PctChange {
+                    expr: inner(e),
+                    n: n.map(i64::from),
+                }
+                .into(),
+            ),
+            FuzzExpr::Rolling(kind, e, window_size, min_periods, center, weights) => {
+                let rolling = proto::Rolling {
+                    expr: inner(e),
+                    window_size: u32::from(*window_size),
+                    min_periods: min_periods.map(u32::from),
+                    center: *center,
+                    weights: weights.clone(),
+                    ddof: None,
+                }
+                .into();
+                match kind {
+                    RollingKind::Mean => Kind::RollingMean(rolling),
+                    RollingKind::Sum => Kind::RollingSum(rolling),
+                    RollingKind::Min => Kind::RollingMin(rolling),
+                    RollingKind::Max => Kind::RollingMax(rolling),
+                    RollingKind::Std => Kind::RollingStd(rolling),
+                }
+            }
+            FuzzExpr::ArgSort(e, descending, nulls_last) => Kind::ArgSort(
+                proto::ArgSort {
+                    expr: inner(e),
+                    descending: *descending,
+                    nulls_last: *nulls_last,
+                }
+                .into(),
+            ),
+            FuzzExpr::StrContains(e, pattern, literal) => Kind::StrContains(
+                proto::StringContains {
+                    expr: inner(e),
+                    pattern: pattern.clone(),
+                    literal: *literal,
+                }
+                .into(),
+            ),
+            FuzzExpr::Extension(inputs, name) => {
+                const NAMES: &[&str] = &["sqrt", "reverse", "drop_nulls", "unique", "unknown"];
+                Kind::Extension(
+                    proto::Extension {
+                        namespace: "polars".into(),
+                        name: NAMES[*name as usize % NAMES.len()].into(),
+                        inputs: inputs.iter().map(|e| e.build(depth + 1)).collect(),
+                        ..Default::default()
+                    }
+                    .into(),
+                )
+            }
+        };
+        proto::Expr { kind: Some(kind) }
+    }
+}
+
+#[derive(Arbitrary, Debug)]
+pub enum FuzzNode {
+    Scan(Vec<ColumnRef>),
+    Project(Box<FuzzNode>, Vec<FuzzExpr>),
+    Filter(Box<FuzzNode>, FuzzExpr),
+    WithColumns(Box<FuzzNode>, Vec<FuzzExpr>),
+    Limit(Box<FuzzNode>, u16),
+    GroupBy(Box<FuzzNode>, Vec<FuzzExpr>, Vec<FuzzExpr>, bool),
+    Join(Box<FuzzNode>, Box<FuzzNode>, Vec<(ColumnRef, ColumnRef)>, u8),
+}
+
+impl FuzzNode {
+    pub fn to_plan(&self) -> proto::Plan {
+        proto::Plan {
+            plan_version: 1,
+            root: Some(self.build(0)),
+            ..Default::default()
+        }
+    }
+
+    fn build(&self, depth: usize) -> proto::Node {
+        use proto::node::Kind;
+
+        if depth >= MAX_DEPTH {
+            return scan(vec![]);
+        }
+        let input = |n: &FuzzNode| Some(Box::new(n.build(depth + 1)));
+        let exprs = |es: &[FuzzExpr]| es.iter().map(FuzzExpr::to_proto).collect::<Vec<_>>();
+
+        let kind = match self {
+            FuzzNode::Scan(columns) => return scan(columns.iter().map(ColumnRef::name).collect()),
+            FuzzNode::Project(n, es) => Kind::Project(
+                proto::Project {
+                    input: input(n),
+                    expressions: exprs(es),
+                }
+                .into(),
+            ),
+            FuzzNode::Filter(n, predicate) => Kind::Filter(
+                proto::Filter {
+                    input: input(n),
+                    predicate: Some(predicate.to_proto()),
+                }
+                .into(),
+            ),
+            FuzzNode::WithColumns(n, es) => Kind::WithColumns(
+                proto::WithColumns {
+                    input: input(n),
+                    expressions: exprs(es),
+                }
+                .into(),
+            ),
+            FuzzNode::Limit(n, limit) => Kind::Limit(
+                proto::Limit {
+                    input: input(n),
+                    n: u64::from(*limit),
+                }
+                .into(),
+            ),
+            FuzzNode::GroupBy(n, keys, aggs, maintain_order) => Kind::GroupBy(
+                proto::GroupBy {
+                    input: input(n),
+                    keys: exprs(keys),
+                    aggs: exprs(aggs),
+                    maintain_order: *maintain_order,
+                    ..Default::default()
+                }
+                .into(),
+            ),
+            FuzzNode::Join(left, right, on, how) => Kind::Join(
+                proto::Join {
+                    left: input(left),
+                    right: input(right),
+                    left_on: on.iter().map(|(l, _)| column(&l.name())).collect(),
+                    right_on: on.iter().map(|(_, r)| column(&r.name())).collect(),
+                    how: *how as i32,
+                    ..Default::default()
+                }
+                .into(),
+            ),
+        };
+        proto::Node {
+            id: depth as u32,
+            kind: Some(kind),
+        }
+    }
+}
+
+fn column(name: &str) -> proto::Expr {
+    proto::Expr {
+        kind: Some(Kind::Col(proto::Column { name: name.to_string() }.into())),
+    }
+}
+
+fn literal(value: &FuzzLiteral) -> proto::Literal {
+    use proto::literal::Value;
+
+    let value = match value {
+        FuzzLiteral::Int(v) => Value::IntVal(*v),
+        FuzzLiteral::Float(v) => Value::FloatVal(*v),
+        FuzzLiteral::Bool(v) => Value::BoolVal(*v),
+        FuzzLiteral::Str(v) => Value::StringVal(v.clone()),
+        FuzzLiteral::Null => Value::NullVal(proto::NullValue {}),
+        FuzzLiteral::Decimal(v) => Value::DecimalVal(v.clone()),
+    };
+    proto::Literal { value: Some(value) }
+}
+
+fn scan(column_names: Vec<String>) -> proto::Node {
+    proto::Node {
+        id: 0,
+        kind: Some(proto::node::Kind::MemoryScan(
+            proto::MemoryScan {
+                column_names,
+                options: None,
+            }
+            .into(),
+        )),
+    }
+}
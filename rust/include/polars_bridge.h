@@ -0,0 +1,560 @@
+#ifndef POLARS_BRIDGE_H
+#define POLARS_BRIDGE_H
+
+/* 由 cbindgen 自动生成，请勿手动修改 */
+
+#include <stddef.h>
+#include <stdint.h>
+#if defined(POLARS_BRIDGE_STATIC)
+#  define POLARS_BRIDGE_API
+#elif defined(_WIN32)
+#  define POLARS_BRIDGE_API __declspec(dllimport)
+#else
+#  define POLARS_BRIDGE_API __attribute__((visibility("default")))
+#endif
+
+#ifndef ARROW_C_DATA_INTERFACE
+#define ARROW_C_DATA_INTERFACE
+
+#define ARROW_FLAG_DICTIONARY_ORDERED 1
+#define ARROW_FLAG_NULLABLE 2
+#define ARROW_FLAG_MAP_KEYS_SORTED 4
+
+struct ArrowSchema {
+  const char* format;
+  const char* name;
+  const char* metadata;
+  int64_t flags;
+  int64_t n_children;
+  struct ArrowSchema** children;
+  struct ArrowSchema* dictionary;
+  void (*release)(struct ArrowSchema*);
+  void* private_data;
+};
+
+struct ArrowArray {
+  int64_t length;
+  int64_t null_count;
+  int64_t offset;
+  int64_t n_buffers;
+  int64_t n_children;
+  const void** buffers;
+  struct ArrowArray** children;
+  struct ArrowArray* dictionary;
+  void (*release)(struct ArrowArray*);
+  void* private_data;
+};
+
+#endif  // ARROW_C_DATA_INTERFACE
+
+#ifndef ARROW_C_STREAM_INTERFACE
+#define ARROW_C_STREAM_INTERFACE
+
+struct ArrowArrayStream {
+  int (*get_schema)(struct ArrowArrayStream*, struct ArrowSchema* out);
+  int (*get_next)(struct ArrowArrayStream*, struct ArrowArray* out);
+  const char* (*get_last_error)(struct ArrowArrayStream*);
+  void (*release)(struct ArrowArrayStream*);
+  void* private_data;
+};
+
+#endif  // ARROW_C_STREAM_INTERFACE
+
+typedef struct ArrowSchema ArrowSchema;
+typedef struct ArrowArray ArrowArray;
+typedef struct ArrowArrayStream ArrowArrayStream;
+
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+POLARS_BRIDGE_API uint32_t bridge_abi_version(void);
+
+/**
+ * 协商协议版本，输出 HandshakeReport（protobuf 字节，需调用 bridge_output_free 释放）
+ */
+POLARS_BRIDGE_API
+int bridge_handshake(uint32_t client_abi,
+                     uint32_t client_plan_version,
+                     uint8_t **output_ptr,
+                     size_t *output_len);
+
+POLARS_BRIDGE_API int bridge_engine_version(const char **ptr_out, size_t *len_out);
+
+/**
+ * 输出 EngineInfo（protobuf 字节，需调用 bridge_output_free 释放）
+ */
+POLARS_BRIDGE_API int bridge_engine_info(uint8_t **output_ptr, size_t *output_len);
+
+POLARS_BRIDGE_API int bridge_capabilities(const char **ptr_out, size_t *len_out);
+
+POLARS_BRIDGE_API int bridge_last_error(uint8_t **ptr_out, size_t *len_out);
+
+/**
+ * 把最近一次错误消息复制到调用方提供的缓冲区（不以 NUL 结尾）
+ *
+ * out_len 总是写入消息的完整长度；缓冲区不足时只复制前 buf_len 字节并返回 InvalidArgument，
+ * 调用方可以按 out_len 重新分配后再次调用。没有错误时 out_len 为 0。
+ */
+POLARS_BRIDGE_API
+int bridge_last_error_copy(uint8_t *buf,
+                           size_t buf_len,
+                           size_t *out_len);
+
+/**
+ * 最近一次错误的结构化形式（ErrorDetail protobuf，需调用 bridge_output_free 释放；没有错误时输出为空）
+ */
+POLARS_BRIDGE_API
+int bridge_last_error_detail(uint8_t **output_ptr,
+                             size_t *output_len);
+
+/**
+ * 释放 bridge_last_error 返回的缓冲区（ptr / len 必须原样传回）
+ */
+POLARS_BRIDGE_API void bridge_last_error_free(uint8_t *ptr, size_t len);
+
+POLARS_BRIDGE_API
+int bridge_plan_compile(const uint8_t *plan_bytes_ptr,
+                        size_t plan_bytes_len,
+                        uint64_t *out_plan_handle_ptr);
+
+/**
+ * Plan 结构指纹（与序列化字段顺序、节点 ID 无关）
+ */
+POLARS_BRIDGE_API int bridge_plan_hash(uint64_t plan_handle, uint64_t *out_hash_ptr);
+
+/**
+ * 估算 Plan 的执行代价（PlanEstimate protobuf，需调用 bridge_output_free 释放）
+ *
+ * input_schema 为 Schema protobuf（可为空），用于在没有数据的情况下推断 MemoryScan 的输出 schema。
+ */
+POLARS_BRIDGE_API
+int bridge_plan_estimate(uint64_t plan_handle,
+                         const uint8_t *input_schema_ptr,
+                         size_t input_schema_len,
+                         uint8_t **output_ptr,
+                         size_t *output_len);
+
+POLARS_BRIDGE_API void bridge_plan_free(uint64_t plan_handle);
+
+POLARS_BRIDGE_API int bridge_regex_precompile(const char *pattern_ptr, size_t pattern_len);
+
+/**
+ * 当前缓存的正则数量
+ */
+POLARS_BRIDGE_API uint64_t bridge_regex_cache_len(void);
+
+POLARS_BRIDGE_API void bridge_regex_cache_clear(void);
+
+POLARS_BRIDGE_API
+int bridge_plan_execute_simple(uint64_t plan_handle,
+                               const char *_input_json_ptr,
+                               size_t _input_json_len,
+                               uint8_t **output_ptr,
+                               size_t *output_len);
+
+POLARS_BRIDGE_API
+int bridge_plan_collect_df(uint64_t plan_handle,
+                           uint64_t input_df_handle,
+                           uint64_t *out_df_handle_ptr);
+
+/**
+ * 获取结果 DataFrame 句柄的执行元数据（ResultMetadata protobuf，需调用 bridge_output_free 释放）
+ */
+POLARS_BRIDGE_API
+int bridge_result_metadata(uint64_t df_handle,
+                           uint8_t **output_ptr,
+                           size_t *output_len);
+
+/**
+ * Explain analyze：执行 Plan 并输出带实际行数与耗时的计划文本（需调用 bridge_output_free 释放）
+ */
+POLARS_BRIDGE_API
+int bridge_plan_explain_analyze(uint64_t plan_handle,
+                                uint64_t input_df_handle,
+                                uint8_t **output_ptr,
+                                size_t *output_len);
+
+/**
+ * 带执行提示（ExecutionHints protobuf，可为空）执行并返回 DataFrame 句柄
+ */
+POLARS_BRIDGE_API
+int bridge_plan_collect_df_with_hints(uint64_t plan_handle,
+                                      uint64_t input_df_handle,
+                                      const uint8_t *hints_ptr,
+                                      size_t hints_len,
+                                      uint64_t *out_df_handle_ptr);
+
+POLARS_BRIDGE_API void bridge_output_free(uint8_t *ptr, size_t len);
+
+POLARS_BRIDGE_API
+int bridge_df_to_ipc(uint64_t df_handle,
+                     uint8_t **output_ptr,
+                     size_t *output_len);
+
+/**
+ * DataFrame -> JSON 行数组（JsonRowOptions protobuf 可为空；输出需调用 bridge_output_free 释放）
+ */
+POLARS_BRIDGE_API
+int bridge_df_to_json(uint64_t df_handle,
+                      const uint8_t *options_ptr,
+                      size_t options_len,
+                      uint8_t **output_ptr,
+                      size_t *output_len);
+
+POLARS_BRIDGE_API int bridge_df_print(uint64_t df_handle);
+
+POLARS_BRIDGE_API
+int bridge_df_col_stats(uint64_t df_handle,
+                        const char *name_ptr,
+                        size_t name_len,
+                        uint8_t **output_ptr,
+                        size_t *output_len);
+
+/**
+ * 读取单个单元格（输出 Scalar protobuf，需调用 bridge_output_free 释放）
+ */
+POLARS_BRIDGE_API
+int bridge_df_scalar(uint64_t df_handle,
+                     const char *name_ptr,
+                     size_t name_len,
+                     uint64_t row,
+                     uint8_t **output_ptr,
+                     size_t *output_len);
+
+/**
+ * 读取 1x1 DataFrame 的唯一值（输出 Scalar protobuf，需调用 bridge_output_free 释放）
+ */
+POLARS_BRIDGE_API
+int bridge_df_item(uint64_t df_handle,
+                   uint8_t **output_ptr,
+                   size_t *output_len);
+
+/**
+ * 检查列是否按指定方向排序（null 视为最小值），结果写入 out_sorted（1 = 已排序）
+ */
+POLARS_BRIDGE_API
+int bridge_df_is_sorted(uint64_t df_handle,
+                        const char *name_ptr,
+                        size_t name_len,
+                        int descending,
+                        int *out_sorted);
+
+/**
+ * 为列设置排序标记并返回新的 DataFrame 句柄（不校验数据，需要时先调用 bridge_df_is_sorted）
+ */
+POLARS_BRIDGE_API
+int bridge_df_set_sorted(uint64_t df_handle,
+                         const char *name_ptr,
+                         size_t name_len,
+                         int descending,
+                         uint64_t *out_df_handle_ptr);
+
+/**
+ * 在 DataFrame 上求值单个 Expr（protobuf），结果为单列 DataFrame 句柄（无需构造完整 Plan）
+ */
+POLARS_BRIDGE_API
+int bridge_expr_evaluate(uint64_t df_handle,
+                         const uint8_t *expr_bytes_ptr,
+                         size_t expr_bytes_len,
+                         uint64_t *out_df_handle_ptr);
+
+/**
+ * 在 DataFrame 上批量添加 / 替换列（ExprList protobuf），结果为新的 DataFrame 句柄（原句柄不变）
+ */
+POLARS_BRIDGE_API
+int bridge_df_with_columns(uint64_t df_handle,
+                           const uint8_t *exprs_ptr,
+                           size_t exprs_len,
+                           uint64_t *out_df_handle_ptr);
+
+/**
+ * 批量改名（RenameOptions protobuf：显式的列名对 + 前缀 / 后缀 / 大小写规则），结果为新的 DataFrame 句柄（原句柄不变）
+ */
+POLARS_BRIDGE_API
+int bridge_df_rename_columns(uint64_t df_handle,
+                             const uint8_t *options_ptr,
+                             size_t options_len,
+                             uint64_t *out_df_handle_ptr);
+
+/**
+ * 按键列对比两个 DataFrame：差异行写入新的 DataFrame 句柄，摘要输出 DataDiff protobuf
+ *
+ * 差异行的 `_diff` 列为 added / removed / changed；changed 行取右侧的新值，
+ * 旧值放在 `<列名>_before` 列中。
+ */
+POLARS_BRIDGE_API
+int bridge_df_diff(uint64_t left_df_handle,
+                   uint64_t right_df_handle,
+                   const uint8_t *options_ptr,
+                   size_t options_len,
+                   uint64_t *out_df_handle_ptr,
+                   uint8_t **output_ptr,
+                   size_t *output_len);
+
+POLARS_BRIDGE_API
+int bridge_df_write_ipc(uint64_t df_handle,
+                        const char *path_ptr,
+                        size_t path_len,
+                        const uint8_t *options_ptr,
+                        size_t options_len);
+
+POLARS_BRIDGE_API
+int bridge_df_write_ndjson(uint64_t df_handle,
+                           const char *path_ptr,
+                           size_t path_len,
+                           const uint8_t *options_ptr,
+                           size_t options_len);
+
+POLARS_BRIDGE_API
+int bridge_df_write_avro(uint64_t df_handle,
+                         const char *path_ptr,
+                         size_t path_len,
+                         const uint8_t *options_ptr,
+                         size_t options_len);
+
+/**
+ * 保存检查点：Arrow IPC 文件，带格式版本等元数据（进程重启后用 bridge_df_restore 恢复）
+ */
+POLARS_BRIDGE_API
+int bridge_df_checkpoint(uint64_t df_handle,
+                         const char *path_ptr,
+                         size_t path_len);
+
+/**
+ * 从检查点恢复 DataFrame，返回新的句柄
+ */
+POLARS_BRIDGE_API
+int bridge_df_restore(const char *path_ptr,
+                      size_t path_len,
+                      uint64_t *out_df_handle);
+
+POLARS_BRIDGE_API void bridge_df_free(uint64_t df_handle);
+
+/**
+ * 为 DataFrame 句柄增加一个引用：每次 retain 都需要对应一次 bridge_df_free
+ */
+POLARS_BRIDGE_API int bridge_df_retain(uint64_t df_handle);
+
+POLARS_BRIDGE_API
+int bridge_parquet_metadata(const char *path_ptr,
+                            size_t path_len,
+                            uint8_t **output_ptr,
+                            size_t *output_len);
+
+/**
+ * 按 CsvScan protobuf 中的路径与解析选项推断 CSV schema
+ */
+POLARS_BRIDGE_API
+int bridge_csv_infer_schema(const uint8_t *scan_ptr,
+                            size_t scan_len,
+                            uint8_t **output_ptr,
+                            size_t *output_len);
+
+POLARS_BRIDGE_API int bridge_plan_execute_and_print(uint64_t plan_handle);
+
+POLARS_BRIDGE_API
+int bridge_plan_execute_arrow(uint64_t plan_handle,
+                              const ArrowSchema *input_schema,
+                              const ArrowArray *input_array,
+                              ArrowSchema *output_schema,
+                              ArrowArray *output_array);
+
+/**
+ * 取出当前线程最近一次 Arrow 导入的警告（ImportWarnings protobuf，需调用 bridge_output_free 释放）
+ *
+ * 在 ArrowImportOptions.unsupported_columns 为宽松模式、或带时区的时间戳回退为 UTC 时产生；读取后清空。
+ */
+POLARS_BRIDGE_API
+int bridge_arrow_import_warnings(uint8_t **output_ptr,
+                                 size_t *output_len);
+
+/**
+ * 执行 Plan 并以 Arrow C Stream 输出（按 ArrowStreamOptions 切分为有界批次，便于流水线消费）
+ */
+POLARS_BRIDGE_API
+int bridge_plan_execute_arrow_stream(uint64_t plan_handle,
+                                     const ArrowSchema *input_schema,
+                                     const ArrowArray *input_array,
+                                     const uint8_t *options_ptr,
+                                     size_t options_len,
+                                     ArrowArrayStream *output_stream);
+
+POLARS_BRIDGE_API int bridge_frame_new(uint64_t *out_frame_handle);
+
+/**
+ * 追加一个 Arrow IPC（文件格式）分块
+ */
+POLARS_BRIDGE_API
+int bridge_frame_append_ipc(uint64_t frame_handle,
+                            const uint8_t *data_ptr,
+                            size_t data_len);
+
+/**
+ * 通过 Arrow C Data Interface 追加一个分块（数组所有权转移给 bridge）
+ */
+POLARS_BRIDGE_API
+int bridge_frame_append_arrow(uint64_t frame_handle,
+                              const ArrowSchema *input_schema,
+                              const ArrowArray *input_array);
+
+/**
+ * 结束构建：释放 frame 句柄并返回 DataFrame 句柄
+ */
+POLARS_BRIDGE_API int bridge_frame_finish(uint64_t frame_handle, uint64_t *out_df_handle);
+
+POLARS_BRIDGE_API void bridge_frame_free(uint64_t frame_handle);
+
+POLARS_BRIDGE_API
+int bridge_reader_register(int (*callback)(void*, uint8_t*, size_t, size_t*),
+                           void *ctx,
+                           uint64_t *out_reader_handle);
+
+/**
+ * 注销尚未被消费的读取器
+ */
+POLARS_BRIDGE_API void bridge_reader_free(uint64_t reader_handle);
+
+POLARS_BRIDGE_API
+int bridge_batch_provider_register(int (*callback)(void*,
+                                                   const uint8_t*,
+                                                   size_t,
+                                                   ArrowSchema*,
+                                                   ArrowArray*,
+                                                   int*),
+                                   void *ctx,
+                                   uint64_t *out_provider_handle);
+
+/**
+ * 注销批次提供者（正在执行的扫描持有自己的引用，不受影响）
+ */
+POLARS_BRIDGE_API void bridge_batch_provider_free(uint64_t provider_handle);
+
+/**
+ *
+ * 生命周期：成功后 Polars 直接引用该缓冲区，Go 必须保持其有效、不可修改、不可移动，
+ * 直到 dealloc(ctx) 被调用（最后一个引用释放时，可能发生在任意线程上）。
+ * 只要 dealloc 非空，无论成功与否它都恰好被调用一次。
+ */
+POLARS_BRIDGE_API
+int bridge_series_from_buffer(const char *name_ptr,
+                              size_t name_len,
+                              int data_type,
+                              const uint8_t *data_ptr,
+                              size_t len,
+                              void (*dealloc)(void*),
+                              void *ctx,
+                              uint64_t *out_df_handle_ptr);
+
+POLARS_BRIDGE_API int bridge_session_new(uint64_t *out_session_handle);
+
+/**
+ * 注册（或替换）一张表：df_handle 非 0 时注册该 DataFrame 的快照（之后释放句柄不影响会话），
+ * 否则注册 node（Node protobuf，通常是扫描节点，执行时才构建）。
+ */
+POLARS_BRIDGE_API
+int bridge_table_register(uint64_t session_handle,
+                          const char *name_ptr,
+                          size_t name_len,
+                          uint64_t df_handle,
+                          const uint8_t *node_bytes_ptr,
+                          size_t node_bytes_len);
+
+/**
+ * 注销一张表（不存在时为空操作）
+ */
+POLARS_BRIDGE_API
+int bridge_table_unregister(uint64_t session_handle,
+                            const char *name_ptr,
+                            size_t name_len);
+
+/**
+ * 释放会话（正在执行的 Plan 持有自己的引用，不受影响）
+ */
+POLARS_BRIDGE_API void bridge_session_free(uint64_t session_handle);
+
+POLARS_BRIDGE_API int bridge_agg_new(uint64_t plan_handle, uint64_t *out_agg_handle);
+
+/**
+ * 通过 Arrow C Data Interface 喂入一个批次（数组所有权转移给 bridge）
+ */
+POLARS_BRIDGE_API
+int bridge_agg_update_arrow(uint64_t agg_handle,
+                            const ArrowSchema *input_schema,
+                            const ArrowArray *input_array);
+
+/**
+ * 喂入一个已有的 DataFrame 句柄（句柄本身不受影响）
+ */
+POLARS_BRIDGE_API int bridge_agg_update_df(uint64_t agg_handle, uint64_t df_handle);
+
+/**
+ * 按当前状态计算聚合结果，返回新的 DataFrame 句柄（状态保留，可继续 update）
+ */
+POLARS_BRIDGE_API
+int bridge_agg_finalize(uint64_t agg_handle,
+                        uint64_t *out_df_handle);
+
+POLARS_BRIDGE_API void bridge_agg_free(uint64_t agg_handle);
+
+POLARS_BRIDGE_API
+int bridge_conformance_run(const char *dir_ptr,
+                           size_t dir_len,
+                           uint8_t **out_report_ptr,
+                           size_t *out_report_len);
+
+/**
+ * 开启或关闭录制（RecorderOptions protobuf，dir 为空表示关闭）
+ */
+POLARS_BRIDGE_API int bridge_recorder_configure(const uint8_t *options_ptr, size_t options_len);
+
+/**
+ * 回放一次录制，返回结果 DataFrame 句柄（可用 bridge_result_metadata 查看耗时）
+ *
+ * input_df_handle 非 0 时代替录制的输入；录制中没有输入且 Plan 含 MemoryScan 时必须提供。
+ * 凭据默认在录制时被抹掉，访问云存储 / 数据库的 Plan 需要在环境中提供凭据。
+ */
+POLARS_BRIDGE_API
+int bridge_replay(const char *dir_ptr,
+                  size_t dir_len,
+                  uint64_t input_df_handle,
+                  uint64_t *out_df_handle);
+
+POLARS_BRIDGE_API
+int bridge_stream_new(uint64_t plan_handle,
+                      const uint8_t *options_ptr,
+                      size_t options_len,
+                      int (*callback)(void*, uint64_t, ArrowSchema*, ArrowArray*),
+                      void *ctx,
+                      uint64_t *out_stream_handle);
+
+/**
+ * 处理至多 max_batches 个微批（0 表示直到数据源结束），输出累计的 StreamStats（需调用 bridge_output_free 释放）
+ */
+POLARS_BRIDGE_API
+int bridge_stream_run(uint64_t stream_handle,
+                      uint64_t max_batches,
+                      uint8_t **out_stats_ptr,
+                      size_t *out_stats_len);
+
+POLARS_BRIDGE_API void bridge_stream_free(uint64_t stream_handle);
+
+POLARS_BRIDGE_API
+int bridge_df_from_columns(const char *json_ptr,
+                           size_t json_len,
+                           uint64_t *out_df_handle);
+
+/**
+ * 从行式 protobuf（RowBatch）创建 DataFrame，适合小批量的请求数据
+ */
+POLARS_BRIDGE_API
+int bridge_df_from_rows(const uint8_t *rows_ptr,
+                        size_t rows_len,
+                        uint64_t *out_df_handle);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* POLARS_BRIDGE_H */
@@ -0,0 +1,500 @@
+//! FFI ABI 的检查
+//!
+//! C 头文件（include/polars_bridge.h）由 build.rs 通过 cbindgen 从导出函数生成并提交到仓库；
+//! 这里把导出函数的签名与 Arrow C Data Interface 结构体的布局固定下来，
+//! 任何不兼容的修改都会在编译时失败，必须同时更新本文件（并视情况提升 ABI_VERSION）。
+//! 测试再核对头文件：每个导出函数都有声明，参数与返回类型与这里的签名一致，结构体布局与头文件中的定义一致。
+
+use std::ffi::c_void;
+use std::mem::{align_of, size_of};
+use std::os::raw::{c_char, c_int};
+
+use polars_arrow::ffi::{ArrowArray, ArrowArrayStream, ArrowSchema};
+
+// Arrow C Data Interface（https://arrow.apache.org/docs/format/CDataInterface.html）
+#[cfg(target_pointer_width = "64")]
+const _: () = {
+    assert!(size_of::<ArrowSchema>() == 72);
+    assert!(align_of::<ArrowSchema>() == 8);
+    assert!(size_of::<ArrowArray>() == 80);
+    assert!(align_of::<ArrowArray>() == 8);
+    assert!(size_of::<ArrowArrayStream>() == 40);
+    assert!(align_of::<ArrowArrayStream>() == 8);
+};
+
+// Go 侧回调以裸函数指针传入，Option<fn> 必须与可空指针同宽
+const _: () = {
+    assert!(size_of::<Option<crate::reader_scan::ReadCallback>>() == size_of::<*const c_void>());
+    assert!(size_of::<Option<crate::batch_provider::BatchCallback>>() == size_of::<*const c_void>());
+    assert!(size_of::<Option<crate::buffer::DeallocCallback>>() == size_of::<*const c_void>());
+    assert!(size_of::<Option<crate::stream::ResultCallback>>() == size_of::<*const c_void>());
+    assert!(size_of::<c_int>() == 4);
+};
+
+// 导出函数签名
+const _: () = {
+    let _: extern "C" fn() -> u32 = crate::bridge_abi_version;
+    let _: extern "C" fn(u32, u32, *mut *mut u8, *mut usize) -> c_int = crate::bridge_handshake;
+    let _: extern "C" fn(*mut *const c_char, *mut usize) -> c_int = crate::bridge_engine_version;
+    let _: extern "C" fn(*mut *mut u8, *mut usize) -> c_int = crate::bridge_engine_info;
+    let _: extern "C" fn(*mut *const c_char, *mut usize) -> c_int = crate::bridge_capabilities;
+    let _: extern "C" fn(*mut *mut u8, *mut usize) -> c_int = crate::bridge_last_error;
+    let _: extern "C" fn(*mut u8, usize, *mut usize) -> c_int = crate::bridge_last_error_copy;
+    let _: extern "C" fn(*mut *mut u8, *mut usize) -> c_int = crate::bridge_last_error_detail;
+    let _: extern "C" fn(*mut u8, usize) = crate::bridge_last_error_free;
+    let _: extern "C" fn(*const u8, usize, *mut u64) -> c_int = crate::bridge_plan_compile;
+    let _: extern "C" fn(u64, *mut u64) -> c_int = crate::bridge_plan_hash;
+    let _: extern "C" fn(
+        u64,
+        *const u8,
+        usize,
+        *mut *mut u8,
+        *mut usize,
+    ) -> c_int = crate::bridge_plan_estimate;
+    let _: extern "C" fn(u64) = crate::bridge_plan_free;
+    let _: extern "C" fn(*const c_char, usize) -> c_int = crate::bridge_regex_precompile;
+    let _: extern "C" fn() -> u64 = crate::bridge_regex_cache_len;
+    let _: extern "C" fn() = crate::bridge_regex_cache_clear;
+    let _: extern "C" fn(
+        u64,
+        *const c_char,
+        usize,
+        *mut *mut u8,
+        *mut usize,
+    ) -> c_int = crate::bridge_plan_execute_simple;
+    let _: extern "C" fn(u64, u64, *mut u64) -> c_int = crate::bridge_plan_collect_df;
+    let _: extern "C" fn(u64, *mut *mut u8, *mut usize) -> c_int = crate::bridge_result_metadata;
+    let _: extern "C" fn(
+        u64,
+        u64,
+        *mut *mut u8,
+        *mut usize,
+    ) -> c_int = crate::bridge_plan_explain_analyze;
+    let _: extern "C" fn(
+        u64,
+        u64,
+        *const u8,
+        usize,
+        *mut u64,
+    ) -> c_int = crate::bridge_plan_collect_df_with_hints;
+    let _: extern "C" fn(*mut u8, usize) = crate::bridge_output_free;
+    let _: extern "C" fn(u64, *mut *mut u8, *mut usize) -> c_int = crate::bridge_df_to_ipc;
+    let _: extern "C" fn(
+        u64,
+        *const u8,
+        usize,
+        *mut *mut u8,
+        *mut usize,
+    ) -> c_int = crate::bridge_df_to_json;
+    let _: extern "C" fn(u64) -> c_int = crate::bridge_df_print;
+    let _: extern "C" fn(
+        u64,
+        *const c_char,
+        usize,
+        *mut *mut u8,
+        *mut usize,
+    ) -> c_int = crate::bridge_df_col_stats;
+    let _: extern "C" fn(
+        u64,
+        *const c_char,
+        usize,
+        u64,
+        *mut *mut u8,
+        *mut usize,
+    ) -> c_int = crate::bridge_df_scalar;
+    let _: extern "C" fn(u64, *mut *mut u8, *mut usize) -> c_int = crate::bridge_df_item;
+    let _: extern "C" fn(
+        u64,
+        *const c_char,
+        usize,
+        c_int,
+        *mut c_int,
+    ) -> c_int = crate::bridge_df_is_sorted;
+    let _: extern "C" fn(
+        u64,
+        *const c_char,
+        usize,
+        c_int,
+        *mut u64,
+    ) -> c_int = crate::bridge_df_set_sorted;
+    let _: extern "C" fn(u64, *const u8, usize, *mut u64) -> c_int = crate::bridge_expr_evaluate;
+    let _: extern "C" fn(u64, *const u8, usize, *mut u64) -> c_int = crate::bridge_df_with_columns;
+    let _: extern "C" fn(u64, *const u8, usize, *mut u64) -> c_int = crate::bridge_df_rename_columns;
+    let _: extern "C" fn(
+        u64,
+        u64,
+        *const u8,
+        usize,
+        *mut u64,
+        *mut *mut u8,
+        *mut usize,
+    ) -> c_int = crate::bridge_df_diff;
+    let _: extern "C" fn(
+        u64,
+        *const c_char,
+        usize,
+        *const u8,
+        usize,
+    ) -> c_int = crate::bridge_df_write_ipc;
+    let _: extern "C" fn(
+        u64,
+        *const c_char,
+        usize,
+        *const u8,
+        usize,
+    ) -> c_int = crate::bridge_df_write_ndjson;
+    let _: extern "C" fn(
+        u64,
+        *const c_char,
+        usize,
+        *const u8,
+        usize,
+    ) -> c_int = crate::bridge_df_write_avro;
+    let _: extern "C" fn(u64, *const c_char, usize) -> c_int = crate::bridge_df_checkpoint;
+    let _: extern "C" fn(*const c_char, usize, *mut u64) -> c_int = crate::bridge_df_restore;
+    let _: extern "C" fn(u64) = crate::bridge_df_free;
+    let _: extern "C" fn(u64) -> c_int = crate::bridge_df_retain;
+    let _: extern "C" fn(
+        *const c_char,
+        usize,
+        *mut *mut u8,
+        *mut usize,
+    ) -> c_int = crate::bridge_parquet_metadata;
+    let _: extern "C" fn(
+        *const u8,
+        usize,
+        *mut *mut u8,
+        *mut usize,
+    ) -> c_int = crate::bridge_csv_infer_schema;
+    let _: extern "C" fn(u64) -> c_int = crate::bridge_plan_execute_and_print;
+    let _: extern "C" fn(
+        u64,
+        *const ArrowSchema,
+        *const ArrowArray,
+        *mut ArrowSchema,
+        *mut ArrowArray,
+    ) -> c_int = crate::bridge_plan_execute_arrow;
+    let _: extern "C" fn(*mut *mut u8, *mut usize) -> c_int = crate::bridge_arrow_import_warnings;
+    let _: extern "C" fn(
+        u64,
+        *const ArrowSchema,
+        *const ArrowArray,
+        *const u8,
+        usize,
+        *mut ArrowArrayStream,
+    ) -> c_int = crate::bridge_plan_execute_arrow_stream;
+    let _: extern "C" fn(*mut u64) -> c_int = crate::bridge_frame_new;
+    let _: extern "C" fn(u64, *const u8, usize) -> c_int = crate::bridge_frame_append_ipc;
+    let _: extern "C" fn(
+        u64,
+        *const ArrowSchema,
+        *const ArrowArray,
+    ) -> c_int = crate::bridge_frame_append_arrow;
+    let _: extern "C" fn(u64, *mut u64) -> c_int = crate::bridge_frame_finish;
+    let _: extern "C" fn(u64) = crate::bridge_frame_free;
+    let _: extern "C" fn(
+        Option<crate::reader_scan::ReadCallback>,
+        *mut c_void,
+        *mut u64,
+    ) -> c_int = crate::bridge_reader_register;
+    let _: extern "C" fn(u64) = crate::bridge_reader_free;
+    let _: extern "C" fn(
+        Option<crate::batch_provider::BatchCallback>,
+        *mut c_void,
+        *mut u64,
+    ) -> c_int = crate::bridge_batch_provider_register;
+    let _: extern "C" fn(u64) = crate::bridge_batch_provider_free;
+    let _: extern "C" fn(
+        *const c_char,
+        usize,
+        c_int,
+        *const u8,
+        usize,
+        Option<crate::buffer::DeallocCallback>,
+        *mut c_void,
+        *mut u64,
+    ) -> c_int = crate::bridge_series_from_buffer;
+    let _: extern "C" fn(*mut u64) -> c_int = crate::bridge_session_new;
+    let _: extern "C" fn(
+        u64,
+        *const c_char,
+        usize,
+        u64,
+        *const u8,
+        usize,
+    ) -> c_int = crate::bridge_table_register;
+    let _: extern "C" fn(u64, *const c_char, usize) -> c_int = crate::bridge_table_unregister;
+    let _: extern "C" fn(u64) = crate::bridge_session_free;
+    let _: extern "C" fn(u64, *mut u64) -> c_int = crate::bridge_agg_new;
+    let _: extern "C" fn(
+        u64,
+        *const ArrowSchema,
+        *const ArrowArray,
+    ) -> c_int = crate::bridge_agg_update_arrow;
+    let _: extern "C" fn(u64, u64) -> c_int = crate::bridge_agg_update_df;
+    let _: extern "C" fn(u64, *mut u64) -> c_int = crate::bridge_agg_finalize;
+    let _: extern "C" fn(u64) = crate::bridge_agg_free;
+    let _: extern "C" fn(*const c_char, usize, *mut *mut u8, *mut usize) -> c_int =
+        crate::bridge_conformance_run;
+    let _: extern "C" fn(*const u8, usize) -> c_int = crate::bridge_recorder_configure;
+    let _: extern "C" fn(*const c_char, usize, u64, *mut u64) -> c_int = crate::bridge_replay;
+    let _: extern "C" fn(
+        u64,
+        *const u8,
+        usize,
+        Option<crate::stream::ResultCallback>,
+        *mut c_void,
+        *mut u64,
+    ) -> c_int = crate::bridge_stream_new;
+    let _: extern "C" fn(u64, u64, *mut *mut u8, *mut usize) -> c_int = crate::bridge_stream_run;
+    let _: extern "C" fn(u64) = crate::bridge_stream_free;
+    let _: extern "C" fn(*const c_char, usize, *mut u64) -> c_int = crate::bridge_df_from_columns;
+    let _: extern "C" fn(*const u8, usize, *mut u64) -> c_int = crate::bridge_df_from_rows;
+};
+
+// 提交到仓库的 C 头文件必须与导出函数及上面的签名一致；Arrow 结构体按头文件中的定义读回字段
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::ffi::CStr;
+
+    use polars_arrow::array::PrimitiveArray;
+    use polars_arrow::datatypes::{ArrowDataType, Field};
+    use polars_arrow::ffi::{export_array_to_c, export_field_to_c};
+
+    use super::*;
+
+    const HEADER: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/include/polars_bridge.h"));
+    const LIB: &str = include_str!("lib.rs");
+    const PINS: &str = include_str!("abi.rs");
+
+    // include/polars_bridge.h 中的 struct ArrowSchema / ArrowArray / ArrowArrayStream
+    #[repr(C)]
+    struct CArrowSchema {
+        format: *const c_char,
+        name: *const c_char,
+        metadata: *const c_char,
+        flags: i64,
+        n_children: i64,
+        children: *mut *mut CArrowSchema,
+        dictionary: *mut CArrowSchema,
+        release: Option<unsafe extern "C" fn(*mut CArrowSchema)>,
+        private_data: *mut c_void,
+    }
+
+    #[repr(C)]
+    struct CArrowArray {
+        length: i64,
+        null_count: i64,
+        offset: i64,
+        n_buffers: i64,
+        n_children: i64,
+        buffers: *mut *const c_void,
+        children: *mut *mut CArrowArray,
+        dictionary: *mut CArrowArray,
+        release: Option<unsafe extern "C" fn(*mut CArrowArray)>,
+        private_data: *mut c_void,
+    }
+
+    #[repr(C)]
+    struct CArrowArrayStream {
+        get_schema: Option<unsafe extern "C" fn(*mut CArrowArrayStream, *mut CArrowSchema) -> c_int>,
+        get_next: Option<unsafe extern "C" fn(*mut CArrowArrayStream, *mut CArrowArray) -> c_int>,
+        get_last_error: Option<unsafe extern "C" fn(*mut CArrowArrayStream) -> *const c_char>,
+        release: Option<unsafe extern "C" fn(*mut CArrowArrayStream)>,
+        private_data: *mut c_void,
+    }
+
+    #[test]
+    fn arrow_structs_match_header_layout() {
+        assert_eq!(size_of::<ArrowSchema>(), size_of::<CArrowSchema>());
+        assert_eq!(align_of::<ArrowSchema>(), align_of::<CArrowSchema>());
+        assert_eq!(size_of::<ArrowArray>(), size_of::<CArrowArray>());
+        assert_eq!(align_of::<ArrowArray>(), align_of::<CArrowArray>());
+        assert_eq!(size_of::<ArrowArrayStream>(), size_of::<CArrowArrayStream>());
+        assert_eq!(align_of::<ArrowArrayStream>(), align_of::<CArrowArrayStream>());
+
+        let field = Field::new("a".into(), ArrowDataType::Int64, true);
+        let schema = export_field_to_c(&field);
+        let c_schema = unsafe { &*(&schema as *const ArrowSchema as *const CArrowSchema) };
+        assert_eq!(unsafe { CStr::from_ptr(c_schema.format) }.to_str(), Ok("l"));
+        assert_eq!(unsafe { CStr::from_ptr(c_schema.name) }.to_str(), Ok("a"));
+        assert_eq!(c_schema.flags, 2); // ARROW_FLAG_NULLABLE
+        assert_eq!(c_schema.n_children, 0);
+        assert!(c_schema.release.is_some());
+
+        let values = PrimitiveArray::<i64>::from([Some(1), None, Some(3)]);
+        let array = export_array_to_c(Box::new(values));
+        let c_array = unsafe { &*(&array as *const ArrowArray as *const CArrowArray) };
+        assert_eq!(c_array.length, 3);
+        assert_eq!(c_array.null_count, 1);
+        assert_eq!(c_array.offset, 0);
+        assert_eq!(c_array.n_buffers, 2);
+        assert_eq!(c_array.n_children, 0);
+        assert!(c_array.release.is_some());
+    }
+
+    #[test]
+    fn header_declares_every_export() {
+        let exports: Vec<&str> = LIB
+            .lines()
+            .filter_map(|line| {
+                line.trim()
+                    .strip_prefix("#[export_name = concat!(env!(\"BRIDGE_SYMBOL_PREFIX\"), \"")?
+                    .strip_suffix("\")]")
+            })
+            .collect();
+        let header = header_functions();
+        let mut declared: Vec<&str> = header.keys().map(String::as_str).collect();
+        let mut pinned: Vec<&str> = pinned_signatures().into_keys().collect();
+        let mut exported = exports.clone();
+        exported.sort_unstable();
+        declared.sort_unstable();
+        pinned.sort_unstable();
+
+        assert!(!exported.is_empty());
+        assert_eq!(exported, declared, "include/polars_bridge.h is out of date (rebuild to regenerate)");
+        assert_eq!(exported, pinned, "every export needs a signature in abi.rs");
+    }
+
+    #[test]
+    fn header_signatures_match_pins() {
+        let declared = header_functions();
+        for (name, (params, ret)) in pinned_signatures() {
+            let expected: Vec<String> = params.iter().map(|p| c_type(p)).collect();
+            let expected_ret = ret.map_or_else(|| "void".to_string(), c_type);
+            let (actual, actual_ret) = &declared[name];
+            assert_eq!(&expected, actual, "parameters of {name}");
+            assert_eq!(&expected_ret, actual_ret, "return type of {name}");
+        }
+    }
+
+    // Rust 签名中的类型在头文件中的写法（去掉空白）
+    fn c_type(rust: &str) -> String {
+        if let Some(inner) = rust.strip_prefix("*mut ") {
+            return format!("{}*", c_type(inner));
+        }
+        if let Some(inner) = rust.strip_prefix("*const ") {
+            let inner = c_type(inner);
+            return if inner.ends_with('*') { format!("{inner}const*") } else { format!("const{inner}*") };
+        }
+        match rust {
+            "u8" => "uint8_t",
+            "u32" => "uint32_t",
+            "u64" => "uint64_t",
+            "usize" => "size_t",
+            "c_int" => "int",
+            "c_char" => "char",
+            "c_void" => "void",
+            "ArrowSchema" | "ArrowArray" | "ArrowArrayStream" => rust,
+            "Option<crate::reader_scan::ReadCallback>" => "int(*)(void*,uint8_t*,size_t,size_t*)",
+            "Option<crate::batch_provider::BatchCallback>" => {
+                "int(*)(void*,constuint8_t*,size_t,ArrowSchema*,ArrowArray*,int*)"
+            }
+            "Option<crate::buffer::DeallocCallback>" => "void(*)(void*)",
+            "Option<crate::stream::ResultCallback>" => "int(*)(void*,uint64_t,ArrowSchema*,ArrowArray*)",
+            other => panic!("no C spelling for Rust type {other}"),
+        }
+        .to_string()
+    }
+
+    // 上面 `let _: extern "C" fn(..) -> .. = crate::name;` 形式的签名：函数名 -> (参数类型, 返回类型)
+    fn pinned_signatures() -> BTreeMap<&'static str, (Vec<&'static str>, Option<&'static str>)> {
+        const PIN: &str = "let _: extern \"C\" fn(";
+
+        let mut pins = BTreeMap::new();
+        let (mut rest, _) = PINS.split_once("#[cfg(test)]").unwrap();
+        while let Some(start) = rest.find(PIN) {
+            rest = &rest[start + PIN.len()..];
+            let close = matching_paren(rest);
+            let params = split_top_level(&rest[..close]);
+            let (signature, tail) = rest[close + 1..].split_once('=').unwrap();
+            let ret = signature.trim().strip_prefix("->").map(str::trim);
+            let (target, _) = tail.split_once(';').unwrap();
+            let name = target.trim().strip_prefix("crate::").unwrap();
+            pins.insert(name, (params, ret));
+        }
+        pins
+    }
+
+    // 头文件中的函数声明：函数名 -> (参数类型, 返回类型)，类型去掉参数名与空白
+    fn header_functions() -> BTreeMap<String, (Vec<String>, String)> {
+        let (_, body) = HEADER.split_once("extern \"C\" {").unwrap();
+        let mut functions = BTreeMap::new();
+        for declaration in strip_comments(body).split(';') {
+            let Some((_, declaration)) = declaration.split_once("POLARS_BRIDGE_API") else {
+                continue;
+            };
+            let open = declaration.find('(').unwrap();
+            let head = declaration[..open].trim_end();
+            let name_start = head.rfind(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap() + 1;
+            let name = &head[name_start..];
+            let ret: String = head[..name_start].split_whitespace().collect();
+
+            let args = &declaration[open + 1..];
+            let params = split_top_level(&args[..matching_paren(args)])
+                .into_iter()
+                .filter(|param| *param != "void")
+                .map(|param| {
+                    let param = match param.split_once("(*") {
+                        // 函数指针参数：去掉 (*name) 中的名字
+                        Some((ret, rest)) => format!("{ret}(*{}", &rest[rest.find(')').unwrap()..]),
+                        None => param
+                            .trim_end_matches(|c: char| c.is_ascii_alphanumeric() || c == '_')
+                            .to_string(),
+                    };
+                    param.split_whitespace().collect()
+                })
+                .collect();
+            functions.insert(name.to_string(), (params, ret));
+        }
+        functions
+    }
+
+    fn strip_comments(text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find("/*") {
+            out.push_str(&rest[..start]);
+            let end = rest[start..].find("*/").unwrap();
+            rest = &rest[start + end + 2..];
+        }
+        out.push_str(rest);
+        out
+    }
+
+    // text 从左括号之后开始，返回与之匹配的右括号位置
+    fn matching_paren(text: &str) -> usize {
+        let mut depth = 0;
+        for (i, c) in text.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' if depth == 0 => return i,
+                ')' => depth -= 1,
+                _ => {}
+            }
+        }
+        panic!("unbalanced parentheses: {text}");
+    }
+
+    // 按不在括号（含尖括号）内的逗号拆分，去掉空项
+    fn split_top_level(text: &str) -> Vec<&str> {
+        let mut parts = Vec::new();
+        let (mut depth, mut start) = (0, 0);
+        for (i, c) in text.char_indices() {
+            match c {
+                '(' | '<' => depth += 1,
+                '>' if text[..i].ends_with('-') => {}
+                ')' | '>' => depth -= 1,
+                ',' if depth == 0 => {
+                    parts.push(text[start..i].trim());
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        parts.push(text[start..].trim());
+        parts.retain(|part| !part.is_empty());
+        parts
+    }
+}
@@ -1,14 +1,18 @@
-use std::sync::Arc;
+use std::cell::RefCell;
 
+use crate::columns::{dedup_column_names, DuplicatePolicy};
 use crate::error::BridgeError;
+use crate::proto;
 use polars::prelude::*;
-use polars_arrow::array::StructArray;
-use polars_arrow::datatypes::{ArrowDataType, ArrowSchema, Field};
+use polars_arrow::array::{
+    get_display, Array, FixedSizeListArray, ListArray, MapArray, PrimitiveArray, StructArray,
+};
+use polars_arrow::datatypes::{ArrowDataType, Field};
+use polars_arrow::offset::Offset;
 use polars_arrow::ffi::{
-    export_array_to_c, export_field_to_c, import_array_from_c, import_field_from_c, ArrowArray,
-    ArrowSchema as FFIArrowSchema,
+    export_array_to_c, export_field_to_c, export_iterator, import_array_from_c,
+    import_field_from_c, ArrowArray, ArrowArrayStream, ArrowSchema as FFIArrowSchema,
 };
-use polars_arrow::record_batch::RecordBatch;
 
 /// 将 Polars DataFrame 导出为 Arrow C Data Interface
 pub fn export_dataframe_to_arrow(
@@ -22,16 +26,9 @@ pub fn export_dataframe_to_arrow(
         ));
     }
 
-    let record_batch = df.clone().rechunk_to_record_batch(CompatLevel::newest());
-    let height = record_batch.height();
-    let (schema, arrays) = record_batch.into_schema_and_arrays();
-    let fields: Vec<Field> = schema.iter_values().cloned().collect();
-    let dtype = ArrowDataType::Struct(fields.clone());
-
-    let struct_array = StructArray::try_new(dtype, height, arrays, None)
-        .map_err(|e| BridgeError::ArrowExport(e.to_string()))?;
+    let struct_array = to_struct_array(df.clone())?;
+    let schema = export_field_to_c(&struct_field(&struct_array));
     let array = export_array_to_c(Box::new(struct_array));
-    let schema = export_field_to_c(&Field::new("".into(), ArrowDataType::Struct(fields), false));
 
     unsafe {
         std::ptr::write(out_array, array);
@@ -41,10 +38,103 @@ pub fn export_dataframe_to_arrow(
     Ok(())
 }
 
+/// 按批次上限把 DataFrame 导出为 Arrow C Stream（每个批次是一个 StructArray）
+///
+/// max_batch_rows 与 max_batch_bytes 同时设置时取更小的行数；都未设置时只有一个批次。
+pub fn export_dataframe_to_arrow_stream(
+    df: DataFrame,
+    options: &proto::ArrowStreamOptions,
+    out_stream: *mut ArrowArrayStream,
+) -> Result<(), BridgeError> {
+    if out_stream.is_null() {
+        return Err(BridgeError::InvalidArgument("Null output stream pointer".into()));
+    }
+
+    let height = df.height();
+    let mut batch_rows = height;
+    if options.max_batch_rows > 0 {
+        batch_rows = batch_rows.min(options.max_batch_rows as usize);
+    }
+    if options.max_batch_bytes > 0 && height > 0 {
+        let row_bytes = df.estimated_size().div_ceil(height).max(1);
+        batch_rows = batch_rows.min((options.max_batch_bytes as usize / row_bytes).max(1));
+    }
+    let batch_rows = batch_rows.max(1);
+
+    let field = struct_field(&to_struct_array(df.clear())?);
+    let batches = (0..height).step_by(batch_rows).map(move |offset| {
+        to_struct_array(df.slice(offset as i64, batch_rows))
+            .map(|array| Box::new(array) as Box<dyn Array>)
+            .map_err(|e| PolarsError::ComputeError(e.to_string().into()))
+    });
+    let stream = export_iterator(Box::new(batches), field);
+
+    unsafe {
+        std::ptr::write(out_stream, stream);
+    }
+    Ok(())
+}
+
+// DataFrame -> StructArray（每列为一个子数组）
+fn to_struct_array(df: DataFrame) -> Result<StructArray, BridgeError> {
+    let record_batch = df.rechunk_to_record_batch(CompatLevel::newest());
+    let height = record_batch.height();
+    let (schema, arrays) = record_batch.into_schema_and_arrays();
+    let fields: Vec<Field> = schema.iter_values().cloned().collect();
+
+    StructArray::try_new(ArrowDataType::Struct(fields), height, arrays, None)
+        .map_err(|e| BridgeError::ArrowExport(e.to_string()))
+}
+
+fn struct_field(array: &StructArray) -> Field {
+    Field::new("".into(), array.dtype().clone(), false)
+}
+
+// 最近一次 Arrow 导入的警告（宽松模式下被丢弃/替换的列等）
+thread_local! {
+    static IMPORT_WARNINGS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// 取出并清空当前线程最近一次导入的警告
+pub fn take_import_warnings() -> Vec<String> {
+    IMPORT_WARNINGS.with(|w| std::mem::take(&mut *w.borrow_mut()))
+}
+
+fn warn(message: String) {
+    IMPORT_WARNINGS.with(|w| w.borrow_mut().push(message));
+}
+
+/// Arrow 导入选项（来自 PlanOptions）
+pub struct ImportOptions {
+    pub duplicates: DuplicatePolicy,
+    pub categorical: proto::CategoricalImport,
+    pub categorical_columns: Vec<String>,
+    pub unsupported: proto::UnsupportedArrowColumns,
+}
+
+impl ImportOptions {
+    pub fn from_options(options: Option<&proto::PlanOptions>) -> Self {
+        let arrow_import = options.and_then(|opts| opts.arrow_import.as_ref());
+        ImportOptions {
+            duplicates: DuplicatePolicy::from_options(options),
+            categorical: arrow_import
+                .and_then(|opts| proto::CategoricalImport::try_from(opts.categorical).ok())
+                .unwrap_or(proto::CategoricalImport::Default),
+            categorical_columns: arrow_import
+                .map(|opts| opts.categorical_columns.clone())
+                .unwrap_or_default(),
+            unsupported: arrow_import
+                .and_then(|opts| proto::UnsupportedArrowColumns::try_from(opts.unsupported_columns).ok())
+                .unwrap_or(proto::UnsupportedArrowColumns::Error),
+        }
+    }
+}
+
 /// 从 Arrow C Data Interface 导入 Polars DataFrame
 pub fn import_dataframe_from_arrow(
     in_schema: *const FFIArrowSchema,
     in_array: *const ArrowArray,
+    options: &ImportOptions,
 ) -> Result<DataFrame, BridgeError> {
     if in_schema.is_null() || in_array.is_null() {
         return Err(BridgeError::InvalidArgument(
@@ -52,21 +142,31 @@ pub fn import_dataframe_from_arrow(
         ));
     }
 
+    take_import_warnings();
+    let lenient = options.unsupported != proto::UnsupportedArrowColumns::Error;
+
+    // 先按值取走两者：之后任何一步失败，drop 时都会调用各自的 release（释放 Go 侧的缓冲区）
     let schema = unsafe { std::ptr::read(in_schema) };
+    let array = unsafe { std::ptr::read(in_array) };
     let field = unsafe { import_field_from_c(&schema) }
-        .map_err(|e| BridgeError::ArrowImport(e.to_string()))?;
+        .map_err(|e| BridgeError::ArrowImport(format!("Failed to import Arrow schema: {}", e)))?;
 
     let dtype = field.dtype.clone();
-    let array = unsafe { std::ptr::read(in_array) };
-    let array = unsafe { import_array_from_c(array, dtype.clone()) }
-        .map_err(|e| BridgeError::ArrowImport(e.to_string()))?;
+    let array = unsafe { import_array_from_c(array, dtype.clone()) }.map_err(|e| {
+        BridgeError::ArrowImport(format!(
+            "Failed to import Arrow array of type {:?}: {}",
+            dtype, e
+        ))
+    })?;
 
     let fields = match dtype {
         ArrowDataType::Struct(fields) => fields,
-        _ => {
-            return Err(BridgeError::ArrowImport(
-                "Arrow record batch must be a Struct type".into(),
-            ))
+        other => {
+            return Err(BridgeError::ArrowImport(format!(
+                "Arrow record batch must be a Struct type, got {:?} (top-level field '{}'); \
+                 export a RecordBatch or a StructArray whose children are the columns",
+                other, field.name
+            )))
         }
     };
 
@@ -74,19 +174,230 @@ pub fn import_dataframe_from_arrow(
         .as_any()
         .downcast_ref::<StructArray>()
         .ok_or_else(|| {
-            BridgeError::ArrowImport("Arrow array is not a StructArray".into())
+            BridgeError::ArrowImport(format!(
+                "Arrow array does not match its Struct schema (got {:?})",
+                array.dtype()
+            ))
         })?;
 
-    if struct_array.validity().is_some() {
-        return Err(BridgeError::ArrowImport(
-            "StructArray contains top-level nulls".into(),
-        ));
+    if let Some(validity) = struct_array.validity() {
+        let nulls = validity.unset_bits();
+        if nulls > 0 {
+            let message = format!(
+                "StructArray has {} top-level null rows; \
+                 record batches must not carry a struct-level validity bitmap",
+                nulls
+            );
+            if !lenient {
+                return Err(BridgeError::ArrowImport(message));
+            }
+            warn(format!("{} (ignored; child values are kept as-is)", message));
+        }
+    }
+
+    // 按策略处理重复列名
+    let names: Vec<PlSmallStr> = fields.iter().map(|f| f.name.clone()).collect();
+    let resolved = dedup_column_names(&names, options.duplicates)
+        .map_err(|e| BridgeError::ArrowImport(e.to_string()))?;
+
+    let height = struct_array.len();
+    let mut columns = Vec::with_capacity(fields.len());
+    for (idx, ((field, array), name)) in fields
+        .into_iter()
+        .zip(struct_array.values().iter())
+        .zip(resolved)
+        .enumerate()
+    {
+        let Some(name) = name else {
+            continue;
+        };
+        let array = normalize_array(array.clone());
+        let field = Field { name, dtype: array.dtype().clone(), ..field };
+
+        match Series::try_from((&field, array.clone())) {
+            Ok(series) => columns.push(Column::from(series)),
+            Err(e) => {
+                if let Some(series) = utc_timestamp(&field, array.as_ref()) {
+                    warn(format!(
+                        "Column '{}' has unsupported time zone {:?}; imported as UTC (instants unchanged)",
+                        field.name, field.dtype
+                    ));
+                    columns.push(Column::from(series));
+                    continue;
+                }
+
+                let message = format!(
+                    "Unsupported Arrow type for column '{}' (field #{}): {:?}: {}",
+                    field.name, idx, field.dtype, e
+                );
+                match options.unsupported {
+                    proto::UnsupportedArrowColumns::Error => {
+                        return Err(BridgeError::ArrowImport(message))
+                    }
+                    proto::UnsupportedArrowColumns::Drop => {
+                        warn(format!("{} (column dropped)", message));
+                    }
+                    proto::UnsupportedArrowColumns::NullStub => {
+                        warn(format!("{} (replaced with a null column)", message));
+                        columns.push(Column::full_null(field.name.clone(), height, &DataType::Null));
+                    }
+                    proto::UnsupportedArrowColumns::String => {
+                        warn(format!("{} (converted to strings)", message));
+                        columns.push(stringify(field.name.clone(), array.as_ref()));
+                    }
+                }
+            }
+        }
     }
 
-    let schema: ArrowSchema = fields.into_iter().collect();
-    let arrays = struct_array.values().iter().cloned().collect::<Vec<_>>();
-    let record_batch = RecordBatch::try_new(struct_array.len(), Arc::new(schema), arrays)
+    let df = DataFrame::new_with_height(height, columns)
         .map_err(|e| BridgeError::ArrowImport(e.to_string()))?;
+    apply_categorical_import(df, options)
+}
+
+/// 把 Polars 不支持的 Map 改写为等价的 List<Struct<key, value>>（包括嵌套在 List / Struct 中的），
+/// 只替换类型，不复制数据缓冲区
+fn normalize_array(array: Box<dyn Array>) -> Box<dyn Array> {
+    if !contains_map(array.dtype()) {
+        return array;
+    }
+
+    let any = array.as_any();
+    if let Some(map) = any.downcast_ref::<MapArray>() {
+        let entries = normalize_array(map.field().clone());
+        let dtype = with_child_dtype(map.dtype(), entries.dtype());
+        return ListArray::<i32>::new(dtype, map.offsets().clone(), entries, map.validity().cloned())
+            .boxed();
+    }
+    if let Some(list) = any.downcast_ref::<ListArray<i32>>() {
+        return normalize_list(list);
+    }
+    if let Some(list) = any.downcast_ref::<ListArray<i64>>() {
+        return normalize_list(list);
+    }
+    if let Some(list) = any.downcast_ref::<FixedSizeListArray>() {
+        let values = normalize_array(list.values().clone());
+        let dtype = with_child_dtype(list.dtype(), values.dtype());
+        return FixedSizeListArray::new(dtype, list.len(), values, list.validity().cloned()).boxed();
+    }
+    if let Some(st) = any.downcast_ref::<StructArray>() {
+        let values: Vec<Box<dyn Array>> = st.values().iter().cloned().map(normalize_array).collect();
+        let fields = st
+            .fields()
+            .iter()
+            .zip(&values)
+            .map(|(f, v)| Field { dtype: v.dtype().clone(), ..f.clone() })
+            .collect();
+        return StructArray::new(ArrowDataType::Struct(fields), st.len(), values, st.validity().cloned())
+            .boxed();
+    }
+    array
+}
+
+fn normalize_list<O: Offset>(list: &ListArray<O>) -> Box<dyn Array> {
+    let values = normalize_array(list.values().clone());
+    let dtype = with_child_dtype(list.dtype(), values.dtype());
+    ListArray::<O>::new(dtype, list.offsets().clone(), values, list.validity().cloned()).boxed()
+}
+
+fn contains_map(dtype: &ArrowDataType) -> bool {
+    match dtype {
+        ArrowDataType::Map(_, _) => true,
+        ArrowDataType::List(f) | ArrowDataType::LargeList(f) | ArrowDataType::FixedSizeList(f, _) => {
+            contains_map(&f.dtype)
+        }
+        ArrowDataType::Struct(fields) => fields.iter().any(|f| contains_map(&f.dtype)),
+        _ => false,
+    }
+}
+
+// 替换列表类型的元素类型；Map 变为以 entries 结构体为元素的 List
+fn with_child_dtype(dtype: &ArrowDataType, child: &ArrowDataType) -> ArrowDataType {
+    let child_field = |f: &Field| Box::new(Field { dtype: child.clone(), ..f.clone() });
+    match dtype {
+        ArrowDataType::Map(f, _) | ArrowDataType::List(f) => ArrowDataType::List(child_field(f)),
+        ArrowDataType::LargeList(f) => ArrowDataType::LargeList(child_field(f)),
+        ArrowDataType::FixedSizeList(f, size) => ArrowDataType::FixedSizeList(child_field(f), *size),
+        other => other.clone(),
+    }
+}
+
+// Arrow 时间戳按 UTC 存储瞬时值，Polars 不认识的时区（如部分固定偏移写法）改按 UTC 导入
+fn utc_timestamp(field: &Field, array: &dyn Array) -> Option<Series> {
+    let ArrowDataType::Timestamp(unit, Some(_)) = &field.dtype else {
+        return None;
+    };
+    let values = array.as_any().downcast_ref::<PrimitiveArray<i64>>()?;
+    let dtype = ArrowDataType::Timestamp(*unit, Some(PlSmallStr::from_static("UTC")));
+    let field = Field { dtype: dtype.clone(), ..field.clone() };
+    Series::try_from((&field, values.clone().to(dtype).boxed())).ok()
+}
+
+// UNSUPPORTED_ARROW_COLUMNS_STRING：按 Arrow 的显示格式把每个值转为字符串
+fn stringify(name: PlSmallStr, array: &dyn Array) -> Column {
+    let display = get_display::<String>(array, "null");
+    let values: Vec<Option<String>> = (0..array.len())
+        .map(|i| {
+            if array.is_null(i) {
+                return None;
+            }
+            let mut value = String::new();
+            display(&mut value, i).ok()?;
+            Some(value)
+        })
+        .collect();
+    Column::new(name, values)
+}
+
+/// 导入 arrow-rs 的 RecordBatch（经 C Data Interface 交换，不复制缓冲区）
+pub fn import_arrow_rs_batch(
+    batch: arrow::record_batch::RecordBatch,
+    options: &ImportOptions,
+) -> Result<DataFrame, BridgeError> {
+    use arrow::array::{Array, StructArray as ArrowRsStructArray};
+
+    let data = ArrowRsStructArray::from(batch).into_data();
+    let (array, schema) =
+        arrow::ffi::to_ffi(&data).map_err(|e| BridgeError::ArrowImport(e.to_string()))?;
+    let df = import_dataframe_from_arrow(
+        &schema as *const _ as *const FFIArrowSchema,
+        &array as *const _ as *const ArrowArray,
+        options,
+    );
+    // 导入时已按值取走并负责释放，这里不能再次 release
+    std::mem::forget(array);
+    std::mem::forget(schema);
+    df
+}
+
+/// 按导入选项在 String 与 Categorical 之间转换
+fn apply_categorical_import(df: DataFrame, options: &ImportOptions) -> Result<DataFrame, BridgeError> {
+    if options.categorical == proto::CategoricalImport::Default {
+        return Ok(df);
+    }
+
+    let selected = |name: &str| {
+        options.categorical_columns.is_empty()
+            || options.categorical_columns.iter().any(|c| c == name)
+    };
+
+    let mut columns = df.take_columns();
+    for column in columns.iter_mut() {
+        if !selected(column.name().as_str()) {
+            continue;
+        }
+
+        let target = match (options.categorical, column.dtype()) {
+            (proto::CategoricalImport::String, DataType::Categorical(_, _)) => DataType::String,
+            (proto::CategoricalImport::Categorical, DataType::String) => {
+                DataType::from_categories(Categories::global())
+            }
+            _ => continue,
+        };
+        *column = column.cast(&target).map_err(|e| {
+            BridgeError::ArrowImport(format!("Failed to convert column '{}': {}", column.name(), e))
+        })?;
+    }
 
-    Ok(DataFrame::from(record_batch))
+    DataFrame::new(columns).map_err(|e| BridgeError::ArrowImport(e.to_string()))
 }
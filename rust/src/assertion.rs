@@ -0,0 +1,75 @@
+use std::fmt;
+use std::sync::Arc;
+
+use polars::prelude::*;
+
+use crate::error::BridgeError;
+
+const DEFAULT_MESSAGE: &str = "Assert failed";
+
+/// Assert 节点：collect 时检查输入的每一行，违反时以 ERR_ASSERTION_FAILED 失败，否则原样输出
+///
+/// 检查以不透明的 map 执行，优化器不会把下游的过滤、投影或切片下推到检查之前，保证检查的是完整输入。
+pub fn apply_assert(lf: LazyFrame, predicate: Expr, message: &str, report_rows: u32) -> LazyFrame {
+    let message = match message {
+        "" => DEFAULT_MESSAGE.to_string(),
+        message => message.to_string(),
+    };
+
+    let function = move |df: DataFrame| {
+        // not(null) 仍为 null，filter 会丢弃这些行：predicate 为 null 视为满足
+        let violations = df
+            .clone()
+            .lazy()
+            .filter(predicate.clone().not())
+            .collect()?;
+        if violations.height() == 0 {
+            return Ok(df);
+        }
+
+        let mut detail = format!(
+            "{}: {} of {} rows violate the predicate",
+            message,
+            violations.height(),
+            df.height()
+        );
+        if report_rows > 0 {
+            let sample = violations.head(Some(report_rows as usize));
+            detail.push_str(&format!("\nFirst offending rows:\n{}", sample));
+        }
+        Err(PolarsError::IO {
+            error: Arc::new(std::io::Error::other(AssertionViolation(detail))),
+            msg: None,
+        })
+    };
+    lf.map(
+        function,
+        AllowedOptimizations::empty(),
+        None,
+        Some("ASSERT"),
+    )
+}
+
+/// collect 失败时取回 Assert 的违反信息（Polars 可能在外层附加上下文）
+pub fn violation(err: &PolarsError) -> Option<BridgeError> {
+    match err {
+        PolarsError::IO { error, .. } => error
+            .get_ref()
+            .and_then(|e| e.downcast_ref::<AssertionViolation>())
+            .map(|v| BridgeError::AssertionFailed(v.0.clone())),
+        PolarsError::Context { error, .. } => violation(error),
+        _ => None,
+    }
+}
+
+// 借助 io::Error 的自定义错误穿过 PolarsError，collect 之后再还原
+#[derive(Debug)]
+struct AssertionViolation(String);
+
+impl fmt::Display for AssertionViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for AssertionViolation {}
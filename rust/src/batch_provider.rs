@@ -0,0 +1,396 @@
+use std::any::Any;
+use std::ffi::c_void;
+use std::os::raw::c_int;
+use std::sync::Arc;
+
+use polars::prelude::*;
+use polars_arrow::ffi::{ArrowArray, ArrowSchema};
+use prost::Message;
+
+use crate::arrow_bridge::{import_dataframe_from_arrow, ImportOptions};
+use crate::error::BridgeError;
+use crate::frame_builder::FrameBuilder;
+use crate::handles;
+use crate::proto;
+use crate::schema::to_polars_schema;
+
+/// Go 侧的批次回调：按 BatchRequest（protobuf）导出下一批数据到 out_schema/out_array；
+/// 没有更多数据时把 out_done 置为 1（此时不需要导出数组），返回非 0 表示错误
+pub type BatchCallback = extern "C" fn(
+    ctx: *mut c_void,
+    request_ptr: *const u8,
+    request_len: usize,
+    out_schema: *mut ArrowSchema,
+    out_array: *mut ArrowArray,
+    out_done: *mut c_int,
+) -> c_int;
+
+/// 已注册的 Go 批次提供者（可被多次执行复用，每次扫描从 batch_index = 0 重新开始）
+pub struct GoBatchProvider {
+    callback: BatchCallback,
+    ctx: *mut c_void,
+}
+
+// ctx 是 Go 侧的不透明句柄，不会在 Rust 侧解引用；回调自身负责线程安全
+unsafe impl Send for GoBatchProvider {}
+unsafe impl Sync for GoBatchProvider {}
+
+impl GoBatchProvider {
+    pub fn new(callback: BatchCallback, ctx: *mut c_void) -> Self {
+        GoBatchProvider { callback, ctx }
+    }
+
+    // 拉取一批数据；返回 None 表示结束
+    pub(crate) fn next(&self, request: &proto::BatchRequest) -> Result<Option<DataFrame>, BridgeError> {
+        let bytes = request.encode_to_vec();
+        let mut schema = ArrowSchema::empty();
+        let mut array = ArrowArray::empty();
+        let mut done: c_int = 0;
+
+        let status = (self.callback)(
+            self.ctx,
+            bytes.as_ptr(),
+            bytes.len(),
+            &mut schema,
+            &mut array,
+            &mut done,
+        );
+        if status != 0 {
+            return Err(BridgeError::Execution(format!(
+                "Go batch provider failed with status {}",
+                status
+            )));
+        }
+        if done != 0 {
+            return Ok(None);
+        }
+
+        let df = import_dataframe_from_arrow(&schema, &array, &ImportOptions::from_options(None));
+        // 导入时已按值取走并负责释放
+        std::mem::forget(array);
+        std::mem::forget(schema);
+        df.map(Some)
+    }
+}
+
+/// 按需回调 Go 拉取批次的匿名扫描：投影、谓词与行数限制作为提示传给 Go，并在 Rust 侧逐批兜底应用
+struct BatchProviderScan {
+    provider: Arc<GoBatchProvider>,
+    schema: SchemaRef,
+}
+
+impl AnonymousScan for BatchProviderScan {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self, _infer_schema_length: Option<usize>) -> PolarsResult<SchemaRef> {
+        Ok(self.schema.clone())
+    }
+
+    fn allows_projection_pushdown(&self) -> bool {
+        true
+    }
+
+    fn allows_predicate_pushdown(&self) -> bool {
+        true
+    }
+
+    fn allows_slice_pushdown(&self) -> bool {
+        true
+    }
+
+    fn scan(&self, scan_opts: AnonymousScanArgs) -> PolarsResult<DataFrame> {
+        let columns: Vec<String> = scan_opts
+            .with_columns
+            .as_deref()
+            .map(|cols| cols.iter().map(|c| c.to_string()).collect())
+            .unwrap_or_default();
+        let mut request = proto::BatchRequest {
+            batch_index: 0,
+            columns,
+            n_rows: scan_opts.n_rows.map(|n| n as u64),
+            predicate: scan_opts.predicate.as_ref().and_then(predicate_hint),
+        };
+
+        // Go 侧可以忽略提示，每批到达后立即应用谓词与投影，只保留需要的行和列；
+        // 满足行数限制后不再向 Go 拉取
+        let to_polars = |e: BridgeError| PolarsError::ComputeError(e.to_string().into());
+        let mut frame = FrameBuilder::default();
+        let mut rows = 0usize;
+        while scan_opts.n_rows.is_none_or(|n| rows < n) {
+            let Some(batch) = self.provider.next(&request).map_err(to_polars)? else {
+                break;
+            };
+            request.batch_index += 1;
+
+            let batch = self.restrict(batch, &scan_opts)?;
+            rows += batch.height();
+            frame.append(batch).map_err(to_polars)?;
+        }
+
+        let df = frame.finish();
+        let df = if df.width() == 0 {
+            self.restrict(DataFrame::empty_with_schema(&self.schema), &scan_opts)?
+        } else {
+            df
+        };
+        Ok(match scan_opts.n_rows {
+            Some(n) => df.head(Some(n)),
+            None => df,
+        })
+    }
+}
+
+impl BatchProviderScan {
+    fn restrict(&self, batch: DataFrame, scan_opts: &AnonymousScanArgs) -> PolarsResult<DataFrame> {
+        let mut lf = batch.lazy();
+        if let Some(predicate) = scan_opts.predicate.clone() {
+            lf = lf.filter(predicate);
+        }
+        if let Some(columns) = scan_opts.with_columns.as_deref() {
+            lf = lf.select(columns.iter().map(|c| col(c.clone())).collect::<Vec<_>>());
+        }
+        lf.collect()
+    }
+}
+
+// 把下推的谓词转换为 Plan 表达式作为提示：AND 连接的各项中只保留能表示的部分（结果更宽松），
+// 其余形式整体放弃
+fn predicate_hint(expr: &Expr) -> Option<proto::Expr> {
+    if let Expr::BinaryExpr { left, op: Operator::And | Operator::LogicalAnd, right } = expr {
+        return match (predicate_hint(left), predicate_hint(right)) {
+            (Some(left), Some(right)) => Some(binary(left, proto::BinaryOperator::And, right)),
+            (left, right) => left.or(right),
+        };
+    }
+    to_proto_expr(expr)
+}
+
+fn to_proto_expr(expr: &Expr) -> Option<proto::Expr> {
+    use proto::expr::Kind;
+    use proto::literal::Value;
+    use proto::BinaryOperator as Op;
+
+    match expr {
+        Expr::Column(name) => Some(proto::Expr {
+            kind: Some(Kind::Col(proto::Column { name: name.to_string() })),
+        }),
+        Expr::Literal(value) => {
+            let value = match value.to_any_value()? {
+                AnyValue::Null => Value::NullVal(proto::NullValue {}),
+                AnyValue::Boolean(v) => Value::BoolVal(v),
+                AnyValue::String(v) => Value::StringVal(v.to_string()),
+                AnyValue::StringOwned(v) => Value::StringVal(v.to_string()),
+                AnyValue::Float32(v) => Value::FloatVal(v as f64),
+                AnyValue::Float64(v) => Value::FloatVal(v),
+                v if v.is_integer() => Value::IntVal(v.extract::<i64>()?),
+                _ => return None,
+            };
+            Some(proto::Expr {
+                kind: Some(Kind::Lit(proto::Literal { value: Some(value) })),
+            })
+        }
+        Expr::BinaryExpr { left, op, right } => {
+            let op = match op {
+                Operator::Eq => Op::Eq,
+                Operator::EqValidity => Op::EqMissing,
+                Operator::NotEq => Op::Ne,
+                Operator::NotEqValidity => Op::NeMissing,
+                Operator::Lt => Op::Lt,
+                Operator::LtEq => Op::Le,
+                Operator::Gt => Op::Gt,
+                Operator::GtEq => Op::Ge,
+                Operator::Plus => Op::Add,
+                Operator::Minus => Op::Sub,
+                Operator::Multiply => Op::Mul,
+                Operator::Divide => Op::Div,
+                Operator::Modulus => Op::Mod,
+                Operator::And | Operator::LogicalAnd => Op::And,
+                Operator::Or | Operator::LogicalOr => Op::Or,
+                Operator::Xor => Op::Xor,
+                Operator::TrueDivide | Operator::FloorDivide => return None,
+            };
+            Some(binary(to_proto_expr(left)?, op, to_proto_expr(right)?))
+        }
+        _ => None,
+    }
+}
+
+fn binary(left: proto::Expr, op: proto::BinaryOperator, right: proto::Expr) -> proto::Expr {
+    proto::Expr {
+        kind: Some(proto::expr::Kind::Binary(Box::new(proto::BinaryExpr {
+            left: Some(Box::new(left)),
+            op: op as i32,
+            right: Some(Box::new(right)),
+        }))),
+    }
+}
+
+/// BatchProviderScan 节点：schema 由 Plan 声明，数据在执行时才向 Go 拉取
+pub fn batch_provider_scan(scan: &proto::BatchProviderScan) -> Result<LazyFrame, BridgeError> {
+    let provider = handles::batch_providers().get(scan.provider_id)?;
+    let fields = scan
+        .schema
+        .as_ref()
+        .map(|s| s.fields.as_slice())
+        .filter(|fields| !fields.is_empty())
+        .ok_or_else(|| BridgeError::PlanSemantic("BatchProviderScan requires a schema".into()))?;
+    let schema: SchemaRef = Arc::new(to_polars_schema(fields)?);
+
+    let args = ScanArgsAnonymous {
+        schema: Some(schema.clone()),
+        name: "BATCH_PROVIDER",
+        ..Default::default()
+    };
+    LazyFrame::anonymous_scan(Arc::new(BatchProviderScan { provider, schema }), args)
+        .map_err(|e| BridgeError::Execution(format!("BatchProviderScan failed: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::arrow_bridge::export_dataframe_to_arrow;
+    use crate::executor;
+    use crate::test_plans::{col, lit, node, plan, select};
+    use proto::node::Kind as N;
+
+    // 模拟 Go 批次提供者：按 batch_index 返回批次，并记录收到的提示
+    struct Provider {
+        batches: Vec<DataFrame>,
+        requests: Mutex<Vec<proto::BatchRequest>>,
+    }
+
+    extern "C" fn next_batch(
+        ctx: *mut c_void,
+        request_ptr: *const u8,
+        request_len: usize,
+        out_schema: *mut ArrowSchema,
+        out_array: *mut ArrowArray,
+        out_done: *mut c_int,
+    ) -> c_int {
+        let provider = unsafe { &*(ctx as *const Provider) };
+        let bytes = unsafe { std::slice::from_raw_parts(request_ptr, request_len) };
+        let request = proto::BatchRequest::decode(bytes).unwrap();
+        let batch = provider.batches.get(request.batch_index as usize);
+        provider.requests.lock().unwrap().push(request);
+        match batch {
+            Some(batch) => {
+                export_dataframe_to_arrow(batch, out_schema, out_array).unwrap();
+                0
+            }
+            None => {
+                unsafe { *out_done = 1 };
+                0
+            }
+        }
+    }
+
+    fn provider() -> Provider {
+        Provider {
+            batches: vec![
+                df!("k" => [1i64, 2], "v" => ["a", "b"], "w" => [true, false]).unwrap(),
+                df!("k" => [3i64, 4], "v" => ["c", "d"], "w" => [true, true]).unwrap(),
+                df!("k" => [5i64], "v" => ["e"], "w" => [false]).unwrap(),
+            ],
+            requests: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn scan_node(provider: &Provider) -> proto::Node {
+        let id = handles::batch_providers()
+            .insert(GoBatchProvider::new(next_batch, provider as *const Provider as *mut c_void));
+        let field = |name: &str, data_type: proto::DataType| proto::Field {
+            name: name.into(),
+            data_type: data_type as i32,
+        };
+        node(1, N::BatchProviderScan(proto::BatchProviderScan {
+            provider_id: id,
+            schema: Some(proto::Schema {
+                fields: vec![
+                    field("k", proto::DataType::Int64),
+                    field("v", proto::DataType::Utf8),
+                    field("w", proto::DataType::Bool),
+                ],
+            }),
+            options: None,
+        }))
+    }
+
+    #[test]
+    fn pulls_every_batch_and_reuses_the_provider() {
+        let provider = provider();
+        let scan = plan(scan_node(&provider));
+
+        for _ in 0..2 {
+            let df = executor::execute_plan_df(&scan, None).unwrap();
+            let expected = df!(
+                "k" => [1i64, 2, 3, 4, 5],
+                "v" => ["a", "b", "c", "d", "e"],
+                "w" => [true, false, true, true, false],
+            )
+            .unwrap();
+            assert!(df.equals(&expected), "{df}");
+        }
+        // 每次扫描拉取 3 批后再收到一次结束，batch_index 从 0 重新开始
+        let indexes: Vec<u64> = provider.requests.lock().unwrap().iter().map(|r| r.batch_index).collect();
+        assert_eq!(indexes, [0, 1, 2, 3, 0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn passes_pushdown_hints_and_still_applies_them() {
+        let provider = provider();
+        let filtered = node(2, N::Filter(Box::new(proto::Filter {
+            input: Some(Box::new(scan_node(&provider))),
+            predicate: Some(binary(col("k"), proto::BinaryOperator::Ge, lit(proto::literal::Value::IntVal(4)))),
+        })));
+
+        // 提供者忽略提示返回整批数据，结果仍按谓词与投影裁剪
+        let df = executor::execute_plan_df(&select(filtered, vec![col("v")]), None).unwrap();
+        assert!(df.equals(&df!("v" => ["d", "e"]).unwrap()), "{df}");
+
+        let requests = provider.requests.lock().unwrap();
+        let first = &requests[0];
+        // 谓词用到的 k 也在投影提示中，未用到的 w 被裁剪
+        let mut columns = first.columns.clone();
+        columns.sort();
+        assert_eq!(columns, ["k", "v"]);
+        assert_eq!(first.n_rows, None);
+        let predicate = first.predicate.as_ref().unwrap();
+        assert!(
+            matches!(predicate.kind.as_ref(), Some(proto::expr::Kind::Binary(b)) if b.op == proto::BinaryOperator::Ge as i32),
+            "{predicate:?}"
+        );
+    }
+
+    #[test]
+    fn stops_pulling_once_the_limit_is_reached() {
+        let provider = provider();
+        let limited = node(2, N::Limit(Box::new(proto::Limit {
+            input: Some(Box::new(scan_node(&provider))),
+            n: 3,
+        })));
+
+        let df = executor::execute_plan_df(&plan(limited), None).unwrap();
+        let expected = df!("k" => [1i64, 2, 3], "v" => ["a", "b", "c"], "w" => [true, false, true]).unwrap();
+        assert!(df.equals(&expected), "{df}");
+
+        // 第 2 批后已满 3 行，不再拉取第 3 批
+        let requests = provider.requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].n_rows, Some(3));
+    }
+
+    #[test]
+    fn requires_a_declared_schema() {
+        let provider = provider();
+        let mut scan = scan_node(&provider);
+        if let Some(N::BatchProviderScan(scan)) = scan.kind.as_mut() {
+            scan.schema = None;
+        }
+        let err = executor::execute_plan_df(&plan(scan), None).unwrap_err();
+        assert!(matches!(err, BridgeError::PlanSemantic(_)), "{err}");
+    }
+}
@@ -0,0 +1,97 @@
+use std::ffi::c_void;
+use std::ptr::NonNull;
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, Float64Array, Int64Array};
+use arrow::buffer::{Buffer, ScalarBuffer};
+use arrow::datatypes::{ArrowNativeType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use polars::prelude::*;
+
+use crate::arrow_bridge::{import_arrow_rs_batch, ImportOptions};
+use crate::error::BridgeError;
+use crate::proto;
+
+/// Go 侧的释放回调：当 Polars 不再引用缓冲区时调用（可能在任意线程上）
+pub type DeallocCallback = extern "C" fn(ctx: *mut c_void);
+
+/// 持有 Go 缓冲区的所有权令牌：drop 时（即最后一个引用释放时）通知 Go
+pub struct GoAllocation {
+    dealloc: DeallocCallback,
+    ctx: *mut c_void,
+}
+
+// ctx 是 Go 侧的不透明句柄，只会原样传回释放回调
+unsafe impl Send for GoAllocation {}
+unsafe impl Sync for GoAllocation {}
+
+impl GoAllocation {
+    pub fn new(dealloc: DeallocCallback, ctx: *mut c_void) -> Self {
+        GoAllocation { dealloc, ctx }
+    }
+}
+
+impl Drop for GoAllocation {
+    fn drop(&mut self) {
+        (self.dealloc)(self.ctx);
+    }
+}
+
+/// 直接引用 Go 持有的数值缓冲区创建单列 DataFrame（不复制数据）
+///
+/// 调用方必须保证 data 指向至少 len 个元素，且在 owner 释放前保持有效、不被修改或移动；
+/// len 为 0 时 data 可以为空指针。出错时 owner 随之释放。
+pub unsafe fn series_from_buffer(
+    name: &str,
+    data_type: i32,
+    data: *const u8,
+    len: usize,
+    owner: GoAllocation,
+) -> Result<DataFrame, BridgeError> {
+    let owner = Arc::new(owner);
+
+    let array: ArrayRef = match proto::DataType::try_from(data_type) {
+        Ok(proto::DataType::Int64) => {
+            Arc::new(Int64Array::new(scalar_buffer::<i64>(data, len, owner)?, None))
+        }
+        Ok(proto::DataType::Float64) => {
+            Arc::new(Float64Array::new(scalar_buffer::<f64>(data, len, owner)?, None))
+        }
+        _ => {
+            return Err(BridgeError::Unsupported(format!(
+                "Zero-copy import supports only INT64 and FLOAT64, got data type {}",
+                data_type
+            )))
+        }
+    };
+
+    let schema = Schema::new(vec![Field::new(name, array.data_type().clone(), false)]);
+    let batch = RecordBatch::try_new(Arc::new(schema), vec![array])
+        .map_err(|e| BridgeError::ArrowImport(e.to_string()))?;
+    import_arrow_rs_batch(batch, &ImportOptions::from_options(None))
+}
+
+unsafe fn scalar_buffer<T: ArrowNativeType>(
+    data: *const u8,
+    len: usize,
+    owner: Arc<GoAllocation>,
+) -> Result<ScalarBuffer<T>, BridgeError> {
+    // 空切片在 Go 侧可能是 nil 指针；不引用 Go 内存，owner 随之释放
+    if len == 0 {
+        return Ok(ScalarBuffer::from(Vec::<T>::new()));
+    }
+    let ptr = NonNull::new(data as *mut u8)
+        .ok_or_else(|| BridgeError::InvalidArgument("Null buffer pointer".into()))?;
+    if ptr.as_ptr().align_offset(std::mem::align_of::<T>()) != 0 {
+        return Err(BridgeError::InvalidArgument(format!(
+            "Buffer must be aligned to {} bytes",
+            std::mem::align_of::<T>()
+        )));
+    }
+    let byte_len = len
+        .checked_mul(std::mem::size_of::<T>())
+        .ok_or_else(|| BridgeError::InvalidArgument("Buffer length overflows".into()))?;
+
+    let buffer = Buffer::from_custom_allocation(ptr, byte_len, owner);
+    Ok(ScalarBuffer::new(buffer, 0, len))
+}
@@ -0,0 +1,109 @@
+use std::fs::{self, File};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use polars::prelude::*;
+
+use crate::error::BridgeError;
+
+/// 检查点文件格式版本（不兼容的变更时递增）
+const CHECKPOINT_VERSION: &str = "1";
+
+const KEY_VERSION: &str = "polars_bridge.checkpoint_version";
+const KEY_CRATE_VERSION: &str = "polars_bridge.crate_version";
+const KEY_ROWS: &str = "polars_bridge.rows";
+const KEY_CREATED_AT: &str = "polars_bridge.created_at";
+
+/// 把 DataFrame 保存为检查点（Arrow IPC 文件，schema 元数据记录格式版本与行数）
+///
+/// 先写入同目录下的临时文件再重命名，进程中途退出不会破坏已有的检查点。
+pub fn checkpoint(df: &DataFrame, path: &str) -> Result<(), BridgeError> {
+    if path.is_empty() {
+        return Err(BridgeError::InvalidArgument("Checkpoint path cannot be empty".into()));
+    }
+
+    let created_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let metadata = [
+        (KEY_VERSION, CHECKPOINT_VERSION.to_string()),
+        (KEY_CRATE_VERSION, env!("CARGO_PKG_VERSION").to_string()),
+        (KEY_ROWS, df.height().to_string()),
+        (KEY_CREATED_AT, created_at.to_string()),
+    ];
+
+    let tmp_path = format!("{}.tmp", path);
+    let file = File::create(&tmp_path)
+        .map_err(|e| BridgeError::Execution(format!("Failed to create '{}': {}", tmp_path, e)))?;
+    let mut writer = IpcWriter::new(file);
+    writer.set_custom_schema_metadata(Arc::new(
+        metadata
+            .into_iter()
+            .map(|(key, value)| (PlSmallStr::from(key), PlSmallStr::from(value)))
+            .collect(),
+    ));
+
+    let mut df = df.clone();
+    let written = writer
+        .finish(&mut df)
+        .map_err(|e| BridgeError::Execution(format!("Failed to write checkpoint '{}': {}", path, e)))
+        .and_then(|_| {
+            fs::rename(&tmp_path, path).map_err(|e| {
+                BridgeError::Execution(format!("Failed to move checkpoint into '{}': {}", path, e))
+            })
+        });
+    if written.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    written
+}
+
+/// 从检查点恢复 DataFrame（校验格式版本与行数）
+pub fn restore(path: &str) -> Result<DataFrame, BridgeError> {
+    if path.is_empty() {
+        return Err(BridgeError::InvalidArgument("Checkpoint path cannot be empty".into()));
+    }
+
+    let file = File::open(path)
+        .map_err(|e| BridgeError::Execution(format!("Failed to open checkpoint '{}': {}", path, e)))?;
+    let mut reader = IpcReader::new(file);
+    let metadata = reader
+        .custom_metadata()
+        .map_err(|e| BridgeError::Execution(format!("Failed to read checkpoint '{}': {}", path, e)))?
+        .ok_or_else(|| {
+            BridgeError::InvalidArgument(format!("'{}' is not a bridge checkpoint", path))
+        })?;
+    let get = |key: &str| metadata.get(key).map(|v| v.as_str());
+
+    match get(KEY_VERSION) {
+        Some(CHECKPOINT_VERSION) => {}
+        Some(version) => {
+            return Err(BridgeError::Unsupported(format!(
+                "Checkpoint '{}' has format version {} (supported: {})",
+                path, version, CHECKPOINT_VERSION
+            )))
+        }
+        None => {
+            return Err(BridgeError::InvalidArgument(format!(
+                "'{}' is not a bridge checkpoint",
+                path
+            )))
+        }
+    }
+
+    let df = reader
+        .finish()
+        .map_err(|e| BridgeError::Execution(format!("Failed to read checkpoint '{}': {}", path, e)))?;
+    if let Some(rows) = get(KEY_ROWS).and_then(|rows| rows.parse::<usize>().ok()) {
+        if rows != df.height() {
+            return Err(BridgeError::Execution(format!(
+                "Checkpoint '{}' is truncated: expected {} rows, found {}",
+                path,
+                rows,
+                df.height()
+            )));
+        }
+    }
+    Ok(df)
+}
@@ -0,0 +1,61 @@
+use polars::io::cloud::CloudOptions;
+#[cfg(feature = "cloud")]
+use polars::prelude::CloudScheme;
+
+use crate::error::BridgeError;
+use crate::proto;
+
+/// 将 proto 云存储配置转换为 Polars CloudOptions
+///
+/// 未提供显式凭据时 Polars 会按默认凭据链（环境变量、实例配置等）读取。
+#[cfg(feature = "cloud")]
+pub fn cloud_options(
+    url: &str,
+    options: Option<&proto::CloudOptions>,
+) -> Result<Option<CloudOptions>, BridgeError> {
+    let options = match options {
+        Some(options) => options,
+        None => return Ok(None),
+    };
+
+    let has_credentials = !options.access_key_id.is_empty()
+        || !options.secret_access_key.is_empty()
+        || !options.session_token.is_empty();
+    if options.use_environment && has_credentials {
+        return Err(BridgeError::InvalidArgument(
+            "CloudOptions: explicit credentials cannot be combined with use_environment".into(),
+        ));
+    }
+
+    let mut config: Vec<(String, String)> = Vec::new();
+    let mut push = |key: &str, value: &str| {
+        if !value.is_empty() {
+            config.push((key.to_string(), value.to_string()));
+        }
+    };
+    push("aws_endpoint_url", &options.endpoint);
+    push("aws_region", &options.region);
+    push("aws_access_key_id", &options.access_key_id);
+    push("aws_secret_access_key", &options.secret_access_key);
+    push("aws_session_token", &options.session_token);
+    if options.allow_http {
+        push("aws_allow_http", "true");
+    }
+    config.extend(options.extra.iter().map(|(k, v)| (k.clone(), v.clone())));
+
+    CloudOptions::from_untyped_config(CloudScheme::from_uri(url).as_ref(), config)
+        .map(Some)
+        .map_err(|e| BridgeError::InvalidArgument(format!("Invalid cloud options: {}", e)))
+}
+
+/// 未编译云存储支持时只允许本地路径
+#[cfg(not(feature = "cloud"))]
+pub fn cloud_options(
+    url: &str,
+    options: Option<&proto::CloudOptions>,
+) -> Result<Option<CloudOptions>, BridgeError> {
+    if options.is_some() || (url.contains("://") && !url.starts_with("file://")) {
+        return Err(crate::features::unavailable("Cloud storage", "cloud"));
+    }
+    Ok(None)
+}
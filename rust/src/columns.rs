@@ -0,0 +1,427 @@
+use std::collections::BTreeMap;
+
+use polars::prelude::*;
+
+use crate::error::BridgeError;
+use crate::proto;
+use crate::scan;
+use crate::schema::to_polars_schema;
+
+/// 列引用解析器：按 Plan 选项把表达式中的列名解析为输入 schema 中的实际列名
+pub struct ColumnResolver {
+    case_insensitive: bool,
+    mapping: BTreeMap<String, String>,
+}
+
+impl ColumnResolver {
+    pub fn from_options(options: Option<&proto::PlanOptions>) -> Self {
+        match options {
+            Some(opts) => ColumnResolver {
+                case_insensitive: opts.column_resolution
+                    == proto::ColumnResolution::CaseInsensitive as i32,
+                mapping: opts.column_mapping.clone(),
+            },
+            None => ColumnResolver {
+                case_insensitive: false,
+                mapping: BTreeMap::new(),
+            },
+        }
+    }
+
+    /// 是否需要解析（默认精确匹配且无映射时跳过，避免额外的 schema 推断）
+    pub fn is_enabled(&self) -> bool {
+        self.case_insensitive || !self.mapping.is_empty()
+    }
+
+    pub fn resolve_exprs(&self, exprs: Vec<Expr>, schema: &Schema) -> Result<Vec<Expr>, BridgeError> {
+        exprs
+            .into_iter()
+            .map(|expr| self.resolve_expr(expr, schema))
+            .collect()
+    }
+
+    pub fn resolve_expr(&self, expr: Expr, schema: &Schema) -> Result<Expr, BridgeError> {
+        let mut error = None;
+        let resolved = expr.map_expr(|e| match e {
+            Expr::Column(name) => match self.resolve_name(name.as_str(), schema) {
+                Ok(resolved) => Expr::Column(resolved),
+                Err(err) => {
+                    error.get_or_insert(err);
+                    Expr::Column(name)
+                }
+            },
+            e => e,
+        });
+
+        match error {
+            Some(err) => Err(err),
+            None => Ok(resolved),
+        }
+    }
+
+    fn resolve_name(&self, name: &str, schema: &Schema) -> Result<PlSmallStr, BridgeError> {
+        let name = self.mapping.get(name).map(String::as_str).unwrap_or(name);
+        if !self.case_insensitive || schema.contains(name) {
+            return Ok(name.into());
+        }
+
+        let lowered = name.to_lowercase();
+        let candidates: Vec<&PlSmallStr> = schema
+            .iter_names()
+            .filter(|c| c.to_lowercase() == lowered)
+            .collect();
+
+        match candidates.as_slice() {
+            // 找不到时保留原名，由 Polars 报告 ColumnNotFound
+            [] => Ok(name.into()),
+            [only] => Ok((*only).clone()),
+            _ => Err(BridgeError::PlanSemantic(format!(
+                "Ambiguous column reference '{}': matches {:?}",
+                name,
+                candidates.iter().map(|c| c.as_str()).collect::<Vec<_>>()
+            ))),
+        }
+    }
+}
+
+/// 重复列名处理策略
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+    /// 保持 Polars 的行为：CSV 读取器自行重命名，其它来源按 Error 处理
+    Default,
+    Error,
+    AutoSuffix,
+    KeepFirst,
+}
+
+impl DuplicatePolicy {
+    pub fn from_options(options: Option<&proto::PlanOptions>) -> Self {
+        use proto::DuplicateColumnPolicy as Policy;
+
+        match options.map(|opts| Policy::try_from(opts.duplicate_columns)) {
+            Some(Ok(Policy::Error)) => DuplicatePolicy::Error,
+            Some(Ok(Policy::AutoSuffix)) => DuplicatePolicy::AutoSuffix,
+            Some(Ok(Policy::KeepFirst)) => DuplicatePolicy::KeepFirst,
+            _ => DuplicatePolicy::Default,
+        }
+    }
+}
+
+/// 按策略处理重复列名：返回每一列的新名字（None 表示丢弃该列）
+pub fn dedup_column_names(
+    names: &[PlSmallStr],
+    policy: DuplicatePolicy,
+) -> Result<Vec<Option<PlSmallStr>>, BridgeError> {
+    let mut seen: PlHashSet<PlSmallStr> = PlHashSet::with_capacity(names.len());
+    let mut suffixes: PlHashMap<PlSmallStr, usize> = PlHashMap::new();
+    let mut resolved = Vec::with_capacity(names.len());
+
+    for (idx, name) in names.iter().enumerate() {
+        if seen.insert(name.clone()) {
+            resolved.push(Some(name.clone()));
+            continue;
+        }
+
+        match policy {
+            DuplicatePolicy::Default | DuplicatePolicy::Error => {
+                let first = names.iter().position(|n| n == name).unwrap_or(idx);
+                return Err(BridgeError::InvalidArgument(format!(
+                    "Duplicate column name '{}' at positions {} and {}",
+                    name, first, idx
+                )));
+            }
+            DuplicatePolicy::KeepFirst => resolved.push(None),
+            DuplicatePolicy::AutoSuffix => {
+                let counter = suffixes.entry(name.clone()).or_insert(0);
+                let candidate = loop {
+                    *counter += 1;
+                    let candidate = PlSmallStr::from(format!("{}_{}", name, counter));
+                    if !seen.contains(&candidate) && !names.contains(&candidate) {
+                        break candidate;
+                    }
+                };
+                seen.insert(candidate.clone());
+                resolved.push(Some(candidate));
+            }
+        }
+    }
+
+    Ok(resolved)
+}
+
+/// 按重复列名策略扫描 CSV
+///
+/// Polars 的 CSV 读取器会把重复表头重命名为 `<name>_duplicated_<n>`，与真实存在的
+/// `x_duplicated_0` 列冲突时无法扫描，因此显式设置策略且表头有重复时，单独读取一次原始表头行，
+/// 再不带表头按位置扫描（跳过表头行），按位置改名。
+/// 默认策略保持 Polars 的行为，不做额外读取。
+pub fn csv_scan_with_policy(
+    scan: &proto::CsvScan,
+    policy: DuplicatePolicy,
+) -> Result<LazyFrame, BridgeError> {
+    if policy == DuplicatePolicy::Default || scan.has_header == Some(false) {
+        return scan::csv_scan(scan);
+    }
+
+    let header = csv_header(scan)?;
+    let resolved = dedup_column_names(&header, policy)?;
+    if resolved.iter().zip(&header).all(|(new_name, name)| new_name.as_ref() == Some(name)) {
+        return scan::csv_scan(scan);
+    }
+
+    // 类型覆盖按表头列名给出，改为按位置的列名
+    let mut reader = scan::csv_reader(scan)?
+        .with_has_header(false)
+        .with_skip_rows_after_header(1);
+    if !scan.schema_overrides.is_empty() {
+        let overrides = to_polars_schema(&scan.schema_overrides)?;
+        let positional: Schema = header
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, name)| {
+                overrides.get(name).map(|dtype| Field::new(positional_name(idx), dtype.clone()))
+            })
+            .collect();
+        reader = reader.with_dtype_overwrite(Some(Arc::new(positional)));
+    }
+    let lf = reader.finish().map_err(|e| {
+        BridgeError::Execution(format!(
+            "CsvScan failed for '{}': {}",
+            scan::display_paths(&scan.path, &scan.paths),
+            e
+        ))
+    })?;
+
+    let exprs: Vec<Expr> = resolved
+        .into_iter()
+        .enumerate()
+        .filter_map(|(idx, new_name)| {
+            new_name.map(|new_name| col(positional_name(idx)).alias(new_name))
+        })
+        .collect();
+    Ok(lf.select(exprs))
+}
+
+// 不带表头读取第一行（全部按字符串解析），得到原始列名
+fn csv_header(scan: &proto::CsvScan) -> Result<Vec<PlSmallStr>, BridgeError> {
+    let to_error = |e: PolarsError| {
+        BridgeError::Execution(format!(
+            "Failed to read CSV header for '{}': {}",
+            scan::display_paths(&scan.path, &scan.paths),
+            e
+        ))
+    };
+    let df = scan::csv_reader(scan)?
+        .with_has_header(false)
+        .with_n_rows(Some(1))
+        .with_infer_schema_length(Some(0))
+        .with_dtype_overwrite(None)
+        .with_try_parse_dates(false)
+        .finish()
+        .and_then(|lf| lf.collect())
+        .map_err(to_error)?;
+
+    df.get_columns()
+        .iter()
+        .map(|column| match column.get(0) {
+            Ok(AnyValue::String(name)) => Ok(name.into()),
+            Ok(AnyValue::StringOwned(name)) => Ok(name),
+            Ok(_) => Ok(PlSmallStr::EMPTY),
+            Err(e) => Err(to_error(e)),
+        })
+        .collect()
+}
+
+// 不带表头扫描时 Polars 生成的列名（从 1 开始）
+fn positional_name(idx: usize) -> PlSmallStr {
+    format!("column_{}", idx + 1).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 真实存在的 `x_duplicated_0` 列紧跟在重复的 `x` 之后
+    const DUPLICATE_HEADER_CSV: &str = "../testdata/duplicate_header.csv";
+
+    fn names(names: &[&str]) -> Vec<PlSmallStr> {
+        names.iter().map(|&name| name.into()).collect()
+    }
+
+    fn resolved(header: &[&str], policy: DuplicatePolicy) -> Vec<Option<String>> {
+        dedup_column_names(&names(header), policy)
+            .unwrap()
+            .into_iter()
+            .map(|name| name.map(|name| name.to_string()))
+            .collect()
+    }
+
+    fn scan_csv(policy: DuplicatePolicy) -> Result<DataFrame, BridgeError> {
+        let scan = proto::CsvScan { path: DUPLICATE_HEADER_CSV.into(), ..Default::default() };
+        csv_scan_with_policy(&scan, policy)?
+            .collect()
+            .map_err(|e| BridgeError::Execution("Failed to collect".into()).with_source(e))
+    }
+
+    fn resolver(case_insensitive: bool, mapping: &[(&str, &str)]) -> ColumnResolver {
+        let resolution = if case_insensitive {
+            proto::ColumnResolution::CaseInsensitive
+        } else {
+            proto::ColumnResolution::Exact
+        };
+        ColumnResolver::from_options(Some(&proto::PlanOptions {
+            column_resolution: resolution as i32,
+            column_mapping: mapping.iter().map(|&(k, v)| (k.into(), v.into())).collect(),
+            ..Default::default()
+        }))
+    }
+
+    fn schema(names: &[&str]) -> Schema {
+        names.iter().map(|&name| Field::new(name.into(), DataType::Int64)).collect()
+    }
+
+    fn resolve(resolver: &ColumnResolver, name: &str, schema: &Schema) -> Result<String, BridgeError> {
+        let resolved = resolver.resolve_expr(col(name).alias("out"), schema)?;
+        let names = resolved.meta().root_names();
+        assert_eq!(names.len(), 1);
+        Ok(names[0].to_string())
+    }
+
+    #[test]
+    fn resolver_is_disabled_by_default() {
+        assert!(!ColumnResolver::from_options(None).is_enabled());
+        assert!(!resolver(false, &[]).is_enabled());
+        assert!(resolver(true, &[]).is_enabled());
+        assert!(resolver(false, &[("a", "b")]).is_enabled());
+    }
+
+    #[test]
+    fn case_insensitive_prefers_exact_match() {
+        let schema = schema(&["Amount", "amount", "Name"]);
+        let folding = resolver(true, &[]);
+        assert_eq!(resolve(&folding, "amount", &schema).unwrap(), "amount");
+        assert_eq!(resolve(&folding, "NAME", &schema).unwrap(), "Name");
+        // 找不到时保留原名
+        assert_eq!(resolve(&folding, "missing", &schema).unwrap(), "missing");
+    }
+
+    #[test]
+    fn case_insensitive_rejects_ambiguous_match() {
+        let schema = schema(&["Amount", "AMOUNT"]);
+        let err = resolve(&resolver(true, &[]), "amount", &schema).unwrap_err();
+        assert!(matches!(err, BridgeError::PlanSemantic(_)));
+        let message = err.to_string();
+        assert!(message.contains("Ambiguous column reference 'amount'"), "{message}");
+        assert!(message.contains("Amount") && message.contains("AMOUNT"), "{message}");
+    }
+
+    #[test]
+    fn mapping_is_applied_before_case_folding() {
+        let schema = schema(&["CustomerID", "Total"]);
+        let folding = resolver(true, &[("cust", "customerid"), ("total", "Total")]);
+        assert_eq!(resolve(&folding, "cust", &schema).unwrap(), "CustomerID");
+        assert_eq!(resolve(&folding, "total", &schema).unwrap(), "Total");
+        // 映射的键区分大小写，未映射的名字直接按大小写不敏感匹配
+        assert_eq!(resolve(&folding, "CUST", &schema).unwrap(), "CUST");
+        assert_eq!(resolve(&folding, "TOTAL", &schema).unwrap(), "Total");
+
+        // 精确模式下只做映射
+        let exact = resolver(false, &[("cust", "customerid")]);
+        assert_eq!(resolve(&exact, "cust", &schema).unwrap(), "customerid");
+    }
+
+    #[test]
+    fn resolver_rewrites_nested_column_references() {
+        let schema = schema(&["A", "B"]);
+        let resolved = resolver(true, &[])
+            .resolve_expr((col("a") + col("b")).alias("sum"), &schema)
+            .unwrap();
+        let names: Vec<_> = resolved.meta().root_names().iter().map(|n| n.to_string()).collect();
+        assert_eq!(names, ["A", "B"]);
+    }
+
+    #[test]
+    fn unique_names_pass_through() {
+        for policy in [
+            DuplicatePolicy::Default,
+            DuplicatePolicy::Error,
+            DuplicatePolicy::AutoSuffix,
+            DuplicatePolicy::KeepFirst,
+        ] {
+            assert_eq!(
+                resolved(&["a", "b", "c"], policy),
+                [Some("a".into()), Some("b".into()), Some("c".into())]
+            );
+        }
+    }
+
+    #[test]
+    fn error_policies_report_both_positions() {
+        for policy in [DuplicatePolicy::Default, DuplicatePolicy::Error] {
+            let err = dedup_column_names(&names(&["a", "b", "a"]), policy).unwrap_err();
+            assert!(matches!(err, BridgeError::InvalidArgument(_)));
+            assert!(err.to_string().contains("positions 0 and 2"), "{err}");
+        }
+    }
+
+    #[test]
+    fn keep_first_drops_later_duplicates() {
+        assert_eq!(
+            resolved(&["a", "b", "a", "a"], DuplicatePolicy::KeepFirst),
+            [Some("a".into()), Some("b".into()), None, None]
+        );
+    }
+
+    #[test]
+    fn auto_suffix_skips_existing_names() {
+        assert_eq!(
+            resolved(&["a", "a_1", "a", "a", "b", "b"], DuplicatePolicy::AutoSuffix),
+            [
+                Some("a".into()),
+                Some("a_1".into()),
+                Some("a_2".into()),
+                Some("a_3".into()),
+                Some("b".into()),
+                Some("b_1".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn csv_scan_applies_policy_to_the_raw_header() {
+        let df = scan_csv(DuplicatePolicy::AutoSuffix).unwrap();
+        let expected = df!(
+            "x" => [1i64, 4],
+            "x_1" => [2i64, 5],
+            "x_duplicated_0" => [3i64, 6],
+            "y" => ["a", "b"],
+        )
+        .unwrap();
+        assert!(df.equals(&expected), "{df}");
+
+        let df = scan_csv(DuplicatePolicy::KeepFirst).unwrap();
+        let expected = df!(
+            "x" => [1i64, 4],
+            "x_duplicated_0" => [3i64, 6],
+            "y" => ["a", "b"],
+        )
+        .unwrap();
+        assert!(df.equals(&expected), "{df}");
+
+        // 类型覆盖按原始表头列名匹配
+        let scan = proto::CsvScan {
+            path: DUPLICATE_HEADER_CSV.into(),
+            schema_overrides: vec![proto::Field {
+                name: "x_duplicated_0".into(),
+                data_type: proto::DataType::Float64 as i32,
+            }],
+            ..Default::default()
+        };
+        let df = csv_scan_with_policy(&scan, DuplicatePolicy::KeepFirst).unwrap().collect().unwrap();
+        assert_eq!(df.column("x_duplicated_0").unwrap().dtype(), &DataType::Float64);
+        assert_eq!(df.column("x").unwrap().dtype(), &DataType::Int64);
+
+        let err = scan_csv(DuplicatePolicy::Error).unwrap_err();
+        assert!(err.to_string().contains("Duplicate column name 'x'"), "{err}");
+    }
+}
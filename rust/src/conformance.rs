@@ -0,0 +1,160 @@
+use std::fs::{self, File};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::Path;
+use std::time::Instant;
+
+use polars::prelude::*;
+use serde_json::{json, Value};
+
+use crate::error::BridgeError;
+use crate::executor;
+use crate::proto;
+
+const PLAN_FILE: &str = "plan.pb";
+const INPUT_FILE: &str = "input.ipc";
+const EXPECTED_FILE: &str = "expected.ipc";
+const EXPECTED_ERROR_FILE: &str = "expected_error.txt";
+
+/// 运行一个目录下的 golden Plan 用例，返回 JSON 报告
+///
+/// 每个子目录是一个用例：`plan.pb`（必需）、`input.ipc`（可选，作为 MemoryScan 的输入）、
+/// `expected.ipc`（期望结果）或 `expected_error.txt`（期望错误消息包含的文本）。
+/// 用例按目录名排序执行，Plan 中的相对路径相对于进程工作目录解析。
+///
+/// 报告格式：`{"total", "passed", "failed", "cases": [{"name", "passed", "message", "duration_us"}]}`
+pub fn run_corpus(dir: &str) -> Result<Value, BridgeError> {
+    let entries = fs::read_dir(dir).map_err(|e| {
+        BridgeError::InvalidArgument(format!("Failed to read conformance corpus '{}': {}", dir, e))
+    })?;
+    let mut case_dirs: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.join(PLAN_FILE).is_file())
+        .collect();
+    case_dirs.sort();
+
+    let cases: Vec<Value> = case_dirs.iter().map(|path| run_case(path)).collect();
+    let passed = cases.iter().filter(|case| case["passed"] == true).count();
+
+    Ok(json!({
+        "total": cases.len(),
+        "passed": passed,
+        "failed": cases.len() - passed,
+        "cases": cases,
+    }))
+}
+
+fn run_case(path: &Path) -> Value {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let started = Instant::now();
+    // 单个用例 panic 不影响其余用例，按失败记录
+    let outcome = catch_unwind(AssertUnwindSafe(|| check_case(path))).unwrap_or_else(|panic| {
+        let msg = panic
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "Unknown panic".into());
+        Err(format!("Panic: {}", msg))
+    });
+
+    json!({
+        "name": name,
+        "passed": outcome.is_ok(),
+        "message": outcome.err().unwrap_or_default(),
+        "duration_us": started.elapsed().as_micros() as u64,
+    })
+}
+
+fn check_case(path: &Path) -> Result<(), String> {
+    let plan_bytes = fs::read(path.join(PLAN_FILE))
+        .map_err(|e| format!("Failed to read {}: {}", PLAN_FILE, e))?;
+    let input = optional_ipc(&path.join(INPUT_FILE))?;
+    let expected_error = match fs::read_to_string(path.join(EXPECTED_ERROR_FILE)) {
+        Ok(text) => Some(text.trim().to_string()),
+        Err(_) => None,
+    };
+
+    let hints = proto::ExecutionHints::default();
+    let result = crate::compile_plan(&plan_bytes)
+        .and_then(|plan| executor::execute_plan_unrecorded(&plan, input.as_ref(), &hints));
+
+    match (result, expected_error) {
+        (Ok(_), Some(expected)) => Err(format!(
+            "Expected an error containing '{}', got a result",
+            expected
+        )),
+        (Err(e), Some(expected)) => {
+            let message = e.to_string();
+            if message.contains(&expected) {
+                Ok(())
+            } else {
+                Err(format!("Expected an error containing '{}', got: {}", expected, message))
+            }
+        }
+        (Err(e), None) => Err(format!("Execution failed: {}", e)),
+        (Ok(actual), None) => {
+            let expected = optional_ipc(&path.join(EXPECTED_FILE))?
+                .ok_or_else(|| format!("Missing {} or {}", EXPECTED_FILE, EXPECTED_ERROR_FILE))?;
+            compare_frames(&actual, &expected)
+        }
+    }
+}
+
+fn optional_ipc(path: &Path) -> Result<Option<DataFrame>, String> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    IpcReader::new(file)
+        .finish()
+        .map(Some)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+}
+
+// 先比较 schema 与形状，给出更具体的差异，再逐值比较（null 与 null 视为相等）
+fn compare_frames(actual: &DataFrame, expected: &DataFrame) -> Result<(), String> {
+    if actual.schema() != expected.schema() {
+        return Err(format!(
+            "Schema mismatch: expected {:?}, got {:?}",
+            expected.schema(),
+            actual.schema()
+        ));
+    }
+    if actual.height() != expected.height() {
+        return Err(format!(
+            "Row count mismatch: expected {}, got {}",
+            expected.height(),
+            actual.height()
+        ));
+    }
+    for (a, e) in actual.get_columns().iter().zip(expected.get_columns()) {
+        if !a.as_materialized_series().equals_missing(e.as_materialized_series()) {
+            return Err(format!("Values differ in column '{}'", a.name()));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run_corpus;
+
+    // cargo test 在 rust/ 下运行，与 Go 测试一样通过 ../testdata 引用语料
+    #[test]
+    fn corpus_passes() {
+        let report = run_corpus("../testdata/conformance").unwrap();
+        let failures: Vec<_> = report["cases"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|case| case["passed"] != true)
+            .map(|case| format!("{}: {}", case["name"], case["message"]))
+            .collect();
+
+        assert!(report["total"].as_u64().unwrap() > 0, "Conformance corpus is empty");
+        assert!(failures.is_empty(), "Conformance cases failed:\n{}", failures.join("\n"));
+    }
+}
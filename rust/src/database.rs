@@ -0,0 +1,56 @@
+use polars::prelude::*;
+
+use crate::error::BridgeError;
+use crate::proto;
+
+/// DatabaseScan：通过 ADBC 驱动执行 SQL，把返回的 Arrow 批次导入为 DataFrame
+///
+/// 驱动以动态库形式按名称或路径加载（如 `adbc_driver_postgresql`），
+/// 需要以 `database` feature 构建。
+#[cfg(feature = "database")]
+pub fn database_scan(scan: &proto::DatabaseScan) -> Result<LazyFrame, BridgeError> {
+    use adbc_core::options::{AdbcVersion, OptionDatabase, OptionValue};
+    use adbc_core::{Connection, Database, Driver, Statement};
+    use adbc_driver_manager::ManagedDriver;
+
+    use crate::arrow_bridge::{import_arrow_rs_batch, ImportOptions};
+    use crate::frame_builder::FrameBuilder;
+
+    if scan.driver.is_empty() || scan.query.is_empty() {
+        return Err(BridgeError::InvalidArgument(
+            "DatabaseScan requires driver and query".into(),
+        ));
+    }
+    let to_error = |e: adbc_core::error::Error| {
+        BridgeError::Execution(format!("DatabaseScan failed ({}): {}", scan.driver, e))
+    };
+
+    let mut driver = ManagedDriver::load_dynamic_from_name(&scan.driver, None, AdbcVersion::default())
+        .map_err(to_error)?;
+    let options = std::iter::once((OptionDatabase::Uri, OptionValue::from(scan.uri.as_str())))
+        .filter(|_| !scan.uri.is_empty())
+        .chain(scan.driver_options.iter().map(|(key, value)| {
+            (OptionDatabase::from(key.as_str()), OptionValue::from(value.as_str()))
+        }));
+    let database = driver.new_database_with_opts(options).map_err(to_error)?;
+    let mut connection = database.new_connection().map_err(to_error)?;
+    let mut statement = connection.new_statement().map_err(to_error)?;
+    statement.set_sql_query(&scan.query).map_err(to_error)?;
+    let reader = statement.execute().map_err(to_error)?;
+
+    let options = ImportOptions::from_options(None);
+    let mut frame = FrameBuilder::default();
+    for batch in reader {
+        let batch = batch.map_err(|e| BridgeError::ArrowImport(e.to_string()))?;
+        frame.append(import_arrow_rs_batch(batch, &options)?)?;
+    }
+
+    Ok(frame.finish().lazy())
+}
+
+#[cfg(not(feature = "database"))]
+pub fn database_scan(_scan: &proto::DatabaseScan) -> Result<LazyFrame, BridgeError> {
+    Err(BridgeError::Unsupported(
+        "DatabaseScan requires the bridge to be built with the `database` feature".into(),
+    ))
+}
@@ -0,0 +1,170 @@
+use polars::prelude::*;
+
+use crate::error::BridgeError;
+use crate::proto;
+
+// Decimal128 的最大精度
+pub const MAX_PRECISION: usize = 38;
+
+/// Decimal 运算语义
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum DecimalMode {
+    Strict,
+    FloatFallback,
+}
+
+impl DecimalMode {
+    pub fn from_options(options: Option<&proto::PlanOptions>) -> Self {
+        match options.map(|opts| proto::DecimalMode::try_from(opts.decimal_mode)) {
+            Some(Ok(proto::DecimalMode::Strict)) => DecimalMode::Strict,
+            _ => DecimalMode::FloatFallback,
+        }
+    }
+}
+
+/// 将十进制字符串（如 "-12.340"）转换为 Decimal 字面量，scale 取小数位数
+pub fn decimal_lit(value: &str) -> Result<Expr, BridgeError> {
+    let invalid = || BridgeError::InvalidArgument(format!("Invalid decimal literal: '{}'", value));
+
+    let (negative, digits) = match value.trim().strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value.trim().trim_start_matches('+')),
+    };
+    let (int_part, frac_part) = digits.split_once('.').unwrap_or((digits, ""));
+    if int_part.is_empty() && frac_part.is_empty()
+        || !int_part.bytes().chain(frac_part.bytes()).all(|b| b.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+
+    // 有效数字（去掉整数部分的前导 0）不能超过 Decimal128 的精度
+    let scale = frac_part.len();
+    if int_part.trim_start_matches('0').len() + scale > MAX_PRECISION {
+        return Err(BridgeError::InvalidArgument(format!(
+            "Decimal literal '{}' has more than {} significant digits",
+            value, MAX_PRECISION
+        )));
+    }
+    let unscaled: i128 = format!("{}{}", int_part, frac_part)
+        .parse()
+        .map_err(|_| invalid())?;
+    let unscaled = if negative { -unscaled } else { unscaled };
+
+    let scalar = Scalar::new(
+        DataType::Decimal(MAX_PRECISION, scale),
+        AnyValue::Decimal(unscaled, MAX_PRECISION, scale),
+    );
+    Ok(lit(scalar))
+}
+
+/// 严格模式：表达式引用了 Decimal 列、结果却是浮点类型时报错，避免静默丢失精度（显式 Cast 除外）
+pub fn check_no_float_fallback(
+    lf: &LazyFrame,
+    exprs: &[Expr],
+    schema: &Schema,
+) -> Result<(), BridgeError> {
+    let decimal_columns: Vec<&PlSmallStr> = schema
+        .iter()
+        .filter(|(_, dtype)| dtype.is_decimal())
+        .map(|(name, _)| name)
+        .collect();
+    if decimal_columns.is_empty() {
+        return Ok(());
+    }
+
+    for expr in exprs {
+        let roots = expr.clone().meta().root_names();
+        let Some(source) = roots.iter().find(|name| decimal_columns.contains(name)) else {
+            continue;
+        };
+        // 显式 Cast 到浮点表示调用方接受精度损失
+        if expr.into_iter().any(is_float_cast) {
+            continue;
+        }
+
+        let output = lf
+            .clone()
+            .select([expr.clone()])
+            .collect_schema()
+            .map_err(|e| BridgeError::Execution(format!("Failed to resolve expression type: {}", e)))?;
+        if let Some((name, dtype)) = output.iter().find(|(_, dtype)| dtype.is_float()) {
+            return Err(BridgeError::PlanSemantic(format!(
+                "Expression '{}' turns decimal column '{}' into {}; cast operands to Decimal \
+                 or to a float type explicitly, or use DECIMAL_MODE_FLOAT_FALLBACK",
+                name, source, dtype
+            )));
+        };
+    }
+
+    Ok(())
+}
+
+fn is_float_cast(expr: &Expr) -> bool {
+    matches!(expr, Expr::Cast { dtype, .. } if dtype.as_literal().is_some_and(|dt| dt.is_float()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(value: &str) -> AnyValue<'static> {
+        let df = DataFrame::empty()
+            .lazy()
+            .select([decimal_lit(value).unwrap().alias("d")])
+            .collect()
+            .unwrap();
+        df.column("d").unwrap().get(0).unwrap().into_static()
+    }
+
+    #[test]
+    fn parses_sign_and_scale() {
+        let cases = [
+            ("-12.340", -12340, 3),
+            ("+1.5", 15, 1),
+            (" 42 ", 42, 0),
+            (".25", 25, 2),
+            ("7.", 7, 0),
+            ("-0.001", -1, 3),
+        ];
+        for (value, unscaled, scale) in cases {
+            assert_eq!(
+                eval(value),
+                AnyValue::Decimal(unscaled, MAX_PRECISION, scale),
+                "literal {value:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn accepts_max_precision() {
+        let digits = "9".repeat(MAX_PRECISION);
+        assert!(decimal_lit(&digits).is_ok());
+        assert!(decimal_lit(&format!("0.{}", digits)).is_ok());
+        assert!(decimal_lit(&format!("000{}", digits)).is_ok());
+        assert!(decimal_lit(&format!("-{}.{}", &digits[..20], &digits[20..])).is_ok());
+    }
+
+    #[test]
+    fn rejects_more_than_max_precision() {
+        let digits = "1".repeat(MAX_PRECISION + 1);
+        for value in [
+            digits.clone(),
+            format!("-{}", digits),
+            format!("0.{}", digits),
+            format!("{}.{}", &digits[..1], &digits[1..]),
+        ] {
+            let err = decimal_lit(&value).unwrap_err();
+            assert!(err.to_string().contains("significant digits"), "{value}: {err}");
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_literals() {
+        for value in ["", "-", ".", "1.2.3", "1e5", "abc", "--1", "+-1", "1_000"] {
+            assert!(
+                matches!(decimal_lit(value), Err(BridgeError::InvalidArgument(_))),
+                "literal {value:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use polars::functions::concat_df_diagonal;
+use polars::prelude::*;
+
+use crate::error::BridgeError;
+use crate::proto;
+
+/// 结果中的差异类型列（added / removed / changed）
+const DIFF: &str = "_diff";
+/// changed 行中旧值列的后缀
+const BEFORE_SUFFIX: &str = "_before";
+
+/// 按键列比较两个 DataFrame：返回差异行（右侧为新值）与 schema/行数摘要
+pub fn df_diff(
+    left: &DataFrame,
+    right: &DataFrame,
+    options: &proto::DiffOptions,
+) -> Result<(DataFrame, proto::DataDiff), BridgeError> {
+    if options.keys.is_empty() {
+        return Err(BridgeError::InvalidArgument("Diff requires at least one key column".into()));
+    }
+
+    let left_schema = left.schema();
+    let right_schema = right.schema();
+    let mut summary = schema_diff(left_schema, right_schema);
+
+    for key in &options.keys {
+        match (left_schema.get(key), right_schema.get(key)) {
+            (Some(l), Some(r)) if l == r => {}
+            (Some(_), Some(_)) => {
+                return Err(BridgeError::InvalidArgument(format!(
+                    "Diff key '{}' has different types on both sides",
+                    key
+                )))
+            }
+            _ => {
+                return Err(BridgeError::InvalidArgument(format!(
+                    "Diff key '{}' must exist in both DataFrames",
+                    key
+                )))
+            }
+        }
+    }
+
+    // 键重复时内连接会放大 changed 行
+    check_unique_keys(left, &options.keys, "left")?;
+    check_unique_keys(right, &options.keys, "right")?;
+
+    let is_key = |name: &str| options.keys.iter().any(|k| k == name);
+    // 只比较两侧都存在且类型相同的列；类型变化的列只体现在 schema 差异中
+    let compared: Vec<PlSmallStr> = left_schema
+        .iter()
+        .filter(|(name, dtype)| !is_key(name) && right_schema.get(name) == Some(*dtype))
+        .map(|(name, _)| name.clone())
+        .collect();
+
+    // 生成的列名不能与输入的列重名
+    let generated = std::iter::once(PlSmallStr::from(DIFF))
+        .chain(compared.iter().map(|name| format!("{}{}", name, BEFORE_SUFFIX).into()));
+    for name in generated {
+        if left_schema.contains(&name) || right_schema.contains(&name) {
+            return Err(BridgeError::InvalidArgument(format!(
+                "Diff output column '{}' collides with an input column",
+                name
+            )));
+        }
+    }
+    let keys: Vec<Expr> = options.keys.iter().map(|k| col(k.as_str())).collect();
+    let anti = || {
+        let mut args = JoinArgs::new(JoinType::Anti);
+        args.nulls_equal = true;
+        args
+    };
+
+    let added = right
+        .clone()
+        .lazy()
+        .join(left.clone().lazy().select(keys.clone()), keys.clone(), keys.clone(), anti());
+
+    let removed_columns: Vec<Expr> = left_schema
+        .iter()
+        .filter(|(name, dtype)| right_schema.get(name).is_none_or(|r| r == *dtype))
+        .map(|(name, _)| col(name.clone()))
+        .collect();
+    let removed = left
+        .clone()
+        .lazy()
+        .join(right.clone().lazy().select(keys.clone()), keys.clone(), keys.clone(), anti())
+        .select(removed_columns);
+
+    let before: Vec<Expr> = keys
+        .iter()
+        .cloned()
+        .chain(compared.iter().map(|name| {
+            col(name.clone()).alias(format!("{}{}", name, BEFORE_SUFFIX))
+        }))
+        .collect();
+    let changed_mask = compared
+        .iter()
+        .map(|name| col(name.clone()).neq_missing(col(format!("{}{}", name, BEFORE_SUFFIX))))
+        .reduce(|acc, e| acc.or(e))
+        .unwrap_or(lit(false));
+    let mut inner = JoinArgs::new(JoinType::Inner);
+    inner.nulls_equal = true;
+    inner.validation = JoinValidation::OneToOne;
+    let changed = right
+        .clone()
+        .lazy()
+        .join(left.clone().lazy().select(before), keys.clone(), keys, inner)
+        .filter(changed_mask);
+
+    let collect = |lf: LazyFrame, kind: &str| {
+        lf.with_column(lit(kind).alias(DIFF))
+            .collect()
+            .map_err(|e| BridgeError::Execution(format!("Diff failed ({kind} rows)")).with_source(e))
+    };
+    let added = collect(added, "added")?;
+    let removed = collect(removed, "removed")?;
+    let changed = collect(changed, "changed")?;
+    summary.added_rows = added.height() as u64;
+    summary.removed_rows = removed.height() as u64;
+    summary.changed_rows = changed.height() as u64;
+
+    let mut result = concat_df_diagonal(&[added, removed, changed])
+        .map_err(|e| BridgeError::Execution("Diff failed".into()).with_source(e))?;
+
+    // _diff 放在第一列
+    let mut order: Vec<PlSmallStr> = vec![DIFF.into()];
+    order.extend(result.get_column_names().into_iter().filter(|n| n.as_str() != DIFF).cloned());
+    result = result
+        .select(order)
+        .map_err(|e| BridgeError::Execution("Diff failed".into()).with_source(e))?;
+
+    Ok((result, summary))
+}
+
+fn check_unique_keys(df: &DataFrame, keys: &[String], side: &str) -> Result<(), BridgeError> {
+    let duplicated = df
+        .select(keys.iter().map(|k| k.as_str()))
+        .and_then(|keys| keys.is_duplicated())
+        .map_err(|e| BridgeError::Execution("Failed to check diff keys".into()).with_source(e))?;
+    if duplicated.any() {
+        return Err(BridgeError::InvalidArgument(format!(
+            "Diff keys are not unique in the {} DataFrame",
+            side
+        )));
+    }
+    Ok(())
+}
+
+fn schema_diff(left: &Schema, right: &Schema) -> proto::DataDiff {
+    let mut summary = proto::DataDiff::default();
+    for (name, dtype) in left.iter() {
+        match right.get(name) {
+            None => summary.removed_columns.push(name.to_string()),
+            Some(other) if other != dtype => summary.type_changes.push(proto::ColumnTypeChange {
+                name: name.to_string(),
+                left_dtype: dtype.to_string(),
+                right_dtype: other.to_string(),
+            }),
+            Some(_) => {}
+        }
+    }
+    summary.added_columns = right
+        .iter_names()
+        .filter(|name| !left.contains(name))
+        .map(|name| name.to_string())
+        .collect();
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(keys: &[&str]) -> proto::DiffOptions {
+        proto::DiffOptions { keys: keys.iter().map(|k| k.to_string()).collect() }
+    }
+
+    #[test]
+    fn reports_added_removed_and_changed_rows() {
+        let left = df!(
+            "id" => [Some(1i64), Some(2), Some(3), None],
+            "v" => [Some(10i64), Some(20), None, Some(40)],
+        )
+        .unwrap();
+        let right = df!(
+            "id" => [Some(1i64), Some(3), Some(4), None],
+            "v" => [Some(10i64), Some(30), Some(40), Some(41)],
+        )
+        .unwrap();
+
+        let (result, summary) = df_diff(&left, &right, &options(&["id"])).unwrap();
+        assert_eq!((summary.added_rows, summary.removed_rows, summary.changed_rows), (1, 1, 2));
+
+        // null 键互相匹配；null 与非 null 的值视为变化
+        let expected = df!(
+            DIFF => ["added", "removed", "changed", "changed"],
+            "id" => [Some(4i64), Some(2), Some(3), None],
+            "v" => [Some(40i64), Some(20), Some(30), Some(41)],
+            "v_before" => [None, None, None, Some(40i64)],
+        )
+        .unwrap();
+        assert!(result.equals_missing(&expected), "{result}");
+    }
+
+    #[test]
+    fn type_changed_columns_only_appear_in_the_summary() {
+        let left = df!("id" => [1i64, 2], "v" => [1i64, 2], "gone" => [0i64, 0]).unwrap();
+        let right = df!("id" => [1i64, 2], "v" => [1.0f64, 5.0], "new" => [0i64, 0]).unwrap();
+
+        let (result, summary) = df_diff(&left, &right, &options(&["id"])).unwrap();
+        assert_eq!(summary.added_columns, ["new"]);
+        assert_eq!(summary.removed_columns, ["gone"]);
+        assert_eq!(summary.type_changes.len(), 1);
+        assert_eq!(summary.type_changes[0].name, "v");
+        assert_eq!(summary.changed_rows, 0);
+        assert_eq!(result.height(), 0);
+    }
+
+    #[test]
+    fn rejects_duplicate_keys() {
+        let left = df!("id" => [1i64, 1], "v" => [1i64, 2]).unwrap();
+        let right = df!("id" => [1i64], "v" => [3i64]).unwrap();
+        let err = df_diff(&left, &right, &options(&["id"])).unwrap_err();
+        assert!(err.to_string().contains("left"), "{err}");
+        let err = df_diff(&right, &left, &options(&["id"])).unwrap_err();
+        assert!(err.to_string().contains("right"), "{err}");
+    }
+
+    #[test]
+    fn rejects_colliding_output_columns() {
+        let left = df!("id" => [1i64], "v" => [1i64], "v_before" => [0i64]).unwrap();
+        let right = df!("id" => [1i64], "v" => [2i64], "v_before" => [0i64]).unwrap();
+        assert!(matches!(
+            df_diff(&left, &right, &options(&["id"])),
+            Err(BridgeError::InvalidArgument(_))
+        ));
+
+        let left = df!("id" => [1i64], DIFF => ["x"]).unwrap();
+        assert!(matches!(
+            df_diff(&left, &left, &options(&["id"])),
+            Err(BridgeError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_missing_or_mismatched_keys() {
+        let left = df!("id" => [1i64]).unwrap();
+        let right = df!("id" => [1i32]).unwrap();
+        assert!(df_diff(&left, &right, &options(&["id"])).is_err());
+        assert!(df_diff(&left, &left, &options(&["missing"])).is_err());
+        assert!(df_diff(&left, &left, &options(&[])).is_err());
+    }
+}
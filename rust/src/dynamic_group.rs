@@ -0,0 +1,89 @@
+use polars::prelude::*;
+
+use crate::error::BridgeError;
+use crate::proto;
+
+const MILLISECONDS_IN_DAY: i64 = 86_400_000;
+
+/// GroupByDynamic 的窗口选项：输出的 index_column 为窗口起点（label = left），不附加边界列
+pub fn options(node: &proto::GroupByDynamic) -> Result<DynamicGroupOptions, BridgeError> {
+    if node.index_column.is_empty() {
+        return Err(BridgeError::PlanSemantic("GroupByDynamic has no index_column".into()));
+    }
+    if node.every.is_empty() {
+        return Err(BridgeError::PlanSemantic("GroupByDynamic has no every".into()));
+    }
+
+    let every = parse_duration("every", &node.every)?;
+    let period = if node.period.is_empty() {
+        every
+    } else {
+        parse_duration("period", &node.period)?
+    };
+    let offset = if node.offset.is_empty() {
+        Duration::new(0)
+    } else {
+        parse_duration("offset", &node.offset)?
+    };
+    let closed_window = match node.closed {
+        Some(closed) => closed_window(closed)?,
+        None => ClosedWindow::Left,
+    };
+
+    Ok(DynamicGroupOptions {
+        index_column: node.index_column.as_str().into(),
+        every,
+        period,
+        offset,
+        label: Label::Left,
+        include_boundaries: false,
+        closed_window,
+        start_by: StartBy::WindowBound,
+    })
+}
+
+/// 窗口长度换算为 index_column 物理值的单位（流式执行按水位线判断窗口是否结束）
+///
+/// 只支持固定长度的 period：按月计的时长无法换算为物理值
+pub fn physical_period(node: &proto::GroupByDynamic, dtype: &DataType) -> Result<i64, BridgeError> {
+    let period = options(node)?.period;
+    if period.months() != 0 {
+        return Err(BridgeError::PlanSemantic(format!(
+            "GroupByDynamic period must have a fixed length for streaming (got '{}')",
+            period
+        )));
+    }
+
+    match dtype {
+        DataType::Datetime(TimeUnit::Nanoseconds, _) => Ok(period.duration_ns()),
+        DataType::Datetime(TimeUnit::Microseconds, _) => Ok(period.duration_us()),
+        DataType::Datetime(TimeUnit::Milliseconds, _) => Ok(period.duration_ms()),
+        DataType::Date => Ok(period.duration_ms() / MILLISECONDS_IN_DAY),
+        dtype if dtype.is_integer() => Ok(period.nanoseconds()),
+        dtype => Err(BridgeError::PlanSemantic(format!(
+            "GroupByDynamic index_column '{}' has unsupported type {}",
+            node.index_column, dtype
+        ))),
+    }
+}
+
+pub fn closed_window(closed: i32) -> Result<ClosedWindow, BridgeError> {
+    use proto::RollingClosedWindow as Closed;
+
+    match Closed::try_from(closed) {
+        Ok(Closed::Right) => Ok(ClosedWindow::Right),
+        Ok(Closed::Left) => Ok(ClosedWindow::Left),
+        Ok(Closed::Both) => Ok(ClosedWindow::Both),
+        Ok(Closed::None) => Ok(ClosedWindow::None),
+        Err(_) => Err(BridgeError::Unsupported(format!(
+            "Unknown rolling closed window: {}",
+            closed
+        ))),
+    }
+}
+
+fn parse_duration(what: &str, value: &str) -> Result<Duration, BridgeError> {
+    Duration::try_parse(value).map_err(|e| {
+        BridgeError::PlanSemantic(format!("GroupByDynamic has invalid {what} '{}': {}", value, e))
+    })
+}
@@ -0,0 +1,61 @@
+use crate::expr_ext;
+use crate::proto;
+
+/// 收集构建与运行时信息
+pub fn engine_info(abi_version: u32) -> proto::EngineInfo {
+    proto::EngineInfo {
+        crate_version: env!("CARGO_PKG_VERSION").to_string(),
+        polars_version: env!("BRIDGE_POLARS_VERSION").to_string(),
+        features: split_list(env!("BRIDGE_FEATURES")),
+        polars_features: split_list(env!("BRIDGE_POLARS_FEATURES")),
+        target_triple: env!("BRIDGE_TARGET").to_string(),
+        allocator: "system".to_string(),
+        simd: detect_simd(),
+        git_commit: env!("BRIDGE_GIT_COMMIT").to_string(),
+        abi_version,
+        build_profile: env!("BRIDGE_PROFILE").to_string(),
+        expr_extensions: expr_ext::registered(),
+        symbol_prefix: env!("BRIDGE_SYMBOL_PREFIX").to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        dylib_suffix: std::env::consts::DLL_SUFFIX.to_string(),
+    }
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+        .collect()
+}
+
+// 运行时检测 CPU 支持的 SIMD 指令集
+fn detect_simd() -> Vec<String> {
+    #[allow(unused_mut)]
+    let mut simd = Vec::new();
+
+    #[cfg(target_arch = "x86_64")]
+    {
+        for (name, detected) in [
+            ("sse4.2", std::arch::is_x86_feature_detected!("sse4.2")),
+            ("avx", std::arch::is_x86_feature_detected!("avx")),
+            ("avx2", std::arch::is_x86_feature_detected!("avx2")),
+            ("fma", std::arch::is_x86_feature_detected!("fma")),
+            ("avx512f", std::arch::is_x86_feature_detected!("avx512f")),
+        ] {
+            if detected {
+                simd.push(name.to_string());
+            }
+        }
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    {
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            simd.push("neon".to_string());
+        }
+    }
+
+    simd
+}
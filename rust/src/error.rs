@@ -1,4 +1,9 @@
+use crate::proto;
+
+// 错误码与 Go 侧一一对应，其中部分只由 Go 侧产生
 #[repr(i32)]
+#[derive(Clone, Copy)]
+#[allow(dead_code)]
 pub enum ErrorCode {
     Ok = 0,
     Unknown = 1,
@@ -12,6 +17,8 @@ pub enum ErrorCode {
     Execution = 9,
     Unsupported = 10,
     Oom = 11,
+    SchemaMismatch = 12,
+    AssertionFailed = 13,
 }
 
 impl std::fmt::Display for ErrorCode {
@@ -29,11 +36,14 @@ impl std::fmt::Display for ErrorCode {
             ErrorCode::Execution => write!(f, "ERR_EXECUTION"),
             ErrorCode::Unsupported => write!(f, "ERR_UNSUPPORTED"),
             ErrorCode::Oom => write!(f, "ERR_OOM"),
+            ErrorCode::SchemaMismatch => write!(f, "ERR_SCHEMA_MISMATCH"),
+            ErrorCode::AssertionFailed => write!(f, "ERR_ASSERTION_FAILED"),
         }
     }
 }
 
 #[derive(Debug)]
+#[allow(dead_code)]
 pub enum BridgeError {
     InvalidArgument(String),
     AbiMismatch(u32, u32),
@@ -44,6 +54,26 @@ pub enum BridgeError {
     ArrowExport(String),
     Execution(String),
     Unsupported(String),
+    SchemaMismatch(String),
+    AssertionFailed(String),
+    /// 附带底层原因的错误（错误码与消息取自 error，source 逐级展开为原因链）
+    WithSource {
+        error: Box<BridgeError>,
+        source: Box<dyn std::error::Error + Send + Sync + 'static>,
+    },
+}
+
+impl BridgeError {
+    /// 附加底层错误（如 PolarsError），保留完整的 source 链
+    pub fn with_source<E>(self, source: E) -> Self
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        BridgeError::WithSource {
+            error: Box::new(self),
+            source: Box::new(source),
+        }
+    }
 }
 
 impl std::fmt::Display for BridgeError {
@@ -58,11 +88,21 @@ impl std::fmt::Display for BridgeError {
             BridgeError::ArrowExport(s) => write!(f, "Arrow export error: {}", s),
             BridgeError::Execution(s) => write!(f, "Execution error: {}", s),
             BridgeError::Unsupported(s) => write!(f, "Unsupported: {}", s),
+            BridgeError::SchemaMismatch(s) => write!(f, "Schema mismatch: {}", s),
+            BridgeError::AssertionFailed(s) => write!(f, "Assertion failed: {}", s),
+            BridgeError::WithSource { error, .. } => write!(f, "{}", error),
         }
     }
 }
 
-impl std::error::Error for BridgeError {}
+impl std::error::Error for BridgeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BridgeError::WithSource { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
 
 pub fn bridge_error_to_code(err: &BridgeError) -> (ErrorCode, String) {
     match err {
@@ -75,5 +115,52 @@ pub fn bridge_error_to_code(err: &BridgeError) -> (ErrorCode, String) {
         BridgeError::ArrowExport(s) => (ErrorCode::ArrowExport, s.clone()),
         BridgeError::Execution(s) => (ErrorCode::Execution, s.clone()),
         BridgeError::Unsupported(s) => (ErrorCode::Unsupported, s.clone()),
+        BridgeError::SchemaMismatch(s) => (ErrorCode::SchemaMismatch, s.clone()),
+        BridgeError::AssertionFailed(s) => (ErrorCode::AssertionFailed, s.clone()),
+        BridgeError::WithSource { error, .. } => bridge_error_to_code(error),
+    }
+}
+
+/// 错误的原因链（从直接原因到根因；与上一级文本相同的层会被跳过）
+pub fn source_chain(err: &BridgeError) -> Vec<String> {
+    let mut causes: Vec<String> = Vec::new();
+    let mut source = std::error::Error::source(err);
+    while let Some(cause) = source {
+        let message = cause.to_string();
+        if causes.last() != Some(&message) {
+            causes.push(message);
+        }
+        source = cause.source();
+    }
+    causes
+}
+
+/// 构造结构化错误（bridge_last_error_detail 输出）
+pub fn error_detail(
+    code: ErrorCode,
+    message: String,
+    causes: Vec<String>,
+    query_id: Option<String>,
+) -> proto::ErrorDetail {
+    proto::ErrorDetail {
+        code: code.to_string(),
+        code_value: code as i32,
+        message,
+        causes,
+        query_id: query_id.unwrap_or_default(),
+    }
+}
+
+/// 渲染为 bridge_last_error 的字符串：`[CODE] [query_id=...] message`，每个原因单独一行
+pub fn render_detail(detail: &proto::ErrorDetail) -> String {
+    let mut out = format!("[{}] ", detail.code);
+    if !detail.query_id.is_empty() {
+        out.push_str(&format!("[query_id={}] ", detail.query_id));
+    }
+    out.push_str(&detail.message);
+    for cause in &detail.causes {
+        out.push_str("\ncaused by: ");
+        out.push_str(cause);
     }
+    out
 }
@@ -0,0 +1,180 @@
+#[cfg(feature = "parquet")]
+use std::fs::File;
+
+use polars::prelude::*;
+
+use crate::error::BridgeError;
+use crate::executor::{build_lazy_frame, plan_root, BuildContext};
+use crate::nodes::{node_inputs, node_name};
+use crate::proto;
+use crate::schema::to_polars_schema;
+
+/// 不执行 Plan 的粗略代价估算（供调度器按规模分配 worker）
+///
+/// - 行数：Parquet 读取文件元数据，其余数据源无法估算时记入 unestimated_sources；
+/// - 字节数：本地文件大小（glob 与云存储路径不计入）；
+/// - 流水线阶段：1 + 阻塞算子（GroupBy / Join / JoinWhere / Upsert）的数量。
+pub fn estimate(
+    plan: &proto::Plan,
+    input_schema: Option<&proto::Schema>,
+) -> Result<proto::PlanEstimate, BridgeError> {
+    use proto::node::Kind;
+
+    let root = plan_root(plan)?;
+    let mut estimate = proto::PlanEstimate {
+        pipeline_stages: 1,
+        ..Default::default()
+    };
+    let rows = visit(root, &mut estimate)?;
+    estimate.rows_scanned = rows;
+
+    // Avro/Excel/Database/Flight/Reader 扫描在构建阶段就会读入数据，TableScan 需要执行会话；MemoryScan 没有输入 schema 时无法构建
+    let eager_scan = contains(root, &|kind| {
+        matches!(
+            kind,
+            Kind::AvroScan(_)
+                | Kind::ExcelScan(_)
+                | Kind::DatabaseScan(_)
+                | Kind::FlightScan(_)
+                | Kind::ReaderScan(_)
+                | Kind::TableScan(_)
+        )
+    });
+    let missing_input = input_schema.is_none()
+        && contains(root, &|kind| matches!(kind, Kind::MemoryScan(_)));
+    if !eager_scan && !missing_input {
+        estimate.output_columns = output_columns(plan, root, input_schema)?;
+    }
+    Ok(estimate)
+}
+
+// 返回该子树扫描的行数（任一数据源未知时为 None）
+fn visit(node: &proto::Node, estimate: &mut proto::PlanEstimate) -> Result<Option<u64>, BridgeError> {
+    use proto::node::Kind;
+
+    estimate.node_count += 1;
+    if matches!(
+        node.kind,
+        Some(Kind::GroupBy(_))
+            | Some(Kind::GroupByDynamic(_))
+            | Some(Kind::Join(_))
+            | Some(Kind::JoinWhere(_))
+            | Some(Kind::Upsert(_))
+            | Some(Kind::Assert(_))
+    ) {
+        estimate.pipeline_stages += 1;
+    }
+
+    let source_rows = match node.kind.as_ref() {
+        // 快照目录要到构建时才能确定读取哪个子目录
+        Some(Kind::ParquetScan(scan)) if scan.as_of.is_some() => None,
+        Some(Kind::ParquetScan(scan)) => {
+            let mut rows = Some(0u64);
+            for path in local_paths(&scan.path, &scan.paths) {
+                estimate.bytes_read += file_size(path);
+                rows = rows.zip(parquet_rows(path)).map(|(a, b)| a + b);
+            }
+            if has_remote_paths(&scan.path, &scan.paths) {
+                rows = None;
+            }
+            rows
+        }
+        Some(Kind::CsvScan(scan)) => {
+            estimate.bytes_read += local_paths(&scan.path, &scan.paths).map(file_size).sum::<u64>();
+            None
+        }
+        Some(Kind::IpcScan(scan)) => {
+            estimate.bytes_read += local_paths(&scan.path, &[]).map(file_size).sum::<u64>();
+            None
+        }
+        Some(Kind::AvroScan(scan)) => {
+            estimate.bytes_read += local_paths(&scan.path, &[]).map(file_size).sum::<u64>();
+            None
+        }
+        Some(Kind::ExcelScan(scan)) => {
+            estimate.bytes_read += local_paths(&scan.path, &[]).map(file_size).sum::<u64>();
+            None
+        }
+        Some(Kind::MemoryScan(_))
+        | Some(Kind::IcebergScan(_))
+        | Some(Kind::DatabaseScan(_))
+        | Some(Kind::FlightScan(_))
+        | Some(Kind::ReaderScan(_))
+        | Some(Kind::BatchProviderScan(_))
+        | Some(Kind::TableScan(_))
+        | Some(Kind::DeltaScan(_)) => None,
+        _ => {
+            let mut rows = Some(0u64);
+            for input in node_inputs(node) {
+                let input_rows = visit(input, estimate)?;
+                rows = rows.zip(input_rows).map(|(a, b)| a + b);
+            }
+            return Ok(rows);
+        }
+    };
+
+    if source_rows.is_none() {
+        estimate.unestimated_sources.push(node_name(node).to_string());
+    }
+    Ok(source_rows)
+}
+
+fn output_columns(
+    plan: &proto::Plan,
+    root: &proto::Node,
+    input_schema: Option<&proto::Schema>,
+) -> Result<Vec<proto::OutputColumn>, BridgeError> {
+    let input_df = match input_schema {
+        Some(schema) => Some(DataFrame::empty_with_schema(&to_polars_schema(&schema.fields)?)),
+        None => None,
+    };
+
+    let ctx = BuildContext::new(plan, input_df.as_ref());
+    let mut lf = build_lazy_frame(root, &ctx)?;
+    let schema = lf.collect_schema()
+        .map_err(|e| BridgeError::PlanSemantic(format!("Failed to resolve output schema: {}", e)))?;
+
+    Ok(schema
+        .iter()
+        .map(|(name, dtype)| proto::OutputColumn {
+            name: name.to_string(),
+            dtype: dtype.to_string(),
+        })
+        .collect())
+}
+
+fn contains(node: &proto::Node, pred: &dyn Fn(&proto::node::Kind) -> bool) -> bool {
+    node.kind.as_ref().is_some_and(pred)
+        || node_inputs(node).into_iter().any(|input| contains(input, pred))
+}
+
+fn local_paths<'a>(path: &'a str, paths: &'a [String]) -> impl Iterator<Item = &'a str> {
+    std::iter::once(path)
+        .chain(paths.iter().map(String::as_str))
+        .filter(|p| !p.is_empty() && !is_remote_or_glob(p))
+}
+
+fn has_remote_paths(path: &str, paths: &[String]) -> bool {
+    std::iter::once(path)
+        .chain(paths.iter().map(String::as_str))
+        .any(|p| !p.is_empty() && is_remote_or_glob(p))
+}
+
+fn is_remote_or_glob(path: &str) -> bool {
+    path.contains("://") || path.contains(['*', '?', '['])
+}
+
+fn file_size(path: &str) -> u64 {
+    std::fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+}
+
+#[cfg(feature = "parquet")]
+fn parquet_rows(path: &str) -> Option<u64> {
+    let file = File::open(path).ok()?;
+    ParquetReader::new(file).num_rows().ok().map(|n| n as u64)
+}
+
+#[cfg(not(feature = "parquet"))]
+fn parquet_rows(_path: &str) -> Option<u64> {
+    None
+}
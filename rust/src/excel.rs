@@ -0,0 +1,189 @@
+use calamine::{open_workbook_auto, Data, DataType as _, Range, Reader};
+use polars::prelude::*;
+
+use crate::columns::{dedup_column_names, DuplicatePolicy};
+use crate::error::BridgeError;
+use crate::proto;
+
+/// ExcelScan：用 calamine 读取工作表，逐列推断类型后转为 LazyFrame
+pub fn excel_scan(scan: &proto::ExcelScan, duplicates: DuplicatePolicy) -> Result<LazyFrame, BridgeError> {
+    let to_error = |e: calamine::Error| {
+        BridgeError::Execution(format!("ExcelScan failed for '{}': {}", scan.path, e))
+    };
+
+    let mut workbook = open_workbook_auto(&scan.path).map_err(to_error)?;
+    let sheet = if scan.sheet_name.is_empty() {
+        workbook
+            .worksheet_range_at(scan.sheet_index as usize)
+            .ok_or_else(|| {
+                BridgeError::InvalidArgument(format!(
+                    "ExcelScan: sheet index {} out of range in '{}'",
+                    scan.sheet_index, scan.path
+                ))
+            })?
+            .map_err(to_error)?
+    } else {
+        workbook.worksheet_range(&scan.sheet_name).map_err(to_error)?
+    };
+
+    let sheet = if scan.range.is_empty() {
+        sheet
+    } else {
+        let (start, end) = parse_range(&scan.range)?;
+        sheet.range(start, end)
+    };
+
+    let df = range_to_df(&sheet, scan, duplicates)?;
+    Ok(df.lazy())
+}
+
+fn range_to_df(
+    sheet: &Range<Data>,
+    scan: &proto::ExcelScan,
+    duplicates: DuplicatePolicy,
+) -> Result<DataFrame, BridgeError> {
+    let mut rows = sheet.rows().skip(scan.header_row as usize);
+    let width = sheet.width();
+
+    let names: Vec<PlSmallStr> = if scan.has_header.unwrap_or(true) {
+        let header = rows.next().unwrap_or(&[]);
+        (0..width)
+            .map(|i| match header.get(i) {
+                Some(cell) if !cell.is_empty() => cell.to_string().into(),
+                _ => format!("column_{}", i + 1).into(),
+            })
+            .collect()
+    } else {
+        (0..width).map(|i| format!("column_{}", i + 1).into()).collect()
+    };
+
+    let mut values: Vec<Vec<AnyValue>> = vec![Vec::new(); width];
+    for row in rows {
+        for (i, column) in values.iter_mut().enumerate() {
+            column.push(row.get(i).map(cell_to_any_value).unwrap_or(AnyValue::Null));
+        }
+    }
+
+    let columns = names
+        .iter()
+        .zip(dedup_column_names(&names, duplicates)?)
+        .zip(values)
+        .filter_map(|((_, name), values)| name.map(|name| (name, values)))
+        .map(|(name, values)| {
+            Series::from_any_values(name.clone(), &values, false)
+                .map(Column::from)
+                .map_err(|e| {
+                    BridgeError::Execution(format!("ExcelScan: failed to build column '{}': {}", name, e))
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    DataFrame::new(columns)
+        .map_err(|e| BridgeError::Execution(format!("ExcelScan: failed to create DataFrame: {}", e)))
+}
+
+fn cell_to_any_value(cell: &Data) -> AnyValue<'static> {
+    match cell {
+        Data::Int(v) => AnyValue::Int64(*v),
+        Data::Float(v) => AnyValue::Float64(*v),
+        Data::Bool(v) => AnyValue::Boolean(*v),
+        Data::String(v) | Data::DateTimeIso(v) | Data::DurationIso(v) => {
+            AnyValue::StringOwned(v.as_str().into())
+        }
+        Data::DateTime(v) => match v.as_datetime() {
+            Some(dt) => AnyValue::Datetime(
+                dt.and_utc().timestamp_micros(),
+                TimeUnit::Microseconds,
+                None,
+            ),
+            None => AnyValue::Float64(v.as_f64()),
+        },
+        Data::Error(_) | Data::Empty => AnyValue::Null,
+    }
+}
+
+// 从 0 开始的 (行, 列) 坐标
+type Cell = (u32, u32);
+
+// 解析 "B2:F100" 形式的单元格范围
+fn parse_range(range: &str) -> Result<(Cell, Cell), BridgeError> {
+    let invalid = || BridgeError::InvalidArgument(format!("ExcelScan: invalid range '{}'", range));
+
+    let (start, end) = range.split_once(':').ok_or_else(invalid)?;
+    let start = parse_cell(start).ok_or_else(invalid)?;
+    let end = parse_cell(end).ok_or_else(invalid)?;
+    if start.0 > end.0 || start.1 > end.1 {
+        return Err(invalid());
+    }
+    Ok((start, end))
+}
+
+fn parse_cell(cell: &str) -> Option<Cell> {
+    let cell = cell.trim().to_ascii_uppercase();
+    let split = cell.find(|c: char| c.is_ascii_digit())?;
+    let (letters, digits) = cell.split_at(split);
+    if letters.is_empty() || !letters.bytes().all(|b| b.is_ascii_uppercase()) {
+        return None;
+    }
+
+    let col = letters
+        .bytes()
+        .try_fold(0u32, |acc, b| acc.checked_mul(26)?.checked_add((b - b'A' + 1) as u32))?;
+    let row: u32 = digits.parse().ok()?;
+    if row == 0 {
+        return None;
+    }
+    Some((row - 1, col - 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 工作表 "notes" 只有一个单元格；"data" 第 1 行是标题，第 2 行是表头（id, name, score），bob 的 score 为空
+    const SMALL_XLSX: &str = "../testdata/small.xlsx";
+
+    fn read(scan: proto::ExcelScan) -> Result<DataFrame, BridgeError> {
+        let scan = proto::ExcelScan { path: SMALL_XLSX.into(), ..scan };
+        excel_scan(&scan, DuplicatePolicy::Default)?
+            .collect()
+            .map_err(|e| BridgeError::Execution("Failed to collect".into()).with_source(e))
+    }
+
+    #[test]
+    fn reads_a_named_sheet_below_a_title_row() {
+        let df = read(proto::ExcelScan { sheet_name: "data".into(), header_row: 1, ..Default::default() }).unwrap();
+        // xlsx 的数值单元格不区分整数与浮点数
+        let expected = df!(
+            "id" => [1.0f64, 2.0, 3.0],
+            "name" => ["ann", "bob", "cy"],
+            "score" => [Some(1.5f64), None, Some(3.0)],
+        )
+        .unwrap();
+        assert!(df.equals_missing(&expected), "{df}");
+    }
+
+    #[test]
+    fn reads_a_range_without_header_by_index() {
+        let df = read(proto::ExcelScan {
+            sheet_index: 1,
+            has_header: Some(false),
+            range: "B3:C4".into(),
+            ..Default::default()
+        })
+        .unwrap();
+        let expected = df!("column_1" => ["ann", "bob"], "column_2" => [Some(1.5f64), None]).unwrap();
+        assert!(df.equals_missing(&expected), "{df}");
+    }
+
+    #[test]
+    fn rejects_bad_sheets_and_ranges() {
+        let err = read(proto::ExcelScan { sheet_index: 2, ..Default::default() }).unwrap_err();
+        assert!(err.to_string().contains("sheet index 2 out of range"), "{err}");
+
+        for range in ["C4:B3", "B3", "3B:C4", "A0:B1"] {
+            let err = read(proto::ExcelScan { range: range.into(), ..Default::default() }).unwrap_err();
+            assert!(err.to_string().contains("invalid range"), "{range}: {err}");
+        }
+    }
+}
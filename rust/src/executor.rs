@@ -1,8 +1,42 @@
 use crate::proto;
+use crate::handles;
 use crate::error::BridgeError;
 use crate::expr_str;
+use crate::expr_agg;
+use crate::expr_ext;
+use crate::expr_cmp;
+use crate::expr_num;
+use crate::expr_fill;
+use crate::expr_shift;
+use crate::expr_index;
+use crate::expr_subset;
+use crate::expr_counts;
+use crate::expr_horizontal;
+use crate::expr_rolling;
+use crate::columns::{self, ColumnResolver, DuplicatePolicy};
+use crate::sink;
+use crate::scan;
+use crate::schema;
+use crate::decimal::{self, DecimalMode};
+use crate::float_keys::{self, FloatKeyMode};
+use crate::naming::{self, NamingPolicy};
+use crate::upsert;
+use crate::dynamic_group;
+use crate::sorted;
+use crate::excel;
+use crate::iceberg;
+use crate::delta;
+use crate::database;
+use crate::flight;
+use crate::reader_scan;
+use crate::batch_provider;
+use crate::session::{self, Session};
+use crate::recorder;
+use crate::assertion;
+use std::sync::Arc;
+use std::time::Instant;
+
 use polars::prelude::*;
-use polars::prelude::PlPath;
 use polars::prelude::IntoLazy;
 
 /// 执行 Plan，返回结果的 Arrow IPC 格式字节流
@@ -22,19 +56,182 @@ pub fn execute_plan_df(
     plan: &proto::Plan,
     input_df: Option<&DataFrame>,
 ) -> Result<DataFrame, BridgeError> {
-    let root = plan.root.as_ref()
-        .ok_or_else(|| BridgeError::PlanSemantic("Plan has no root node".into()))?;
+    execute_plan_df_with_hints(plan, input_df, &proto::ExecutionHints::default())
+}
+
+/// 执行 Plan，并在优化前叠加单次执行的列裁剪/行数限制（开启录制时记录本次执行）
+pub fn execute_plan_df_with_hints(
+    plan: &proto::Plan,
+    input_df: Option<&DataFrame>,
+    hints: &proto::ExecutionHints,
+) -> Result<DataFrame, BridgeError> {
+    let started = Instant::now();
+    let result = execute_plan_unrecorded(plan, input_df, hints);
+    recorder::record(plan, input_df, hints, started.elapsed(), &result);
+    result
+}
+
+/// 同 execute_plan_df_with_hints，但不录制（回放录制的查询时使用）
+pub fn execute_plan_unrecorded(
+    plan: &proto::Plan,
+    input_df: Option<&DataFrame>,
+    hints: &proto::ExecutionHints,
+) -> Result<DataFrame, BridgeError> {
+    let root = plan_root(plan)?;
+    let mut ctx = BuildContext::new(plan, input_df);
+    if hints.session_handle != 0 {
+        ctx.session = Some(handles::sessions().get(hints.session_handle)?);
+    }
 
     // 从 Plan 构建 LazyFrame（根据节点类型自动决定数据源）
-    let lf = build_lazy_frame(root, input_df)?;
+    let lf = build_lazy_frame(root, &ctx)?;
+    let mut lf = apply_hints(lf, root, hints)?;
+
+    // 调用方开启时，小批量的内存 Project/Filter 链跳过查询优化（优化器开销可能大于计算本身），
+    // 执行仍走 LazyFrame；类型强转与类型检查不是可选优化，必须保留（without_optimizations 会丢掉 TYPE_CHECK）
+    if skips_optimizations(plan, root, input_df) {
+        lf = lf.with_optimizations(OptFlags::TYPE_COERCION | OptFlags::TYPE_CHECK);
+    }
 
     // 执行 LazyFrame
-    let result_df = lf.collect()
-        .map_err(|e| BridgeError::Execution(format!("Failed to collect LazyFrame: {}", e)))?;
+    let result_df = lf.collect().map_err(|e| match assertion::violation(&e) {
+        Some(err) => err,
+        None => BridgeError::Execution("Failed to collect LazyFrame".into()).with_source(e),
+    })?;
+
+    // 校验输出 schema 契约（Sink 节点没有输出，跳过）
+    // needed_columns 裁剪后只校验保留下来的列
+    if let Some(expected) = plan.expected_schema.as_ref() {
+        if !is_sink(root) {
+            let narrowed;
+            let expected = if hints.needed_columns.is_empty() {
+                expected
+            } else {
+                narrowed = proto::Schema {
+                    fields: expected.fields.iter()
+                        .filter(|f| hints.needed_columns.contains(&f.name))
+                        .cloned()
+                        .collect(),
+                };
+                &narrowed
+            };
+            schema::validate_schema(expected, result_df.schema())?;
+        }
+    }
 
     Ok(result_df)
 }
 
+// 叠加执行提示；Polars 优化器会把它们下推到扫描节点
+fn apply_hints(
+    mut lf: LazyFrame,
+    root: &proto::Node,
+    hints: &proto::ExecutionHints,
+) -> Result<LazyFrame, BridgeError> {
+    if hints.needed_columns.is_empty() && hints.max_rows.is_none() {
+        return Ok(lf);
+    }
+    if is_sink(root) {
+        return Err(BridgeError::InvalidArgument(
+            "Execution hints cannot be applied to sink plans".into(),
+        ));
+    }
+
+    if !hints.needed_columns.is_empty() {
+        let exprs: Vec<Expr> = hints.needed_columns.iter()
+            .map(|name| col(name.as_str()))
+            .collect();
+        lf = lf.select(&exprs);
+    }
+    if let Some(max_rows) = hints.max_rows {
+        let max_rows = IdxSize::try_from(max_rows).map_err(|_| {
+            BridgeError::InvalidArgument(format!(
+                "max_rows {} exceeds the supported row count {}",
+                max_rows,
+                IdxSize::MAX
+            ))
+        })?;
+        lf = lf.limit(max_rows);
+    }
+    Ok(lf)
+}
+
+/// 在 DataFrame 上求值单个表达式，返回只有一列的结果
+pub fn evaluate_expr(df: &DataFrame, expr: &proto::Expr) -> Result<DataFrame, BridgeError> {
+    let expr = build_expr(expr)?;
+    df.clone()
+        .lazy()
+        .select([expr])
+        .collect()
+        .map_err(|e| BridgeError::Execution("Failed to evaluate expression".into()).with_source(e))
+}
+
+/// 在 DataFrame 上执行 with_columns：同名列被替换，新列追加在末尾
+pub fn with_columns(df: &DataFrame, exprs: &[proto::Expr]) -> Result<DataFrame, BridgeError> {
+    if exprs.is_empty() {
+        return Err(BridgeError::InvalidArgument("ExprList has no expressions".into()));
+    }
+    let exprs = exprs.iter().map(build_expr).collect::<Result<Vec<_>, _>>()?;
+    df.clone()
+        .lazy()
+        .with_columns(exprs)
+        .collect()
+        .map_err(|e| BridgeError::Execution("Failed to apply with_columns".into()).with_source(e))
+}
+
+// 是否跳过查询优化：PlanOptions.skip_optimizations_max_rows 非 0，
+// 且仅包含 Project/Filter、数据来自行数不超过阈值的 MemoryScan
+fn skips_optimizations(plan: &proto::Plan, root: &proto::Node, input_df: Option<&DataFrame>) -> bool {
+    use proto::node::Kind;
+
+    let Some(max_rows) = plan
+        .options
+        .as_ref()
+        .and_then(|opts| opts.skip_optimizations_max_rows)
+        .filter(|&max_rows| max_rows > 0)
+    else {
+        return false;
+    };
+    let Some(df) = input_df else {
+        return false;
+    };
+    if df.height() as u64 > max_rows {
+        return false;
+    }
+
+    let mut node = root;
+    loop {
+        node = match node.kind.as_ref() {
+            Some(Kind::Project(proj)) => match proj.input.as_deref() {
+                Some(input) => input,
+                None => return false,
+            },
+            Some(Kind::Filter(filter)) => match filter.input.as_deref() {
+                Some(input) => input,
+                None => return false,
+            },
+            Some(Kind::MemoryScan(_)) => return true,
+            _ => return false,
+        };
+    }
+}
+
+/// 获取 Plan 的根节点
+pub fn plan_root(plan: &proto::Plan) -> Result<&proto::Node, BridgeError> {
+    plan.root.as_ref()
+        .ok_or_else(|| BridgeError::PlanSemantic("Plan has no root node".into()))
+}
+
+/// 是否为终端 Sink 节点
+fn is_sink(node: &proto::Node) -> bool {
+    use proto::node::Kind;
+
+    matches!(
+        node.kind,
+        Some(Kind::SinkParquet(_)) | Some(Kind::SinkCsv(_)) | Some(Kind::SinkNdjson(_)) | Some(Kind::SinkIpc(_))
+    )
+}
+
 /// 将 DataFrame 转换为 Arrow IPC 格式
 pub fn df_to_ipc(df: &DataFrame) -> Result<Vec<u8>, BridgeError> {
     let mut output = Vec::new();
@@ -51,10 +248,67 @@ pub fn df_print(df: &DataFrame) -> Result<(), BridgeError> {
     Ok(())
 }
 
+/// Plan 构建上下文（输入数据与 Plan 级选项）
+pub struct BuildContext<'a> {
+    pub input_df: Option<&'a DataFrame>,
+    pub resolver: ColumnResolver,
+    pub duplicates: DuplicatePolicy,
+    pub decimal_mode: DecimalMode,
+    pub naming: NamingPolicy,
+    pub float_keys: FloatKeyMode,
+    pub session: Option<Arc<Session>>,
+}
+
+impl<'a> BuildContext<'a> {
+    pub fn new(plan: &proto::Plan, input_df: Option<&'a DataFrame>) -> Self {
+        BuildContext {
+            input_df,
+            resolver: ColumnResolver::from_options(plan.options.as_ref()),
+            duplicates: DuplicatePolicy::from_options(plan.options.as_ref()),
+            decimal_mode: DecimalMode::from_options(plan.options.as_ref()),
+            naming: NamingPolicy::from_options(plan.options.as_ref()),
+            float_keys: FloatKeyMode::from_options(plan.options.as_ref()),
+            session: None,
+        }
+    }
+}
+
+/// 构建节点上的表达式，并按 Plan 选项解析列引用
+pub fn build_node_exprs(
+    lf: &mut LazyFrame,
+    exprs: &[proto::Expr],
+    ctx: &BuildContext,
+) -> Result<Vec<Expr>, BridgeError> {
+    let exprs: Vec<Expr> = exprs.iter()
+        .map(build_expr)
+        .collect::<Result<_, _>>()?;
+
+    if !ctx.resolver.is_enabled() && ctx.decimal_mode == DecimalMode::FloatFallback {
+        return Ok(exprs);
+    }
+
+    let schema = collect_schema(lf)?;
+    let exprs = if ctx.resolver.is_enabled() {
+        ctx.resolver.resolve_exprs(exprs, &schema)?
+    } else {
+        exprs
+    };
+
+    if ctx.decimal_mode == DecimalMode::Strict {
+        decimal::check_no_float_fallback(lf, &exprs, &schema)?;
+    }
+    Ok(exprs)
+}
+
+fn collect_schema(lf: &mut LazyFrame) -> Result<SchemaRef, BridgeError> {
+    lf.collect_schema()
+        .map_err(|e| BridgeError::Execution("Failed to resolve input schema".into()).with_source(e))
+}
+
 /// 从 Node 构建 LazyFrame（递归）
-fn build_lazy_frame(
+pub fn build_lazy_frame(
     node: &proto::Node,
-    input_df: Option<&DataFrame>,
+    ctx: &BuildContext,
 ) -> Result<LazyFrame, BridgeError> {
     use proto::node::Kind;
     
@@ -64,19 +318,58 @@ fn build_lazy_frame(
     match kind {
         Kind::CsvScan(scan) => {
             // 从 CSV 文件路径懒加载
-            LazyCsvReader::new(PlPath::new(scan.path.as_str()))
-                .finish()
-                .map_err(|e| BridgeError::Execution(format!("CsvScan failed for '{}': {}", scan.path, e)))
+            let lf = columns::csv_scan_with_policy(scan, ctx.duplicates)?;
+            scan::apply_scan_options(lf, scan.options.as_ref())
+        }
+        Kind::IpcScan(scan) => {
+            let lf = scan::ipc_scan(scan)?;
+            scan::apply_scan_options(lf, scan.options.as_ref())
+        }
+        Kind::AvroScan(scan) => {
+            let lf = scan::avro_scan(scan)?;
+            scan::apply_scan_options(lf, scan.options.as_ref())
+        }
+        Kind::ExcelScan(scan) => {
+            let lf = excel::excel_scan(scan, ctx.duplicates)?;
+            scan::apply_scan_options(lf, scan.options.as_ref())
+        }
+        Kind::IcebergScan(scan) => {
+            let lf = iceberg::iceberg_scan(scan)?;
+            scan::apply_scan_options(lf, scan.options.as_ref())
+        }
+        Kind::DeltaScan(scan) => {
+            let lf = delta::delta_scan(scan)?;
+            scan::apply_scan_options(lf, scan.options.as_ref())
+        }
+        Kind::DatabaseScan(scan) => {
+            let lf = database::database_scan(scan)?;
+            scan::apply_scan_options(lf, scan.options.as_ref())
         }
-        Kind::ParquetScan(_scan) => {
-            // TODO: Parquet 支持
-            Err(BridgeError::Unsupported("ParquetScan not yet implemented".into()))
+        Kind::FlightScan(scan) => {
+            let lf = flight::flight_scan(scan)?;
+            scan::apply_scan_options(lf, scan.options.as_ref())
+        }
+        Kind::ReaderScan(scan) => {
+            let lf = reader_scan::reader_scan(scan)?;
+            scan::apply_scan_options(lf, scan.options.as_ref())
+        }
+        Kind::BatchProviderScan(scan) => {
+            let lf = batch_provider::batch_provider_scan(scan)?;
+            scan::apply_scan_options(lf, scan.options.as_ref())
+        }
+        Kind::TableScan(scan) => {
+            let lf = session::table_scan(scan, ctx)?;
+            scan::apply_scan_options(lf, scan.options.as_ref())
+        }
+        Kind::ParquetScan(scan) => {
+            let lf = scan::parquet_scan(scan)?;
+            scan::apply_scan_options(lf, scan.options.as_ref())
         }
         Kind::MemoryScan(scan) => {
-            let df = input_df.ok_or_else(|| {
+            let df = ctx.input_df.ok_or_else(|| {
                 BridgeError::Unsupported("MemoryScan requires input DataFrame".into())
             })?;
-            let mut lf = df.clone().lazy();
+            let mut lf = scan::in_memory_scan(df.clone(), scan.options.as_ref(), "MemoryScan")?;
             if !scan.column_names.is_empty() {
                 let exprs: Vec<Expr> = scan
                     .column_names
@@ -85,51 +378,220 @@ fn build_lazy_frame(
                     .collect();
                 lf = lf.select(&exprs);
             }
-            Ok(lf)
+            scan::apply_scan_options(lf, scan.options.as_ref())
         }
         Kind::Project(proj) => {
             let input_node = proj.input.as_ref()
                 .ok_or_else(|| BridgeError::PlanSemantic("Project has no input".into()))?;
-            let lf = build_lazy_frame(input_node, input_df)?;
+            let mut lf = build_lazy_frame(input_node, ctx)?;
             
-            let exprs: Vec<Expr> = proj.expressions.iter()
-                .map(|e| build_expr(e))
-                .collect::<Result<_, _>>()?;
+            let exprs = build_node_exprs(&mut lf, &proj.expressions, ctx)?;
+            let exprs = naming::apply_naming(exprs, &proj.expressions, ctx.naming, "Project")?;
             
             Ok(lf.select(&exprs))
         }
         Kind::Filter(filter) => {
             let input_node = filter.input.as_ref()
                 .ok_or_else(|| BridgeError::PlanSemantic("Filter has no input".into()))?;
-            let lf = build_lazy_frame(input_node, input_df)?;
+            let mut lf = build_lazy_frame(input_node, ctx)?;
             
             let pred = filter.predicate.as_ref()
                 .ok_or_else(|| BridgeError::PlanSemantic("Filter has no predicate".into()))?;
-            let pred_expr = build_expr(pred)?;
+            let mut pred_exprs = build_node_exprs(&mut lf, std::slice::from_ref(pred), ctx)?;
             
-            Ok(lf.filter(pred_expr))
+            Ok(lf.filter(pred_exprs.remove(0)))
+        }
+        Kind::Assert(assert) => {
+            let input_node = assert.input.as_ref()
+                .ok_or_else(|| BridgeError::PlanSemantic("Assert has no input".into()))?;
+            let mut lf = build_lazy_frame(input_node, ctx)?;
+
+            let pred = assert.predicate.as_ref()
+                .ok_or_else(|| BridgeError::PlanSemantic("Assert has no predicate".into()))?;
+            let mut pred_exprs = build_node_exprs(&mut lf, std::slice::from_ref(pred), ctx)?;
+
+            Ok(assertion::apply_assert(lf, pred_exprs.remove(0), &assert.message, assert.report_rows))
         }
         Kind::WithColumns(with_cols) => {
             let input_node = with_cols.input.as_ref()
                 .ok_or_else(|| BridgeError::PlanSemantic("WithColumns has no input".into()))?;
-            let lf = build_lazy_frame(input_node, input_df)?;
+            let mut lf = build_lazy_frame(input_node, ctx)?;
             
-            let exprs: Vec<Expr> = with_cols.expressions.iter()
-                .map(|e| build_expr(e))
-                .collect::<Result<_, _>>()?;
+            let exprs = build_node_exprs(&mut lf, &with_cols.expressions, ctx)?;
+            let exprs = naming::apply_naming(exprs, &with_cols.expressions, ctx.naming, "WithColumns")?;
             
             Ok(lf.with_columns(&exprs))
         }
         Kind::Limit(limit) => {
             let input_node = limit.input.as_ref()
                 .ok_or_else(|| BridgeError::PlanSemantic("Limit has no input".into()))?;
-            let lf = build_lazy_frame(input_node, input_df)?;
+            let lf = build_lazy_frame(input_node, ctx)?;
             
             Ok(lf.limit(limit.n as u32))
         }
+        Kind::GroupBy(group_by) => {
+            let input_node = group_by.input.as_ref()
+                .ok_or_else(|| BridgeError::PlanSemantic("GroupBy has no input".into()))?;
+            if group_by.keys.is_empty() {
+                return Err(BridgeError::PlanSemantic("GroupBy has no keys".into()));
+            }
+            let mut lf = build_lazy_frame(input_node, ctx)?;
+
+            let keys = build_node_exprs(&mut lf, &group_by.keys, ctx)?;
+            let keys = float_keys::apply(&lf, keys, ctx.float_keys, "GroupBy")?;
+            let aggs = build_node_exprs(&mut lf, &group_by.aggs, ctx)?;
+            let aggs = naming::apply_naming(aggs, &group_by.aggs, ctx.naming, "GroupBy")?;
+
+            if group_by.drop_null_keys {
+                let not_null = keys.iter()
+                    .map(|k| k.clone().is_not_null())
+                    .reduce(|acc, e| acc.and(e));
+                if let Some(predicate) = not_null {
+                    lf = lf.filter(predicate);
+                }
+            }
+
+            let grouped = if group_by.maintain_order {
+                lf.group_by_stable(keys)
+            } else {
+                lf.group_by(keys)
+            };
+            Ok(grouped.agg(aggs))
+        }
+        Kind::GroupByDynamic(group_by) => {
+            let input_node = group_by.input.as_ref()
+                .ok_or_else(|| BridgeError::PlanSemantic("GroupByDynamic has no input".into()))?;
+            let options = dynamic_group::options(group_by)?;
+            let mut lf = build_lazy_frame(input_node, ctx)?;
+
+            let keys = build_node_exprs(&mut lf, &group_by.keys, ctx)?;
+            let keys = float_keys::apply(&lf, keys, ctx.float_keys, "GroupByDynamic")?;
+            let aggs = build_node_exprs(&mut lf, &group_by.aggs, ctx)?;
+            let aggs = naming::apply_naming(aggs, &group_by.aggs, ctx.naming, "GroupByDynamic")?;
+
+            Ok(lf.group_by_dynamic(col(group_by.index_column.as_str()), keys, options).agg(aggs))
+        }
+        Kind::Join(join) => {
+            let left_node = join.left.as_ref()
+                .ok_or_else(|| BridgeError::PlanSemantic("Join has no left input".into()))?;
+            let right_node = join.right.as_ref()
+                .ok_or_else(|| BridgeError::PlanSemantic("Join has no right input".into()))?;
+            let how = join_type(join.how)?;
+            if how != JoinType::Cross && (join.left_on.is_empty() || join.left_on.len() != join.right_on.len()) {
+                return Err(BridgeError::PlanSemantic(
+                    "Join requires matching, non-empty left_on and right_on".into(),
+                ));
+            }
+
+            let mut left = build_lazy_frame(left_node, ctx)?;
+            let mut right = build_lazy_frame(right_node, ctx)?;
+            let left_on = build_node_exprs(&mut left, &join.left_on, ctx)?;
+            let right_on = build_node_exprs(&mut right, &join.right_on, ctx)?;
+            let left_on = float_keys::apply(&left, left_on, ctx.float_keys, "Join")?;
+            let right_on = float_keys::apply(&right, right_on, ctx.float_keys, "Join")?;
+
+            let mut args = JoinArgs::new(how);
+            args.nulls_equal = join.nulls_equal;
+            if !join.suffix.is_empty() {
+                args = args.with_suffix(Some(join.suffix.as_str().into()));
+            }
+            Ok(left.join(right, left_on, right_on, args))
+        }
+        Kind::Upsert(node) => {
+            let target_node = node.target.as_ref()
+                .ok_or_else(|| BridgeError::PlanSemantic("Upsert has no target input".into()))?;
+            let source_node = node.source.as_ref()
+                .ok_or_else(|| BridgeError::PlanSemantic("Upsert has no source input".into()))?;
+
+            let target = build_lazy_frame(target_node, ctx)?;
+            let source = build_lazy_frame(source_node, ctx)?;
+            upsert::upsert(target, source, node)
+        }
+        Kind::JoinWhere(join) => {
+            let left_node = join.left.as_ref()
+                .ok_or_else(|| BridgeError::PlanSemantic("JoinWhere has no left input".into()))?;
+            let right_node = join.right.as_ref()
+                .ok_or_else(|| BridgeError::PlanSemantic("JoinWhere has no right input".into()))?;
+            if join.predicates.is_empty() {
+                return Err(BridgeError::PlanSemantic("JoinWhere has no predicates".into()));
+            }
+
+            let mut left = build_lazy_frame(left_node, ctx)?;
+            let mut right = build_lazy_frame(right_node, ctx)?;
+
+            let mut predicates: Vec<Expr> = join.predicates.iter()
+                .map(build_expr)
+                .collect::<Result<_, _>>()?;
+            if ctx.resolver.is_enabled() {
+                // 谓词同时引用左右两侧的列，按合并后的 schema 解析
+                let mut schema = collect_schema(&mut left)?.as_ref().clone();
+                schema.merge(collect_schema(&mut right)?.as_ref().clone());
+                predicates = ctx.resolver.resolve_exprs(predicates, &schema)?;
+            }
+
+            let mut builder = left.join_builder().with(right);
+            if !join.suffix.is_empty() {
+                builder = builder.suffix(join.suffix.as_str());
+            }
+            Ok(builder.join_where(predicates))
+        }
+        Kind::SinkParquet(sink) => {
+            let input_node = sink.input.as_ref()
+                .ok_or_else(|| BridgeError::PlanSemantic("SinkParquet has no input".into()))?;
+            let lf = build_lazy_frame(input_node, ctx)?;
+            
+            sink::sink_parquet(lf, sink)
+        }
+        Kind::SinkCsv(sink) => {
+            let input_node = sink.input.as_ref()
+                .ok_or_else(|| BridgeError::PlanSemantic("SinkCsv has no input".into()))?;
+            let lf = build_lazy_frame(input_node, ctx)?;
+            
+            sink::sink_csv(lf, sink)
+        }
+        Kind::SinkNdjson(sink) => {
+            let input_node = sink.input.as_ref()
+                .ok_or_else(|| BridgeError::PlanSemantic("SinkNdjson has no input".into()))?;
+            let lf = build_lazy_frame(input_node, ctx)?;
+            
+            sink::sink_ndjson(lf, sink)
+        }
+        Kind::SinkIpc(sink) => {
+            let input_node = sink.input.as_ref()
+                .ok_or_else(|| BridgeError::PlanSemantic("SinkIpc has no input".into()))?;
+            let lf = build_lazy_frame(input_node, ctx)?;
+            
+            sink::sink_ipc(lf, sink)
+        }
+    }
+}
+
+fn join_type(how: i32) -> Result<JoinType, BridgeError> {
+    use proto::JoinType as How;
+
+    match How::try_from(how) {
+        Ok(How::Inner) => Ok(JoinType::Inner),
+        Ok(How::Left) => Ok(JoinType::Left),
+        Ok(How::Right) => Ok(JoinType::Right),
+        Ok(How::Full) => Ok(JoinType::Full),
+        Ok(How::Semi) => Ok(JoinType::Semi),
+        Ok(How::Anti) => Ok(JoinType::Anti),
+        Ok(How::Cross) => Ok(JoinType::Cross),
+        Err(_) => Err(BridgeError::Unsupported(format!("Unknown join type: {}", how))),
     }
 }
 
+/// 构建消息中必填的子表达式（缺失时报 PlanSemantic 错误）
+pub(crate) fn build_inner_expr(
+    expr: &Option<Box<proto::Expr>>,
+    name: &str,
+) -> Result<Expr, BridgeError> {
+    let expr = expr
+        .as_ref()
+        .ok_or_else(|| BridgeError::PlanSemantic(format!("{name} has no expr")))?;
+    build_expr(expr.as_ref())
+}
+
 pub fn build_expr(expr: &proto::Expr) -> Result<Expr, BridgeError> {
     use proto::expr::Kind;
     
@@ -139,6 +601,36 @@ pub fn build_expr(expr: &proto::Expr) -> Result<Expr, BridgeError> {
     if let Some(result) = expr_str::build_string_expr(kind) {
         return result;
     }
+    if let Some(result) = expr_agg::build_agg_expr(kind) {
+        return result;
+    }
+    if let Some(result) = expr_cmp::build_cmp_expr(kind) {
+        return result;
+    }
+    if let Some(result) = expr_num::build_numeric_expr(kind) {
+        return result;
+    }
+    if let Some(result) = expr_fill::build_fill_expr(kind) {
+        return result;
+    }
+    if let Some(result) = expr_shift::build_shift_expr(kind) {
+        return result;
+    }
+    if let Some(result) = expr_index::build_index_expr(kind) {
+        return result;
+    }
+    if let Some(result) = expr_subset::build_subset_expr(kind) {
+        return result;
+    }
+    if let Some(result) = expr_counts::build_counts_expr(kind) {
+        return result;
+    }
+    if let Some(result) = expr_horizontal::build_horizontal_expr(kind) {
+        return result;
+    }
+    if let Some(result) = expr_rolling::build_rolling_expr(kind) {
+        return result;
+    }
     
     match kind {
         Kind::Col(col) => {
@@ -155,6 +647,7 @@ pub fn build_expr(expr: &proto::Expr) -> Result<Expr, BridgeError> {
                 Value::BoolVal(v) => Ok(polars::prelude::lit(*v)),
                 Value::StringVal(v) => Ok(polars::prelude::lit(v.as_str())),
                 Value::NullVal(_) => Ok(polars::prelude::lit(NULL)),
+                Value::DecimalVal(v) => decimal::decimal_lit(v),
             }
         }
         Kind::Binary(bin) => {
@@ -183,6 +676,8 @@ pub fn build_expr(expr: &proto::Expr) -> Result<Expr, BridgeError> {
                 Ok(BinaryOperator::Mod) => Ok(left_expr % right_expr),
                 Ok(BinaryOperator::Pow) => Ok(left_expr.pow(right_expr)),
                 Ok(BinaryOperator::Xor) => Ok(left_expr.xor(right_expr)),
+                Ok(BinaryOperator::EqMissing) => Ok(left_expr.eq_missing(right_expr)),
+                Ok(BinaryOperator::NeMissing) => Ok(left_expr.neq_missing(right_expr)),
                 Err(_) => Err(BridgeError::Unsupported(format!("Unknown binary operator: {}", bin.op))),
             }
         }
@@ -219,26 +714,7 @@ pub fn build_expr(expr: &proto::Expr) -> Result<Expr, BridgeError> {
             let e = build_expr(expr)?;
             
             // 将 proto DataType 转换为 Polars DataType
-            let target_type = match proto::DataType::try_from(cast.data_type) {
-                Ok(proto::DataType::Int64) => DataType::Int64,
-                Ok(proto::DataType::Int32) => DataType::Int32,
-                Ok(proto::DataType::Int16) => DataType::Int16,
-                Ok(proto::DataType::Int8) => DataType::Int8,
-                Ok(proto::DataType::Uint64) => DataType::UInt64,
-                Ok(proto::DataType::Uint32) => DataType::UInt32,
-                Ok(proto::DataType::Uint16) => DataType::UInt16,
-                Ok(proto::DataType::Uint8) => DataType::UInt8,
-                Ok(proto::DataType::Float64) => DataType::Float64,
-                Ok(proto::DataType::Float32) => DataType::Float32,
-                Ok(proto::DataType::Bool) => DataType::Boolean,
-                Ok(proto::DataType::Utf8) => DataType::String,
-                Ok(proto::DataType::Date) => DataType::Date,
-                Ok(proto::DataType::Datetime) => DataType::Datetime(TimeUnit::Microseconds, None),
-                Ok(proto::DataType::Time) => DataType::Time,
-                Err(_) => return Err(BridgeError::Unsupported(
-                    format!("Unknown data type: {}", cast.data_type)
-                )),
-            };
+            let target_type = schema::cast_dtype(cast)?;
             
             // 根据 strict 参数选择 cast 或 strict_cast
             if cast.strict {
@@ -247,8 +723,80 @@ pub fn build_expr(expr: &proto::Expr) -> Result<Expr, BridgeError> {
                 Ok(e.cast(target_type))
             }
         }
+        Kind::Extension(ext) => expr_ext::build_extension(ext),
+        Kind::SetSorted(set_sorted) => {
+            let expr = set_sorted.expr.as_ref()
+                .ok_or_else(|| BridgeError::PlanSemantic("SetSorted has no expr".into()))?;
+            let e = build_expr(expr)?;
+            Ok(e.set_sorted_flag(sorted::sorted_flag(set_sorted.descending)))
+        }
         _ => Err(BridgeError::Unsupported(
             "Expression type is not yet supported".into(),
         )),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use polars::prelude::{df, DataFrame};
+
+    use super::{execute_plan_df, plan_root, skips_optimizations};
+    use crate::proto;
+    use crate::test_plans::{alias, binary, col, input, lit, memory_scan, node, plan};
+    use proto::literal::Value;
+    use proto::node::Kind as N;
+    use proto::BinaryOperator as Op;
+
+    // Project(Filter(MemoryScan))：过滤条件需要 Int64 与 Float64 之间的类型强转
+    fn project_filter_plan(skip_optimizations_max_rows: Option<u64>) -> proto::Plan {
+        let filtered = node(2, N::Filter(Box::new(proto::Filter {
+            input: input(memory_scan()),
+            predicate: Some(binary(col("v"), Op::Gt, lit(Value::FloatVal(1.5)))),
+        })));
+        let expressions = vec![
+            col("k"),
+            alias(binary(col("v"), Op::Mul, lit(Value::IntVal(2))), "doubled"),
+        ];
+        let mut plan = plan(node(3, N::Project(Box::new(proto::Project {
+            input: input(filtered),
+            expressions,
+        }))));
+        plan.options = Some(proto::PlanOptions {
+            skip_optimizations_max_rows,
+            ..Default::default()
+        });
+        plan
+    }
+
+    fn data() -> DataFrame {
+        df!(
+            "k" => [Some("a"), Some("b"), None, Some("d"), Some("e")],
+            "v" => [Some(1i64), Some(2), Some(3), None, Some(5)],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn skipping_optimizations_is_opt_in() {
+        let data = data();
+        let enabled = |max_rows| {
+            let plan = project_filter_plan(max_rows);
+            skips_optimizations(&plan, plan_root(&plan).unwrap(), Some(&data))
+        };
+        assert!(!enabled(None));
+        assert!(!enabled(Some(0)));
+        assert!(!enabled(Some(4)));
+        assert!(enabled(Some(5)));
+    }
+
+    #[test]
+    fn skipping_optimizations_gives_identical_results() {
+        let data = data();
+        let expected = execute_plan_df(&project_filter_plan(None), Some(&data)).unwrap();
+        assert_eq!(expected.height(), 3);
+        for max_rows in [Some(0), Some(4), Some(5), Some(4096)] {
+            let actual = execute_plan_df(&project_filter_plan(max_rows), Some(&data)).unwrap();
+            assert!(actual.equals_missing(&expected), "max_rows={max_rows:?}\n{actual}\n{expected}");
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use polars::prelude::*;
+
+use crate::error::BridgeError;
+use crate::executor::build_inner_expr;
+use crate::proto;
+
+pub fn build_agg_expr(kind: &proto::expr::Kind) -> Option<Result<Expr, BridgeError>> {
+    use proto::expr::Kind;
+
+    match kind {
+        Kind::Sum(func) => Some(build_unary(func, "Sum", |expr| expr.sum())),
+        Kind::Mean(func) => Some(build_unary(func, "Mean", |expr| expr.mean())),
+        Kind::Min(func) => Some(build_unary(func, "Min", |expr| expr.min())),
+        Kind::Max(func) => Some(build_unary(func, "Max", |expr| expr.max())),
+        Kind::Count(func) => Some(build_unary(func, "Count", |expr| expr.count())),
+        Kind::Std(disp) => Some(build_dispersion(disp, "Std", |expr, ddof| expr.std(ddof))),
+        Kind::Var(disp) => Some(build_dispersion(disp, "Var", |expr, ddof| expr.var(ddof))),
+        Kind::Median(func) => Some(build_unary(func, "Median", |expr| expr.median())),
+        Kind::Quantile(quantile) => Some(build_quantile(quantile)),
+        Kind::NUnique(func) => Some(build_unary(func, "NUnique", |expr| expr.n_unique())),
+        Kind::NullCount(func) => Some(build_unary(func, "NullCount", |expr| expr.null_count())),
+        Kind::Len(_) => Some(Ok(len())),
+        Kind::First(func) => Some(build_unary(func, "First", |expr| expr.first())),
+        Kind::Last(func) => Some(build_unary(func, "Last", |expr| expr.last())),
+        Kind::Implode(func) => Some(build_unary(func, "Implode", |expr| expr.implode())),
+        _ => None,
+    }
+}
+
+fn build_unary<F>(func: &proto::AggFunction, name: &str, op: F) -> Result<Expr, BridgeError>
+where
+    F: FnOnce(Expr) -> Expr,
+{
+    let expr = build_inner_expr(&func.expr, name)?;
+    Ok(op(expr))
+}
+
+fn build_dispersion<F>(disp: &proto::Dispersion, name: &str, op: F) -> Result<Expr, BridgeError>
+where
+    F: FnOnce(Expr, u8) -> Expr,
+{
+    let expr = build_inner_expr(&disp.expr, name)?;
+    let ddof = u8::try_from(disp.ddof.unwrap_or(1)).map_err(|_| {
+        BridgeError::InvalidArgument(format!("{name} ddof must be at most {}", u8::MAX))
+    })?;
+    Ok(op(expr, ddof))
+}
+
+fn build_quantile(quantile: &proto::Quantile) -> Result<Expr, BridgeError> {
+    let expr = build_inner_expr(&quantile.expr, "Quantile")?;
+    if !(0.0..=1.0).contains(&quantile.quantile) {
+        return Err(BridgeError::InvalidArgument(format!(
+            "Quantile must be between 0 and 1, got {}",
+            quantile.quantile
+        )));
+    }
+    Ok(expr.quantile(lit(quantile.quantile), quantile_method(quantile.method)?))
+}
+
+fn quantile_method(method: i32) -> Result<QuantileMethod, BridgeError> {
+    use proto::QuantileMethod as Method;
+
+    match Method::try_from(method) {
+        Ok(Method::Nearest) => Ok(QuantileMethod::Nearest),
+        Ok(Method::Lower) => Ok(QuantileMethod::Lower),
+        Ok(Method::Higher) => Ok(QuantileMethod::Higher),
+        Ok(Method::Midpoint) => Ok(QuantileMethod::Midpoint),
+        Ok(Method::Linear) => Ok(QuantileMethod::Linear),
+        Ok(Method::Equiprobable) => Ok(QuantileMethod::Equiprobable),
+        Err(_) => Err(BridgeError::Unsupported(format!("Unknown quantile method: {}", method))),
+    }
+}
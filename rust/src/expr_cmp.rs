@@ -0,0 +1,46 @@
+use polars::prelude::*;
+
+use crate::error::BridgeError;
+use crate::executor::{build_expr, build_inner_expr};
+use crate::proto;
+use crate::rows::literal_to_any_value;
+
+pub fn build_cmp_expr(kind: &proto::expr::Kind) -> Option<Result<Expr, BridgeError>> {
+    use proto::expr::Kind;
+
+    match kind {
+        Kind::IsIn(is_in) => Some(build_is_in(is_in)),
+        Kind::IsDuplicated(func) => {
+            Some(build_inner_expr(&func.expr, "IsDuplicated").map(Expr::is_duplicated))
+        }
+        Kind::IsUnique(func) => Some(build_inner_expr(&func.expr, "IsUnique").map(Expr::is_unique)),
+        Kind::IsFirstDistinct(func) => {
+            Some(build_inner_expr(&func.expr, "IsFirstDistinct").map(Expr::is_first_distinct))
+        }
+        Kind::IsLastDistinct(func) => {
+            Some(build_inner_expr(&func.expr, "IsLastDistinct").map(Expr::is_last_distinct))
+        }
+        _ => None,
+    }
+}
+
+fn build_is_in(is_in: &proto::IsIn) -> Result<Expr, BridgeError> {
+    use proto::is_in::Values;
+
+    let expr = build_inner_expr(&is_in.expr, "IsIn")?;
+    let values = is_in
+        .values
+        .as_ref()
+        .ok_or_else(|| BridgeError::PlanSemantic("IsIn has no values".into()))?;
+
+    let other = match values {
+        Values::List(list) => {
+            let values: Vec<AnyValue> = list.values.iter().map(literal_to_any_value).collect();
+            let series = Series::from_any_values(PlSmallStr::EMPTY, &values, false)
+                .map_err(|e| BridgeError::InvalidArgument(format!("Invalid IsIn values: {}", e)))?;
+            lit(series).implode()
+        }
+        Values::Other(other) => build_expr(other)?,
+    };
+    Ok(expr.is_in(other, is_in.nulls_equal))
+}
@@ -0,0 +1,28 @@
+use polars::prelude::*;
+
+use crate::error::BridgeError;
+use crate::executor::build_inner_expr;
+use crate::proto;
+
+pub fn build_counts_expr(kind: &proto::expr::Kind) -> Option<Result<Expr, BridgeError>> {
+    use proto::expr::Kind;
+
+    match kind {
+        Kind::Mode(func) => Some(build_inner_expr(&func.expr, "Mode").map(Expr::mode)),
+        Kind::ValueCounts(counts) => Some(build_value_counts(counts)),
+        Kind::UniqueCounts(func) => {
+            Some(build_inner_expr(&func.expr, "UniqueCounts").map(Expr::unique_counts))
+        }
+        _ => None,
+    }
+}
+
+fn build_value_counts(counts: &proto::ValueCounts) -> Result<Expr, BridgeError> {
+    let expr = build_inner_expr(&counts.expr, "ValueCounts")?;
+    let name = match counts.name.as_str() {
+        "" if counts.normalize => "proportion",
+        "" => "count",
+        name => name,
+    };
+    Ok(expr.value_counts(counts.sort, false, name, counts.normalize))
+}
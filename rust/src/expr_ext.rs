@@ -0,0 +1,158 @@
+use std::collections::BTreeMap;
+use std::sync::{OnceLock, RwLock};
+
+use polars::prelude::*;
+
+use crate::error::BridgeError;
+use crate::executor::build_expr;
+use crate::proto;
+
+/// 扩展函数：输入表达式 + 打包参数（由各函数自行解码，约定为 JSON）
+pub type ExtensionFn = fn(Vec<Expr>, &[u8]) -> Result<Expr, BridgeError>;
+
+fn registry() -> &'static RwLock<BTreeMap<(String, String), ExtensionFn>> {
+    static REGISTRY: OnceLock<RwLock<BTreeMap<(String, String), ExtensionFn>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(builtin_extensions()))
+}
+
+/// 注册实验性扩展函数（同名覆盖）
+#[allow(dead_code)]
+pub fn register(namespace: &str, name: &str, function: ExtensionFn) {
+    registry()
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert((namespace.to_string(), name.to_string()), function);
+}
+
+/// 已注册的扩展函数（`namespace.name`，按字典序）
+pub fn registered() -> Vec<String> {
+    registry()
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .keys()
+        .map(|(namespace, name)| format!("{}.{}", namespace, name))
+        .collect()
+}
+
+pub fn build_extension(ext: &proto::Extension) -> Result<Expr, BridgeError> {
+    let function = registry()
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&(ext.namespace.clone(), ext.name.clone()))
+        .copied()
+        .ok_or_else(|| {
+            BridgeError::Unsupported(format!(
+                "Unknown extension function: {}.{}",
+                ext.namespace, ext.name
+            ))
+        })?;
+
+    let inputs: Vec<Expr> = ext.inputs.iter().map(build_expr).collect::<Result<_, _>>()?;
+    function(inputs, &ext.args)
+}
+
+fn builtin_extensions() -> BTreeMap<(String, String), ExtensionFn> {
+    let builtins: [(&str, ExtensionFn); 4] = [
+        ("sqrt", |inputs, _| Ok(single_input(inputs, "polars.sqrt")?.sqrt())),
+        ("reverse", |inputs, _| Ok(single_input(inputs, "polars.reverse")?.reverse())),
+        ("drop_nulls", |inputs, _| Ok(single_input(inputs, "polars.drop_nulls")?.drop_nulls())),
+        ("unique", build_unique),
+    ];
+
+    builtins
+        .into_iter()
+        .map(|(name, function)| (("polars".to_string(), name.to_string()), function))
+        .collect()
+}
+
+// args: {"maintain_order": bool}
+fn build_unique(inputs: Vec<Expr>, args: &[u8]) -> Result<Expr, BridgeError> {
+    let expr = single_input(inputs, "polars.unique")?;
+    let maintain_order = parse_args(args, "polars.unique")?
+        .get("maintain_order")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    if maintain_order {
+        Ok(expr.unique_stable())
+    } else {
+        Ok(expr.unique())
+    }
+}
+
+fn single_input(inputs: Vec<Expr>, name: &str) -> Result<Expr, BridgeError> {
+    let [expr]: [Expr; 1] = inputs.try_into().map_err(|inputs: Vec<Expr>| {
+        BridgeError::PlanSemantic(format!("{name} expects 1 input, got {}", inputs.len()))
+    })?;
+    Ok(expr)
+}
+
+fn parse_args(args: &[u8], name: &str) -> Result<serde_json::Value, BridgeError> {
+    if args.is_empty() {
+        return Ok(serde_json::Value::Null);
+    }
+    serde_json::from_slice(args)
+        .map_err(|e| BridgeError::InvalidArgument(format!("Invalid {name} args: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use polars::prelude::{df, DataFrame};
+
+    use crate::error::BridgeError;
+    use crate::executor::execute_plan_df;
+    use crate::proto;
+    use crate::test_plans::{alias, col, expr, memory_scan, select};
+    use proto::expr::Kind as E;
+
+    fn ext(name: &str, inputs: Vec<proto::Expr>, args: &str) -> proto::Expr {
+        expr(E::Extension(proto::Extension {
+            namespace: "polars".into(),
+            name: name.into(),
+            inputs,
+            args: args.as_bytes().to_vec(),
+        }))
+    }
+
+    fn run(expressions: Vec<proto::Expr>) -> Result<DataFrame, BridgeError> {
+        let data = df!(
+            "x" => [4.0f64, 9.0, 16.0, 25.0],
+            "k" => [Some("b"), Some("a"), None, Some("b")],
+        )
+        .unwrap();
+        execute_plan_df(&select(memory_scan(), expressions), Some(&data))
+    }
+
+    #[test]
+    fn builtin_extensions_compute_values() {
+        let out = run(vec![
+            alias(ext("sqrt", vec![col("x")], ""), "root"),
+            alias(ext("reverse", vec![col("k")], ""), "reversed"),
+        ])
+        .unwrap();
+        let expected = df!(
+            "root" => [2.0f64, 3.0, 4.0, 5.0],
+            "reversed" => [Some("b"), None, Some("a"), Some("b")],
+        )
+        .unwrap();
+        assert!(out.equals_missing(&expected), "{out}");
+
+        let out = run(vec![ext("drop_nulls", vec![col("k")], "")]).unwrap();
+        assert!(out.equals(&df!("k" => ["b", "a", "b"]).unwrap()), "{out}");
+
+        let out = run(vec![ext("unique", vec![col("k")], r#"{"maintain_order": true}"#)]).unwrap();
+        assert!(out.equals_missing(&df!("k" => [Some("b"), Some("a"), None]).unwrap()), "{out}");
+    }
+
+    #[test]
+    fn extension_errors() {
+        let err = run(vec![ext("nope", vec![col("x")], "")]).unwrap_err();
+        assert!(matches!(&err, BridgeError::Unsupported(m) if m == "Unknown extension function: polars.nope"));
+
+        let err = run(vec![ext("sqrt", vec![col("x"), col("x")], "")]).unwrap_err();
+        assert!(matches!(&err, BridgeError::PlanSemantic(m) if m == "polars.sqrt expects 1 input, got 2"));
+
+        let err = run(vec![ext("unique", vec![col("k")], "{")]).unwrap_err();
+        assert!(matches!(&err, BridgeError::InvalidArgument(m) if m.starts_with("Invalid polars.unique args")));
+    }
+}
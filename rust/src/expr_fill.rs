@@ -0,0 +1,66 @@
+use polars::prelude::*;
+
+use crate::error::BridgeError;
+use crate::executor::{build_expr, build_inner_expr};
+use crate::proto;
+
+pub fn build_fill_expr(kind: &proto::expr::Kind) -> Option<Result<Expr, BridgeError>> {
+    use proto::expr::Kind;
+
+    match kind {
+        Kind::FillNull(fill) => Some(build_fill_null(fill)),
+        Kind::ForwardFill(fill) => {
+            Some(build_directional(fill, "ForwardFill", FillNullStrategy::Forward))
+        }
+        Kind::BackwardFill(fill) => {
+            Some(build_directional(fill, "BackwardFill", FillNullStrategy::Backward))
+        }
+        _ => None,
+    }
+}
+
+fn build_fill_null(fill: &proto::FillNull) -> Result<Expr, BridgeError> {
+    use proto::fill_null::Fill;
+
+    let expr = build_inner_expr(&fill.expr, "FillNull")?;
+    let fill_with = fill
+        .fill
+        .as_ref()
+        .ok_or_else(|| BridgeError::PlanSemantic("FillNull has no value or strategy".into()))?;
+
+    match fill_with {
+        Fill::Value(value) => Ok(expr.fill_null(build_expr(value)?)),
+        Fill::Strategy(strategy) => {
+            let limit = fill.limit.map(|n| n as IdxSize);
+            Ok(expr.fill_null_with_strategy(fill_strategy(*strategy, limit)?))
+        }
+    }
+}
+
+fn build_directional(
+    fill: &proto::DirectionalFill,
+    name: &str,
+    strategy: fn(Option<IdxSize>) -> FillNullStrategy,
+) -> Result<Expr, BridgeError> {
+    let expr = build_inner_expr(&fill.expr, name)?;
+    let limit = fill.limit.map(|n| n as IdxSize);
+    Ok(expr.fill_null_with_strategy(strategy(limit)))
+}
+
+fn fill_strategy(strategy: i32, limit: Option<IdxSize>) -> Result<FillNullStrategy, BridgeError> {
+    use proto::FillNullStrategy as Strategy;
+
+    match Strategy::try_from(strategy) {
+        Ok(Strategy::Forward) => Ok(FillNullStrategy::Forward(limit)),
+        Ok(Strategy::Backward) => Ok(FillNullStrategy::Backward(limit)),
+        Ok(Strategy::Min) => Ok(FillNullStrategy::Min),
+        Ok(Strategy::Max) => Ok(FillNullStrategy::Max),
+        Ok(Strategy::Mean) => Ok(FillNullStrategy::Mean),
+        Ok(Strategy::Zero) => Ok(FillNullStrategy::Zero),
+        Ok(Strategy::One) => Ok(FillNullStrategy::One),
+        Err(_) => Err(BridgeError::Unsupported(format!(
+            "Unknown fill null strategy: {}",
+            strategy
+        ))),
+    }
+}
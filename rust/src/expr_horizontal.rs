@@ -0,0 +1,107 @@
+use polars::prelude::*;
+
+use crate::error::BridgeError;
+use crate::executor::{build_expr, build_inner_expr};
+use crate::proto;
+
+pub fn build_horizontal_expr(kind: &proto::expr::Kind) -> Option<Result<Expr, BridgeError>> {
+    use proto::expr::Kind;
+
+    match kind {
+        Kind::MinHorizontal(h) => Some(build_horizontal(h, "MinHorizontal", |_| {
+            FunctionExpr::MinHorizontal
+        })),
+        Kind::MaxHorizontal(h) => Some(build_horizontal(h, "MaxHorizontal", |_| {
+            FunctionExpr::MaxHorizontal
+        })),
+        Kind::SumHorizontal(h) => Some(build_horizontal(h, "SumHorizontal", |ignore_nulls| {
+            FunctionExpr::SumHorizontal { ignore_nulls }
+        })),
+        Kind::MeanHorizontal(h) => Some(build_horizontal(h, "MeanHorizontal", |ignore_nulls| {
+            FunctionExpr::MeanHorizontal { ignore_nulls }
+        })),
+        Kind::Fold(fold) => Some(build_fold(fold)),
+        Kind::Reduce(reduce) => Some(build_reduce(reduce)),
+        Kind::AsStruct(pack) => Some(build_as_struct(pack)),
+        _ => None,
+    }
+}
+
+// 直接构造 n 元函数表达式：polars 的 min_horizontal 等函数经多条 glob 路径重复导出，按名字引用会触发歧义告警
+fn build_horizontal<F>(
+    horizontal: &proto::Horizontal,
+    name: &str,
+    function: F,
+) -> Result<Expr, BridgeError>
+where
+    F: FnOnce(bool) -> FunctionExpr,
+{
+    if horizontal.exprs.is_empty() {
+        return Err(BridgeError::PlanSemantic(format!("{name} has no exprs")));
+    }
+    let exprs = horizontal
+        .exprs
+        .iter()
+        .map(build_expr)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Expr::n_ary(function(horizontal.ignore_nulls.unwrap_or(true)), exprs))
+}
+
+fn build_fold(fold: &proto::Fold) -> Result<Expr, BridgeError> {
+    let acc = build_inner_expr(&fold.acc, "Fold acc")?;
+    fold_exprs_with(acc, &fold.exprs, fold.op, "Fold")
+}
+
+fn build_reduce(reduce: &proto::Reduce) -> Result<Expr, BridgeError> {
+    let (first, rest) = reduce
+        .exprs
+        .split_first()
+        .ok_or_else(|| BridgeError::PlanSemantic("Reduce has no exprs".into()))?;
+    if rest.is_empty() {
+        return build_expr(first);
+    }
+    fold_exprs_with(build_expr(first)?, rest, reduce.op, "Reduce")
+}
+
+fn build_as_struct(pack: &proto::AsStruct) -> Result<Expr, BridgeError> {
+    if pack.exprs.is_empty() {
+        return Err(BridgeError::PlanSemantic("AsStruct has no exprs".into()));
+    }
+    let exprs = pack
+        .exprs
+        .iter()
+        .map(build_expr)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(as_struct(exprs))
+}
+
+// 在构建阶段展开为从左到右的二元表达式链，优化器可以照常处理
+fn fold_exprs_with(
+    acc: Expr,
+    exprs: &[proto::Expr],
+    op: i32,
+    name: &str,
+) -> Result<Expr, BridgeError> {
+    use proto::FoldOperator;
+
+    if exprs.is_empty() {
+        return Err(BridgeError::PlanSemantic(format!("{name} has no exprs")));
+    }
+    let op = FoldOperator::try_from(op)
+        .map_err(|_| BridgeError::Unsupported(format!("Unknown fold operator: {}", op)))?;
+
+    exprs.iter().try_fold(acc, |acc, expr| {
+        let expr = build_expr(expr)?;
+        let combined = match op {
+            FoldOperator::Add => acc + expr,
+            FoldOperator::Sub => acc - expr,
+            FoldOperator::Mul => acc * expr,
+            FoldOperator::Div => acc / expr,
+            FoldOperator::Min => Expr::n_ary(FunctionExpr::MinHorizontal, vec![acc, expr]),
+            FoldOperator::Max => Expr::n_ary(FunctionExpr::MaxHorizontal, vec![acc, expr]),
+            FoldOperator::And => acc.and(expr),
+            FoldOperator::Or => acc.or(expr),
+        };
+        Ok(combined)
+    })
+}
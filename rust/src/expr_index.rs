@@ -0,0 +1,55 @@
+use polars::prelude::*;
+
+use crate::error::BridgeError;
+use crate::executor::{build_expr, build_inner_expr};
+use crate::proto;
+
+pub fn build_index_expr(kind: &proto::expr::Kind) -> Option<Result<Expr, BridgeError>> {
+    use proto::expr::Kind;
+
+    match kind {
+        Kind::ArgMin(func) => Some(build_inner_expr(&func.expr, "ArgMin").map(Expr::arg_min)),
+        Kind::ArgMax(func) => Some(build_inner_expr(&func.expr, "ArgMax").map(Expr::arg_max)),
+        Kind::ArgSort(sort) => Some(build_arg_sort(sort)),
+        Kind::ArgUnique(func) => Some(build_inner_expr(&func.expr, "ArgUnique").map(Expr::arg_unique)),
+        Kind::SearchSorted(search) => Some(build_search_sorted(search)),
+        Kind::IndexOf(index_of) => Some(build_index_of(index_of)),
+        _ => None,
+    }
+}
+
+fn build_arg_sort(sort: &proto::ArgSort) -> Result<Expr, BridgeError> {
+    let expr = build_inner_expr(&sort.expr, "ArgSort")?;
+    Ok(expr.arg_sort(sort.descending, sort.nulls_last))
+}
+
+fn build_search_sorted(search: &proto::SearchSorted) -> Result<Expr, BridgeError> {
+    use proto::SearchSortedSide as Side;
+
+    let expr = build_inner_expr(&search.expr, "SearchSorted")?;
+    let element = search
+        .element
+        .as_ref()
+        .ok_or_else(|| BridgeError::PlanSemantic("SearchSorted has no element".into()))?;
+    let side = match Side::try_from(search.side) {
+        Ok(Side::Any) => SearchSortedSide::Any,
+        Ok(Side::Left) => SearchSortedSide::Left,
+        Ok(Side::Right) => SearchSortedSide::Right,
+        Err(_) => {
+            return Err(BridgeError::Unsupported(format!(
+                "Unknown search sorted side: {}",
+                search.side
+            )))
+        }
+    };
+    Ok(expr.search_sorted(build_expr(element)?, side, search.descending))
+}
+
+fn build_index_of(index_of: &proto::IndexOf) -> Result<Expr, BridgeError> {
+    let expr = build_inner_expr(&index_of.expr, "IndexOf")?;
+    let value = index_of
+        .value
+        .as_ref()
+        .ok_or_else(|| BridgeError::PlanSemantic("IndexOf has no value".into()))?;
+    Ok(expr.index_of(build_expr(value)?))
+}
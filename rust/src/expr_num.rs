@@ -0,0 +1,84 @@
+use polars::prelude::*;
+use polars::series::ops::NullBehavior;
+
+use crate::error::BridgeError;
+use crate::executor::{build_expr, build_inner_expr};
+use crate::proto;
+
+pub fn build_numeric_expr(kind: &proto::expr::Kind) -> Option<Result<Expr, BridgeError>> {
+    use proto::expr::Kind;
+
+    match kind {
+        Kind::IsNan(func) => Some(build_unary(func, "IsNan", |expr| expr.is_nan())),
+        Kind::IsNotNan(func) => Some(build_unary(func, "IsNotNan", |expr| expr.is_not_nan())),
+        Kind::IsFinite(func) => Some(build_unary(func, "IsFinite", |expr| expr.is_finite())),
+        Kind::IsInfinite(func) => Some(build_unary(func, "IsInfinite", |expr| expr.is_infinite())),
+        Kind::FillNan(fill) => Some(build_fill_nan(fill)),
+        Kind::Clip(clip) => Some(build_clip(clip)),
+        Kind::CumSum(cum) => Some(build_cumulative(cum, "CumSum", Expr::cum_sum)),
+        Kind::CumMin(cum) => Some(build_cumulative(cum, "CumMin", Expr::cum_min)),
+        Kind::CumMax(cum) => Some(build_cumulative(cum, "CumMax", Expr::cum_max)),
+        Kind::CumProd(cum) => Some(build_cumulative(cum, "CumProd", Expr::cum_prod)),
+        Kind::CumCount(cum) => Some(build_cumulative(cum, "CumCount", Expr::cum_count)),
+        Kind::Diff(diff) => Some(build_diff(diff)),
+        Kind::PctChange(pct) => Some(build_pct_change(pct)),
+        _ => None,
+    }
+}
+
+fn build_unary<F>(func: &proto::NumericFunction, name: &str, op: F) -> Result<Expr, BridgeError>
+where
+    F: FnOnce(Expr) -> Expr,
+{
+    let expr = build_inner_expr(&func.expr, name)?;
+    Ok(op(expr))
+}
+
+fn build_fill_nan(fill: &proto::FillNan) -> Result<Expr, BridgeError> {
+    let expr = build_inner_expr(&fill.expr, "FillNan")?;
+    let value = build_inner_expr(&fill.value, "FillNan value")?;
+    Ok(expr.fill_nan(value))
+}
+
+fn build_clip(clip: &proto::Clip) -> Result<Expr, BridgeError> {
+    let expr = build_inner_expr(&clip.expr, "Clip")?;
+    let min = clip.min.as_deref().map(build_expr).transpose()?;
+    let max = clip.max.as_deref().map(build_expr).transpose()?;
+
+    match (min, max) {
+        (Some(min), Some(max)) => Ok(expr.clip(min, max)),
+        (Some(min), None) => Ok(expr.clip_min(min)),
+        (None, Some(max)) => Ok(expr.clip_max(max)),
+        (None, None) => Err(BridgeError::PlanSemantic("Clip requires min or max".into())),
+    }
+}
+
+fn build_cumulative<F>(cum: &proto::Cumulative, name: &str, op: F) -> Result<Expr, BridgeError>
+where
+    F: FnOnce(Expr, bool) -> Expr,
+{
+    let expr = build_inner_expr(&cum.expr, name)?;
+    Ok(op(expr, cum.reverse))
+}
+
+fn build_diff(diff: &proto::Diff) -> Result<Expr, BridgeError> {
+    use proto::DiffNullBehavior as Behavior;
+
+    let expr = build_inner_expr(&diff.expr, "Diff")?;
+    let null_behavior = match Behavior::try_from(diff.null_behavior) {
+        Ok(Behavior::Ignore) => NullBehavior::Ignore,
+        Ok(Behavior::Drop) => NullBehavior::Drop,
+        Err(_) => {
+            return Err(BridgeError::Unsupported(format!(
+                "Unknown diff null behavior: {}",
+                diff.null_behavior
+            )))
+        }
+    };
+    Ok(expr.diff(lit(diff.n.unwrap_or(1)), null_behavior))
+}
+
+fn build_pct_change(pct: &proto::PctChange) -> Result<Expr, BridgeError> {
+    let expr = build_inner_expr(&pct.expr, "PctChange")?;
+    Ok(expr.pct_change(lit(pct.n.unwrap_or(1))))
+}
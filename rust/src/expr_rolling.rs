@@ -0,0 +1,134 @@
+use polars::prelude::*;
+
+use crate::dynamic_group::closed_window;
+use crate::error::BridgeError;
+use crate::executor::build_inner_expr;
+use crate::proto;
+
+pub fn build_rolling_expr(kind: &proto::expr::Kind) -> Option<Result<Expr, BridgeError>> {
+    use proto::expr::Kind;
+
+    match kind {
+        Kind::RollingMean(r) => Some(build_rolling(r, "RollingMean", Expr::rolling_mean)),
+        Kind::RollingSum(r) => Some(build_rolling(r, "RollingSum", Expr::rolling_sum)),
+        Kind::RollingMin(r) => Some(build_rolling(r, "RollingMin", Expr::rolling_min)),
+        Kind::RollingMax(r) => Some(build_rolling(r, "RollingMax", Expr::rolling_max)),
+        Kind::RollingStd(r) => Some(build_rolling(r, "RollingStd", Expr::rolling_std)),
+        Kind::RollingMeanBy(r) => Some(build_rolling_by(r, "RollingMeanBy", Expr::rolling_mean_by)),
+        Kind::RollingSumBy(r) => Some(build_rolling_by(r, "RollingSumBy", Expr::rolling_sum_by)),
+        Kind::RollingMinBy(r) => Some(build_rolling_by(r, "RollingMinBy", Expr::rolling_min_by)),
+        Kind::RollingMaxBy(r) => Some(build_rolling_by(r, "RollingMaxBy", Expr::rolling_max_by)),
+        Kind::RollingStdBy(r) => Some(build_rolling_by(r, "RollingStdBy", Expr::rolling_std_by)),
+        Kind::EwmMean(ewm) => Some(build_ewm(ewm, "EwmMean", Expr::ewm_mean)),
+        Kind::EwmStd(ewm) => Some(build_ewm(ewm, "EwmStd", Expr::ewm_std)),
+        Kind::EwmVar(ewm) => Some(build_ewm(ewm, "EwmVar", Expr::ewm_var)),
+        _ => None,
+    }
+}
+
+// 固定行数窗口
+fn build_rolling<F>(rolling: &proto::Rolling, name: &str, op: F) -> Result<Expr, BridgeError>
+where
+    F: FnOnce(Expr, RollingOptionsFixedWindow) -> Expr,
+{
+    let expr = build_inner_expr(&rolling.expr, name)?;
+    let window_size = rolling.window_size as usize;
+    if window_size == 0 {
+        return Err(BridgeError::PlanSemantic(format!("{name} window_size must be positive")));
+    }
+    let min_periods = rolling.min_periods.map(|n| n as usize).unwrap_or(window_size);
+    if min_periods > window_size {
+        return Err(BridgeError::PlanSemantic(format!(
+            "{name} min_periods ({}) cannot exceed window_size ({})",
+            min_periods, window_size
+        )));
+    }
+    let weights = if rolling.weights.is_empty() {
+        None
+    } else if rolling.weights.len() == window_size {
+        Some(rolling.weights.clone())
+    } else {
+        return Err(BridgeError::PlanSemantic(format!(
+            "{name} has {} weights but window_size is {}",
+            rolling.weights.len(),
+            window_size
+        )));
+    };
+
+    let options = RollingOptionsFixedWindow {
+        window_size,
+        min_periods,
+        weights,
+        center: rolling.center,
+        fn_params: var_params(rolling.ddof),
+    };
+    Ok(op(expr, options))
+}
+
+// 按时间列的动态窗口（window_size 为时长字符串，如 "1h" / "7d"）
+fn build_rolling_by<F>(rolling: &proto::RollingBy, name: &str, op: F) -> Result<Expr, BridgeError>
+where
+    F: FnOnce(Expr, Expr, RollingOptionsDynamicWindow) -> Expr,
+{
+    let expr = build_inner_expr(&rolling.expr, name)?;
+    let by = build_inner_expr(&rolling.by, &format!("{name} by"))?;
+    if rolling.window_size.is_empty() {
+        return Err(BridgeError::PlanSemantic(format!("{name} has no window_size")));
+    }
+    let window_size = Duration::try_parse(&rolling.window_size).map_err(|e| {
+        BridgeError::PlanSemantic(format!(
+            "{name} has invalid window_size '{}': {}",
+            rolling.window_size, e
+        ))
+    })?;
+
+    let options = RollingOptionsDynamicWindow {
+        window_size,
+        min_periods: rolling.min_periods.map(|n| n as usize).unwrap_or(1),
+        closed_window: closed_window(rolling.closed)?,
+        fn_params: var_params(rolling.ddof),
+    };
+    Ok(op(expr, by, options))
+}
+
+// 指数加权：把 span / half_life / com 统一换算为 alpha
+fn build_ewm<F>(ewm: &proto::Ewm, name: &str, op: F) -> Result<Expr, BridgeError>
+where
+    F: FnOnce(Expr, EWMOptions) -> Expr,
+{
+    use proto::ewm::Decay;
+
+    let expr = build_inner_expr(&ewm.expr, name)?;
+    let invalid = |what: &str| BridgeError::PlanSemantic(format!("{name} has invalid {what}"));
+    let alpha = match ewm.decay {
+        Some(Decay::Alpha(alpha)) if alpha > 0.0 && alpha <= 1.0 => alpha,
+        Some(Decay::Alpha(_)) => return Err(invalid("alpha (expected 0 < alpha <= 1)")),
+        Some(Decay::Span(span)) if span >= 1.0 => 2.0 / (span + 1.0),
+        Some(Decay::Span(_)) => return Err(invalid("span (expected span >= 1)")),
+        Some(Decay::HalfLife(half_life)) if half_life > 0.0 => {
+            1.0 - (-std::f64::consts::LN_2 / half_life).exp()
+        }
+        Some(Decay::HalfLife(_)) => return Err(invalid("half_life (expected half_life > 0)")),
+        Some(Decay::Com(com)) if com >= 0.0 => 1.0 / (1.0 + com),
+        Some(Decay::Com(_)) => return Err(invalid("com (expected com >= 0)")),
+        None => {
+            return Err(BridgeError::PlanSemantic(format!(
+                "{name} requires one of alpha, span, half_life or com"
+            )))
+        }
+    };
+
+    let options = EWMOptions {
+        alpha,
+        adjust: ewm.adjust.unwrap_or(true),
+        bias: ewm.bias,
+        min_periods: ewm.min_periods.map(|n| n as usize).unwrap_or(1),
+        ignore_nulls: ewm.ignore_nulls,
+    };
+    Ok(op(expr, options))
+}
+
+// 只有 std 使用 ddof；未设置时沿用 Polars 默认值（1）
+fn var_params(ddof: Option<u32>) -> Option<RollingFnParams> {
+    ddof.map(|ddof| RollingFnParams::Var(RollingVarParams { ddof: ddof as u8 }))
+}
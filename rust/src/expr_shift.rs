@@ -0,0 +1,24 @@
+use polars::prelude::*;
+
+use crate::error::BridgeError;
+use crate::executor::{build_expr, build_inner_expr};
+use crate::proto;
+
+pub fn build_shift_expr(kind: &proto::expr::Kind) -> Option<Result<Expr, BridgeError>> {
+    use proto::expr::Kind;
+
+    match kind {
+        Kind::Shift(shift) => Some(build_shift(shift)),
+        _ => None,
+    }
+}
+
+fn build_shift(shift: &proto::Shift) -> Result<Expr, BridgeError> {
+    let expr = build_inner_expr(&shift.expr, "Shift")?;
+    let n = lit(shift.n);
+
+    match shift.fill_value.as_deref() {
+        Some(fill_value) => Ok(expr.shift_and_fill(n, build_expr(fill_value)?)),
+        None => Ok(expr.shift(n)),
+    }
+}
@@ -1,8 +1,9 @@
 use polars::prelude::*;
 
 use crate::error::BridgeError;
-use crate::executor::build_expr;
+use crate::executor::{build_expr, build_inner_expr};
 use crate::proto;
+use crate::regex_cache;
 
 pub fn build_string_expr(kind: &proto::expr::Kind) -> Option<Result<Expr, BridgeError>> {
     use proto::expr::Kind;
@@ -31,6 +32,8 @@ pub fn build_string_expr(kind: &proto::expr::Kind) -> Option<Result<Expr, Bridge
         Kind::StrSplit(split) => Some(build_split(split)),
         Kind::StrPadStart(pad) => Some(build_pad_start(pad)),
         Kind::StrPadEnd(pad) => Some(build_pad_end(pad)),
+        Kind::ConcatStr(concat) => Some(build_concat_str(concat)),
+        Kind::Format(format) => Some(build_format(format)),
         _ => None,
     }
 }
@@ -51,11 +54,17 @@ fn build_contains(contains: &proto::StringContains) -> Result<Expr, BridgeError>
     let expr = build_inner_expr(&contains.expr, "StrContains")?;
     let pattern = lit(contains.pattern.as_str());
 
-    if contains.literal {
-        Ok(expr.str().contains_literal(pattern))
-    } else {
-        Ok(expr.str().contains(pattern, true))
+    if use_literal(&contains.pattern, contains.literal) {
+        return Ok(expr.str().contains_literal(pattern));
     }
+    let regex = regex_cache::compile(&contains.pattern)?;
+    Ok(map_regex(expr, "str.contains", DataType::Boolean, move |ca| {
+        BooleanChunked::from_iter_options(
+            ca.name().clone(),
+            ca.iter().map(|value| value.map(|s| regex.is_match(s))),
+        )
+        .into_series()
+    }))
 }
 
 fn build_starts_with(starts: &proto::StringStartsWith) -> Result<Expr, BridgeError> {
@@ -70,27 +79,85 @@ fn build_ends_with(ends: &proto::StringEndsWith) -> Result<Expr, BridgeError> {
 
 fn build_extract(extract: &proto::StringExtract) -> Result<Expr, BridgeError> {
     let expr = build_inner_expr(&extract.expr, "StrExtract")?;
-    Ok(expr
-        .str()
-        .extract(lit(extract.pattern.as_str()), extract.group_index as usize))
+    let regex = regex_cache::compile(&extract.pattern)?;
+    let group = extract.group_index as usize;
+    Ok(map_regex(expr, "str.extract", DataType::String, move |ca| {
+        StringChunked::from_iter_options(
+            ca.name().clone(),
+            ca.iter().map(|value| {
+                value
+                    .and_then(|s| regex.captures(s))
+                    .and_then(|captures| captures.get(group))
+                    .map(|m| m.as_str())
+            }),
+        )
+        .into_series()
+    }))
 }
 
 fn build_replace(replace: &proto::StringReplace) -> Result<Expr, BridgeError> {
     let expr = build_inner_expr(&replace.expr, "StrReplace")?;
-    Ok(expr.str().replace(
-        lit(replace.pattern.as_str()),
-        lit(replace.value.as_str()),
-        replace.literal,
-    ))
+    if use_literal_replace(replace) {
+        return Ok(expr.str().replace(
+            lit(replace.pattern.as_str()),
+            lit(replace.value.as_str()),
+            true,
+        ));
+    }
+    let regex = regex_cache::compile(&replace.pattern)?;
+    let value = replace.value.clone();
+    Ok(map_regex(expr, "str.replace", DataType::String, move |ca| {
+        StringChunked::from_iter_options(
+            ca.name().clone(),
+            ca.iter().map(|s| s.map(|s| regex.replacen(s, 1, value.as_str()))),
+        )
+        .into_series()
+    }))
 }
 
 fn build_replace_all(replace: &proto::StringReplace) -> Result<Expr, BridgeError> {
     let expr = build_inner_expr(&replace.expr, "StrReplaceAll")?;
-    Ok(expr.str().replace_all(
-        lit(replace.pattern.as_str()),
-        lit(replace.value.as_str()),
-        replace.literal,
-    ))
+    if use_literal_replace(replace) {
+        return Ok(expr.str().replace_all(
+            lit(replace.pattern.as_str()),
+            lit(replace.value.as_str()),
+            true,
+        ));
+    }
+    let regex = regex_cache::compile(&replace.pattern)?;
+    let value = replace.value.clone();
+    Ok(map_regex(expr, "str.replace_all", DataType::String, move |ca| {
+        StringChunked::from_iter_options(
+            ca.name().clone(),
+            ca.iter().map(|s| s.map(|s| regex.replace_all(s, value.as_str()))),
+        )
+        .into_series()
+    }))
+}
+
+// 不含元字符的模式改走字面匹配（非法的正则模式在构建期由 regex_cache::compile 报错）
+fn use_literal(pattern: &str, literal: bool) -> bool {
+    literal || regex_cache::is_plain(pattern)
+}
+
+fn use_literal_replace(replace: &proto::StringReplace) -> bool {
+    // 正则模式下替换值中的 `$` 引用捕获组，此时不能改写为字面替换
+    if !replace.literal && replace.value.contains('$') {
+        return false;
+    }
+    use_literal(&replace.pattern, replace.literal)
+}
+
+// 用缓存中已编译的正则逐行计算，执行时不再由 Polars 按模式字符串重新编译
+fn map_regex<F>(expr: Expr, name: &'static str, dtype: DataType, op: F) -> Expr
+where
+    F: Fn(&StringChunked) -> Series + Send + Sync + 'static,
+{
+    expr.map_with_fmt_str(
+        move |column| Ok(op(column.str()?).into_column()),
+        move |_, field| Ok(Field::new(field.name().clone(), dtype.clone())),
+        name,
+    )
 }
 
 fn build_strip_chars(strip: &proto::StringStripChars) -> Result<Expr, BridgeError> {
@@ -132,14 +199,40 @@ fn build_pad_end(pad: &proto::StringPad) -> Result<Expr, BridgeError> {
     Ok(expr.str().pad_end(lit(pad.length), fill_char))
 }
 
-fn build_inner_expr(
-    expr: &Option<Box<proto::Expr>>,
-    name: &str,
-) -> Result<Expr, BridgeError> {
-    let expr = expr
-        .as_ref()
-        .ok_or_else(|| BridgeError::PlanSemantic(format!("{name} has no expr")))?;
-    build_expr(expr.as_ref())
+fn build_concat_str(concat: &proto::ConcatStr) -> Result<Expr, BridgeError> {
+    if concat.exprs.is_empty() {
+        return Err(BridgeError::PlanSemantic("ConcatStr has no exprs".into()));
+    }
+    let exprs = concat.exprs.iter().map(build_expr).collect::<Result<Vec<_>, _>>()?;
+    Ok(concat_str(exprs, &concat.separator, concat.ignore_nulls))
+}
+
+// 模板中的文本段作为字面量插在参数之间，再整体拼接
+fn build_format(format: &proto::Format) -> Result<Expr, BridgeError> {
+    let segments: Vec<&str> = format.template.split("{}").collect();
+    let placeholders = segments.len() - 1;
+    if placeholders != format.args.len() {
+        return Err(BridgeError::InvalidArgument(format!(
+            "Format template has {} placeholders but {} args were given",
+            placeholders,
+            format.args.len()
+        )));
+    }
+    if placeholders == 0 {
+        return Ok(lit(format.template.as_str()));
+    }
+
+    let mut exprs = Vec::with_capacity(segments.len() + placeholders);
+    for (segment, arg) in segments.iter().zip(&format.args) {
+        if !segment.is_empty() {
+            exprs.push(lit(*segment));
+        }
+        exprs.push(build_expr(arg)?);
+    }
+    if let Some(last) = segments.last().filter(|s| !s.is_empty()) {
+        exprs.push(lit(*last));
+    }
+    Ok(concat_str(exprs, "", false))
 }
 
 fn parse_fill_char(value: &str, name: &str) -> Result<char, BridgeError> {
@@ -0,0 +1,85 @@
+use polars::prelude::*;
+
+use crate::error::BridgeError;
+use crate::executor::{build_expr, build_inner_expr};
+use crate::proto;
+
+pub fn build_subset_expr(kind: &proto::expr::Kind) -> Option<Result<Expr, BridgeError>> {
+    use proto::expr::Kind;
+
+    match kind {
+        Kind::TopK(top) => Some(build_top_k(top, "TopK", Expr::top_k)),
+        Kind::BottomK(top) => Some(build_top_k(top, "BottomK", Expr::bottom_k)),
+        Kind::TopKBy(top) => Some(build_top_k_by(top, "TopKBy", false)),
+        Kind::BottomKBy(top) => Some(build_top_k_by(top, "BottomKBy", true)),
+        Kind::Head(head) => Some(build_head(head, "Head", Expr::head)),
+        Kind::Tail(tail) => Some(build_head(tail, "Tail", Expr::tail)),
+        Kind::Slice(slice) => Some(build_slice(slice)),
+        Kind::GatherEvery(gather) => Some(build_gather_every(gather)),
+        _ => None,
+    }
+}
+
+fn build_top_k<F>(top: &proto::TopK, name: &str, op: F) -> Result<Expr, BridgeError>
+where
+    F: FnOnce(Expr, Expr) -> Expr,
+{
+    let expr = build_inner_expr(&top.expr, name)?;
+    Ok(op(expr, lit(top.k)))
+}
+
+fn build_top_k_by(top: &proto::TopKBy, name: &str, bottom: bool) -> Result<Expr, BridgeError> {
+    let expr = build_inner_expr(&top.expr, name)?;
+    if top.by.is_empty() {
+        return Err(BridgeError::PlanSemantic(format!("{name} requires at least one by expression")));
+    }
+    let by = top.by.iter().map(build_expr).collect::<Result<Vec<_>, _>>()?;
+
+    let reverse = match top.reverse.as_slice() {
+        [] => vec![false; by.len()],
+        [single] => vec![*single; by.len()],
+        reverse if reverse.len() == by.len() => reverse.to_vec(),
+        reverse => {
+            return Err(BridgeError::PlanSemantic(format!(
+                "{name} has {} by expressions but {} reverse flags",
+                by.len(),
+                reverse.len()
+            )))
+        }
+    };
+
+    if bottom {
+        Ok(expr.bottom_k_by(lit(top.k), by, reverse))
+    } else {
+        Ok(expr.top_k_by(lit(top.k), by, reverse))
+    }
+}
+
+/// 未设置 n 时与 Polars 的 head / tail 默认值一致
+const DEFAULT_HEAD_ROWS: u64 = 10;
+
+fn build_head<F>(head: &proto::Head, name: &str, op: F) -> Result<Expr, BridgeError>
+where
+    F: FnOnce(Expr, Option<usize>) -> Expr,
+{
+    let expr = build_inner_expr(&head.expr, name)?;
+    Ok(op(expr, Some(head.n.unwrap_or(DEFAULT_HEAD_ROWS) as usize)))
+}
+
+fn build_slice(slice: &proto::SliceExpr) -> Result<Expr, BridgeError> {
+    let expr = build_inner_expr(&slice.expr, "SliceExpr")?;
+    // 未设置 length 时取整列（GroupBy 中为整组）的长度，负 offset 同样截取到末尾
+    let length = match slice.length {
+        Some(length) => lit(length),
+        None => len(),
+    };
+    Ok(expr.slice(lit(slice.offset), length))
+}
+
+fn build_gather_every(gather: &proto::GatherEvery) -> Result<Expr, BridgeError> {
+    let expr = build_inner_expr(&gather.expr, "GatherEvery")?;
+    if gather.n == 0 {
+        return Err(BridgeError::InvalidArgument("GatherEvery n must be greater than 0".into()));
+    }
+    Ok(expr.gather_every(gather.n as usize, gather.offset as usize))
+}
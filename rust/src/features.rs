@@ -0,0 +1,228 @@
+//! 按 cargo feature 裁剪的功能
+//!
+//! 精简构建（`--no-default-features --features ...`）不编译的模块在这里提供同名的替代实现：
+//! 导出函数与 Plan 协议保持不变，用到未编译功能的 Plan 会得到 `Unsupported` 错误，
+//! 能力报告（bridge_capabilities）也只列出实际编译进来的节点与表达式。
+
+use crate::error::BridgeError;
+
+// 只有关闭某些 feature 时才有调用方
+#[allow(dead_code)]
+pub fn unavailable(what: &str, feature: &str) -> BridgeError {
+    BridgeError::Unsupported(format!(
+        "{what} requires the bridge to be built with the `{feature}` feature"
+    ))
+}
+
+/// 能力报告中的节点类型
+pub fn supported_nodes() -> Vec<&'static str> {
+    nodes()
+        .into_iter()
+        .filter_map(|(name, enabled)| enabled.then_some(name))
+        .collect()
+}
+
+/// 能力报告中的表达式类型
+pub fn supported_exprs() -> Vec<&'static str> {
+    exprs()
+        .into_iter()
+        .filter_map(|(name, enabled)| enabled.then_some(name))
+        .collect()
+}
+
+/// 因 cargo feature 未编译而不可用的节点、表达式与云存储路径（握手报告）
+pub fn compiled_out() -> Vec<&'static str> {
+    let mut missing: Vec<_> = nodes()
+        .into_iter()
+        .chain(exprs())
+        .filter_map(|(name, enabled)| (!enabled).then_some(name))
+        .collect();
+    if !cfg!(feature = "cloud") {
+        missing.push("CloudStorage");
+    }
+    missing
+}
+
+fn nodes() -> Vec<(&'static str, bool)> {
+    vec![
+        ("MemoryScan", true),
+        ("CsvScan", true),
+        ("ParquetScan", cfg!(feature = "parquet")),
+        ("IpcScan", true),
+        ("AvroScan", cfg!(feature = "avro")),
+        ("ExcelScan", cfg!(feature = "excel")),
+        ("IcebergScan", cfg!(all(feature = "parquet", feature = "avro"))),
+        ("DeltaScan", cfg!(feature = "parquet")),
+        ("DatabaseScan", cfg!(feature = "database")),
+        ("FlightScan", cfg!(feature = "flight")),
+        ("ReaderScan", true),
+        ("BatchProviderScan", true),
+        ("TableScan", true),
+        ("Project", true),
+        ("Filter", true),
+        ("Assert", true),
+        ("WithColumns", true),
+        ("Limit", true),
+        ("GroupBy", true),
+        ("GroupByDynamic", true),
+        ("Join", true),
+        ("JoinWhere", true),
+        ("Upsert", true),
+        ("SinkParquet", cfg!(feature = "parquet")),
+        ("SinkCsv", true),
+        ("SinkNdjson", true),
+        ("SinkIpc", true),
+    ]
+}
+
+fn exprs() -> Vec<(&'static str, bool)> {
+    const CORE: &[&str] = &[
+        "Col", "Lit", "Binary", "Alias", "IsNull", "Not", "Wildcard", "Cast", "SetSorted",
+        "Sum", "Mean", "Min", "Max", "Count", "Std", "Var", "Median", "Quantile", "NUnique",
+        "NullCount", "Len", "First", "Last", "Implode", "IsIn", "IsNan", "IsNotNan", "IsFinite",
+        "IsInfinite", "FillNan", "FillNull", "ForwardFill", "BackwardFill", "Clip", "CumSum",
+        "CumMin", "CumMax", "CumProd", "CumCount", "Shift", "Diff", "PctChange", "ArgMin", "ArgMax",
+        "ArgSort", "ArgUnique", "TopK", "BottomK", "TopKBy", "BottomKBy", "Head", "Tail",
+        "Slice", "GatherEvery", "Mode", "ValueCounts", "UniqueCounts", "SearchSorted",
+        "IndexOf", "MinHorizontal", "MaxHorizontal", "SumHorizontal", "MeanHorizontal",
+        "IsDuplicated", "IsUnique", "IsFirstDistinct", "IsLastDistinct", "Fold",
+        "Reduce", "AsStruct", "Extension",
+    ];
+    const WINDOW: &[&str] = &[
+        "RollingMean", "RollingSum", "RollingMin", "RollingMax", "RollingStd", "RollingMeanBy",
+        "RollingSumBy", "RollingMinBy", "RollingMaxBy", "RollingStdBy", "EwmMean", "EwmStd",
+        "EwmVar",
+    ];
+    const STRINGS: &[&str] = &[
+        "StrLenBytes", "StrLenChars", "StrContains", "StrStartsWith", "StrEndsWith", "StrExtract",
+        "StrReplace", "StrReplaceAll", "StrToLowercase", "StrToUppercase", "StrStripChars",
+        "StrSlice", "StrSplit", "StrPadStart", "StrPadEnd", "ConcatStr", "Format",
+    ];
+
+    let window = cfg!(feature = "window");
+    let strings = cfg!(feature = "strings");
+    CORE.iter()
+        .map(|name| (*name, true))
+        .chain(WINDOW.iter().map(|name| (*name, window)))
+        .chain(STRINGS.iter().map(|name| (*name, strings)))
+        .collect()
+}
+
+#[cfg(not(feature = "strings"))]
+pub mod expr_str {
+    use polars::prelude::Expr;
+
+    use crate::error::BridgeError;
+    use crate::proto;
+
+    pub fn build_string_expr(kind: &proto::expr::Kind) -> Option<Result<Expr, BridgeError>> {
+        use proto::expr::Kind;
+
+        match kind {
+            Kind::StrLenBytes(_)
+            | Kind::StrLenChars(_)
+            | Kind::StrContains(_)
+            | Kind::StrStartsWith(_)
+            | Kind::StrEndsWith(_)
+            | Kind::StrExtract(_)
+            | Kind::StrReplace(_)
+            | Kind::StrReplaceAll(_)
+            | Kind::StrToLowercase(_)
+            | Kind::StrToUppercase(_)
+            | Kind::StrStripChars(_)
+            | Kind::StrSlice(_)
+            | Kind::StrSplit(_)
+            | Kind::StrPadStart(_)
+            | Kind::StrPadEnd(_)
+            | Kind::ConcatStr(_)
+            | Kind::Format(_) => Some(Err(super::unavailable("String expressions", "strings"))),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(not(feature = "strings"))]
+pub mod regex_cache {
+    use crate::error::BridgeError;
+
+    pub fn compile(_pattern: &str) -> Result<(), BridgeError> {
+        Err(super::unavailable("Regex patterns", "strings"))
+    }
+
+    pub fn len() -> usize {
+        0
+    }
+
+    pub fn clear() {}
+}
+
+#[cfg(not(feature = "window"))]
+pub mod expr_rolling {
+    use polars::prelude::Expr;
+
+    use crate::error::BridgeError;
+    use crate::proto;
+
+    pub fn build_rolling_expr(kind: &proto::expr::Kind) -> Option<Result<Expr, BridgeError>> {
+        use proto::expr::Kind;
+
+        match kind {
+            Kind::RollingMean(_)
+            | Kind::RollingSum(_)
+            | Kind::RollingMin(_)
+            | Kind::RollingMax(_)
+            | Kind::RollingStd(_)
+            | Kind::RollingMeanBy(_)
+            | Kind::RollingSumBy(_)
+            | Kind::RollingMinBy(_)
+            | Kind::RollingMaxBy(_)
+            | Kind::RollingStdBy(_)
+            | Kind::EwmMean(_)
+            | Kind::EwmStd(_)
+            | Kind::EwmVar(_) => Some(Err(super::unavailable("Window expressions", "window"))),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(not(feature = "excel"))]
+pub mod excel {
+    use polars::prelude::LazyFrame;
+
+    use crate::columns::DuplicatePolicy;
+    use crate::error::BridgeError;
+    use crate::proto;
+
+    pub fn excel_scan(
+        _scan: &proto::ExcelScan,
+        _duplicates: DuplicatePolicy,
+    ) -> Result<LazyFrame, BridgeError> {
+        Err(super::unavailable("ExcelScan", "excel"))
+    }
+}
+
+#[cfg(not(all(feature = "parquet", feature = "avro")))]
+pub mod iceberg {
+    use polars::prelude::LazyFrame;
+
+    use crate::error::BridgeError;
+    use crate::proto;
+
+    pub fn iceberg_scan(_scan: &proto::IcebergScan) -> Result<LazyFrame, BridgeError> {
+        Err(BridgeError::Unsupported(
+            "IcebergScan requires the bridge to be built with the `parquet` and `avro` features".into(),
+        ))
+    }
+}
+
+#[cfg(not(feature = "parquet"))]
+pub mod delta {
+    use polars::prelude::LazyFrame;
+
+    use crate::error::BridgeError;
+    use crate::proto;
+
+    pub fn delta_scan(_scan: &proto::DeltaScan) -> Result<LazyFrame, BridgeError> {
+        Err(super::unavailable("DeltaScan", "parquet"))
+    }
+}
@@ -0,0 +1,112 @@
+#[cfg(feature = "parquet")]
+use std::fs::File;
+
+#[cfg(feature = "parquet")]
+use polars::prelude::*;
+#[cfg(feature = "parquet")]
+use polars_parquet::parquet::metadata::ColumnChunkMetadata;
+#[cfg(feature = "parquet")]
+use polars_parquet::parquet::statistics::Statistics;
+
+use crate::error::BridgeError;
+use crate::proto;
+use crate::scan;
+
+/// 只读取 Parquet 文件的 footer：schema、行组与列统计信息
+#[cfg(feature = "parquet")]
+pub fn parquet_metadata(path: &str) -> Result<proto::FileMetadata, BridgeError> {
+    let to_error = |e: PolarsError| {
+        BridgeError::Execution(format!("Failed to read parquet metadata from '{}': {}", path, e))
+    };
+
+    let file = File::open(path)
+        .map_err(|e| BridgeError::Execution(format!("Failed to open '{}': {}", path, e)))?;
+    let mut reader = ParquetReader::new(file);
+    let schema = reader.schema().map_err(to_error)?;
+    let metadata = reader.get_metadata().map_err(to_error)?;
+
+    let row_groups = metadata
+        .row_groups
+        .iter()
+        .map(|rg| proto::RowGroupMetadata {
+            num_rows: rg.num_rows() as u64,
+            total_byte_size: rg.total_byte_size() as u64,
+            columns: rg.parquet_columns().iter().map(column_chunk_stats).collect(),
+        })
+        .collect();
+
+    Ok(proto::FileMetadata {
+        num_rows: Some(metadata.num_rows as u64),
+        schema: schema
+            .iter_values()
+            .map(|field| proto::OutputColumn {
+                name: field.name.to_string(),
+                dtype: DataType::from_arrow_field(field).to_string(),
+            })
+            .collect(),
+        row_groups,
+    })
+}
+
+#[cfg(not(feature = "parquet"))]
+pub fn parquet_metadata(_path: &str) -> Result<proto::FileMetadata, BridgeError> {
+    Err(crate::features::unavailable("Parquet metadata", "parquet"))
+}
+
+/// 按 CsvScan 的解析选项推断 CSV 的 schema（只读取推断所需的行）
+pub fn csv_metadata(options: &proto::CsvScan) -> Result<proto::FileMetadata, BridgeError> {
+    let mut lf = scan::csv_scan(options)?;
+    let schema = lf.collect_schema()
+        .map_err(|e| BridgeError::Execution(format!("Failed to infer CSV schema: {}", e)))?;
+
+    Ok(proto::FileMetadata {
+        num_rows: None,
+        schema: schema
+            .iter()
+            .map(|(name, dtype)| proto::OutputColumn {
+                name: name.to_string(),
+                dtype: dtype.to_string(),
+            })
+            .collect(),
+        row_groups: Vec::new(),
+    })
+}
+
+#[cfg(feature = "parquet")]
+fn column_chunk_stats(column: &ColumnChunkMetadata) -> proto::ColumnChunkStats {
+    let mut stats = proto::ColumnChunkStats {
+        path: column.descriptor().path_in_schema.join("."),
+        compressed_size: column.compressed_size() as u64,
+        uncompressed_size: column.uncompressed_size() as u64,
+        ..Default::default()
+    };
+
+    // 统计信息缺失或无法解码时保持为空
+    let Some(Ok(statistics)) = column.statistics() else {
+        return stats;
+    };
+    let (null_count, min, max) = match &statistics {
+        Statistics::Binary(s) => (s.null_count, lossy(&s.min_value), lossy(&s.max_value)),
+        Statistics::FixedLen(s) => (s.null_count, lossy(&s.min_value), lossy(&s.max_value)),
+        Statistics::Boolean(s) => (s.null_count, display(&s.min_value), display(&s.max_value)),
+        Statistics::Int32(s) => (s.null_count, display(&s.min_value), display(&s.max_value)),
+        Statistics::Int64(s) => (s.null_count, display(&s.min_value), display(&s.max_value)),
+        Statistics::Float(s) => (s.null_count, display(&s.min_value), display(&s.max_value)),
+        Statistics::Double(s) => (s.null_count, display(&s.min_value), display(&s.max_value)),
+        Statistics::Int96(s) => (s.null_count, None, None),
+    };
+    stats.null_count = null_count;
+    stats.min = min;
+    stats.max = max;
+    stats
+}
+
+#[cfg(feature = "parquet")]
+fn lossy(value: &Option<Vec<u8>>) -> Option<String> {
+    value.as_deref().map(|bytes| String::from_utf8_lossy(bytes).into_owned())
+}
+
+#[cfg(feature = "parquet")]
+fn display<T: ToString>(value: &Option<T>) -> Option<String> {
+    value.as_ref().map(ToString::to_string)
+}
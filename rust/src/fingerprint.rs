@@ -0,0 +1,34 @@
+use prost::Message;
+
+use crate::nodes::node_inputs_mut;
+use crate::proto;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// 计算 Plan 的结构指纹
+///
+/// 对解码后的 Plan 做规范化（清空节点 ID）后重新编码：prost 按字段号顺序编码，
+/// map 字段为 BTreeMap，因此与 SDK 的序列化顺序、未知字段无关。
+/// 使用 FNV-1a 64 位哈希，结果在不同构建之间保持稳定。
+pub fn plan_fingerprint(plan: &proto::Plan) -> u64 {
+    let mut canonical = plan.clone();
+    if let Some(root) = canonical.root.as_mut() {
+        clear_node_ids(root);
+    }
+
+    canonical
+        .encode_to_vec()
+        .iter()
+        .fold(FNV_OFFSET_BASIS, |hash, byte| {
+            (hash ^ *byte as u64).wrapping_mul(FNV_PRIME)
+        })
+}
+
+// 节点 ID 只用于调试输出，不影响语义
+fn clear_node_ids(node: &mut proto::Node) {
+    node.id = 0;
+    for input in node_inputs_mut(node) {
+        clear_node_ids(input);
+    }
+}
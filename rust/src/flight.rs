@@ -0,0 +1,80 @@
+use polars::prelude::*;
+
+use crate::error::BridgeError;
+use crate::proto;
+
+/// FlightScan：从 Arrow Flight 服务拉取记录批次作为数据源
+///
+/// 设置 ticket 时直接 DoGet；否则先用 descriptor 调用 GetFlightInfo，再依次读取每个 endpoint。
+/// 需要以 `flight` feature 构建。
+#[cfg(feature = "flight")]
+pub fn flight_scan(scan: &proto::FlightScan) -> Result<LazyFrame, BridgeError> {
+    use arrow_flight::{FlightClient, FlightDescriptor, Ticket};
+    use futures::TryStreamExt;
+    use tonic::transport::Endpoint;
+
+    use crate::arrow_bridge::{import_arrow_rs_batch, ImportOptions};
+    use crate::frame_builder::FrameBuilder;
+
+    if scan.endpoint.is_empty() {
+        return Err(BridgeError::InvalidArgument("FlightScan requires endpoint".into()));
+    }
+    let to_error = |e: &dyn std::fmt::Display| {
+        BridgeError::Execution(format!("FlightScan failed for '{}': {}", scan.endpoint, e))
+    };
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| to_error(&e))?;
+
+    runtime.block_on(async {
+        let channel = Endpoint::from_shared(scan.endpoint.clone())
+            .map_err(|e| to_error(&e))?
+            .connect()
+            .await
+            .map_err(|e| to_error(&e))?;
+        let mut client = FlightClient::new(channel);
+        if !scan.auth_token.is_empty() {
+            client
+                .add_header("authorization", &format!("Bearer {}", scan.auth_token))
+                .map_err(|e| to_error(&e))?;
+        }
+
+        let tickets = if !scan.ticket.is_empty() {
+            vec![Ticket::new(scan.ticket.clone())]
+        } else {
+            let descriptor = if !scan.command.is_empty() {
+                FlightDescriptor::new_cmd(scan.command.clone())
+            } else if !scan.path.is_empty() {
+                FlightDescriptor::new_path(scan.path.clone())
+            } else {
+                return Err(BridgeError::InvalidArgument(
+                    "FlightScan requires ticket, command or path".into(),
+                ));
+            };
+            let info = client
+                .get_flight_info(descriptor)
+                .await
+                .map_err(|e| to_error(&e))?;
+            info.endpoint.into_iter().filter_map(|e| e.ticket).collect()
+        };
+
+        let options = ImportOptions::from_options(None);
+        let mut frame = FrameBuilder::default();
+        for ticket in tickets {
+            let mut stream = client.do_get(ticket).await.map_err(|e| to_error(&e))?;
+            while let Some(batch) = stream.try_next().await.map_err(|e| to_error(&e))? {
+                frame.append(import_arrow_rs_batch(batch, &options)?)?;
+            }
+        }
+        Ok(frame.finish().lazy())
+    })
+}
+
+#[cfg(not(feature = "flight"))]
+pub fn flight_scan(_scan: &proto::FlightScan) -> Result<LazyFrame, BridgeError> {
+    Err(BridgeError::Unsupported(
+        "FlightScan requires the bridge to be built with the `flight` feature".into(),
+    ))
+}
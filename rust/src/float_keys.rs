@@ -0,0 +1,163 @@
+use polars::prelude::*;
+
+use crate::error::BridgeError;
+use crate::proto;
+
+/// GroupBy / Join 中浮点键的相等语义
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum FloatKeyMode {
+    Default,
+    TotalOrder,
+    Reject,
+}
+
+impl FloatKeyMode {
+    pub fn from_options(options: Option<&proto::PlanOptions>) -> Self {
+        match options.map(|opts| proto::FloatKeyMode::try_from(opts.float_keys)) {
+            Some(Ok(proto::FloatKeyMode::TotalOrder)) => FloatKeyMode::TotalOrder,
+            Some(Ok(proto::FloatKeyMode::Reject)) => FloatKeyMode::Reject,
+            _ => FloatKeyMode::Default,
+        }
+    }
+}
+
+/// 按模式处理键表达式：TotalOrder 把 -0.0 归一为 0.0、把所有 NaN 归一为同一个 NaN，
+/// Reject 在任一键为浮点类型时报错；其他类型的键保持不变
+pub fn apply(
+    lf: &LazyFrame,
+    keys: Vec<Expr>,
+    mode: FloatKeyMode,
+    node: &str,
+) -> Result<Vec<Expr>, BridgeError> {
+    if mode == FloatKeyMode::Default {
+        return Ok(keys);
+    }
+
+    keys.into_iter()
+        .map(|key| {
+            let output = lf
+                .clone()
+                .select([key.clone()])
+                .collect_schema()
+                .map_err(|e| {
+                    BridgeError::Execution(format!("Failed to resolve {} key type: {}", node, e))
+                })?;
+            if mode == FloatKeyMode::Reject {
+                if let Some((name, dtype)) = output.iter().find(|(_, dtype)| dtype.is_float()) {
+                    return Err(BridgeError::PlanSemantic(format!(
+                        "{} key '{}' has floating-point type {}; cast it or set float_keys to \
+                         FLOAT_KEY_MODE_TOTAL_ORDER",
+                        node, name, dtype
+                    )));
+                }
+                return Ok(key);
+            }
+
+            // 展开为多列的键（如通配符）需要逐列改写，这里只归一化单列键
+            // Join 键中不允许 alias，用 name().keep() 保留原列名
+            let single = output.len() == 1;
+            if let Some(dtype) = output.iter_values().next().filter(|dtype| single && dtype.is_float()) {
+                return Ok(canonical(key, dtype).name().keep());
+            }
+            Ok(key)
+        })
+        .collect()
+}
+
+// -0.0 == 0.0 为真，因此 eq(0.0) 同时匹配两种零
+fn canonical(key: Expr, dtype: &DataType) -> Expr {
+    when(key.clone().is_nan())
+        .then(lit(f64::NAN).cast(dtype.clone()))
+        .when(key.clone().eq(lit(0.0)))
+        .then(lit(0.0).cast(dtype.clone()))
+        .otherwise(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use polars::prelude::{df, DataFrame};
+
+    use crate::error::BridgeError;
+    use crate::executor::execute_plan_df;
+    use crate::proto;
+    use crate::test_plans::{agg, alias, col, expr, input, memory_scan, node, plan};
+    use proto::expr::Kind as E;
+    use proto::node::Kind as N;
+
+    fn data() -> DataFrame {
+        df!(
+            "x" => [0.0f64, -0.0, f64::NAN, -f64::NAN, 1.5],
+            "v" => [1i64, 2, 3, 4, 5],
+        )
+        .unwrap()
+    }
+
+    fn with_mode(mut plan: proto::Plan, mode: proto::FloatKeyMode) -> proto::Plan {
+        plan.options = Some(proto::PlanOptions { float_keys: mode as i32, ..Default::default() });
+        plan
+    }
+
+    fn group_by_plan(mode: proto::FloatKeyMode) -> proto::Plan {
+        let group_by = N::GroupBy(Box::new(proto::GroupBy {
+            input: input(memory_scan()),
+            keys: vec![col("x")],
+            aggs: vec![alias(expr(E::Sum(agg(col("v")))), "total")],
+            maintain_order: true,
+            ..Default::default()
+        }));
+        with_mode(plan(node(2, group_by)), mode)
+    }
+
+    fn join_plan(mode: proto::FloatKeyMode) -> proto::Plan {
+        let join = N::Join(Box::new(proto::Join {
+            left: input(memory_scan()),
+            right: input(memory_scan()),
+            left_on: vec![col("x")],
+            right_on: vec![col("x")],
+            how: proto::JoinType::Inner as i32,
+            ..Default::default()
+        }));
+        with_mode(plan(node(2, join)), mode)
+    }
+
+    fn floats(df: &DataFrame, name: &str) -> Vec<f64> {
+        df.column(name).unwrap().f64().unwrap().into_no_null_iter().collect()
+    }
+
+    fn ints(df: &DataFrame, name: &str) -> Vec<i64> {
+        df.column(name).unwrap().i64().unwrap().into_no_null_iter().collect()
+    }
+
+    #[test]
+    fn total_order_merges_zeros_and_nans() {
+        let out = execute_plan_df(&group_by_plan(proto::FloatKeyMode::TotalOrder), Some(&data())).unwrap();
+        let keys = floats(&out, "x");
+        assert_eq!(keys.len(), 3, "{out}");
+        assert_eq!(keys[0].to_bits(), 0.0f64.to_bits());
+        assert!(keys[1].is_nan());
+        assert_eq!(keys[2], 1.5);
+        assert_eq!(ints(&out, "total"), [3, 7, 5]);
+
+        // 每个零与两个零匹配、每个 NaN 与两个 NaN 匹配：2*2 + 2*2 + 1
+        let out = execute_plan_df(&join_plan(proto::FloatKeyMode::TotalOrder), Some(&data())).unwrap();
+        assert_eq!(out.height(), 9, "{out}");
+    }
+
+    #[test]
+    fn reject_fails_on_float_keys_only() {
+        for plan in [group_by_plan(proto::FloatKeyMode::Reject), join_plan(proto::FloatKeyMode::Reject)] {
+            let err = execute_plan_df(&plan, Some(&data())).unwrap_err();
+            assert!(
+                matches!(&err, BridgeError::PlanSemantic(m) if m.contains("key 'x' has floating-point type f64")),
+                "{err:?}"
+            );
+        }
+
+        let mut plan = group_by_plan(proto::FloatKeyMode::Reject);
+        if let Some(N::GroupBy(group_by)) = plan.root.as_mut().and_then(|root| root.kind.as_mut()) {
+            group_by.keys = vec![col("v")];
+        }
+        let out = execute_plan_df(&plan, Some(&data())).unwrap();
+        assert_eq!(ints(&out, "v"), [1, 2, 3, 4, 5]);
+    }
+}
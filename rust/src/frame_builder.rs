@@ -0,0 +1,37 @@
+use std::io::Cursor;
+
+use polars::prelude::*;
+
+use crate::error::BridgeError;
+
+/// 分块构建 DataFrame：每次追加只记录新的 chunk，不做 rechunk
+#[derive(Default)]
+pub struct FrameBuilder {
+    df: Option<DataFrame>,
+}
+
+impl FrameBuilder {
+    /// 追加一个分块（schema 必须与已追加的分块一致）
+    pub fn append(&mut self, chunk: DataFrame) -> Result<(), BridgeError> {
+        match self.df.as_mut() {
+            None => self.df = Some(chunk),
+            Some(df) => {
+                df.vstack_mut_owned(chunk)
+                    .map_err(|e| BridgeError::InvalidArgument(format!("Failed to append chunk: {}", e)))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 取出已追加的数据（没有追加过任何分块时返回空 DataFrame）
+    pub fn finish(&mut self) -> DataFrame {
+        self.df.take().unwrap_or_default()
+    }
+}
+
+/// 解析 Arrow IPC 文件格式的分块
+pub fn read_ipc_chunk(bytes: &[u8]) -> Result<DataFrame, BridgeError> {
+    IpcReader::new(Cursor::new(bytes))
+        .finish()
+        .map_err(|e| BridgeError::InvalidArgument(format!("Invalid IPC chunk: {}", e)))
+}
@@ -0,0 +1,112 @@
+//! 模糊测试入口（仅在 `--cfg fuzzing` 下编译，由 fuzz/ 目录下的 cargo-fuzz 目标调用）
+//!
+//! 入口直接调用内部实现而不经过 ffi_guard：panic 由 libFuzzer 记为崩溃，返回错误属于正常结果。
+//! 执行入口只接受 MemoryScan 数据源（输入为内置的样例数据），并限制 Plan 规模、连接数与结果行数。
+
+use std::sync::OnceLock;
+
+use polars::prelude::*;
+use prost::Message;
+
+use crate::error::BridgeError;
+use crate::executor;
+use crate::handles;
+use crate::nodes::{node_inputs, node_name};
+
+pub use crate::proto;
+
+/// 执行入口允许的编码后 Plan 大小
+pub const MAX_PLAN_BYTES: usize = 64 * 1024;
+/// 执行入口允许的节点数
+pub const MAX_NODES: usize = 32;
+/// 执行入口允许的连接节点数（限制笛卡尔积放大）
+pub const MAX_JOINS: usize = 2;
+/// 执行结果的行数上限
+pub const MAX_ROWS: u64 = 1024;
+
+/// bridge_plan_compile 的解码与校验：成功时注册句柄后立即释放
+pub fn plan_compile(data: &[u8]) {
+    if let Ok(plan) = crate::compile_plan(data) {
+        let handle = handles::plans().insert(plan);
+        handles::plans().remove(handle);
+    }
+}
+
+/// 构建表达式，成功时在样例数据上求值
+pub fn build_expr(expr: &proto::Expr) {
+    if executor::build_expr(expr).is_ok() {
+        let _ = executor::evaluate_expr(sample_frame(), expr);
+    }
+}
+
+/// 有资源上限的执行：超出 check_limits 的 Plan 直接跳过
+pub fn execute_plan(plan: &proto::Plan) {
+    if check_limits(plan).is_err() {
+        return;
+    }
+
+    let hints = proto::ExecutionHints {
+        max_rows: Some(MAX_ROWS),
+        ..Default::default()
+    };
+    let _ = executor::execute_plan_df_with_hints(plan, Some(sample_frame()), &hints);
+}
+
+/// 执行入口的资源限制：不访问文件、网络与会话，不产生 Sink 副作用
+pub fn check_limits(plan: &proto::Plan) -> Result<(), BridgeError> {
+    use proto::node::Kind;
+
+    if plan.encoded_len() > MAX_PLAN_BYTES {
+        return Err(BridgeError::InvalidArgument(format!(
+            "Plan exceeds {} bytes",
+            MAX_PLAN_BYTES
+        )));
+    }
+
+    let mut stack = vec![executor::plan_root(plan)?];
+    let (mut nodes, mut joins) = (0, 0);
+    while let Some(node) = stack.pop() {
+        nodes += 1;
+        match node.kind.as_ref() {
+            Some(Kind::MemoryScan(_))
+            | Some(Kind::Project(_))
+            | Some(Kind::Filter(_))
+            | Some(Kind::WithColumns(_))
+            | Some(Kind::Limit(_))
+            | Some(Kind::GroupBy(_)) => {}
+            Some(Kind::Join(_)) | Some(Kind::JoinWhere(_)) | Some(Kind::Upsert(_)) => joins += 1,
+            _ => {
+                return Err(BridgeError::Unsupported(format!(
+                    "{} is not allowed in fuzzed execution",
+                    node_name(node)
+                )))
+            }
+        }
+        if nodes > MAX_NODES || joins > MAX_JOINS {
+            return Err(BridgeError::InvalidArgument(format!(
+                "Plan exceeds {} nodes or {} joins",
+                MAX_NODES, MAX_JOINS
+            )));
+        }
+        stack.extend(node_inputs(node));
+    }
+    Ok(())
+}
+
+/// 样例数据的列名（供结构化输入生成列引用）
+pub const SAMPLE_COLUMNS: &[&str] = &["a", "b", "s", "k", "flag"];
+
+/// 固定的样例数据：包含 null、NaN、空字符串与重复键
+pub fn sample_frame() -> &'static DataFrame {
+    static FRAME: OnceLock<DataFrame> = OnceLock::new();
+    FRAME.get_or_init(|| {
+        df!(
+            "a" => [Some(1i64), None, Some(-3), Some(i64::MAX), Some(0), Some(7), None, Some(i64::MIN)],
+            "b" => [Some(0.5f64), Some(f64::NAN), None, Some(-0.0), Some(f64::INFINITY), Some(1e300), Some(2.0), None],
+            "s" => [Some("x"), Some(""), None, Some("héllo"), Some("a,b"), Some("x"), Some("\u{0}"), Some("zz")],
+            "k" => [1i32, 1, 2, 2, 3, 1, 3, 2],
+            "flag" => [Some(true), Some(false), None, Some(true), Some(true), Some(false), None, Some(false)],
+        )
+        .expect("sample frame columns have equal length")
+    })
+}
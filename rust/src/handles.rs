@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+
+use polars::prelude::DataFrame;
+
+use crate::batch_provider::GoBatchProvider;
+use crate::error::BridgeError;
+use crate::frame_builder::FrameBuilder;
+use crate::incremental::IncrementalAgg;
+use crate::reader_scan::GoReader;
+use crate::session::Session;
+use crate::stream::MicroBatchStream;
+use crate::proto;
+
+/// 句柄注册表
+///
+/// 句柄是注册表分配的不透明 ID，而不是裸指针：
+/// - 取出的对象以 `Arc<T>` 共享，只读访问可以在多个 goroutine 中并发进行；
+/// - 对象本身不可变，需要修改时调用方应生成新的句柄；
+/// - 已释放或伪造的句柄会返回 `InvalidArgument`，重复释放是安全的空操作；
+/// - 句柄带引用计数：`retain` 加一，`release` 减一，归零时才真正释放对象。
+pub struct HandleRegistry<T> {
+    kind: &'static str,
+    next_id: AtomicU64,
+    items: RwLock<HashMap<u64, Entry<T>>>,
+}
+
+struct Entry<T> {
+    value: Arc<T>,
+    refs: usize,
+}
+
+impl<T: Send + Sync> HandleRegistry<T> {
+    fn new(kind: &'static str) -> Self {
+        HandleRegistry {
+            kind,
+            next_id: AtomicU64::new(1),
+            items: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn insert(&self, value: T) -> u64 {
+        self.insert_arc(Arc::new(value))
+    }
+
+    pub fn insert_arc(&self, value: Arc<T>) -> u64 {
+        let handle = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.items
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(handle, Entry { value, refs: 1 });
+        handle
+    }
+
+    pub fn get(&self, handle: u64) -> Result<Arc<T>, BridgeError> {
+        self.items
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&handle)
+            .map(|entry| Arc::clone(&entry.value))
+            .ok_or_else(|| self.invalid(handle))
+    }
+
+    /// 增加一个引用（之后需要多调用一次 release）
+    pub fn retain(&self, handle: u64) -> Result<(), BridgeError> {
+        let mut items = self.items.write().unwrap_or_else(|e| e.into_inner());
+        let entry = items.get_mut(&handle).ok_or_else(|| self.invalid(handle))?;
+        entry.refs += 1;
+        Ok(())
+    }
+
+    /// 释放一个引用，返回对象是否因此被移出注册表
+    pub fn release(&self, handle: u64) -> bool {
+        let mut items = self.items.write().unwrap_or_else(|e| e.into_inner());
+        match items.get_mut(&handle) {
+            Some(entry) if entry.refs > 1 => {
+                entry.refs -= 1;
+                false
+            }
+            Some(_) => items.remove(&handle).is_some(),
+            None => false,
+        }
+    }
+
+    /// 无视引用计数直接移出注册表
+    pub fn remove(&self, handle: u64) -> Option<Arc<T>> {
+        self.items
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&handle)
+            .map(|entry| entry.value)
+    }
+
+    fn invalid(&self, handle: u64) -> BridgeError {
+        BridgeError::InvalidArgument(format!("Invalid {} handle: {}", self.kind, handle))
+    }
+}
+
+// 编译期保证注册表中的对象可以跨线程共享
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<DataFrame>();
+    assert_send_sync::<proto::Plan>();
+    assert_send_sync::<Mutex<FrameBuilder>>();
+    assert_send_sync::<GoReader>();
+    assert_send_sync::<GoBatchProvider>();
+    assert_send_sync::<Session>();
+    assert_send_sync::<Mutex<IncrementalAgg>>();
+    assert_send_sync::<Mutex<MicroBatchStream>>();
+};
+
+pub fn dataframes() -> &'static HandleRegistry<DataFrame> {
+    static REGISTRY: OnceLock<HandleRegistry<DataFrame>> = OnceLock::new();
+    REGISTRY.get_or_init(|| HandleRegistry::new("dataframe"))
+}
+
+pub fn plans() -> &'static HandleRegistry<proto::Plan> {
+    static REGISTRY: OnceLock<HandleRegistry<proto::Plan>> = OnceLock::new();
+    REGISTRY.get_or_init(|| HandleRegistry::new("plan"))
+}
+
+/// 可追加的分块构建器（追加需要独占访问，因此包在 Mutex 中）
+pub fn frames() -> &'static HandleRegistry<Mutex<FrameBuilder>> {
+    static REGISTRY: OnceLock<HandleRegistry<Mutex<FrameBuilder>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| HandleRegistry::new("frame"))
+}
+
+/// Go 侧注册的读取回调（由 ReaderScan 消费）
+pub fn readers() -> &'static HandleRegistry<GoReader> {
+    static REGISTRY: OnceLock<HandleRegistry<GoReader>> = OnceLock::new();
+    REGISTRY.get_or_init(|| HandleRegistry::new("reader"))
+}
+
+/// Go 侧注册的批次提供者（由 BatchProviderScan 使用）
+pub fn batch_providers() -> &'static HandleRegistry<GoBatchProvider> {
+    static REGISTRY: OnceLock<HandleRegistry<GoBatchProvider>> = OnceLock::new();
+    REGISTRY.get_or_init(|| HandleRegistry::new("batch provider"))
+}
+
+/// 表注册会话（TableScan 在执行时按名称解析）
+pub fn sessions() -> &'static HandleRegistry<Session> {
+    static REGISTRY: OnceLock<HandleRegistry<Session>> = OnceLock::new();
+    REGISTRY.get_or_init(|| HandleRegistry::new("session"))
+}
+
+pub fn aggregators() -> &'static HandleRegistry<Mutex<IncrementalAgg>> {
+    static REGISTRY: OnceLock<HandleRegistry<Mutex<IncrementalAgg>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| HandleRegistry::new("aggregator"))
+}
+
+/// 微批流（执行会推进流的状态，因此包在 Mutex 中）
+pub fn streams() -> &'static HandleRegistry<Mutex<MicroBatchStream>> {
+    static REGISTRY: OnceLock<HandleRegistry<Mutex<MicroBatchStream>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| HandleRegistry::new("stream"))
+}
@@ -0,0 +1,95 @@
+use crate::features;
+use crate::proto;
+
+/// ABI 1 的 bridge_last_error 所有权规则不同（见 lib.rs 中的 ABI_VERSION），不再兼容
+pub const MIN_ABI_VERSION: u32 = 2;
+pub const MIN_PLAN_VERSION: u32 = 1;
+pub const MAX_PLAN_VERSION: u32 = 1;
+
+/// 需要高于 v1 的 Plan 版本才可用的功能（功能名, 最低 Plan 版本）
+///
+/// 目前只有 v1，新增 Plan 版本时在这里登记该版本引入的节点与表达式
+const VERSIONED_FEATURES: &[(&str, u32)] = &[];
+
+/// 协商客户端与 bridge 的协议版本
+///
+/// 协商结果只通过报告返回，不保存在进程内：同一进程中的其他调用方（各自的 Bridge、回放等）
+/// 不受影响，bridge 始终接受 MIN_PLAN_VERSION..=MAX_PLAN_VERSION 范围内的 Plan。
+pub fn negotiate(abi_version: u32, client_abi: u32, client_plan_version: u32) -> proto::HandshakeReport {
+    let mut report = proto::HandshakeReport {
+        bridge_abi_version: abi_version,
+        min_plan_version: MIN_PLAN_VERSION,
+        max_plan_version: MAX_PLAN_VERSION,
+        ..Default::default()
+    };
+
+    if client_abi < MIN_ABI_VERSION || client_abi > abi_version {
+        report.warnings.push(format!(
+            "client ABI {} is outside supported range {}..={}",
+            client_abi, MIN_ABI_VERSION, abi_version
+        ));
+        return report;
+    }
+    if client_plan_version < MIN_PLAN_VERSION {
+        report.warnings.push(format!(
+            "client plan version {} is older than minimum supported {}",
+            client_plan_version, MIN_PLAN_VERSION
+        ));
+        return report;
+    }
+
+    let plan_version = client_plan_version.min(MAX_PLAN_VERSION);
+    if client_plan_version > MAX_PLAN_VERSION {
+        report.warnings.push(format!(
+            "client plan version {} is newer than bridge maximum {}; newer plan features will be rejected",
+            client_plan_version, MAX_PLAN_VERSION
+        ));
+    }
+
+    report.compatible = true;
+    report.negotiated_abi_version = client_abi;
+    report.negotiated_plan_version = plan_version;
+    // 未编译进 bridge 的功能，加上协商版本低于其最低 Plan 版本的功能
+    report.unavailable_features = features::compiled_out()
+        .into_iter()
+        .chain(
+            VERSIONED_FEATURES
+                .iter()
+                .filter(|(_, min_version)| *min_version > plan_version)
+                .map(|(name, _)| *name),
+        )
+        .map(String::from)
+        .collect();
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_compiled_out_features() {
+        let report = negotiate(crate::ABI_VERSION, crate::ABI_VERSION, MAX_PLAN_VERSION);
+        assert!(report.compatible);
+        assert_eq!(report.negotiated_plan_version, MAX_PLAN_VERSION);
+
+        let unavailable = &report.unavailable_features;
+        assert_eq!(unavailable.contains(&"FlightScan".to_string()), !cfg!(feature = "flight"));
+        assert_eq!(unavailable.contains(&"ParquetScan".to_string()), !cfg!(feature = "parquet"));
+        assert_eq!(unavailable.contains(&"RollingMean".to_string()), !cfg!(feature = "window"));
+        assert!(!unavailable.contains(&"Filter".to_string()));
+    }
+
+    #[test]
+    fn newer_client_is_capped_without_state() {
+        let report = negotiate(crate::ABI_VERSION, crate::ABI_VERSION, MAX_PLAN_VERSION + 1);
+        assert!(report.compatible);
+        assert_eq!(report.negotiated_plan_version, MAX_PLAN_VERSION);
+        assert_eq!(report.warnings.len(), 1);
+
+        let report = negotiate(crate::ABI_VERSION, 1, MAX_PLAN_VERSION);
+        assert!(!report.compatible);
+        assert!(report.unavailable_features.is_empty());
+    }
+}
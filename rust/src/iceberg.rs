@@ -0,0 +1,564 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::path::Path;
+
+use polars::io::avro::AvroReader;
+use polars::prelude::*;
+
+use crate::cloud::cloud_options;
+use crate::error::BridgeError;
+use crate::executor::build_expr;
+use crate::proto;
+use crate::scan::local_path;
+
+// manifest entry 状态：2 = DELETED
+const STATUS_DELETED: i32 = 2;
+
+/// IcebergScan：从表元数据解析快照的数据文件，再作为 Parquet 扫描
+///
+/// 表元数据来自 metadata_path，或 catalog_uri 指向的文件系统 catalog 表目录（按 metadata/version-hint.text 取当前版本）。
+/// 设置 predicate 时先用 manifest 的分区摘要、数据文件的分区值与列上下界裁剪文件，再作为过滤条件应用；
+/// 只读取本地的元数据/manifest 文件，数据文件可以在云存储上，暂不支持 merge-on-read 的删除文件。
+pub fn iceberg_scan(scan: &proto::IcebergScan) -> Result<LazyFrame, BridgeError> {
+    let metadata_path = metadata_location(scan)?;
+    let conditions = scan.predicate.as_ref().map(conditions).unwrap_or_default();
+    let files = data_files(scan, &metadata_path, &conditions)?;
+    let Some(first) = files.first() else {
+        return Ok(DataFrame::empty().lazy());
+    };
+
+    let mut args = ScanArgsParquet {
+        cloud_options: cloud_options(first.to_str(), scan.cloud.as_ref())?,
+        glob: false,
+        ..Default::default()
+    };
+    if let Some(options) = scan.options.as_ref() {
+        args.rechunk = options.rechunk;
+        args.cache = options.cache.unwrap_or(true);
+        args.low_memory = options.low_memory;
+    }
+    let lf = LazyFrame::scan_parquet_files(files.into(), args).map_err(|e| {
+        BridgeError::Execution(format!("IcebergScan failed for '{}': {}", metadata_path, e))
+    })?;
+
+    match scan.predicate.as_ref() {
+        Some(predicate) => Ok(lf.filter(build_expr(predicate)?)),
+        None => Ok(lf),
+    }
+}
+
+// 文件系统 catalog（Hadoop catalog）的表目录布局：metadata/v<N>.metadata.json，当前版本记录在 version-hint.text
+fn metadata_location(scan: &proto::IcebergScan) -> Result<String, BridgeError> {
+    match (scan.metadata_path.is_empty(), scan.catalog_uri.is_empty()) {
+        (false, true) => return Ok(scan.metadata_path.clone()),
+        (false, false) => {
+            return Err(BridgeError::InvalidArgument(
+                "IcebergScan cannot set both metadata_path and catalog_uri".into(),
+            ))
+        }
+        (true, true) => {
+            return Err(BridgeError::InvalidArgument(
+                "IcebergScan requires metadata_path or catalog_uri".into(),
+            ))
+        }
+        (true, false) => {}
+    }
+
+    let table = local_path(&scan.catalog_uri);
+    if table.contains("://") {
+        return Err(BridgeError::Unsupported(format!(
+            "IcebergScan only supports filesystem catalogs on the local filesystem: '{}'",
+            scan.catalog_uri
+        )));
+    }
+    let metadata_dir = Path::new(table).join("metadata");
+    let version = match fs::read_to_string(metadata_dir.join("version-hint.text")) {
+        Ok(hint) => hint.trim().parse::<u64>().map_err(|_| {
+            BridgeError::Execution(format!(
+                "Invalid Iceberg version hint in '{}': '{}'",
+                metadata_dir.display(),
+                hint.trim()
+            ))
+        })?,
+        // 没有 version-hint.text 时取目录中版本号最大的元数据文件
+        Err(_) => latest_metadata_version(&metadata_dir)?,
+    };
+    Ok(metadata_dir
+        .join(format!("v{version}.metadata.json"))
+        .to_string_lossy()
+        .into_owned())
+}
+
+fn latest_metadata_version(metadata_dir: &Path) -> Result<u64, BridgeError> {
+    let entries = fs::read_dir(metadata_dir).map_err(|e| {
+        BridgeError::Execution(format!(
+            "Failed to list Iceberg metadata directory '{}': {}",
+            metadata_dir.display(),
+            e
+        ))
+    })?;
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            name.strip_prefix('v')?.strip_suffix(".metadata.json")?.parse::<u64>().ok()
+        })
+        .max()
+        .ok_or_else(|| {
+            BridgeError::Execution(format!(
+                "No Iceberg metadata files found in '{}'",
+                metadata_dir.display()
+            ))
+        })
+}
+
+fn data_files(
+    scan: &proto::IcebergScan,
+    metadata_path: &str,
+    conditions: &[Condition],
+) -> Result<Vec<PlPath>, BridgeError> {
+    let file = open_local(metadata_path)?;
+    let metadata: serde_json::Value = serde_json::from_reader(file).map_err(|e| {
+        BridgeError::Execution(format!("Invalid Iceberg metadata '{}': {}", metadata_path, e))
+    })?;
+
+    let snapshot_id = match scan.snapshot_id {
+        Some(id) => id,
+        None => match metadata["current-snapshot-id"].as_i64() {
+            Some(id) if id >= 0 => id,
+            // 还没有任何快照的空表
+            _ => return Ok(Vec::new()),
+        },
+    };
+    let snapshot = metadata["snapshots"]
+        .as_array()
+        .and_then(|snapshots| {
+            snapshots
+                .iter()
+                .find(|s| s["snapshot-id"].as_i64() == Some(snapshot_id))
+        })
+        .ok_or_else(|| {
+            BridgeError::InvalidArgument(format!("Iceberg snapshot {} not found", snapshot_id))
+        })?;
+    let manifest_list = snapshot["manifest-list"].as_str().ok_or_else(|| {
+        BridgeError::Unsupported(format!(
+            "Iceberg snapshot {} has no manifest-list (format v1 inline manifests)",
+            snapshot_id
+        ))
+    })?;
+
+    let table = TableLayout::new(&metadata, snapshot);
+    let manifests = read_avro(manifest_list)?;
+    let spec_ids = manifests
+        .column("partition_spec_id")
+        .and_then(|c| c.cast(&DataType::Int64))
+        .ok();
+    let summaries = manifests.column("partitions").ok();
+
+    let mut files = Vec::new();
+    for (row, manifest) in string_values(&manifests, "manifest_path")?.into_iter().enumerate() {
+        let spec = spec_ids
+            .as_ref()
+            .and_then(|ids| ids.i64().ok()?.get(row))
+            .and_then(|id| table.specs.get(&id))
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let summary = summaries
+            .and_then(|column| column.get(row).ok())
+            .map(|value| partition_summary_bounds(&value, spec, &table))
+            .unwrap_or_default();
+        if !may_match(conditions, &summary) {
+            continue;
+        }
+
+        let entries = read_avro(&manifest)?;
+        let status = entries
+            .column("status")
+            .and_then(|c| c.cast(&DataType::Int32))
+            .map_err(|e| manifest_error(&manifest, e))?;
+        let data_file = entries
+            .column("data_file")
+            .and_then(|c| c.struct_().cloned())
+            .map_err(|e| manifest_error(&manifest, e))?;
+
+        if let Ok(content) = data_file.field_by_name("content") {
+            let content = content
+                .cast(&DataType::Int32)
+                .map_err(|e| manifest_error(&manifest, e))?;
+            let content = content.i32().map_err(|e| manifest_error(&manifest, e))?;
+            if content.into_iter().any(|c| c.unwrap_or(0) != 0) {
+                return Err(BridgeError::Unsupported(format!(
+                    "Iceberg delete files are not supported (manifest '{}')",
+                    manifest
+                )));
+            }
+        }
+
+        let paths = data_file
+            .field_by_name("file_path")
+            .map_err(|e| manifest_error(&manifest, e))?;
+        let paths = paths.str().map_err(|e| manifest_error(&manifest, e))?;
+        let status = status.i32().map_err(|e| manifest_error(&manifest, e))?;
+        for (row, (path, status)) in paths.into_iter().zip(status).enumerate() {
+            let (Some(path), Some(status)) = (path, status) else {
+                continue;
+            };
+            if status == STATUS_DELETED {
+                continue;
+            }
+            if !conditions.is_empty() && !may_match(conditions, &file_bounds(&data_file, row, spec, &table)) {
+                continue;
+            }
+            files.push(PlPath::new(local_path(path)));
+        }
+    }
+
+    Ok(files)
+}
+
+/// 表的列与分区布局（取快照对应的 schema）
+struct TableLayout {
+    // 字段 ID -> (列名, Iceberg 类型)
+    columns: HashMap<i64, (String, String)>,
+    // 分区规范 ID -> 分区字段
+    specs: HashMap<i64, Vec<PartitionField>>,
+}
+
+struct PartitionField {
+    name: String,
+    source_id: i64,
+    identity: bool,
+}
+
+impl TableLayout {
+    fn new(metadata: &serde_json::Value, snapshot: &serde_json::Value) -> Self {
+        // v2 元数据有多个 schema，v1 只有单个 schema 字段
+        let schema_id = snapshot["schema-id"]
+            .as_i64()
+            .or_else(|| metadata["current-schema-id"].as_i64());
+        let schema = metadata["schemas"]
+            .as_array()
+            .and_then(|schemas| {
+                schemas
+                    .iter()
+                    .find(|s| schema_id.is_none() || s["schema-id"].as_i64() == schema_id)
+            })
+            .unwrap_or(&metadata["schema"]);
+        let columns = schema["fields"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|field| {
+                // 只用到原始类型的顶层列，嵌套类型的 type 是对象
+                let id = field["id"].as_i64()?;
+                let name = field["name"].as_str()?.to_string();
+                let dtype = field["type"].as_str()?.to_string();
+                Some((id, (name, dtype)))
+            })
+            .collect();
+
+        let specs = match metadata["partition-specs"].as_array() {
+            Some(specs) => specs
+                .iter()
+                .filter_map(|spec| Some((spec["spec-id"].as_i64()?, partition_fields(&spec["fields"]))))
+                .collect(),
+            None => HashMap::from([(0, partition_fields(&metadata["partition-spec"]))]),
+        };
+
+        TableLayout { columns, specs }
+    }
+
+    fn column(&self, id: i64) -> Option<&(String, String)> {
+        self.columns.get(&id)
+    }
+}
+
+fn partition_fields(fields: &serde_json::Value) -> Vec<PartitionField> {
+    fields
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|field| {
+            Some(PartitionField {
+                name: field["name"].as_str()?.to_string(),
+                source_id: field["source-id"].as_i64()?,
+                identity: field["transform"].as_str() == Some("identity"),
+            })
+        })
+        .collect()
+}
+
+/// 裁剪用的边界值
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Bool(bool),
+}
+
+impl Value {
+    fn compare(&self, other: &Value) -> Option<Ordering> {
+        match (self, other) {
+            (Value::Int(a), Value::Int(b)) => Some(a.cmp(b)),
+            (Value::Float(a), Value::Float(b)) => a.partial_cmp(b),
+            (Value::Int(a), Value::Float(b)) => (*a as f64).partial_cmp(b),
+            (Value::Float(a), Value::Int(b)) => a.partial_cmp(&(*b as f64)),
+            (Value::Str(a), Value::Str(b)) => Some(a.cmp(b)),
+            (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+            _ => None,
+        }
+    }
+
+    // Iceberg 单值二进制编码（小端）
+    fn from_bound(bytes: &[u8], dtype: &str) -> Option<Value> {
+        match dtype {
+            "int" | "date" => Some(Value::Int(i32::from_le_bytes(bytes.try_into().ok()?) as i64)),
+            "long" | "time" | "timestamp" | "timestamptz" | "timestamp_ns" | "timestamptz_ns" => {
+                Some(Value::Int(i64::from_le_bytes(bytes.try_into().ok()?)))
+            }
+            "float" => Some(Value::Float(f32::from_le_bytes(bytes.try_into().ok()?) as f64)),
+            "double" => Some(Value::Float(f64::from_le_bytes(bytes.try_into().ok()?))),
+            "string" => Some(Value::Str(std::str::from_utf8(bytes).ok()?.to_string())),
+            "boolean" => Some(Value::Bool(*bytes.first()? != 0)),
+            _ => None,
+        }
+    }
+
+    fn from_any_value(value: &AnyValue) -> Option<Value> {
+        match value {
+            AnyValue::Boolean(v) => Some(Value::Bool(*v)),
+            AnyValue::String(v) => Some(Value::Str(v.to_string())),
+            AnyValue::StringOwned(v) => Some(Value::Str(v.to_string())),
+            AnyValue::Float32(v) => Some(Value::Float(*v as f64)),
+            AnyValue::Float64(v) => Some(Value::Float(*v)),
+            AnyValue::Date(v) => Some(Value::Int(*v as i64)),
+            AnyValue::Datetime(v, _, _) | AnyValue::DatetimeOwned(v, _, _) => Some(Value::Int(*v)),
+            value if value.is_integer() => value.extract::<i64>().map(Value::Int),
+            _ => None,
+        }
+    }
+}
+
+/// 谓词中可用于裁剪的一个合取项：`列 op 字面量`
+struct Condition {
+    column: String,
+    op: proto::BinaryOperator,
+    value: Value,
+}
+
+impl Condition {
+    // 值域 [lower, upper] 内是否可能有满足条件的行；缺少边界时保守地返回 true
+    fn may_match(&self, lower: Option<&Value>, upper: Option<&Value>) -> bool {
+        use proto::BinaryOperator as Op;
+
+        let cmp = |bound: Option<&Value>| bound.and_then(|b| b.compare(&self.value));
+        match self.op {
+            Op::Eq => !matches!(cmp(lower), Some(Ordering::Greater)) && !matches!(cmp(upper), Some(Ordering::Less)),
+            Op::Lt => !matches!(cmp(lower), Some(Ordering::Greater | Ordering::Equal)),
+            Op::Le => !matches!(cmp(lower), Some(Ordering::Greater)),
+            Op::Gt => !matches!(cmp(upper), Some(Ordering::Less | Ordering::Equal)),
+            Op::Ge => !matches!(cmp(upper), Some(Ordering::Less)),
+            _ => true,
+        }
+    }
+}
+
+// 列名 -> (下界, 上界)
+type Bounds = HashMap<String, (Option<Value>, Option<Value>)>;
+
+fn may_match(conditions: &[Condition], bounds: &Bounds) -> bool {
+    conditions.iter().all(|condition| match bounds.get(&condition.column) {
+        Some((lower, upper)) => condition.may_match(lower.as_ref(), upper.as_ref()),
+        None => true,
+    })
+}
+
+// 拆出 AND 连接的 `列 op 字面量` 比较；其它形式的子表达式不参与裁剪
+fn conditions(expr: &proto::Expr) -> Vec<Condition> {
+    use proto::expr::Kind;
+    use proto::BinaryOperator as Op;
+
+    let Some(Kind::Binary(binary)) = expr.kind.as_ref() else {
+        return Vec::new();
+    };
+    let (Some(left), Some(right)) = (binary.left.as_deref(), binary.right.as_deref()) else {
+        return Vec::new();
+    };
+    let Ok(op) = Op::try_from(binary.op) else {
+        return Vec::new();
+    };
+    if op == Op::And {
+        let mut out = conditions(left);
+        out.extend(conditions(right));
+        return out;
+    }
+
+    // 字面量在左侧时交换操作数
+    let (column, value, op) = match (column_name(left), literal_value(right)) {
+        (Some(column), Some(value)) => (column, value, op),
+        _ => match (column_name(right), literal_value(left)) {
+            (Some(column), Some(value)) => {
+                let flipped = match op {
+                    Op::Lt => Op::Gt,
+                    Op::Le => Op::Ge,
+                    Op::Gt => Op::Lt,
+                    Op::Ge => Op::Le,
+                    op => op,
+                };
+                (column, value, flipped)
+            }
+            _ => return Vec::new(),
+        },
+    };
+    match op {
+        Op::Eq | Op::Lt | Op::Le | Op::Gt | Op::Ge => vec![Condition { column, op, value }],
+        _ => Vec::new(),
+    }
+}
+
+fn column_name(expr: &proto::Expr) -> Option<String> {
+    match expr.kind.as_ref()? {
+        proto::expr::Kind::Col(col) => Some(col.name.clone()),
+        _ => None,
+    }
+}
+
+fn literal_value(expr: &proto::Expr) -> Option<Value> {
+    use proto::literal::Value as Lit;
+
+    let proto::expr::Kind::Lit(lit) = expr.kind.as_ref()? else {
+        return None;
+    };
+    match lit.value.as_ref()? {
+        Lit::IntVal(v) => Some(Value::Int(*v)),
+        Lit::FloatVal(v) => Some(Value::Float(*v)),
+        Lit::BoolVal(v) => Some(Value::Bool(*v)),
+        Lit::StringVal(v) => Some(Value::Str(v.clone())),
+        _ => None,
+    }
+}
+
+// manifest list 的分区摘要（与分区规范的字段一一对应）；只有 identity 分区的边界就是源列的边界
+fn partition_summary_bounds(value: &AnyValue, spec: &[PartitionField], table: &TableLayout) -> Bounds {
+    let mut bounds = Bounds::new();
+    let AnyValue::List(summaries) = value else {
+        return bounds;
+    };
+    let Ok(summaries) = summaries.struct_() else {
+        return bounds;
+    };
+    let (Ok(lower), Ok(upper)) = (summaries.field_by_name("lower_bound"), summaries.field_by_name("upper_bound")) else {
+        return bounds;
+    };
+    let (Ok(lower), Ok(upper)) = (lower.binary(), upper.binary()) else {
+        return bounds;
+    };
+
+    for (field, (lower, upper)) in spec.iter().zip(lower.into_iter().zip(upper)) {
+        let Some((name, dtype)) = table.column(field.source_id).filter(|_| field.identity) else {
+            continue;
+        };
+        bounds.insert(
+            name.clone(),
+            (
+                lower.and_then(|b| Value::from_bound(b, dtype)),
+                upper.and_then(|b| Value::from_bound(b, dtype)),
+            ),
+        );
+    }
+    bounds
+}
+
+// 数据文件的列上下界（lower_bounds / upper_bounds，按字段 ID），identity 分区值同时作为上下界
+fn file_bounds(data_file: &StructChunked, row: usize, spec: &[PartitionField], table: &TableLayout) -> Bounds {
+    let lower = bound_map(data_file, "lower_bounds", row);
+    let upper = bound_map(data_file, "upper_bounds", row);
+    let mut bounds = Bounds::new();
+    for (id, (name, dtype)) in &table.columns {
+        let lower = lower.get(id).and_then(|b| Value::from_bound(b, dtype));
+        let upper = upper.get(id).and_then(|b| Value::from_bound(b, dtype));
+        if lower.is_some() || upper.is_some() {
+            bounds.insert(name.clone(), (lower, upper));
+        }
+    }
+
+    let Ok(partition) = data_file.field_by_name("partition") else {
+        return bounds;
+    };
+    let Ok(partition) = partition.struct_() else {
+        return bounds;
+    };
+    for field in spec.iter().filter(|field| field.identity) {
+        let Some((name, _)) = table.column(field.source_id) else {
+            continue;
+        };
+        let value = partition
+            .field_by_name(&field.name)
+            .ok()
+            .and_then(|values| values.get(row).ok().as_ref().and_then(Value::from_any_value));
+        if let Some(value) = value {
+            bounds.insert(name.clone(), (Some(value.clone()), Some(value)));
+        }
+    }
+    bounds
+}
+
+// Avro 中非字符串键的 map 存为 {key, value} 记录数组
+fn bound_map(data_file: &StructChunked, name: &str, row: usize) -> HashMap<i64, Vec<u8>> {
+    let mut out = HashMap::new();
+    let Ok(column) = data_file.field_by_name(name) else {
+        return out;
+    };
+    let Ok(AnyValue::List(entries)) = column.get(row) else {
+        return out;
+    };
+    let Ok(entries) = entries.struct_() else {
+        return out;
+    };
+    let (Ok(keys), Ok(values)) = (entries.field_by_name("key"), entries.field_by_name("value")) else {
+        return out;
+    };
+    let (Ok(keys), Ok(values)) = (keys.cast(&DataType::Int64), values.binary().cloned()) else {
+        return out;
+    };
+    let Ok(keys) = keys.i64() else {
+        return out;
+    };
+    for (key, value) in keys.into_iter().zip(&values) {
+        if let (Some(key), Some(value)) = (key, value) {
+            out.insert(key, value.to_vec());
+        }
+    }
+    out
+}
+
+fn read_avro(path: &str) -> Result<DataFrame, BridgeError> {
+    AvroReader::new(open_local(path)?)
+        .finish()
+        .map_err(|e| BridgeError::Execution(format!("Failed to read Iceberg manifest '{}': {}", path, e)))
+}
+
+fn string_values(df: &DataFrame, name: &str) -> Result<Vec<String>, BridgeError> {
+    let column = df
+        .column(name)
+        .and_then(|c| c.str().cloned())
+        .map_err(|e| BridgeError::Execution(format!("Invalid Iceberg manifest list: {}", e)))?;
+    Ok(column.into_iter().flatten().map(str::to_string).collect())
+}
+
+fn manifest_error(manifest: &str, e: PolarsError) -> BridgeError {
+    BridgeError::Execution(format!("Invalid Iceberg manifest '{}': {}", manifest, e))
+}
+
+fn open_local(path: &str) -> Result<File, BridgeError> {
+    let local = local_path(path);
+    if local.contains("://") {
+        return Err(BridgeError::Unsupported(format!(
+            "IcebergScan can only read metadata from the local filesystem: '{}'",
+            path
+        )));
+    }
+    File::open(local).map_err(|e| BridgeError::Execution(format!("Failed to open '{}': {}", path, e)))
+}
@@ -0,0 +1,314 @@
+use polars::functions::concat_df_diagonal;
+use polars::prelude::*;
+
+use crate::error::BridgeError;
+use crate::executor::{self, BuildContext};
+use crate::proto;
+
+/// 增量聚合：GroupBy Plan 按批次喂入数据，状态中只保留每组的部分聚合结果
+///
+/// 只支持可合并的聚合（Sum / Count / Len / Min / Max / Mean / First / Last，可带 Alias）；
+/// 分组按首次出现的顺序输出。
+pub struct IncrementalAgg {
+    plan: proto::Plan,
+    aggs: Vec<PartialAgg>,
+    state: Option<DataFrame>,
+}
+
+// 一个输出聚合对应的部分状态列（Mean 拆为 sum 与 count 两列）
+struct PartialAgg {
+    name: PlSmallStr,
+    kind: PartialKind,
+    partials: Vec<(PlSmallStr, proto::Expr)>,
+}
+
+#[derive(Clone, Copy)]
+enum PartialKind {
+    Sum,
+    Count,
+    Min,
+    Max,
+    Mean,
+    First,
+    Last,
+}
+
+impl IncrementalAgg {
+    pub fn new(plan: proto::Plan) -> Result<Self, BridgeError> {
+        let group_by = root_group_by(&plan)?;
+        if group_by.input.is_none() {
+            return Err(BridgeError::PlanSemantic("GroupBy has no input".into()));
+        }
+        if group_by.keys.is_empty() {
+            return Err(BridgeError::PlanSemantic("GroupBy has no keys".into()));
+        }
+
+        let aggs = group_by.aggs.iter()
+            .enumerate()
+            .map(|(idx, agg)| partial_agg(idx, agg))
+            .collect::<Result<_, _>>()?;
+
+        Ok(IncrementalAgg { plan, aggs, state: None })
+    }
+
+    pub fn plan(&self) -> &proto::Plan {
+        &self.plan
+    }
+
+    /// 喂入一个批次：执行 GroupBy 的输入子树，把本批的部分聚合合并进状态
+    pub fn update(&mut self, batch: &DataFrame) -> Result<(), BridgeError> {
+        let group_by = root_group_by(&self.plan)?;
+        let input_node = group_by.input.as_ref()
+            .ok_or_else(|| BridgeError::PlanSemantic("GroupBy has no input".into()))?;
+
+        let ctx = BuildContext::new(&self.plan, Some(batch));
+        let mut lf = executor::build_lazy_frame(input_node, &ctx)?;
+
+        let keys = executor::build_node_exprs(&mut lf, &group_by.keys, &ctx)?;
+        let partial_exprs: Vec<proto::Expr> = self.aggs.iter()
+            .flat_map(|agg| agg.partials.iter().map(|(_, expr)| expr.clone()))
+            .collect();
+        let partials = executor::build_node_exprs(&mut lf, &partial_exprs, &ctx)?
+            .into_iter()
+            .zip(self.partial_names())
+            .map(|(expr, name)| expr.alias(name))
+            .collect::<Vec<_>>();
+
+        if group_by.drop_null_keys {
+            let not_null = keys.iter()
+                .map(|k| k.clone().is_not_null())
+                .reduce(|acc, e| acc.and(e));
+            if let Some(predicate) = not_null {
+                lf = lf.filter(predicate);
+            }
+        }
+
+        let batch_state = lf.group_by_stable(keys)
+            .agg(partials)
+            .collect()
+            .map_err(|e| BridgeError::Execution("Failed to aggregate batch".into()).with_source(e))?;
+
+        let state = match self.state.take() {
+            Some(state) => self.merge(state, batch_state)?,
+            None => batch_state,
+        };
+        self.state = Some(state);
+        Ok(())
+    }
+
+    /// 按当前状态计算聚合结果；状态保留，之后还可以继续喂入批次
+    pub fn finalize(&self) -> Result<DataFrame, BridgeError> {
+        let Some(state) = self.state.as_ref() else {
+            return Ok(DataFrame::empty());
+        };
+
+        let exprs: Vec<Expr> = self.key_names(state)
+            .into_iter()
+            .map(col)
+            .chain(self.aggs.iter().map(final_expr))
+            .collect();
+        state.clone()
+            .lazy()
+            .select(exprs)
+            .collect()
+            .map_err(|e| BridgeError::Execution("Failed to finalize aggregation".into()).with_source(e))
+    }
+
+    // 已有状态在前、本批在后，保证 First / Last 与分组顺序和整体执行一致
+    fn merge(&self, state: DataFrame, batch_state: DataFrame) -> Result<DataFrame, BridgeError> {
+        let keys: Vec<Expr> = self.key_names(&state).into_iter().map(col).collect();
+        let merges: Vec<Expr> = self.aggs.iter()
+            .flat_map(|agg| agg.partials.iter().map(move |(name, _)| merge_expr(agg.kind, name)))
+            .collect();
+
+        concat_df_diagonal(&[state, batch_state])
+            .and_then(|combined| combined.lazy().group_by_stable(keys).agg(merges).collect())
+            .map_err(|e| BridgeError::Execution("Failed to merge aggregation state".into()).with_source(e))
+    }
+
+    // 状态的前几列是分组键，之后是部分聚合列
+    fn key_names(&self, state: &DataFrame) -> Vec<PlSmallStr> {
+        let n_partials = self.aggs.iter().map(|agg| agg.partials.len()).sum::<usize>();
+        let names = state.get_column_names_owned();
+        names[..names.len() - n_partials].to_vec()
+    }
+
+    fn partial_names(&self) -> impl Iterator<Item = PlSmallStr> + '_ {
+        self.aggs.iter().flat_map(|agg| agg.partials.iter().map(|(name, _)| name.clone()))
+    }
+}
+
+fn root_group_by(plan: &proto::Plan) -> Result<&proto::GroupBy, BridgeError> {
+    match executor::plan_root(plan)?.kind.as_ref() {
+        Some(proto::node::Kind::GroupBy(group_by)) => Ok(group_by),
+        _ => Err(BridgeError::PlanSemantic(
+            "Incremental aggregation requires a GroupBy root node".into(),
+        )),
+    }
+}
+
+fn partial_agg(idx: usize, agg: &proto::Expr) -> Result<PartialAgg, BridgeError> {
+    use proto::expr::Kind;
+
+    let (expr, alias) = match agg.kind.as_ref() {
+        Some(Kind::Alias(alias)) => {
+            let expr = alias.expr.as_deref()
+                .ok_or_else(|| BridgeError::PlanSemantic("Alias has no expr".into()))?;
+            (expr, Some(PlSmallStr::from(alias.name.as_str())))
+        }
+        _ => (agg, None),
+    };
+
+    let kind = match expr.kind.as_ref() {
+        Some(Kind::Sum(_)) => PartialKind::Sum,
+        Some(Kind::Count(_)) | Some(Kind::Len(_)) => PartialKind::Count,
+        Some(Kind::Min(_)) => PartialKind::Min,
+        Some(Kind::Max(_)) => PartialKind::Max,
+        Some(Kind::Mean(_)) => PartialKind::Mean,
+        Some(Kind::First(_)) => PartialKind::First,
+        Some(Kind::Last(_)) => PartialKind::Last,
+        _ => {
+            return Err(BridgeError::Unsupported(format!(
+                "GroupBy aggregation #{} cannot be computed incrementally \
+                 (supported: Sum, Count, Len, Min, Max, Mean, First, Last)",
+                idx
+            )))
+        }
+    };
+
+    let name = match alias {
+        Some(name) => name,
+        None => executor::build_expr(agg)?
+            .meta()
+            .output_name()
+            .map_err(|e| {
+                BridgeError::PlanSemantic(format!("GroupBy aggregation #{} has no output name", idx))
+                    .with_source(e)
+            })?,
+    };
+
+    let column = |part: usize| PlSmallStr::from(format!("__incremental_{}_{}", idx, part));
+    let partials = match (kind, expr.kind.as_ref()) {
+        (PartialKind::Mean, Some(Kind::Mean(func))) => vec![
+            (column(0), agg_expr(Kind::Sum(func.clone()))),
+            (column(1), agg_expr(Kind::Count(func.clone()))),
+        ],
+        _ => vec![(column(0), expr.clone())],
+    };
+
+    Ok(PartialAgg { name, kind, partials })
+}
+
+fn agg_expr(kind: proto::expr::Kind) -> proto::Expr {
+    proto::Expr { kind: Some(kind) }
+}
+
+// 合并同一分组的多行部分结果：计数类求和，其余按同类聚合
+fn merge_expr(kind: PartialKind, name: &PlSmallStr) -> Expr {
+    let partial = col(name.clone());
+    match kind {
+        PartialKind::Sum | PartialKind::Count | PartialKind::Mean => partial.sum(),
+        PartialKind::Min => partial.min(),
+        PartialKind::Max => partial.max(),
+        PartialKind::First => partial.first(),
+        PartialKind::Last => partial.last(),
+    }
+    .alias(name.clone())
+}
+
+// 全为 null 的分组 count 为 0，与一次性 GroupBy 一样输出 null 而不是 NaN
+fn final_expr(agg: &PartialAgg) -> Expr {
+    let partial = |i: usize| col(agg.partials[i].0.clone());
+    match agg.kind {
+        PartialKind::Mean => when(partial(1).gt(lit(0)))
+            .then(partial(0).cast(DataType::Float64) / partial(1).cast(DataType::Float64))
+            .otherwise(lit(NULL).cast(DataType::Float64))
+            .alias(agg.name.clone()),
+        _ => partial(0).alias(agg.name.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use polars::prelude::{df, DataFrame, DataType};
+
+    use super::IncrementalAgg;
+    use crate::error::BridgeError;
+    use crate::executor;
+    use crate::proto;
+    use crate::test_plans::*;
+    use proto::expr::Kind as E;
+    use proto::node::Kind as N;
+
+    fn group_by_plan(drop_null_keys: bool) -> proto::Plan {
+        let aggs = vec![
+            alias(expr(E::Sum(agg(col("v")))), "sum"),
+            alias(expr(E::Count(agg(col("v")))), "count"),
+            alias(expr(E::Len(proto::Len {})), "len"),
+            alias(expr(E::Min(agg(col("v")))), "min"),
+            alias(expr(E::Max(agg(col("v")))), "max"),
+            alias(expr(E::Mean(agg(col("v")))), "mean"),
+            alias(expr(E::First(agg(col("v")))), "first"),
+            alias(expr(E::Last(agg(col("v")))), "last"),
+        ];
+        plan(node(2, N::GroupBy(Box::new(proto::GroupBy {
+            input: input(memory_scan()),
+            keys: vec![col("k")],
+            aggs,
+            maintain_order: true,
+            drop_null_keys,
+        }))))
+    }
+
+    // 分组 "c" 的值全为 null；null 键也是一个分组；First / Last 跨批次
+    fn data() -> DataFrame {
+        df!(
+            "k" => [Some("a"), Some("b"), None, Some("c"), Some("a"), Some("b"), Some("c"), None, Some("a")],
+            "v" => [Some(1i64), Some(10), Some(5), None, Some(3), None, None, Some(7), Some(2)],
+        )
+        .unwrap()
+    }
+
+    fn incremental(plan: proto::Plan, batches: &[(i64, usize)]) -> DataFrame {
+        let data = data();
+        let mut agg = IncrementalAgg::new(plan).unwrap();
+        for &(offset, len) in batches {
+            agg.update(&data.slice(offset, len)).unwrap();
+        }
+        agg.finalize().unwrap()
+    }
+
+    #[test]
+    fn matches_one_shot_group_by() {
+        for drop_null_keys in [false, true] {
+            let plan = group_by_plan(drop_null_keys);
+            let expected = executor::execute_plan_df(&plan, Some(&data())).unwrap();
+            for batches in [&[(0, 9)][..], &[(0, 2), (2, 3), (5, 4)], &[(0, 1), (1, 1), (2, 7)]] {
+                let actual = incremental(plan.clone(), batches);
+                assert!(
+                    actual.equals_missing(&expected),
+                    "drop_null_keys={drop_null_keys} batches={batches:?}\n{actual}\n{expected}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn mean_of_all_null_group_is_null() {
+        let result = incremental(group_by_plan(true), &[(0, 4), (4, 5)]);
+        let mean = result.column("mean").unwrap();
+        assert_eq!(mean.dtype(), &DataType::Float64);
+        // 分组顺序 a, b, c
+        assert_eq!(mean.null_count(), 1);
+        assert!(mean.get(2).unwrap().is_null());
+    }
+
+    #[test]
+    fn rejects_non_mergeable_aggregation() {
+        let mut plan = group_by_plan(false);
+        if let Some(N::GroupBy(group_by)) = plan.root.as_mut().and_then(|root| root.kind.as_mut()) {
+            group_by.aggs = vec![expr(E::Median(agg(col("v"))))];
+        }
+        assert!(matches!(IncrementalAgg::new(plan), Err(BridgeError::Unsupported(_))));
+    }
+}
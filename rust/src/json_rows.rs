@@ -0,0 +1,140 @@
+use polars::prelude::*;
+use serde_json::{Map, Number, Value};
+
+use crate::error::BridgeError;
+use crate::proto;
+
+const MS_PER_DAY: i64 = 86_400_000;
+
+/// 将 DataFrame 序列化为 JSON 行数组：`[{"a": 1, "b": [..]}, ...]`
+///
+/// 支持嵌套的 List/Array/Struct；时间类型按选项输出为字符串或毫秒时间戳。
+/// Decimal 以字符串输出以保证精度，NaN/Inf 输出为 null。
+pub fn df_to_json_rows(
+    df: &DataFrame,
+    options: &proto::JsonRowOptions,
+) -> Result<Vec<u8>, BridgeError> {
+    let format = proto::TemporalFormat::try_from(options.temporal_format).map_err(|_| {
+        BridgeError::Unsupported(format!("Unknown temporal format: {}", options.temporal_format))
+    })?;
+    let df = apply_custom_formats(df, options, format)?;
+
+    let names: Vec<&str> = df.get_column_names().into_iter().map(|n| n.as_str()).collect();
+    let mut rows: Vec<Map<String, Value>> = (0..df.height())
+        .map(|_| Map::with_capacity(names.len()))
+        .collect();
+
+    for (name, column) in names.iter().zip(df.get_columns()) {
+        for (row, value) in rows.iter_mut().zip(column.as_materialized_series().iter()) {
+            row.insert(name.to_string(), to_json(&value, format));
+        }
+    }
+
+    let rows: Vec<Value> = rows.into_iter().map(Value::Object).collect();
+    serde_json::to_vec(&rows)
+        .map_err(|e| BridgeError::Execution(format!("Failed to serialize rows to JSON: {}", e)))
+}
+
+// 自定义 strftime 格式只作用于顶层的 Date/Datetime 列，嵌套值仍使用默认字符串格式
+fn apply_custom_formats(
+    df: &DataFrame,
+    options: &proto::JsonRowOptions,
+    format: proto::TemporalFormat,
+) -> Result<DataFrame, BridgeError> {
+    if format != proto::TemporalFormat::String
+        || (options.datetime_format.is_empty() && options.date_format.is_empty())
+    {
+        return Ok(df.clone());
+    }
+
+    let exprs: Vec<Expr> = df
+        .get_columns()
+        .iter()
+        .map(|c| {
+            let e = col(c.name().clone());
+            match c.dtype() {
+                DataType::Datetime(_, _) if !options.datetime_format.is_empty() => {
+                    e.dt().to_string(&options.datetime_format)
+                }
+                DataType::Date if !options.date_format.is_empty() => {
+                    e.dt().to_string(&options.date_format)
+                }
+                _ => e,
+            }
+        })
+        .collect();
+
+    df.clone()
+        .lazy()
+        .select(exprs)
+        .collect()
+        .map_err(|e| BridgeError::Execution(format!("Failed to format temporal columns: {}", e)))
+}
+
+fn to_json(value: &AnyValue, format: proto::TemporalFormat) -> Value {
+    use proto::TemporalFormat;
+
+    match value {
+        AnyValue::Null => Value::Null,
+        AnyValue::Boolean(v) => Value::Bool(*v),
+        AnyValue::Int8(v) => Value::from(*v),
+        AnyValue::Int16(v) => Value::from(*v),
+        AnyValue::Int32(v) => Value::from(*v),
+        AnyValue::Int64(v) => Value::from(*v),
+        AnyValue::UInt8(v) => Value::from(*v),
+        AnyValue::UInt16(v) => Value::from(*v),
+        AnyValue::UInt32(v) => Value::from(*v),
+        AnyValue::UInt64(v) => Value::from(*v),
+        AnyValue::Float32(v) => float(*v as f64),
+        AnyValue::Float64(v) => float(*v),
+        AnyValue::String(v) => Value::String(v.to_string()),
+        AnyValue::StringOwned(v) => Value::String(v.to_string()),
+        AnyValue::Binary(v) => Value::from(v.to_vec()),
+        AnyValue::BinaryOwned(v) => Value::from(v.clone()),
+        AnyValue::List(series) | AnyValue::Array(series, _) => {
+            Value::Array(series.iter().map(|v| to_json(&v, format)).collect())
+        }
+        AnyValue::Struct(_, _, fields) => {
+            let values: Vec<AnyValue> = value._iter_struct_av().collect();
+            struct_to_json(fields, &values, format)
+        }
+        AnyValue::StructOwned(payload) => struct_to_json(&payload.1, &payload.0, format),
+        AnyValue::Date(days) if format == TemporalFormat::EpochMillis => {
+            Value::from(*days as i64 * MS_PER_DAY)
+        }
+        AnyValue::Datetime(v, unit, _) | AnyValue::Duration(v, unit)
+            if format == TemporalFormat::EpochMillis =>
+        {
+            Value::from(to_millis(*v, *unit))
+        }
+        AnyValue::DatetimeOwned(v, unit, _) if format == TemporalFormat::EpochMillis => {
+            Value::from(to_millis(*v, *unit))
+        }
+        AnyValue::Time(ns) if format == TemporalFormat::EpochMillis => {
+            Value::from(*ns / 1_000_000)
+        }
+        // 其余类型（时间、Decimal、Categorical 等）使用 Polars 的显示格式
+        other => Value::String(other.to_string()),
+    }
+}
+
+fn struct_to_json(fields: &[Field], values: &[AnyValue], format: proto::TemporalFormat) -> Value {
+    let object = fields
+        .iter()
+        .zip(values)
+        .map(|(field, value)| (field.name.to_string(), to_json(value, format)))
+        .collect();
+    Value::Object(object)
+}
+
+fn float(v: f64) -> Value {
+    Number::from_f64(v).map(Value::Number).unwrap_or(Value::Null)
+}
+
+fn to_millis(v: i64, unit: TimeUnit) -> i64 {
+    match unit {
+        TimeUnit::Nanoseconds => v / 1_000_000,
+        TimeUnit::Microseconds => v / 1_000,
+        TimeUnit::Milliseconds => v,
+    }
+}
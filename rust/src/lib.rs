@@ -1,13 +1,19 @@
+// 导出函数以 C ABI 供 Go 调用，指针参数的有效性由调用约定保证（见下方内存所有权规则）
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
 use std::cell::RefCell;
-use std::ffi::CString;
+use std::ffi::{c_void, CString};
 use std::os::raw::{c_char, c_int};
 use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::ptr;
 use std::slice;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Instant;
 use prost::Message;
-use polars::prelude::{DataFrame, Series, AnyValue, IntoLazy, NamedFrom};
-use polars::series::SeriesIter;
+use polars::prelude::{DataFrame, Series, AnyValue, PlSmallStr};
 
+// prost 生成的代码不做 lint
+#[allow(clippy::all)]
 mod proto {
     include!("proto/polars_bridge.rs");
 }
@@ -15,21 +21,124 @@ mod proto {
 mod executor;
 mod error;
 mod arrow_bridge;
+#[cfg(feature = "strings")]
 mod expr_str;
+mod expr_agg;
+mod expr_ext;
+mod expr_cmp;
+mod expr_num;
+mod expr_fill;
+mod expr_shift;
+mod expr_index;
+mod expr_subset;
+mod expr_counts;
+mod expr_horizontal;
+#[cfg(feature = "window")]
+mod expr_rolling;
+mod columns;
+mod sink;
+mod handshake;
+mod engine_info;
+mod handles;
+mod writer;
+mod scan;
+mod cloud;
+mod schema;
+mod stats;
+mod nodes;
+mod explain;
+mod fingerprint;
+mod metadata;
+mod decimal;
+mod float_keys;
+mod upsert;
+mod dynamic_group;
+mod sorted;
+mod frame_builder;
+#[cfg(feature = "excel")]
+mod excel;
+mod trace;
+#[cfg(all(feature = "parquet", feature = "avro"))]
+mod iceberg;
+#[cfg(feature = "parquet")]
+mod delta;
+mod estimate;
+mod database;
+mod file_metadata;
+mod flight;
+mod json_rows;
+mod reader_scan;
+mod rows;
+mod batch_provider;
+#[cfg(feature = "strings")]
+mod regex_cache;
+mod diff;
+mod naming;
+mod buffer;
+mod session;
+mod incremental;
+mod checkpoint;
+mod conformance;
+mod recorder;
+mod stream;
+#[cfg(feature = "parquet")]
+mod parquet_columns;
+mod rename;
+mod assertion;
+mod abi;
+mod features;
+#[cfg(test)]
+mod test_plans;
+#[cfg(fuzzing)]
+pub mod fuzz;
+
+// 未编译的模块由 features 中的同名替代实现顶上
+#[cfg(not(feature = "strings"))]
+use features::{expr_str, regex_cache};
+#[cfg(not(feature = "window"))]
+use features::expr_rolling;
+#[cfg(not(feature = "excel"))]
+use features::excel;
+#[cfg(not(all(feature = "parquet", feature = "avro")))]
+use features::iceberg;
+#[cfg(not(feature = "parquet"))]
+use features::delta;
 
 use error::{BridgeError, ErrorCode};
+use columns::{dedup_column_names, DuplicatePolicy};
+use frame_builder::FrameBuilder;
 
 // ABI 版本
-const ABI_VERSION: u32 = 1;
+//
+// 2：bridge_last_error 改为返回 bridge 分配的缓冲区（调用方必须用 bridge_last_error_free 释放），
+//    1 返回的是线程局部存储中的指针（不能释放）；SDK 按版本决定是否释放，因此不兼容
+const ABI_VERSION: u32 = 2;
+
+// 内存所有权规则（Windows 上 DLL 与调用方可能使用不同的 C 运行时，macOS / Linux 同样适用）：
+// - bridge 分配的内存只能由 bridge 的释放函数释放（bridge_output_free / bridge_last_error_free /
+//   各类 *_free），调用方不能用自己的 free；
+// - 调用方传入的指针在调用期间借用，bridge 不会保存或释放它们（Arrow C Data Interface 的
+//   release 回调与 DeallocCallback 除外，它们由分配方自己实现）；
+// - bridge_engine_version / bridge_capabilities 返回静态字符串，不需要释放。
+
+// 导出函数的符号名为 BRIDGE_SYMBOL_PREFIX + 函数名（默认前缀为空，见 build.rs 与 versioned-symbols feature）
+//
+// 回调参数写成字面的 Option<extern "C" fn(..)>（与 reader_scan::ReadCallback 等类型相同，由 abi.rs 检查）：
+// cbindgen 不展开类型别名，Option<别名> 会被生成为不透明的结构体而不是可空的函数指针。
 
 // 线程局部错误存储
 thread_local! {
-    static LAST_ERROR: RefCell<Option<CString>> = RefCell::new(None);
+    static LAST_ERROR: RefCell<Option<String>> = const { RefCell::new(None) };
+    static LAST_ERROR_DETAIL: RefCell<Option<proto::ErrorDetail>> = const { RefCell::new(None) };
 }
 
-fn set_last_error(msg: String) {
+// 同时记录字符串形式（bridge_last_error）与结构化形式（bridge_last_error_detail）
+fn set_last_error(detail: proto::ErrorDetail) {
     LAST_ERROR.with(|e| {
-        *e.borrow_mut() = CString::new(msg).ok();
+        *e.borrow_mut() = Some(error::render_detail(&detail));
+    });
+    LAST_ERROR_DETAIL.with(|e| {
+        *e.borrow_mut() = Some(detail);
     });
 }
 
@@ -37,6 +146,9 @@ fn clear_last_error() {
     LAST_ERROR.with(|e| {
         *e.borrow_mut() = None;
     });
+    LAST_ERROR_DETAIL.with(|e| {
+        *e.borrow_mut() = None;
+    });
 }
 
 // 错误处理宏
@@ -44,13 +156,16 @@ macro_rules! ffi_guard {
     ($body:expr) => {
         match catch_unwind(AssertUnwindSafe(|| $body)) {
             Ok(Ok(v)) => {
+                trace::take_query_id();
                 clear_last_error();
                 v
             }
             Ok(Err(e)) => {
                 let (code, msg) = error::bridge_error_to_code(&e);
-                set_last_error(format!("[{}] {}", code, msg));
-                code as c_int
+                let code_value = code as c_int;
+                let causes = error::source_chain(&e);
+                set_last_error(error::error_detail(code, msg, causes, trace::take_query_id()));
+                code_value
             }
             Err(panic) => {
                 let msg = if let Some(s) = panic.downcast_ref::<&str>() {
@@ -60,7 +175,12 @@ macro_rules! ffi_guard {
                 } else {
                     "Unknown panic".to_string()
                 };
-                set_last_error(format!("[ERR_UNKNOWN] Panic: {}", msg));
+                set_last_error(error::error_detail(
+                    ErrorCode::Unknown,
+                    format!("Panic: {}", msg),
+                    Vec::new(),
+                    trace::take_query_id(),
+                ));
                 ErrorCode::Unknown as c_int
             }
         }
@@ -68,91 +188,182 @@ macro_rules! ffi_guard {
 }
 
 // 1. 版本与能力
-#[no_mangle]
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_abi_version")]
 pub extern "C" fn bridge_abi_version() -> u32 {
     ABI_VERSION
 }
 
-#[no_mangle]
+/// 协商协议版本，输出 HandshakeReport（protobuf 字节，需调用 bridge_output_free 释放）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_handshake")]
+pub extern "C" fn bridge_handshake(
+    client_abi: u32,
+    client_plan_version: u32,
+    output_ptr: *mut *mut u8,
+    output_len: *mut usize,
+) -> c_int {
+    ffi_guard!({
+        if output_ptr.is_null() || output_len.is_null() {
+            return Err(BridgeError::InvalidArgument("Null output pointers".into()));
+        }
+
+        let report = handshake::negotiate(ABI_VERSION, client_abi, client_plan_version);
+        write_output(report.encode_to_vec(), output_ptr, output_len);
+        Ok(0)
+    })
+}
+
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_engine_version")]
 pub extern "C" fn bridge_engine_version(ptr_out: *mut *const c_char, len_out: *mut usize) -> c_int {
     ffi_guard!({
         if ptr_out.is_null() || len_out.is_null() {
             return Err(BridgeError::InvalidArgument("Null output pointers".into()));
         }
         
-        let version = CString::new(env!("CARGO_PKG_VERSION")).unwrap();
+        // 静态字符串：调用方只读，不需要（也不能）释放
+        static VERSION: OnceLock<CString> = OnceLock::new();
+        let version = VERSION.get_or_init(|| CString::new(env!("CARGO_PKG_VERSION")).unwrap());
         unsafe {
             *ptr_out = version.as_ptr();
             *len_out = version.as_bytes().len();
         }
-        std::mem::forget(version);
         Ok(0)
     })
 }
 
-#[no_mangle]
+/// 输出 EngineInfo（protobuf 字节，需调用 bridge_output_free 释放）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_engine_info")]
+pub extern "C" fn bridge_engine_info(output_ptr: *mut *mut u8, output_len: *mut usize) -> c_int {
+    ffi_guard!({
+        if output_ptr.is_null() || output_len.is_null() {
+            return Err(BridgeError::InvalidArgument("Null output pointers".into()));
+        }
+
+        let info = engine_info::engine_info(ABI_VERSION);
+        write_output(info.encode_to_vec(), output_ptr, output_len);
+        Ok(0)
+    })
+}
+
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_capabilities")]
 pub extern "C" fn bridge_capabilities(ptr_out: *mut *const c_char, len_out: *mut usize) -> c_int {
     ffi_guard!({
         if ptr_out.is_null() || len_out.is_null() {
             return Err(BridgeError::InvalidArgument("Null output pointers".into()));
         }
         
-        let caps = r#"{
-            "abi_version": 1,
-            "min_plan_version_supported": 1,
-            "max_plan_version_supported": 1,
-            "supported_nodes": ["MemoryScan", "Project", "Filter", "WithColumns", "Limit"],
-            "supported_exprs": ["Col", "Lit", "Binary", "Alias", "IsNull", "Not", "Wildcard", "Cast", "StrLenBytes", "StrLenChars", "StrContains", "StrStartsWith", "StrEndsWith", "StrExtract", "StrReplace", "StrReplaceAll", "StrToLowercase", "StrToUppercase", "StrStripChars", "StrSlice", "StrSplit", "StrPadStart", "StrPadEnd"],
-            "supported_dtypes": ["Int64", "Float64", "Bool", "Utf8"],
-            "execution_modes": ["collect"],
-            "copy_behavior": "copy_on_boundary"
-        }"#;
-        
-        let caps_cstr = CString::new(caps).unwrap();
+        // 静态字符串：调用方只读，不需要（也不能）释放
+        static CAPS: OnceLock<CString> = OnceLock::new();
+        let caps = CAPS.get_or_init(|| {
+            let caps = serde_json::json!({
+                "abi_version": ABI_VERSION,
+                "min_plan_version_supported": handshake::MIN_PLAN_VERSION,
+                "max_plan_version_supported": handshake::MAX_PLAN_VERSION,
+                "supported_nodes": features::supported_nodes(),
+                "supported_exprs": features::supported_exprs(),
+                "supported_dtypes": schema::supported_dtypes(),
+                "execution_modes": ["collect", "sink"],
+                "copy_behavior": "copy_on_boundary",
+                "features": env!("BRIDGE_FEATURES").split(',').filter(|f| !f.is_empty()).collect::<Vec<_>>(),
+            })
+            .to_string();
+            CString::new(caps).unwrap()
+        });
         unsafe {
-            *ptr_out = caps_cstr.as_ptr();
-            *len_out = caps_cstr.as_bytes().len();
+            *ptr_out = caps.as_ptr();
+            *len_out = caps.as_bytes().len();
         }
-        std::mem::forget(caps_cstr);
         Ok(0)
     })
 }
 
 // 2. 错误通道
-#[no_mangle]
-pub extern "C" fn bridge_last_error(ptr_out: *mut *const c_char, len_out: *mut usize) -> c_int {
+//
+// 错误消息保存在线程局部存储中，但不会把其中的指针交给调用方：
+// bridge_last_error 把消息复制到 bridge 分配的缓冲区（调用 bridge_last_error_free 释放），
+// bridge_last_error_copy 则写入调用方提供的缓冲区，两者都不受之后的错误覆盖影响。
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_last_error")]
+pub extern "C" fn bridge_last_error(ptr_out: *mut *mut u8, len_out: *mut usize) -> c_int {
     if ptr_out.is_null() || len_out.is_null() {
         return ErrorCode::InvalidArgument as c_int;
     }
-    
+
+    let message = LAST_ERROR.with(|e| e.borrow().clone());
+    match message {
+        Some(message) => write_output(message.into_bytes(), ptr_out, len_out),
+        None => unsafe {
+            *ptr_out = ptr::null_mut();
+            *len_out = 0;
+        },
+    }
+    0
+}
+
+/// 把最近一次错误消息复制到调用方提供的缓冲区（不以 NUL 结尾）
+///
+/// out_len 总是写入消息的完整长度；缓冲区不足时只复制前 buf_len 字节并返回 InvalidArgument，
+/// 调用方可以按 out_len 重新分配后再次调用。没有错误时 out_len 为 0。
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_last_error_copy")]
+pub extern "C" fn bridge_last_error_copy(buf: *mut u8, buf_len: usize, out_len: *mut usize) -> c_int {
+    if out_len.is_null() || (buf.is_null() && buf_len > 0) {
+        return ErrorCode::InvalidArgument as c_int;
+    }
+
     LAST_ERROR.with(|e| {
-        if let Some(ref err) = *e.borrow() {
-            unsafe {
-                *ptr_out = err.as_ptr();
-                *len_out = err.as_bytes().len();
+        let message = e.borrow();
+        let bytes = message.as_deref().unwrap_or_default().as_bytes();
+        let copied = bytes.len().min(buf_len);
+        unsafe {
+            if copied > 0 {
+                ptr::copy_nonoverlapping(bytes.as_ptr(), buf, copied);
             }
-            0
+            *out_len = bytes.len();
+        }
+        if copied < bytes.len() {
+            ErrorCode::InvalidArgument as c_int
         } else {
-            unsafe {
-                *ptr_out = ptr::null();
-                *len_out = 0;
-            }
             0
         }
     })
 }
 
-#[no_mangle]
-pub extern "C" fn bridge_last_error_free(ptr: *const c_char, _len: usize) {
-    if !ptr.is_null() {
-        unsafe {
-            let _ = CString::from_raw(ptr as *mut c_char);
-        }
+/// 最近一次错误的结构化形式（ErrorDetail protobuf，需调用 bridge_output_free 释放；没有错误时输出为空）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_last_error_detail")]
+pub extern "C" fn bridge_last_error_detail(
+    output_ptr: *mut *mut u8,
+    output_len: *mut usize,
+) -> c_int {
+    if output_ptr.is_null() || output_len.is_null() {
+        return ErrorCode::InvalidArgument as c_int;
     }
+
+    let bytes = LAST_ERROR_DETAIL.with(|e| {
+        e.borrow().as_ref().map(|detail| detail.encode_to_vec()).unwrap_or_default()
+    });
+    write_output(bytes, output_ptr, output_len);
+    0
+}
+
+/// 释放 bridge_last_error 返回的缓冲区（ptr / len 必须原样传回）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_last_error_free")]
+pub extern "C" fn bridge_last_error_free(ptr: *mut u8, len: usize) {
+    bridge_output_free(ptr, len);
 }
 
 // 3. Plan 编译
-#[no_mangle]
+/// 解码 Plan 并校验协议版本
+pub(crate) fn compile_plan(plan_bytes: &[u8]) -> Result<proto::Plan, BridgeError> {
+    let plan = proto::Plan::decode(plan_bytes)
+        .map_err(|e| BridgeError::PlanDecode(e.to_string()))?;
+
+    if plan.plan_version < handshake::MIN_PLAN_VERSION
+        || plan.plan_version > handshake::MAX_PLAN_VERSION
+    {
+        return Err(BridgeError::PlanVersionUnsupported(plan.plan_version));
+    }
+    Ok(plan)
+}
+
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_plan_compile")]
 pub extern "C" fn bridge_plan_compile(
     plan_bytes_ptr: *const u8,
     plan_bytes_len: usize,
@@ -164,14 +375,9 @@ pub extern "C" fn bridge_plan_compile(
         }
         
         let plan_bytes = unsafe { slice::from_raw_parts(plan_bytes_ptr, plan_bytes_len) };
-        let plan = proto::Plan::decode(plan_bytes)
-            .map_err(|e| BridgeError::PlanDecode(e.to_string()))?;
-        
-        if plan.plan_version != 1 {
-            return Err(BridgeError::PlanVersionUnsupported(plan.plan_version));
-        }
+        let plan = compile_plan(plan_bytes)?;
         
-        let handle = Box::into_raw(Box::new(plan)) as u64;
+        let handle = handles::plans().insert(plan);
         unsafe {
             *out_plan_handle_ptr = handle;
         }
@@ -180,17 +386,84 @@ pub extern "C" fn bridge_plan_compile(
     })
 }
 
-#[no_mangle]
-pub extern "C" fn bridge_plan_free(plan_handle: u64) {
-    if plan_handle != 0 {
+/// Plan 结构指纹（与序列化字段顺序、节点 ID 无关）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_plan_hash")]
+pub extern "C" fn bridge_plan_hash(plan_handle: u64, out_hash_ptr: *mut u64) -> c_int {
+    ffi_guard!({
+        if plan_handle == 0 || out_hash_ptr.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        let plan = handles::plans().get(plan_handle)?;
         unsafe {
-            let _ = Box::from_raw(plan_handle as *mut proto::Plan);
+            *out_hash_ptr = fingerprint::plan_fingerprint(&plan);
+        }
+        Ok(0)
+    })
+}
+
+/// 估算 Plan 的执行代价（PlanEstimate protobuf，需调用 bridge_output_free 释放）
+///
+/// input_schema 为 Schema protobuf（可为空），用于在没有数据的情况下推断 MemoryScan 的输出 schema。
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_plan_estimate")]
+pub extern "C" fn bridge_plan_estimate(
+    plan_handle: u64,
+    input_schema_ptr: *const u8,
+    input_schema_len: usize,
+    output_ptr: *mut *mut u8,
+    output_len: *mut usize,
+) -> c_int {
+    ffi_guard!({
+        if plan_handle == 0 || output_ptr.is_null() || output_len.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
         }
+
+        let plan = handles::plans().get(plan_handle)?;
+        let input_schema: Option<proto::Schema> = if input_schema_ptr.is_null() {
+            None
+        } else {
+            Some(decode_options(input_schema_ptr, input_schema_len)?)
+        };
+
+        let estimate = estimate::estimate(&plan, input_schema.as_ref())?;
+        write_output(estimate.encode_to_vec(), output_ptr, output_len);
+        Ok(0)
+    })
+}
+
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_plan_free")]
+pub extern "C" fn bridge_plan_free(plan_handle: u64) {
+    if plan_handle != 0 {
+        handles::plans().remove(plan_handle);
     }
 }
 
+// 3b. 正则预编译（放入进程内缓存，之后构建的 Plan 直接取用；非法模式在此处直接报错）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_regex_precompile")]
+pub extern "C" fn bridge_regex_precompile(
+    pattern_ptr: *const c_char,
+    pattern_len: usize,
+) -> c_int {
+    ffi_guard!({
+        let pattern = read_str(pattern_ptr, pattern_len)?;
+        regex_cache::compile(pattern)?;
+        Ok(0)
+    })
+}
+
+/// 当前缓存的正则数量
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_regex_cache_len")]
+pub extern "C" fn bridge_regex_cache_len() -> u64 {
+    regex_cache::len() as u64
+}
+
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_regex_cache_clear")]
+pub extern "C" fn bridge_regex_cache_clear() {
+    regex_cache::clear();
+}
+
 // 4. 执行（返回 Arrow IPC 二进制数据）
-#[no_mangle]
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_plan_execute_simple")]
 pub extern "C" fn bridge_plan_execute_simple(
     plan_handle: u64,
     _input_json_ptr: *const c_char,
@@ -203,29 +476,17 @@ pub extern "C" fn bridge_plan_execute_simple(
             return Err(BridgeError::InvalidArgument("Null pointers".into()));
         }
         
-        let plan = unsafe { &*(plan_handle as *const proto::Plan) };
+        let plan = handles::plans().get(plan_handle)?;
         
         // 注意：input_json 参数被忽略，因为数据源已经在 Plan 里（CsvScan 等）
-        let result_bytes = executor::execute_plan(plan)?;
-        
-        // 分配内存并拷贝结果
-        let len = result_bytes.len();
-        let ptr = result_bytes.as_ptr() as *mut u8;
-        
-        unsafe {
-            *output_len = len;
-            *output_ptr = ptr;
-        }
-        
-        // 防止 Rust 释放这块内存
-        std::mem::forget(result_bytes);
-        
+        let result_bytes = executor::execute_plan(&plan)?;
+        write_output(result_bytes, output_ptr, output_len);
         Ok(0)
     })
 }
 
 // 4b. 执行并返回 DataFrame（句柄）
-#[no_mangle]
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_plan_collect_df")]
 pub extern "C" fn bridge_plan_collect_df(
     plan_handle: u64,
     input_df_handle: u64,
@@ -236,15 +497,137 @@ pub extern "C" fn bridge_plan_collect_df(
             return Err(BridgeError::InvalidArgument("Null pointers".into()));
         }
 
-        let plan = unsafe { &*(plan_handle as *const proto::Plan) };
+        let plan = handles::plans().get(plan_handle)?;
+        let input_df = if input_df_handle != 0 {
+            Some(handles::dataframes().get(input_df_handle)?)
+        } else {
+            None
+        };
+
+        metadata::reset_cache_hits();
+        let started = Instant::now();
+        let df = executor::execute_plan_df(&plan, input_df.as_deref())?;
+        let elapsed = started.elapsed();
+        let df = Arc::new(df);
+        let handle = handles::dataframes().insert_arc(Arc::clone(&df));
+        metadata::record(handle, &plan, input_df.as_deref(), &df, elapsed, "");
+        unsafe {
+            *out_df_handle_ptr = handle;
+        }
+
+        Ok(0)
+    })
+}
+
+/// 获取结果 DataFrame 句柄的执行元数据（ResultMetadata protobuf，需调用 bridge_output_free 释放）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_result_metadata")]
+pub extern "C" fn bridge_result_metadata(
+    df_handle: u64,
+    output_ptr: *mut *mut u8,
+    output_len: *mut usize,
+) -> c_int {
+    ffi_guard!({
+        if df_handle == 0 || output_ptr.is_null() || output_len.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        let metadata = metadata::get(df_handle).ok_or_else(|| {
+            BridgeError::InvalidArgument(format!(
+                "No result metadata for dataframe handle {}",
+                df_handle
+            ))
+        })?;
+        write_output(metadata.encode_to_vec(), output_ptr, output_len);
+        Ok(0)
+    })
+}
+
+// 将结果字节交给调用方（长度与容量一致，由 bridge_output_free 释放）
+fn write_output(bytes: Vec<u8>, output_ptr: *mut *mut u8, output_len: *mut usize) {
+    let bytes = bytes.into_boxed_slice();
+    let len = bytes.len();
+    let ptr = Box::into_raw(bytes) as *mut u8;
+    unsafe {
+        *output_len = len;
+        *output_ptr = ptr;
+    }
+}
+
+// 读取调用方传入的 UTF-8 字符串
+fn read_str<'a>(ptr: *const c_char, len: usize) -> Result<&'a str, BridgeError> {
+    if ptr.is_null() {
+        return Err(BridgeError::InvalidArgument("Null string pointer".into()));
+    }
+    let bytes = unsafe { slice::from_raw_parts(ptr as *const u8, len) };
+    std::str::from_utf8(bytes)
+        .map_err(|e| BridgeError::InvalidArgument(format!("Invalid UTF-8: {}", e)))
+}
+
+// 解码可选的 protobuf 选项（空指针表示使用默认值）
+fn decode_options<T: Message + Default>(ptr: *const u8, len: usize) -> Result<T, BridgeError> {
+    if ptr.is_null() || len == 0 {
+        return Ok(T::default());
+    }
+    let bytes = unsafe { slice::from_raw_parts(ptr, len) };
+    T::decode(bytes).map_err(|e| BridgeError::InvalidArgument(format!("Invalid options: {}", e)))
+}
+
+/// Explain analyze：执行 Plan 并输出带实际行数与耗时的计划文本（需调用 bridge_output_free 释放）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_plan_explain_analyze")]
+pub extern "C" fn bridge_plan_explain_analyze(
+    plan_handle: u64,
+    input_df_handle: u64,
+    output_ptr: *mut *mut u8,
+    output_len: *mut usize,
+) -> c_int {
+    ffi_guard!({
+        if plan_handle == 0 || output_ptr.is_null() || output_len.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        let plan = handles::plans().get(plan_handle)?;
+        let input_df = if input_df_handle != 0 {
+            Some(handles::dataframes().get(input_df_handle)?)
+        } else {
+            None
+        };
+
+        let report = explain::explain_analyze(&plan, input_df.as_deref())?;
+        write_output(report.into_bytes(), output_ptr, output_len);
+        Ok(0)
+    })
+}
+
+/// 带执行提示（ExecutionHints protobuf，可为空）执行并返回 DataFrame 句柄
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_plan_collect_df_with_hints")]
+pub extern "C" fn bridge_plan_collect_df_with_hints(
+    plan_handle: u64,
+    input_df_handle: u64,
+    hints_ptr: *const u8,
+    hints_len: usize,
+    out_df_handle_ptr: *mut u64,
+) -> c_int {
+    ffi_guard!({
+        if plan_handle == 0 || out_df_handle_ptr.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        let plan = handles::plans().get(plan_handle)?;
         let input_df = if input_df_handle != 0 {
-            Some(unsafe { &*(input_df_handle as *const DataFrame) })
+            Some(handles::dataframes().get(input_df_handle)?)
         } else {
             None
         };
+        let hints: proto::ExecutionHints = decode_options(hints_ptr, hints_len)?;
+        trace::set_query_id(&hints.query_id);
 
-        let df = executor::execute_plan_df(plan, input_df)?;
-        let handle = Box::into_raw(Box::new(df)) as u64;
+        metadata::reset_cache_hits();
+        let started = Instant::now();
+        let df = executor::execute_plan_df_with_hints(&plan, input_df.as_deref(), &hints)?;
+        let elapsed = started.elapsed();
+        let df = Arc::new(df);
+        let handle = handles::dataframes().insert_arc(Arc::clone(&df));
+        metadata::record(handle, &plan, input_df.as_deref(), &df, elapsed, &hints.query_id);
         unsafe {
             *out_df_handle_ptr = handle;
         }
@@ -253,7 +636,7 @@ pub extern "C" fn bridge_plan_collect_df(
     })
 }
 
-#[no_mangle]
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_output_free")]
 pub extern "C" fn bridge_output_free(ptr: *mut u8, len: usize) {
     if !ptr.is_null() && len > 0 {
         unsafe {
@@ -264,7 +647,7 @@ pub extern "C" fn bridge_output_free(ptr: *mut u8, len: usize) {
 }
 
 // 4c. DataFrame -> Arrow IPC
-#[no_mangle]
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_df_to_ipc")]
 pub extern "C" fn bridge_df_to_ipc(
     df_handle: u64,
     output_ptr: *mut *mut u8,
@@ -275,127 +658,1074 @@ pub extern "C" fn bridge_df_to_ipc(
             return Err(BridgeError::InvalidArgument("Null pointers".into()));
         }
 
-        let df = unsafe { &*(df_handle as *const DataFrame) };
-        let result_bytes = executor::df_to_ipc(df)?;
-
-        let len = result_bytes.len();
-        let ptr = result_bytes.as_ptr() as *mut u8;
+        let df = handles::dataframes().get(df_handle)?;
+        let result_bytes = executor::df_to_ipc(&df)?;
+        write_output(result_bytes, output_ptr, output_len);
+        Ok(0)
+    })
+}
 
-        unsafe {
-            *output_len = len;
-            *output_ptr = ptr;
+/// DataFrame -> JSON 行数组（JsonRowOptions protobuf 可为空；输出需调用 bridge_output_free 释放）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_df_to_json")]
+pub extern "C" fn bridge_df_to_json(
+    df_handle: u64,
+    options_ptr: *const u8,
+    options_len: usize,
+    output_ptr: *mut *mut u8,
+    output_len: *mut usize,
+) -> c_int {
+    ffi_guard!({
+        if df_handle == 0 || output_ptr.is_null() || output_len.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
         }
 
-        std::mem::forget(result_bytes);
+        let df = handles::dataframes().get(df_handle)?;
+        let options: proto::JsonRowOptions = decode_options(options_ptr, options_len)?;
+        let json = json_rows::df_to_json_rows(&df, &options)?;
+        write_output(json, output_ptr, output_len);
         Ok(0)
     })
 }
 
 // 4d. 打印 DataFrame
-#[no_mangle]
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_df_print")]
 pub extern "C" fn bridge_df_print(df_handle: u64) -> c_int {
     ffi_guard!({
         if df_handle == 0 {
             return Err(BridgeError::InvalidArgument("Null dataframe handle".into()));
         }
 
-        let df = unsafe { &*(df_handle as *const DataFrame) };
-        executor::df_print(df)?;
+        let df = handles::dataframes().get(df_handle)?;
+        executor::df_print(&df)?;
         Ok(0)
     })
 }
 
-#[no_mangle]
-pub extern "C" fn bridge_df_free(df_handle: u64) {
-    if df_handle != 0 {
-        unsafe {
-            let _ = Box::from_raw(df_handle as *mut DataFrame);
+// 4e. 列统计信息（输出 ColumnStats protobuf，需调用 bridge_output_free 释放）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_df_col_stats")]
+pub extern "C" fn bridge_df_col_stats(
+    df_handle: u64,
+    name_ptr: *const c_char,
+    name_len: usize,
+    output_ptr: *mut *mut u8,
+    output_len: *mut usize,
+) -> c_int {
+    ffi_guard!({
+        if df_handle == 0 || output_ptr.is_null() || output_len.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
         }
-    }
+
+        let df = handles::dataframes().get(df_handle)?;
+        let name = read_str(name_ptr, name_len)?;
+        let stats = stats::column_stats(&df, name)?;
+        write_output(stats.encode_to_vec(), output_ptr, output_len);
+        Ok(0)
+    })
 }
 
-// 5. 执行并直接打印（使用 Polars 原生 Display）
-#[no_mangle]
-pub extern "C" fn bridge_plan_execute_and_print(
-    plan_handle: u64,
+/// 读取单个单元格（输出 Scalar protobuf，需调用 bridge_output_free 释放）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_df_scalar")]
+pub extern "C" fn bridge_df_scalar(
+    df_handle: u64,
+    name_ptr: *const c_char,
+    name_len: usize,
+    row: u64,
+    output_ptr: *mut *mut u8,
+    output_len: *mut usize,
 ) -> c_int {
     ffi_guard!({
-        if plan_handle == 0 {
-            return Err(BridgeError::InvalidArgument("Null plan handle".into()));
+        if df_handle == 0 || output_ptr.is_null() || output_len.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
         }
-        
-        let plan = unsafe { &*(plan_handle as *const proto::Plan) };
-        
-        // 执行并打印结果
-        executor::execute_and_print(plan)?;
-        
+
+        let df = handles::dataframes().get(df_handle)?;
+        let name = read_str(name_ptr, name_len)?;
+        let scalar = stats::scalar(&df, name, row as usize)?;
+        write_output(scalar.encode_to_vec(), output_ptr, output_len);
         Ok(0)
     })
 }
 
-// 5. Arrow-based execution (zero-copy)
-use polars_arrow::ffi::{ArrowArray, ArrowSchema};
-
-#[no_mangle]
-pub extern "C" fn bridge_plan_execute_arrow(
-    plan_handle: u64,
-    input_schema: *const ArrowSchema,
-    input_array: *const ArrowArray,
-    output_schema: *mut ArrowSchema,
-    output_array: *mut ArrowArray,
+/// 读取 1x1 DataFrame 的唯一值（输出 Scalar protobuf，需调用 bridge_output_free 释放）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_df_item")]
+pub extern "C" fn bridge_df_item(
+    df_handle: u64,
+    output_ptr: *mut *mut u8,
+    output_len: *mut usize,
 ) -> c_int {
     ffi_guard!({
-        if plan_handle == 0 || output_schema.is_null() || output_array.is_null() {
+        if df_handle == 0 || output_ptr.is_null() || output_len.is_null() {
             return Err(BridgeError::InvalidArgument("Null pointers".into()));
         }
 
-        if (input_schema.is_null() && !input_array.is_null())
-            || (!input_schema.is_null() && input_array.is_null())
-        {
-            return Err(BridgeError::InvalidArgument(
-                "Input schema/array must both be null or both be set".into(),
-            ));
-        }
+        let df = handles::dataframes().get(df_handle)?;
+        let scalar = stats::item(&df)?;
+        write_output(scalar.encode_to_vec(), output_ptr, output_len);
+        Ok(0)
+    })
+}
 
-        let plan = unsafe { &*(plan_handle as *const proto::Plan) };
-        let input_df = if input_schema.is_null() {
-            None
-        } else {
-            Some(arrow_bridge::import_dataframe_from_arrow(
-                input_schema,
-                input_array,
-            )?)
-        };
+/// 检查列是否按指定方向排序（null 视为最小值），结果写入 out_sorted（1 = 已排序）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_df_is_sorted")]
+pub extern "C" fn bridge_df_is_sorted(
+    df_handle: u64,
+    name_ptr: *const c_char,
+    name_len: usize,
+    descending: c_int,
+    out_sorted: *mut c_int,
+) -> c_int {
+    ffi_guard!({
+        if df_handle == 0 || out_sorted.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
 
-        let df = executor::execute_plan_df(plan, input_df.as_ref())?;
-        arrow_bridge::export_dataframe_to_arrow(&df, output_schema, output_array)?;
+        let df = handles::dataframes().get(df_handle)?;
+        let name = read_str(name_ptr, name_len)?;
+        let is_sorted = sorted::is_sorted(&df, name, descending != 0)?;
+        unsafe {
+            *out_sorted = is_sorted as c_int;
+        }
         Ok(0)
     })
 }
 
-// 6. 从列数据创建 DataFrame（支持动态类型推断）
-// 数据格式：JSON array of columns
-// [{"name": "col1", "values": [1, 2, 3]}, {"name": "col2", "values": ["a", "b", "c"]}]
-#[no_mangle]
-pub extern "C" fn bridge_df_from_columns(
-    json_ptr: *const c_char,
-    json_len: usize,
-    out_df_handle: *mut u64,
+/// 为列设置排序标记并返回新的 DataFrame 句柄（不校验数据，需要时先调用 bridge_df_is_sorted）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_df_set_sorted")]
+pub extern "C" fn bridge_df_set_sorted(
+    df_handle: u64,
+    name_ptr: *const c_char,
+    name_len: usize,
+    descending: c_int,
+    out_df_handle_ptr: *mut u64,
 ) -> c_int {
     ffi_guard!({
-        if json_ptr.is_null() || out_df_handle.is_null() {
+        if df_handle == 0 || out_df_handle_ptr.is_null() {
             return Err(BridgeError::InvalidArgument("Null pointers".into()));
         }
 
-        // 读取 JSON 数据
-        let json_slice = unsafe { slice::from_raw_parts(json_ptr as *const u8, json_len) };
-        let json_str = std::str::from_utf8(json_slice)
+        let df = handles::dataframes().get(df_handle)?;
+        let name = read_str(name_ptr, name_len)?;
+        let sorted_df = sorted::set_sorted(&df, name, descending != 0)?;
+        let handle = handles::dataframes().insert(sorted_df);
+        unsafe {
+            *out_df_handle_ptr = handle;
+        }
+        Ok(0)
+    })
+}
+
+/// 在 DataFrame 上求值单个 Expr（protobuf），结果为单列 DataFrame 句柄（无需构造完整 Plan）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_expr_evaluate")]
+pub extern "C" fn bridge_expr_evaluate(
+    df_handle: u64,
+    expr_bytes_ptr: *const u8,
+    expr_bytes_len: usize,
+    out_df_handle_ptr: *mut u64,
+) -> c_int {
+    ffi_guard!({
+        if df_handle == 0 || expr_bytes_ptr.is_null() || out_df_handle_ptr.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        let df = handles::dataframes().get(df_handle)?;
+        let expr_bytes = unsafe { slice::from_raw_parts(expr_bytes_ptr, expr_bytes_len) };
+        let expr = proto::Expr::decode(expr_bytes)
+            .map_err(|e| BridgeError::PlanDecode(e.to_string()))?;
+
+        let result = executor::evaluate_expr(&df, &expr)?;
+        let handle = handles::dataframes().insert(result);
+        unsafe {
+            *out_df_handle_ptr = handle;
+        }
+        Ok(0)
+    })
+}
+
+/// 在 DataFrame 上批量添加 / 替换列（ExprList protobuf），结果为新的 DataFrame 句柄（原句柄不变）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_df_with_columns")]
+pub extern "C" fn bridge_df_with_columns(
+    df_handle: u64,
+    exprs_ptr: *const u8,
+    exprs_len: usize,
+    out_df_handle_ptr: *mut u64,
+) -> c_int {
+    ffi_guard!({
+        if df_handle == 0 || exprs_ptr.is_null() || out_df_handle_ptr.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        let df = handles::dataframes().get(df_handle)?;
+        let exprs_bytes = unsafe { slice::from_raw_parts(exprs_ptr, exprs_len) };
+        let exprs = proto::ExprList::decode(exprs_bytes)
+            .map_err(|e| BridgeError::PlanDecode(e.to_string()))?;
+
+        let result = executor::with_columns(&df, &exprs.expressions)?;
+        let handle = handles::dataframes().insert(result);
+        unsafe {
+            *out_df_handle_ptr = handle;
+        }
+        Ok(0)
+    })
+}
+
+/// 批量改名（RenameOptions protobuf：显式的列名对 + 前缀 / 后缀 / 大小写规则），结果为新的 DataFrame 句柄（原句柄不变）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_df_rename_columns")]
+pub extern "C" fn bridge_df_rename_columns(
+    df_handle: u64,
+    options_ptr: *const u8,
+    options_len: usize,
+    out_df_handle_ptr: *mut u64,
+) -> c_int {
+    ffi_guard!({
+        if df_handle == 0 || out_df_handle_ptr.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        let df = handles::dataframes().get(df_handle)?;
+        let options: proto::RenameOptions = decode_options(options_ptr, options_len)?;
+
+        let result = rename::rename_columns(&df, &options)?;
+        let handle = handles::dataframes().insert(result);
+        unsafe {
+            *out_df_handle_ptr = handle;
+        }
+        Ok(0)
+    })
+}
+
+/// 按键列对比两个 DataFrame：差异行写入新的 DataFrame 句柄，摘要输出 DataDiff protobuf
+///
+/// 差异行的 `_diff` 列为 added / removed / changed；changed 行取右侧的新值，
+/// 旧值放在 `<列名>_before` 列中。
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_df_diff")]
+pub extern "C" fn bridge_df_diff(
+    left_df_handle: u64,
+    right_df_handle: u64,
+    options_ptr: *const u8,
+    options_len: usize,
+    out_df_handle_ptr: *mut u64,
+    output_ptr: *mut *mut u8,
+    output_len: *mut usize,
+) -> c_int {
+    ffi_guard!({
+        if left_df_handle == 0
+            || right_df_handle == 0
+            || out_df_handle_ptr.is_null()
+            || output_ptr.is_null()
+            || output_len.is_null()
+        {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        let left = handles::dataframes().get(left_df_handle)?;
+        let right = handles::dataframes().get(right_df_handle)?;
+        let options: proto::DiffOptions = decode_options(options_ptr, options_len)?;
+
+        let (rows, summary) = diff::df_diff(&left, &right, &options)?;
+        let handle = handles::dataframes().insert(rows);
+        unsafe {
+            *out_df_handle_ptr = handle;
+        }
+        write_output(summary.encode_to_vec(), output_ptr, output_len);
+        Ok(0)
+    })
+}
+
+// 4f. DataFrame 写入文件
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_df_write_ipc")]
+pub extern "C" fn bridge_df_write_ipc(
+    df_handle: u64,
+    path_ptr: *const c_char,
+    path_len: usize,
+    options_ptr: *const u8,
+    options_len: usize,
+) -> c_int {
+    ffi_guard!({
+        if df_handle == 0 {
+            return Err(BridgeError::InvalidArgument("Null dataframe handle".into()));
+        }
+
+        let df = handles::dataframes().get(df_handle)?;
+        let path = read_str(path_ptr, path_len)?;
+        let options: proto::IpcWriteOptions = decode_options(options_ptr, options_len)?;
+        writer::write_ipc(&df, path, &options)?;
+        Ok(0)
+    })
+}
+
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_df_write_ndjson")]
+pub extern "C" fn bridge_df_write_ndjson(
+    df_handle: u64,
+    path_ptr: *const c_char,
+    path_len: usize,
+    options_ptr: *const u8,
+    options_len: usize,
+) -> c_int {
+    ffi_guard!({
+        if df_handle == 0 {
+            return Err(BridgeError::InvalidArgument("Null dataframe handle".into()));
+        }
+
+        let df = handles::dataframes().get(df_handle)?;
+        let path = read_str(path_ptr, path_len)?;
+        let options: proto::NdjsonWriteOptions = decode_options(options_ptr, options_len)?;
+        writer::write_ndjson(&df, path, &options)?;
+        Ok(0)
+    })
+}
+
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_df_write_avro")]
+pub extern "C" fn bridge_df_write_avro(
+    df_handle: u64,
+    path_ptr: *const c_char,
+    path_len: usize,
+    options_ptr: *const u8,
+    options_len: usize,
+) -> c_int {
+    ffi_guard!({
+        if df_handle == 0 {
+            return Err(BridgeError::InvalidArgument("Null dataframe handle".into()));
+        }
+
+        let df = handles::dataframes().get(df_handle)?;
+        let path = read_str(path_ptr, path_len)?;
+        let options: proto::AvroWriteOptions = decode_options(options_ptr, options_len)?;
+        writer::write_avro(&df, path, &options)?;
+        Ok(0)
+    })
+}
+
+/// 保存检查点：Arrow IPC 文件，带格式版本等元数据（进程重启后用 bridge_df_restore 恢复）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_df_checkpoint")]
+pub extern "C" fn bridge_df_checkpoint(
+    df_handle: u64,
+    path_ptr: *const c_char,
+    path_len: usize,
+) -> c_int {
+    ffi_guard!({
+        if df_handle == 0 {
+            return Err(BridgeError::InvalidArgument("Null dataframe handle".into()));
+        }
+
+        let df = handles::dataframes().get(df_handle)?;
+        let path = read_str(path_ptr, path_len)?;
+        checkpoint::checkpoint(&df, path)?;
+        Ok(0)
+    })
+}
+
+/// 从检查点恢复 DataFrame，返回新的句柄
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_df_restore")]
+pub extern "C" fn bridge_df_restore(
+    path_ptr: *const c_char,
+    path_len: usize,
+    out_df_handle: *mut u64,
+) -> c_int {
+    ffi_guard!({
+        if out_df_handle.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        let path = read_str(path_ptr, path_len)?;
+        let df = checkpoint::restore(path)?;
+        let handle = handles::dataframes().insert(df);
+        unsafe {
+            *out_df_handle = handle;
+        }
+        Ok(0)
+    })
+}
+
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_df_free")]
+pub extern "C" fn bridge_df_free(df_handle: u64) {
+    if df_handle != 0 && handles::dataframes().release(df_handle) {
+        metadata::remove(df_handle);
+    }
+}
+
+/// 为 DataFrame 句柄增加一个引用：每次 retain 都需要对应一次 bridge_df_free
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_df_retain")]
+pub extern "C" fn bridge_df_retain(df_handle: u64) -> c_int {
+    ffi_guard!({
+        if df_handle == 0 {
+            return Err(BridgeError::InvalidArgument("Null dataframe handle".into()));
+        }
+
+        handles::dataframes().retain(df_handle)?;
+        Ok(0)
+    })
+}
+
+// 4g. 文件元数据（FileMetadata protobuf，需调用 bridge_output_free 释放）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_parquet_metadata")]
+pub extern "C" fn bridge_parquet_metadata(
+    path_ptr: *const c_char,
+    path_len: usize,
+    output_ptr: *mut *mut u8,
+    output_len: *mut usize,
+) -> c_int {
+    ffi_guard!({
+        if output_ptr.is_null() || output_len.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        let path = read_str(path_ptr, path_len)?;
+        let metadata = file_metadata::parquet_metadata(path)?;
+        write_output(metadata.encode_to_vec(), output_ptr, output_len);
+        Ok(0)
+    })
+}
+
+/// 按 CsvScan protobuf 中的路径与解析选项推断 CSV schema
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_csv_infer_schema")]
+pub extern "C" fn bridge_csv_infer_schema(
+    scan_ptr: *const u8,
+    scan_len: usize,
+    output_ptr: *mut *mut u8,
+    output_len: *mut usize,
+) -> c_int {
+    ffi_guard!({
+        if scan_ptr.is_null() || output_ptr.is_null() || output_len.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        let scan: proto::CsvScan = decode_options(scan_ptr, scan_len)?;
+        let metadata = file_metadata::csv_metadata(&scan)?;
+        write_output(metadata.encode_to_vec(), output_ptr, output_len);
+        Ok(0)
+    })
+}
+
+// 5. 执行并直接打印（使用 Polars 原生 Display）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_plan_execute_and_print")]
+pub extern "C" fn bridge_plan_execute_and_print(
+    plan_handle: u64,
+) -> c_int {
+    ffi_guard!({
+        if plan_handle == 0 {
+            return Err(BridgeError::InvalidArgument("Null plan handle".into()));
+        }
+        
+        let plan = handles::plans().get(plan_handle)?;
+        
+        // 执行并打印结果
+        executor::execute_and_print(&plan)?;
+        
+        Ok(0)
+    })
+}
+
+// 5. Arrow-based execution (zero-copy)
+use polars_arrow::ffi::{ArrowArray, ArrowSchema};
+
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_plan_execute_arrow")]
+pub extern "C" fn bridge_plan_execute_arrow(
+    plan_handle: u64,
+    input_schema: *const ArrowSchema,
+    input_array: *const ArrowArray,
+    output_schema: *mut ArrowSchema,
+    output_array: *mut ArrowArray,
+) -> c_int {
+    ffi_guard!({
+        if plan_handle == 0 || output_schema.is_null() || output_array.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        if (input_schema.is_null() && !input_array.is_null())
+            || (!input_schema.is_null() && input_array.is_null())
+        {
+            return Err(BridgeError::InvalidArgument(
+                "Input schema/array must both be null or both be set".into(),
+            ));
+        }
+
+        let plan = handles::plans().get(plan_handle)?;
+        let input_df = if input_schema.is_null() {
+            None
+        } else {
+            Some(arrow_bridge::import_dataframe_from_arrow(
+                input_schema,
+                input_array,
+                &arrow_bridge::ImportOptions::from_options(plan.options.as_ref()),
+            )?)
+        };
+
+        let df = executor::execute_plan_df(&plan, input_df.as_ref())?;
+        arrow_bridge::export_dataframe_to_arrow(&df, output_schema, output_array)?;
+        Ok(0)
+    })
+}
+
+/// 取出当前线程最近一次 Arrow 导入的警告（ImportWarnings protobuf，需调用 bridge_output_free 释放）
+///
+/// 在 ArrowImportOptions.unsupported_columns 为宽松模式、或带时区的时间戳回退为 UTC 时产生；读取后清空。
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_arrow_import_warnings")]
+pub extern "C" fn bridge_arrow_import_warnings(
+    output_ptr: *mut *mut u8,
+    output_len: *mut usize,
+) -> c_int {
+    ffi_guard!({
+        if output_ptr.is_null() || output_len.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        let warnings = proto::ImportWarnings {
+            warnings: arrow_bridge::take_import_warnings(),
+        };
+        write_output(warnings.encode_to_vec(), output_ptr, output_len);
+        Ok(0)
+    })
+}
+
+/// 执行 Plan 并以 Arrow C Stream 输出（按 ArrowStreamOptions 切分为有界批次，便于流水线消费）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_plan_execute_arrow_stream")]
+pub extern "C" fn bridge_plan_execute_arrow_stream(
+    plan_handle: u64,
+    input_schema: *const ArrowSchema,
+    input_array: *const ArrowArray,
+    options_ptr: *const u8,
+    options_len: usize,
+    output_stream: *mut polars_arrow::ffi::ArrowArrayStream,
+) -> c_int {
+    ffi_guard!({
+        if plan_handle == 0 || output_stream.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        if (input_schema.is_null() && !input_array.is_null())
+            || (!input_schema.is_null() && input_array.is_null())
+        {
+            return Err(BridgeError::InvalidArgument(
+                "Input schema/array must both be null or both be set".into(),
+            ));
+        }
+
+        let plan = handles::plans().get(plan_handle)?;
+        let options: proto::ArrowStreamOptions = decode_options(options_ptr, options_len)?;
+        let input_df = if input_schema.is_null() {
+            None
+        } else {
+            Some(arrow_bridge::import_dataframe_from_arrow(
+                input_schema,
+                input_array,
+                &arrow_bridge::ImportOptions::from_options(plan.options.as_ref()),
+            )?)
+        };
+
+        let df = executor::execute_plan_df(&plan, input_df.as_ref())?;
+        arrow_bridge::export_dataframe_to_arrow_stream(df, &options, output_stream)?;
+        Ok(0)
+    })
+}
+
+// 5b. 分块构建 DataFrame（append 若干次后 finish 得到 DataFrame 句柄）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_frame_new")]
+pub extern "C" fn bridge_frame_new(out_frame_handle: *mut u64) -> c_int {
+    ffi_guard!({
+        if out_frame_handle.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        let handle = handles::frames().insert(Mutex::new(FrameBuilder::default()));
+        unsafe {
+            *out_frame_handle = handle;
+        }
+        Ok(0)
+    })
+}
+
+/// 追加一个 Arrow IPC（文件格式）分块
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_frame_append_ipc")]
+pub extern "C" fn bridge_frame_append_ipc(
+    frame_handle: u64,
+    data_ptr: *const u8,
+    data_len: usize,
+) -> c_int {
+    ffi_guard!({
+        if frame_handle == 0 || data_ptr.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        let frame = handles::frames().get(frame_handle)?;
+        let bytes = unsafe { slice::from_raw_parts(data_ptr, data_len) };
+        let chunk = frame_builder::read_ipc_chunk(bytes)?;
+        frame.lock().unwrap_or_else(|e| e.into_inner()).append(chunk)?;
+        Ok(0)
+    })
+}
+
+/// 通过 Arrow C Data Interface 追加一个分块（数组所有权转移给 bridge）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_frame_append_arrow")]
+pub extern "C" fn bridge_frame_append_arrow(
+    frame_handle: u64,
+    input_schema: *const ArrowSchema,
+    input_array: *const ArrowArray,
+) -> c_int {
+    ffi_guard!({
+        if frame_handle == 0 || input_schema.is_null() || input_array.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        let frame = handles::frames().get(frame_handle)?;
+        let chunk = arrow_bridge::import_dataframe_from_arrow(
+            input_schema,
+            input_array,
+            &arrow_bridge::ImportOptions::from_options(None),
+        )?;
+        frame.lock().unwrap_or_else(|e| e.into_inner()).append(chunk)?;
+        Ok(0)
+    })
+}
+
+/// 结束构建：释放 frame 句柄并返回 DataFrame 句柄
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_frame_finish")]
+pub extern "C" fn bridge_frame_finish(frame_handle: u64, out_df_handle: *mut u64) -> c_int {
+    ffi_guard!({
+        if frame_handle == 0 || out_df_handle.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        let frame = handles::frames().remove(frame_handle).ok_or_else(|| {
+            BridgeError::InvalidArgument(format!("Invalid frame handle: {}", frame_handle))
+        })?;
+        let df = frame.lock().unwrap_or_else(|e| e.into_inner()).finish();
+        let handle = handles::dataframes().insert(df);
+        unsafe {
+            *out_df_handle = handle;
+        }
+        Ok(0)
+    })
+}
+
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_frame_free")]
+pub extern "C" fn bridge_frame_free(frame_handle: u64) {
+    if frame_handle != 0 {
+        handles::frames().remove(frame_handle);
+    }
+}
+
+// 5c. 注册 Go 读取回调（供 ReaderScan 使用；被扫描消费后自动注销）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_reader_register")]
+pub extern "C" fn bridge_reader_register(
+    callback: Option<extern "C" fn(*mut c_void, *mut u8, usize, *mut usize) -> c_int>,
+    ctx: *mut c_void,
+    out_reader_handle: *mut u64,
+) -> c_int {
+    ffi_guard!({
+        let callback = callback
+            .ok_or_else(|| BridgeError::InvalidArgument("Null reader callback".into()))?;
+        if out_reader_handle.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        let handle = handles::readers().insert(reader_scan::GoReader::new(callback, ctx));
+        unsafe {
+            *out_reader_handle = handle;
+        }
+        Ok(0)
+    })
+}
+
+/// 注销尚未被消费的读取器
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_reader_free")]
+pub extern "C" fn bridge_reader_free(reader_handle: u64) {
+    if reader_handle != 0 {
+        handles::readers().remove(reader_handle);
+    }
+}
+
+// 5d. 注册 Go 批次提供者（供 BatchProviderScan 在执行时按需拉取数据）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_batch_provider_register")]
+pub extern "C" fn bridge_batch_provider_register(
+    callback: Option<
+        extern "C" fn(*mut c_void, *const u8, usize, *mut ArrowSchema, *mut ArrowArray, *mut c_int) -> c_int,
+    >,
+    ctx: *mut c_void,
+    out_provider_handle: *mut u64,
+) -> c_int {
+    ffi_guard!({
+        let callback = callback
+            .ok_or_else(|| BridgeError::InvalidArgument("Null batch provider callback".into()))?;
+        if out_provider_handle.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        let provider = batch_provider::GoBatchProvider::new(callback, ctx);
+        let handle = handles::batch_providers().insert(provider);
+        unsafe {
+            *out_provider_handle = handle;
+        }
+        Ok(0)
+    })
+}
+
+/// 注销批次提供者（正在执行的扫描持有自己的引用，不受影响）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_batch_provider_free")]
+pub extern "C" fn bridge_batch_provider_free(provider_handle: u64) {
+    if provider_handle != 0 {
+        handles::batch_providers().remove(provider_handle);
+    }
+}
+
+// 5e. 零拷贝导入 Go 持有的数值缓冲区（INT64 / FLOAT64，结果为单列 DataFrame 句柄）
+///
+/// 生命周期：成功后 Polars 直接引用该缓冲区，Go 必须保持其有效、不可修改、不可移动，
+/// 直到 dealloc(ctx) 被调用（最后一个引用释放时，可能发生在任意线程上）。
+/// 只要 dealloc 非空，无论成功与否它都恰好被调用一次。
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_series_from_buffer")]
+pub extern "C" fn bridge_series_from_buffer(
+    name_ptr: *const c_char,
+    name_len: usize,
+    data_type: c_int,
+    data_ptr: *const u8,
+    len: usize,
+    dealloc: Option<extern "C" fn(*mut c_void)>,
+    ctx: *mut c_void,
+    out_df_handle_ptr: *mut u64,
+) -> c_int {
+    ffi_guard!({
+        let dealloc = dealloc
+            .ok_or_else(|| BridgeError::InvalidArgument("Null dealloc callback".into()))?;
+        // 从这里开始，任何提前返回都会释放 owner 并调用 dealloc
+        let owner = buffer::GoAllocation::new(dealloc, ctx);
+        if out_df_handle_ptr.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        let name = read_str(name_ptr, name_len)?;
+        let df = unsafe { buffer::series_from_buffer(name, data_type, data_ptr, len, owner)? };
+
+        let handle = handles::dataframes().insert(df);
+        unsafe {
+            *out_df_handle_ptr = handle;
+        }
+        Ok(0)
+    })
+}
+
+// 5f. 表注册会话（Plan 通过 TableScan 引用逻辑表名，执行时经 ExecutionHints.session_handle 解析）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_session_new")]
+pub extern "C" fn bridge_session_new(out_session_handle: *mut u64) -> c_int {
+    ffi_guard!({
+        if out_session_handle.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        let handle = handles::sessions().insert(session::Session::default());
+        unsafe {
+            *out_session_handle = handle;
+        }
+        Ok(0)
+    })
+}
+
+/// 注册（或替换）一张表：df_handle 非 0 时注册该 DataFrame 的快照（之后释放句柄不影响会话），
+/// 否则注册 node（Node protobuf，通常是扫描节点，执行时才构建）。
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_table_register")]
+pub extern "C" fn bridge_table_register(
+    session_handle: u64,
+    name_ptr: *const c_char,
+    name_len: usize,
+    df_handle: u64,
+    node_bytes_ptr: *const u8,
+    node_bytes_len: usize,
+) -> c_int {
+    ffi_guard!({
+        if session_handle == 0 {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        let session = handles::sessions().get(session_handle)?;
+        let name = read_str(name_ptr, name_len)?;
+        let source = match (df_handle, node_bytes_ptr.is_null()) {
+            (0, true) => {
+                return Err(BridgeError::InvalidArgument(
+                    "Either a DataFrame handle or a node must be provided".into(),
+                ))
+            }
+            (0, false) => {
+                let node_bytes = unsafe { slice::from_raw_parts(node_bytes_ptr, node_bytes_len) };
+                let node = proto::Node::decode(node_bytes)
+                    .map_err(|e| BridgeError::PlanDecode(e.to_string()))?;
+                session::TableSource::Node(Box::new(node))
+            }
+            (_, true) => session::TableSource::DataFrame(handles::dataframes().get(df_handle)?),
+            (_, false) => {
+                return Err(BridgeError::InvalidArgument(
+                    "DataFrame handle and node are mutually exclusive".into(),
+                ))
+            }
+        };
+
+        session.register(name, source)?;
+        Ok(0)
+    })
+}
+
+/// 注销一张表（不存在时为空操作）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_table_unregister")]
+pub extern "C" fn bridge_table_unregister(
+    session_handle: u64,
+    name_ptr: *const c_char,
+    name_len: usize,
+) -> c_int {
+    ffi_guard!({
+        let session = handles::sessions().get(session_handle)?;
+        let name = read_str(name_ptr, name_len)?;
+        session.unregister(name);
+        Ok(0)
+    })
+}
+
+/// 释放会话（正在执行的 Plan 持有自己的引用，不受影响）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_session_free")]
+pub extern "C" fn bridge_session_free(session_handle: u64) {
+    if session_handle != 0 {
+        handles::sessions().remove(session_handle);
+    }
+}
+
+// 5g. 增量聚合（GroupBy Plan 按批次更新状态，随时 finalize 得到当前结果）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_agg_new")]
+pub extern "C" fn bridge_agg_new(plan_handle: u64, out_agg_handle: *mut u64) -> c_int {
+    ffi_guard!({
+        if plan_handle == 0 || out_agg_handle.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        let plan = handles::plans().get(plan_handle)?;
+        let agg = incremental::IncrementalAgg::new(plan.as_ref().clone())?;
+        let handle = handles::aggregators().insert(Mutex::new(agg));
+        unsafe {
+            *out_agg_handle = handle;
+        }
+        Ok(0)
+    })
+}
+
+/// 通过 Arrow C Data Interface 喂入一个批次（数组所有权转移给 bridge）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_agg_update_arrow")]
+pub extern "C" fn bridge_agg_update_arrow(
+    agg_handle: u64,
+    input_schema: *const ArrowSchema,
+    input_array: *const ArrowArray,
+) -> c_int {
+    ffi_guard!({
+        if agg_handle == 0 || input_schema.is_null() || input_array.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        let agg = handles::aggregators().get(agg_handle)?;
+        let mut agg = agg.lock().unwrap_or_else(|e| e.into_inner());
+        let batch = arrow_bridge::import_dataframe_from_arrow(
+            input_schema,
+            input_array,
+            &arrow_bridge::ImportOptions::from_options(agg.plan().options.as_ref()),
+        )?;
+        agg.update(&batch)?;
+        Ok(0)
+    })
+}
+
+/// 喂入一个已有的 DataFrame 句柄（句柄本身不受影响）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_agg_update_df")]
+pub extern "C" fn bridge_agg_update_df(agg_handle: u64, df_handle: u64) -> c_int {
+    ffi_guard!({
+        if agg_handle == 0 || df_handle == 0 {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        let agg = handles::aggregators().get(agg_handle)?;
+        let batch = handles::dataframes().get(df_handle)?;
+        agg.lock().unwrap_or_else(|e| e.into_inner()).update(&batch)?;
+        Ok(0)
+    })
+}
+
+/// 按当前状态计算聚合结果，返回新的 DataFrame 句柄（状态保留，可继续 update）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_agg_finalize")]
+pub extern "C" fn bridge_agg_finalize(agg_handle: u64, out_df_handle: *mut u64) -> c_int {
+    ffi_guard!({
+        if agg_handle == 0 || out_df_handle.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        let agg = handles::aggregators().get(agg_handle)?;
+        let df = agg.lock().unwrap_or_else(|e| e.into_inner()).finalize()?;
+        let handle = handles::dataframes().insert(df);
+        unsafe {
+            *out_df_handle = handle;
+        }
+        Ok(0)
+    })
+}
+
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_agg_free")]
+pub extern "C" fn bridge_agg_free(agg_handle: u64) {
+    if agg_handle != 0 {
+        handles::aggregators().remove(agg_handle);
+    }
+}
+
+// 5h. 协议一致性测试：运行 golden Plan 用例目录，输出 JSON 报告（需调用 bridge_output_free 释放）
+// 用例格式见 conformance::run_corpus；Rust 与 Go 两侧的测试共用同一份用例
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_conformance_run")]
+pub extern "C" fn bridge_conformance_run(
+    dir_ptr: *const c_char,
+    dir_len: usize,
+    out_report_ptr: *mut *mut u8,
+    out_report_len: *mut usize,
+) -> c_int {
+    ffi_guard!({
+        if out_report_ptr.is_null() || out_report_len.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        let dir = read_str(dir_ptr, dir_len)?;
+        let report = conformance::run_corpus(dir)?;
+        let bytes = serde_json::to_vec(&report)
+            .map_err(|e| BridgeError::Execution(format!("Failed to encode conformance report: {}", e)))?;
+        write_output(bytes, out_report_ptr, out_report_len);
+        Ok(0)
+    })
+}
+
+// 5i. 查询录制与回放（录制目录格式见 recorder.rs）
+/// 开启或关闭录制（RecorderOptions protobuf，dir 为空表示关闭）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_recorder_configure")]
+pub extern "C" fn bridge_recorder_configure(options_ptr: *const u8, options_len: usize) -> c_int {
+    ffi_guard!({
+        let options: proto::RecorderOptions = decode_options(options_ptr, options_len)?;
+        recorder::configure(options)?;
+        Ok(0)
+    })
+}
+
+/// 回放一次录制，返回结果 DataFrame 句柄（可用 bridge_result_metadata 查看耗时）
+///
+/// input_df_handle 非 0 时代替录制的输入；录制中没有输入且 Plan 含 MemoryScan 时必须提供。
+/// 凭据默认在录制时被抹掉，访问云存储 / 数据库的 Plan 需要在环境中提供凭据。
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_replay")]
+pub extern "C" fn bridge_replay(
+    dir_ptr: *const c_char,
+    dir_len: usize,
+    input_df_handle: u64,
+    out_df_handle: *mut u64,
+) -> c_int {
+    ffi_guard!({
+        if out_df_handle.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        let dir = read_str(dir_ptr, dir_len)?;
+        let (plan, recorded_input, hints) = recorder::load(dir)?;
+        let input_df = match input_df_handle {
+            0 => recorded_input.map(Arc::new),
+            handle => Some(handles::dataframes().get(handle)?),
+        };
+
+        metadata::reset_cache_hits();
+        let started = Instant::now();
+        let df = executor::execute_plan_unrecorded(&plan, input_df.as_deref(), &hints)?;
+        let elapsed = started.elapsed();
+        let df = Arc::new(df);
+        let handle = handles::dataframes().insert_arc(Arc::clone(&df));
+        metadata::record(handle, &plan, input_df.as_deref(), &df, elapsed, &hints.query_id);
+        unsafe {
+            *out_df_handle = handle;
+        }
+        Ok(0)
+    })
+}
+
+// 5j. 微批流式执行（数据源按批喂入 Plan，结果经 Go 回调推送）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_stream_new")]
+pub extern "C" fn bridge_stream_new(
+    plan_handle: u64,
+    options_ptr: *const u8,
+    options_len: usize,
+    callback: Option<extern "C" fn(*mut c_void, u64, *mut ArrowSchema, *mut ArrowArray) -> c_int>,
+    ctx: *mut c_void,
+    out_stream_handle: *mut u64,
+) -> c_int {
+    ffi_guard!({
+        let callback = callback
+            .ok_or_else(|| BridgeError::InvalidArgument("Null stream result callback".into()))?;
+        if plan_handle == 0 || out_stream_handle.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        let plan = handles::plans().get(plan_handle)?;
+        let options: proto::StreamOptions = decode_options(options_ptr, options_len)?;
+        let stream = stream::MicroBatchStream::new(plan.as_ref().clone(), options, callback, ctx)?;
+        let handle = handles::streams().insert(Mutex::new(stream));
+        unsafe {
+            *out_stream_handle = handle;
+        }
+        Ok(0)
+    })
+}
+
+/// 处理至多 max_batches 个微批（0 表示直到数据源结束），输出累计的 StreamStats（需调用 bridge_output_free 释放）
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_stream_run")]
+pub extern "C" fn bridge_stream_run(
+    stream_handle: u64,
+    max_batches: u64,
+    out_stats_ptr: *mut *mut u8,
+    out_stats_len: *mut usize,
+) -> c_int {
+    ffi_guard!({
+        if stream_handle == 0 || out_stats_ptr.is_null() || out_stats_len.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        let stream = handles::streams().get(stream_handle)?;
+        let stats = stream.lock().unwrap_or_else(|e| e.into_inner()).run(max_batches)?;
+        write_output(stats.encode_to_vec(), out_stats_ptr, out_stats_len);
+        Ok(0)
+    })
+}
+
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_stream_free")]
+pub extern "C" fn bridge_stream_free(stream_handle: u64) {
+    if stream_handle != 0 {
+        handles::streams().remove(stream_handle);
+    }
+}
+
+// 6. 从列数据创建 DataFrame（支持动态类型推断）
+// 数据格式：JSON array of columns
+// [{"name": "col1", "values": [1, 2, 3]}, {"name": "col2", "values": ["a", "b", "c"]}]
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_df_from_columns")]
+pub extern "C" fn bridge_df_from_columns(
+    json_ptr: *const c_char,
+    json_len: usize,
+    out_df_handle: *mut u64,
+) -> c_int {
+    ffi_guard!({
+        if json_ptr.is_null() || out_df_handle.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        // 读取 JSON 数据
+        let json_slice = unsafe { slice::from_raw_parts(json_ptr as *const u8, json_len) };
+        let json_str = std::str::from_utf8(json_slice)
             .map_err(|e| BridgeError::InvalidArgument(format!("Invalid UTF-8: {}", e)))?;
 
         // 解析 JSON
         let columns: Vec<serde_json::Value> = serde_json::from_str(json_str)
             .map_err(|e| BridgeError::InvalidArgument(format!("Invalid JSON: {}", e)))?;
 
+        // 提前检查重复列名，给出明确的错误信息
+        let names = columns.iter()
+            .map(|col| {
+                col["name"].as_str().map(PlSmallStr::from).ok_or_else(|| {
+                    BridgeError::InvalidArgument("Column missing 'name' field".into())
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        dedup_column_names(&names, DuplicatePolicy::Error)?;
+
         // 构建 Series 列表
         let mut series_vec = Vec::new();
         
@@ -423,13 +1753,36 @@ pub extern "C" fn bridge_df_from_columns(
             .map_err(|e| BridgeError::Execution(format!("Failed to create DataFrame: {}", e)))?;
 
         // 存储 DataFrame 并返回句柄
-        let handle = Box::into_raw(Box::new(df)) as u64;
+        let handle = handles::dataframes().insert(df);
         unsafe { *out_df_handle = handle };
 
         Ok(0)
     })
 }
 
+/// 从行式 protobuf（RowBatch）创建 DataFrame，适合小批量的请求数据
+#[export_name = concat!(env!("BRIDGE_SYMBOL_PREFIX"), "bridge_df_from_rows")]
+pub extern "C" fn bridge_df_from_rows(
+    rows_ptr: *const u8,
+    rows_len: usize,
+    out_df_handle: *mut u64,
+) -> c_int {
+    ffi_guard!({
+        if rows_ptr.is_null() || out_df_handle.is_null() {
+            return Err(BridgeError::InvalidArgument("Null pointers".into()));
+        }
+
+        let bytes = unsafe { slice::from_raw_parts(rows_ptr, rows_len) };
+        let batch = proto::RowBatch::decode(bytes)
+            .map_err(|e| BridgeError::InvalidArgument(format!("Invalid RowBatch: {}", e)))?;
+        let df = rows::df_from_rows(&batch)?;
+
+        let handle = handles::dataframes().insert(df);
+        unsafe { *out_df_handle = handle };
+        Ok(0)
+    })
+}
+
 // 辅助函数：将 JSON 值转换为 AnyValue
 fn json_value_to_any_value(v: &serde_json::Value) -> AnyValue<'static> {
     match v {
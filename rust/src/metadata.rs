@@ -0,0 +1,169 @@
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+use std::time::Duration;
+
+use polars::prelude::DataFrame;
+
+use crate::nodes::node_inputs;
+use crate::proto;
+
+fn registry() -> &'static RwLock<HashMap<u64, proto::ResultMetadata>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<u64, proto::ResultMetadata>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+thread_local! {
+    // 当前线程正在进行的执行中的缓存命中次数（Plan 在调用线程上构建）
+    static CACHE_HITS: Cell<u64> = const { Cell::new(0) };
+}
+
+/// 开始一次执行前清零缓存命中计数
+pub fn reset_cache_hits() {
+    CACHE_HITS.with(|hits| hits.set(0));
+}
+
+/// bridge 内部缓存命中时调用，计入本次执行的 cache_hits
+// 目前只有正则缓存（strings feature）调用
+#[cfg_attr(not(feature = "strings"), allow(dead_code))]
+pub fn count_cache_hit() {
+    CACHE_HITS.with(|hits| hits.set(hits.get() + 1));
+}
+
+/// 记录某个结果 DataFrame 句柄的执行元数据
+pub fn record(
+    df_handle: u64,
+    plan: &proto::Plan,
+    input_df: Option<&DataFrame>,
+    result: &DataFrame,
+    elapsed: Duration,
+    query_id: &str,
+) {
+    let metadata = proto::ResultMetadata {
+        rows: result.height() as u64,
+        columns: result.width() as u64,
+        wall_time_us: elapsed.as_micros() as u64,
+        input_file_bytes: input_file_bytes(plan),
+        cache_hits: CACHE_HITS.with(Cell::take),
+        result_bytes: result.estimated_size() as u64,
+        query_id: query_id.to_string(),
+        input_df_bytes: input_df.map(|df| df.estimated_size() as u64).unwrap_or(0),
+    };
+
+    registry()
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(df_handle, metadata);
+}
+
+pub fn get(df_handle: u64) -> Option<proto::ResultMetadata> {
+    registry()
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&df_handle)
+        .cloned()
+}
+
+pub fn remove(df_handle: u64) {
+    registry()
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(&df_handle);
+}
+
+// 只统计能直接 stat 的本地路径：glob 与云存储路径跳过
+fn input_file_bytes(plan: &proto::Plan) -> u64 {
+    let mut paths = Vec::new();
+    if let Some(root) = plan.root.as_ref() {
+        collect_scan_paths(root, &mut paths);
+    }
+
+    paths
+        .iter()
+        .filter(|p| !p.contains(['*', '?', '[']) && !p.contains("://"))
+        .filter_map(|p| std::fs::metadata(p).ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+fn collect_scan_paths<'a>(node: &'a proto::Node, paths: &mut Vec<&'a str>) {
+    use proto::node::Kind;
+
+    match node.kind.as_ref() {
+        Some(Kind::CsvScan(scan)) => {
+            paths.push(&scan.path);
+            paths.extend(scan.paths.iter().map(String::as_str));
+        }
+        Some(Kind::ParquetScan(scan)) => {
+            paths.push(&scan.path);
+            paths.extend(scan.paths.iter().map(String::as_str));
+        }
+        Some(Kind::IpcScan(scan)) => paths.push(&scan.path),
+        _ => {}
+    }
+
+    for input in node_inputs(node) {
+        collect_scan_paths(input, paths);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use polars::prelude::df;
+
+    use super::*;
+    use crate::test_plans::{input, memory_scan, node, plan};
+    use proto::node::Kind as N;
+
+    const CSV: &str = "../testdata/small.csv";
+
+    fn csv_scan(id: u32, path: &str) -> proto::Node {
+        node(id, N::CsvScan(proto::CsvScan { path: path.into(), ..Default::default() }))
+    }
+
+    #[test]
+    fn input_file_bytes_counts_local_scan_files() {
+        let csv_len = std::fs::metadata(CSV).unwrap().len();
+        let mut scan = csv_scan(1, CSV);
+        if let Some(N::CsvScan(csv)) = scan.kind.as_mut() {
+            // glob、云存储与不存在的路径都不计入
+            csv.paths = vec![
+                CSV.into(),
+                "../testdata/*.csv".into(),
+                "s3://bucket/small.csv".into(),
+                "../testdata/missing.csv".into(),
+            ];
+        }
+        let join = node(3, N::Join(Box::new(proto::Join {
+            left: input(scan),
+            right: input(csv_scan(2, CSV)),
+            ..Default::default()
+        })));
+        assert_eq!(input_file_bytes(&plan(join)), 3 * csv_len);
+        assert_eq!(input_file_bytes(&plan(memory_scan())), 0);
+    }
+
+    #[test]
+    fn record_separates_file_and_in_memory_input() {
+        let input_df = df!("a" => [1i64, 2, 3]).unwrap();
+        let result = df!("a" => [1i64]).unwrap();
+        let handle = u64::MAX;
+
+        let limit = proto::Limit { input: input(memory_scan()), n: 1 };
+        let memory_plan = plan(node(2, N::Limit(Box::new(limit))));
+        record(handle, &memory_plan, Some(&input_df), &result, Duration::from_micros(7), "q");
+        let metadata = get(handle).unwrap();
+        assert_eq!(metadata.input_file_bytes, 0);
+        assert_eq!(metadata.input_df_bytes, input_df.estimated_size() as u64);
+        assert_eq!((metadata.rows, metadata.columns, metadata.wall_time_us), (1, 1, 7));
+        assert_eq!(metadata.query_id, "q");
+
+        record(handle, &plan(csv_scan(1, CSV)), None, &result, Duration::ZERO, "");
+        let metadata = get(handle).unwrap();
+        assert_eq!(metadata.input_file_bytes, std::fs::metadata(CSV).unwrap().len());
+        assert_eq!(metadata.input_df_bytes, 0);
+
+        remove(handle);
+        assert!(get(handle).is_none());
+    }
+}
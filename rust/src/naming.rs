@@ -0,0 +1,124 @@
+use polars::prelude::*;
+
+use crate::error::BridgeError;
+use crate::proto;
+
+/// 未命名派生表达式（不引用任何列，如字面量、len()）的输出列命名策略
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum NamingPolicy {
+    PolarsDefault,
+    Positional,
+    RequireAlias,
+}
+
+impl NamingPolicy {
+    pub fn from_options(options: Option<&proto::PlanOptions>) -> Self {
+        use proto::OutputNaming as Naming;
+
+        match options.map(|opts| Naming::try_from(opts.output_naming)) {
+            Some(Ok(Naming::Positional)) => NamingPolicy::Positional,
+            Some(Ok(Naming::RequireAlias)) => NamingPolicy::RequireAlias,
+            _ => NamingPolicy::PolarsDefault,
+        }
+    }
+}
+
+/// 按策略处理节点输出表达式的列名：Positional 命名为 `column_<位置>`，RequireAlias 直接报错
+pub fn apply_naming(
+    exprs: Vec<Expr>,
+    sources: &[proto::Expr],
+    policy: NamingPolicy,
+    node: &str,
+) -> Result<Vec<Expr>, BridgeError> {
+    if policy == NamingPolicy::PolarsDefault {
+        return Ok(exprs);
+    }
+
+    exprs
+        .into_iter()
+        .zip(sources)
+        .enumerate()
+        .map(|(idx, (expr, source))| {
+            if !is_anonymous(&expr, source) {
+                return Ok(expr);
+            }
+            match policy {
+                NamingPolicy::RequireAlias => Err(BridgeError::PlanSemantic(format!(
+                    "{} expression #{} has no column input and requires an Alias",
+                    node, idx
+                ))),
+                _ => Ok(expr.alias(format!("column_{}", idx))),
+            }
+        })
+        .collect()
+}
+
+// 不引用任何列的表达式由 Polars 命名为 "literal"、"len" 等，对调用方没有意义
+fn is_anonymous(expr: &Expr, source: &proto::Expr) -> bool {
+    use proto::expr::Kind;
+
+    if matches!(
+        source.kind,
+        Some(Kind::Alias(_)) | Some(Kind::Col(_)) | Some(Kind::Wildcard(_)) | Some(Kind::Exclude(_))
+    ) {
+        return false;
+    }
+    expr.clone().meta().root_names().is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use polars::prelude::{df, DataFrame};
+
+    use crate::error::BridgeError;
+    use crate::executor::execute_plan_df;
+    use crate::proto;
+    use crate::test_plans::{alias, binary, col, expr, lit, memory_scan, select};
+    use proto::expr::Kind as E;
+    use proto::literal::Value;
+
+    fn run(naming: proto::OutputNaming) -> Result<DataFrame, BridgeError> {
+        let mut plan = select(
+            memory_scan(),
+            vec![
+                alias(col("v"), "original"),
+                lit(Value::IntVal(7)),
+                alias(expr(E::Len(proto::Len {})), "rows"),
+                binary(col("v"), proto::BinaryOperator::Add, lit(Value::IntVal(1))),
+            ],
+        );
+        plan.options = Some(proto::PlanOptions { output_naming: naming as i32, ..Default::default() });
+        execute_plan_df(&plan, Some(&df!("v" => [1i64, 2]).unwrap()))
+    }
+
+    fn names(df: &DataFrame) -> Vec<String> {
+        df.get_column_names().iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn positional_names_only_anonymous_expressions() {
+        let out = run(proto::OutputNaming::PolarsDefault).unwrap();
+        assert_eq!(names(&out), ["original", "literal", "rows", "v"]);
+
+        let out = run(proto::OutputNaming::Positional).unwrap();
+        assert_eq!(names(&out), ["original", "column_1", "rows", "v"]);
+        let expected = df!(
+            "original" => [1i64, 2],
+            "column_1" => [7i32, 7],
+            "rows" => [2u32, 2],
+            "v" => [2i64, 3],
+        )
+        .unwrap();
+        assert!(out.equals(&expected), "{out}");
+    }
+
+    #[test]
+    fn require_alias_rejects_anonymous_expressions() {
+        let err = run(proto::OutputNaming::RequireAlias).unwrap_err();
+        assert!(
+            matches!(&err, BridgeError::PlanSemantic(m)
+                if m == "Project expression #1 has no column input and requires an Alias"),
+            "{err:?}"
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use crate::proto;
+
+/// 节点类型名称（与 capabilities 中的 supported_nodes 一致）
+pub fn node_name(node: &proto::Node) -> &'static str {
+    use proto::node::Kind;
+
+    match node.kind.as_ref() {
+        Some(Kind::MemoryScan(_)) => "MemoryScan",
+        Some(Kind::CsvScan(_)) => "CsvScan",
+        Some(Kind::ParquetScan(_)) => "ParquetScan",
+        Some(Kind::IpcScan(_)) => "IpcScan",
+        Some(Kind::AvroScan(_)) => "AvroScan",
+        Some(Kind::ExcelScan(_)) => "ExcelScan",
+        Some(Kind::IcebergScan(_)) => "IcebergScan",
+        Some(Kind::DatabaseScan(_)) => "DatabaseScan",
+        Some(Kind::FlightScan(_)) => "FlightScan",
+        Some(Kind::ReaderScan(_)) => "ReaderScan",
+        Some(Kind::BatchProviderScan(_)) => "BatchProviderScan",
+        Some(Kind::TableScan(_)) => "TableScan",
+        Some(Kind::DeltaScan(_)) => "DeltaScan",
+        Some(Kind::Project(_)) => "Project",
+        Some(Kind::Filter(_)) => "Filter",
+        Some(Kind::Assert(_)) => "Assert",
+        Some(Kind::WithColumns(_)) => "WithColumns",
+        Some(Kind::Limit(_)) => "Limit",
+        Some(Kind::SinkParquet(_)) => "SinkParquet",
+        Some(Kind::SinkCsv(_)) => "SinkCsv",
+        Some(Kind::SinkNdjson(_)) => "SinkNdjson",
+        Some(Kind::SinkIpc(_)) => "SinkIpc",
+        Some(Kind::GroupBy(_)) => "GroupBy",
+        Some(Kind::GroupByDynamic(_)) => "GroupByDynamic",
+        Some(Kind::JoinWhere(_)) => "JoinWhere",
+        Some(Kind::Join(_)) => "Join",
+        Some(Kind::Upsert(_)) => "Upsert",
+        None => "Unknown",
+    }
+}
+
+/// 子树中是否有只能读取一次的数据源（ReaderScan 的 Go 读取器在执行时被消费）
+pub fn reads_once(node: &proto::Node) -> bool {
+    matches!(node.kind, Some(proto::node::Kind::ReaderScan(_)))
+        || node_inputs(node).into_iter().any(reads_once)
+}
+
+/// 节点的直接输入（按 proto 中的声明顺序）
+pub fn node_inputs(node: &proto::Node) -> Vec<&proto::Node> {
+    use proto::node::Kind;
+
+    let inputs = match node.kind.as_ref() {
+        Some(Kind::MemoryScan(_))
+        | Some(Kind::CsvScan(_))
+        | Some(Kind::ParquetScan(_))
+        | Some(Kind::IpcScan(_))
+        | Some(Kind::AvroScan(_))
+        | Some(Kind::ExcelScan(_))
+        | Some(Kind::IcebergScan(_))
+        | Some(Kind::DatabaseScan(_))
+        | Some(Kind::FlightScan(_))
+        | Some(Kind::ReaderScan(_))
+        | Some(Kind::BatchProviderScan(_))
+        | Some(Kind::TableScan(_))
+        | Some(Kind::DeltaScan(_))
+        | None => vec![],
+        Some(Kind::Project(n)) => vec![n.input.as_deref()],
+        Some(Kind::Filter(n)) => vec![n.input.as_deref()],
+        Some(Kind::Assert(n)) => vec![n.input.as_deref()],
+        Some(Kind::WithColumns(n)) => vec![n.input.as_deref()],
+        Some(Kind::Limit(n)) => vec![n.input.as_deref()],
+        Some(Kind::SinkParquet(n)) => vec![n.input.as_deref()],
+        Some(Kind::SinkCsv(n)) => vec![n.input.as_deref()],
+        Some(Kind::SinkNdjson(n)) => vec![n.input.as_deref()],
+        Some(Kind::SinkIpc(n)) => vec![n.input.as_deref()],
+        Some(Kind::GroupBy(n)) => vec![n.input.as_deref()],
+        Some(Kind::GroupByDynamic(n)) => vec![n.input.as_deref()],
+        Some(Kind::JoinWhere(n)) => vec![n.left.as_deref(), n.right.as_deref()],
+        Some(Kind::Join(n)) => vec![n.left.as_deref(), n.right.as_deref()],
+        Some(Kind::Upsert(n)) => vec![n.target.as_deref(), n.source.as_deref()],
+    };
+
+    inputs.into_iter().flatten().collect()
+}
+
+/// 节点的直接输入（可变引用）
+pub fn node_inputs_mut(node: &mut proto::Node) -> Vec<&mut proto::Node> {
+    use proto::node::Kind;
+
+    let inputs = match node.kind.as_mut() {
+        Some(Kind::MemoryScan(_))
+        | Some(Kind::CsvScan(_))
+        | Some(Kind::ParquetScan(_))
+        | Some(Kind::IpcScan(_))
+        | Some(Kind::AvroScan(_))
+        | Some(Kind::ExcelScan(_))
+        | Some(Kind::IcebergScan(_))
+        | Some(Kind::DatabaseScan(_))
+        | Some(Kind::FlightScan(_))
+        | Some(Kind::ReaderScan(_))
+        | Some(Kind::BatchProviderScan(_))
+        | Some(Kind::TableScan(_))
+        | Some(Kind::DeltaScan(_))
+        | None => vec![],
+        Some(Kind::Project(n)) => vec![n.input.as_deref_mut()],
+        Some(Kind::Filter(n)) => vec![n.input.as_deref_mut()],
+        Some(Kind::Assert(n)) => vec![n.input.as_deref_mut()],
+        Some(Kind::WithColumns(n)) => vec![n.input.as_deref_mut()],
+        Some(Kind::Limit(n)) => vec![n.input.as_deref_mut()],
+        Some(Kind::SinkParquet(n)) => vec![n.input.as_deref_mut()],
+        Some(Kind::SinkCsv(n)) => vec![n.input.as_deref_mut()],
+        Some(Kind::SinkNdjson(n)) => vec![n.input.as_deref_mut()],
+        Some(Kind::SinkIpc(n)) => vec![n.input.as_deref_mut()],
+        Some(Kind::GroupBy(n)) => vec![n.input.as_deref_mut()],
+        Some(Kind::GroupByDynamic(n)) => vec![n.input.as_deref_mut()],
+        Some(Kind::JoinWhere(n)) => vec![n.left.as_deref_mut(), n.right.as_deref_mut()],
+        Some(Kind::Join(n)) => vec![n.left.as_deref_mut(), n.right.as_deref_mut()],
+        Some(Kind::Upsert(n)) => vec![n.target.as_deref_mut(), n.source.as_deref_mut()],
+    };
+
+    inputs.into_iter().flatten().collect()
+}
@@ -0,0 +1,208 @@
+use std::fs::File;
+
+use polars::prelude::*;
+use polars_arrow::datatypes::{ArrowDataType, PhysicalType, PrimitiveType};
+use polars_parquet::parquet::error::ParquetError;
+use polars_parquet::write::{
+    array_to_columns, transverse, ChildWriteOptions, ColumnWriteOptions, Compressor, DynIter,
+    DynStreamingIterator, Encoding, FallibleStreamingIterator, FieldWriteOptions, FileWriter,
+    ListLikeFieldWriteOptions, StructFieldWriteOptions, Version, WriteOptions,
+};
+
+use crate::error::BridgeError;
+use crate::proto;
+use crate::sink::parquet_compression;
+
+// 未设置 row_group_size 时每个行组的行数（与 Polars 默认一致）
+const DEFAULT_ROW_GROUP_SIZE: usize = 512 * 512;
+
+/// 带按列编码 / 压缩覆盖的 SinkParquet：collect 时收集结果，再按行组逐列编码写出
+///
+/// Polars 的 Parquet 写入只支持文件级的压缩与按类型选择的编码，因此这里直接使用 polars-parquet 组装行组。
+pub fn sink_parquet_columns(lf: LazyFrame, sink: &proto::SinkParquet) -> Result<LazyFrame, BridgeError> {
+    // 构建阶段先校验选项，避免执行到写出时才报错
+    for column in &sink.columns {
+        if column.name.is_empty() {
+            return Err(BridgeError::InvalidArgument("SinkParquet column options need a name".into()));
+        }
+        write_options(sink, Some(column))?;
+        if column.dictionary == Some(true) && column.encoding != proto::ParquetColumnEncoding::Default as i32 {
+            return Err(BridgeError::InvalidArgument(format!(
+                "SinkParquet column '{}' cannot set both dictionary and encoding",
+                column.name
+            )));
+        }
+    }
+
+    let sink = proto::SinkParquet {
+        input: None,
+        ..sink.clone()
+    };
+    let function = move |df: DataFrame| {
+        write_parquet(&df, &sink).map_err(|e| PolarsError::ComputeError(e.to_string().into()))?;
+        Ok(DataFrame::empty())
+    };
+    Ok(lf.map(function, AllowedOptimizations::default(), None, Some("SINK_PARQUET")))
+}
+
+fn write_parquet(df: &DataFrame, sink: &proto::SinkParquet) -> Result<(), BridgeError> {
+    let to_error = |e: PolarsError| {
+        BridgeError::Execution(format!("SinkParquet failed for '{}': {}", sink.path, e))
+    };
+
+    if let Some(column) = sink.columns.iter().find(|c| df.column(&c.name).is_err()) {
+        return Err(BridgeError::InvalidArgument(format!(
+            "SinkParquet column options refer to unknown column '{}'",
+            column.name
+        )));
+    }
+
+    let arrow_schema = df.schema().to_arrow(CompatLevel::newest());
+    let columns = arrow_schema
+        .iter_values()
+        .map(|field| {
+            let column = sink.columns.iter().find(|c| c.name == field.name.as_str());
+            Ok((write_options(sink, column)?, column_encodings(&field.dtype, column)?))
+        })
+        .collect::<Result<Vec<_>, BridgeError>>()?;
+    let column_options: Vec<ColumnWriteOptions> =
+        columns.iter().map(|(_, options)| options.clone()).collect();
+
+    let file = File::create(&sink.path).map_err(|e| {
+        BridgeError::Execution(format!("Failed to create '{}': {}", sink.path, e))
+    })?;
+    let mut writer =
+        FileWriter::try_new(file, arrow_schema, write_options(sink, None)?, &column_options)
+            .map_err(to_error)?;
+    let parquet_fields = writer.parquet_schema().fields().to_vec();
+
+    let row_group_size = sink
+        .row_group_size
+        .map(|n| n as usize)
+        .filter(|n| *n > 0)
+        .unwrap_or(DEFAULT_ROW_GROUP_SIZE);
+    for offset in (0..df.height()).step_by(row_group_size) {
+        let mut chunk = df.slice(offset as i64, row_group_size);
+        chunk.rechunk_mut();
+
+        for batch in chunk.iter_chunks(CompatLevel::newest(), false) {
+            let mut row_group = Vec::new();
+            for ((array, type_), (options, encodings)) in
+                batch.into_arrays().into_iter().zip(&parquet_fields).zip(&columns)
+            {
+                let compression = options.compression;
+                for pages in array_to_columns(array, type_.clone(), encodings, *options).map_err(to_error)? {
+                    let pages = DynIter::new(pages.map(|page| {
+                        page.map_err(|e| ParquetError::FeatureNotSupported(e.to_string()))
+                    }));
+                    let compressed = Compressor::new(pages, compression, vec![]).map_err(PolarsError::from);
+                    row_group.push(Ok(DynStreamingIterator::new(compressed)));
+                }
+            }
+            writer.write(DynIter::new(row_group.into_iter())).map_err(to_error)?;
+        }
+    }
+    writer.end(None, &column_options).map_err(to_error)?;
+    Ok(())
+}
+
+// 列级设置覆盖文件级设置；只覆盖压缩级别时沿用文件级的压缩算法
+fn write_options(
+    sink: &proto::SinkParquet,
+    column: Option<&proto::ParquetColumnOptions>,
+) -> Result<WriteOptions, BridgeError> {
+    let (compression, level) = match column {
+        Some(column) => match column.compression {
+            Some(compression) => (compression, column.compression_level),
+            None => (sink.compression, column.compression_level.or(sink.compression_level)),
+        },
+        None => (sink.compression, sink.compression_level),
+    };
+    let compression = parquet_compression(compression, level)?;
+    let statistics = match sink.statistics {
+        Some(false) => StatisticsOptions::empty(),
+        _ => StatisticsOptions::default(),
+    };
+
+    Ok(WriteOptions {
+        statistics,
+        version: Version::V1,
+        compression: compression.into(),
+        data_page_size: sink.data_page_size.map(|n| n as usize),
+    })
+}
+
+// 每个叶子列一个编码（嵌套列的所有叶子使用同一规则）
+fn column_encodings(
+    dtype: &ArrowDataType,
+    column: Option<&proto::ParquetColumnOptions>,
+) -> Result<ColumnWriteOptions, BridgeError> {
+    use proto::ParquetColumnEncoding as ColumnEncoding;
+
+    let Some(column) = column else {
+        return Ok(leaf_encodings(dtype, &default_encoding));
+    };
+    let encoding = ColumnEncoding::try_from(column.encoding).map_err(|_| {
+        BridgeError::Unsupported(format!("Unknown parquet column encoding: {}", column.encoding))
+    })?;
+
+    match (encoding, column.dictionary) {
+        (ColumnEncoding::ByteStreamSplit, _) => {
+            if !transverse(dtype, is_float).into_iter().all(|float| float) {
+                return Err(BridgeError::InvalidArgument(format!(
+                    "SinkParquet column '{}': BYTE_STREAM_SPLIT requires a Float32/Float64 column, got {:?}",
+                    column.name, dtype
+                )));
+            }
+            Ok(leaf_encodings(dtype, &|_| Encoding::ByteStreamSplit))
+        }
+        (ColumnEncoding::Plain, _) | (ColumnEncoding::Default, Some(false)) => {
+            Ok(leaf_encodings(dtype, &|_| Encoding::Plain))
+        }
+        (ColumnEncoding::Default, Some(true)) => Ok(leaf_encodings(dtype, &|_| Encoding::RleDictionary)),
+        (ColumnEncoding::Default, None) => Ok(leaf_encodings(dtype, &default_encoding)),
+    }
+}
+
+// 按 Arrow 类型的嵌套结构构造列写入选项，叶子列的编码由 encoding 决定
+fn leaf_encodings(dtype: &ArrowDataType, encoding: &dyn Fn(&ArrowDataType) -> Encoding) -> ColumnWriteOptions {
+    let children = match dtype.to_logical_type() {
+        ArrowDataType::List(inner) | ArrowDataType::LargeList(inner) | ArrowDataType::FixedSizeList(inner, _) => {
+            ChildWriteOptions::ListLike(Box::new(ListLikeFieldWriteOptions {
+                child: leaf_encodings(&inner.dtype, encoding),
+            }))
+        }
+        ArrowDataType::Struct(fields) if !fields.is_empty() => {
+            ChildWriteOptions::Struct(Box::new(StructFieldWriteOptions {
+                children: fields.iter().map(|field| leaf_encodings(&field.dtype, encoding)).collect(),
+            }))
+        }
+        // 空结构体按布尔列写出（与 Polars 一致）
+        ArrowDataType::Struct(_) => ChildWriteOptions::Leaf(FieldWriteOptions { encoding: Encoding::Rle }),
+        _ => ChildWriteOptions::Leaf(FieldWriteOptions { encoding: encoding(dtype) }),
+    };
+    ColumnWriteOptions::default_with(children)
+}
+
+// 与 Polars 写入时按类型选择的编码一致：浮点列 PLAIN，其余可字典编码的列使用 RLE_DICTIONARY
+fn default_encoding(dtype: &ArrowDataType) -> Encoding {
+    match dtype.to_physical_type() {
+        PhysicalType::Dictionary(_)
+        | PhysicalType::LargeBinary
+        | PhysicalType::LargeUtf8
+        | PhysicalType::Utf8View
+        | PhysicalType::BinaryView => Encoding::RleDictionary,
+        PhysicalType::Primitive(PrimitiveType::Float16 | PrimitiveType::Float32 | PrimitiveType::Float64) => {
+            Encoding::Plain
+        }
+        PhysicalType::Primitive(_) => Encoding::RleDictionary,
+        _ => Encoding::Plain,
+    }
+}
+
+fn is_float(dtype: &ArrowDataType) -> bool {
+    matches!(
+        dtype.to_physical_type(),
+        PhysicalType::Primitive(PrimitiveType::Float32 | PrimitiveType::Float64)
+    )
+}
@@ -0,0 +1,461 @@
+use std::any::Any;
+use std::ffi::c_void;
+use std::io::{Cursor, Read};
+use std::os::raw::c_int;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use polars::prelude::*;
+
+use crate::error::BridgeError;
+use crate::frame_builder::FrameBuilder;
+use crate::handles;
+use crate::proto;
+use crate::sink::parse_ascii_char;
+
+// 每次从 Go 读取器拉取的字节数
+const CHUNK_SIZE: usize = 1 << 20;
+// 推断 CSV/NDJSON schema 使用的记录数（与 Polars 默认的 infer_schema_length 一致）
+const INFER_SCHEMA_ROWS: usize = 100;
+
+/// Go 侧提供的读取回调：把最多 len 字节写入 buf，实际字节数写入 out_read（0 表示 EOF），返回非 0 表示错误
+pub type ReadCallback =
+    extern "C" fn(ctx: *mut c_void, buf: *mut u8, len: usize, out_read: *mut usize) -> c_int;
+
+/// 已注册的 Go 读取器（只能被 ReaderScan 消费一次）
+pub struct GoReader {
+    callback: ReadCallback,
+    ctx: *mut c_void,
+    // 构建计划时为推断 schema 预读的数据，执行时先于剩余数据读出
+    prefix: Mutex<Prefix>,
+}
+
+#[derive(Default)]
+struct Prefix {
+    bytes: Vec<u8>,
+    eof: bool,
+}
+
+// ctx 是 Go 侧的不透明句柄（如 cgo.Handle），不会在 Rust 侧解引用；
+// 回调自身负责线程安全，因此可以在注册表中跨线程传递
+unsafe impl Send for GoReader {}
+unsafe impl Sync for GoReader {}
+
+impl GoReader {
+    pub fn new(callback: ReadCallback, ctx: *mut c_void) -> Self {
+        GoReader {
+            callback,
+            ctx,
+            prefix: Mutex::new(Prefix::default()),
+        }
+    }
+
+    // 预读直到 enough 返回 true 或到达 EOF；返回的数据仍留在读取器中
+    fn buffered(
+        &self,
+        enough: impl Fn(&[u8]) -> bool,
+    ) -> std::io::Result<MutexGuard<'_, Prefix>> {
+        let mut prefix = self.prefix.lock().unwrap_or_else(|e| e.into_inner());
+        while !prefix.eof && !enough(&prefix.bytes) {
+            let start = prefix.bytes.len();
+            prefix.bytes.resize(start + CHUNK_SIZE, 0);
+            let mut reader = self;
+            let read = match reader.read(&mut prefix.bytes[start..]) {
+                Ok(read) => read,
+                Err(e) => {
+                    prefix.bytes.truncate(start);
+                    return Err(e);
+                }
+            };
+            prefix.bytes.truncate(start + read);
+            prefix.eof = read == 0;
+        }
+        Ok(prefix)
+    }
+
+    // 执行时的数据流：先读出预读的数据，再继续从回调读取
+    fn stream(&self) -> impl Read + '_ {
+        let prefix = std::mem::take(&mut *self.prefix.lock().unwrap_or_else(|e| e.into_inner()));
+        let rest: Box<dyn Read + '_> = if prefix.eof {
+            Box::new(std::io::empty())
+        } else {
+            Box::new(self)
+        };
+        Cursor::new(prefix.bytes).chain(rest)
+    }
+}
+
+impl Read for &GoReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut read = 0usize;
+        let status = (self.callback)(self.ctx, buf.as_mut_ptr(), buf.len(), &mut read);
+        if status != 0 {
+            let message = format!("Go reader callback failed with status {}", status);
+            return Err(std::io::Error::other(message));
+        }
+        Ok(read.min(buf.len()))
+    }
+}
+
+/// ReaderScan：从 Go 读取器按格式解析数据
+///
+/// 构建计划时只预读推断 schema 所需的数据，读取器在执行（collect）时才从注册表中取出并消费；
+/// CSV/NDJSON 按记录分块拉取与解析，并应用投影与行数限制（达到行数后不再读取），
+/// Parquet/IPC 需要随机访问，因此会先缓冲全部数据。
+pub fn reader_scan(scan: &proto::ReaderScan) -> Result<LazyFrame, BridgeError> {
+    let reader = handles::readers().get(scan.reader_id).map_err(|_| {
+        BridgeError::InvalidArgument(format!(
+            "Invalid or already consumed reader handle: {}",
+            scan.reader_id
+        ))
+    })?;
+    let format = proto::ReaderFormat::try_from(scan.format).map_err(|_| {
+        BridgeError::Unsupported(format!("Unknown reader format: {}", scan.format))
+    })?;
+    #[cfg(not(feature = "parquet"))]
+    if format == proto::ReaderFormat::Parquet {
+        return Err(crate::features::unavailable("ReaderScan with Parquet format", "parquet"));
+    }
+
+    let mut parse_options = CsvParseOptions::default();
+    if !scan.separator.is_empty() {
+        parse_options.separator = parse_ascii_char(&scan.separator, "ReaderScan separator")?;
+    }
+    let mut source = ReaderSource {
+        reader_id: scan.reader_id,
+        format,
+        has_header: scan.has_header.unwrap_or(true),
+        parse_options,
+        schema: Arc::new(Schema::default()),
+    };
+    source.schema = source.infer_schema(&reader).map_err(|e| {
+        BridgeError::Execution(format!("ReaderScan failed to infer schema: {}", e))
+    })?;
+
+    let args = ScanArgsAnonymous {
+        schema: Some(source.schema.clone()),
+        name: "READER",
+        ..Default::default()
+    };
+    LazyFrame::anonymous_scan(Arc::new(source), args)
+        .map_err(|e| BridgeError::Execution(format!("ReaderScan failed: {}", e)))
+}
+
+struct ReaderSource {
+    reader_id: u64,
+    format: proto::ReaderFormat,
+    has_header: bool,
+    parse_options: CsvParseOptions,
+    schema: SchemaRef,
+}
+
+impl ReaderSource {
+    fn quote_char(&self) -> Option<u8> {
+        match self.format {
+            proto::ReaderFormat::Csv => self.parse_options.quote_char,
+            // JSON 字符串中不会出现未转义的换行
+            _ => None,
+        }
+    }
+
+    fn infer_schema(&self, reader: &GoReader) -> PolarsResult<SchemaRef> {
+        let quote = self.quote_char();
+        let df = match self.format {
+            proto::ReaderFormat::Csv | proto::ReaderFormat::Ndjson => {
+                let records = INFER_SCHEMA_ROWS + usize::from(self.has_header);
+                let prefix = reader
+                    .buffered(|bytes| record_ends(bytes, quote).nth(records - 1).is_some())
+                    .map_err(read_error)?;
+                let end = record_ends(&prefix.bytes, quote)
+                    .nth(records - 1)
+                    .unwrap_or(prefix.bytes.len());
+                if self.format == proto::ReaderFormat::Csv {
+                    self.csv_options(self.has_header, None)
+                        .into_reader_with_file_handle(Cursor::new(&prefix.bytes[..end]))
+                        .finish()?
+                } else {
+                    JsonReader::new(Cursor::new(&prefix.bytes[..end]))
+                        .with_json_format(JsonFormat::JsonLines)
+                        .finish()?
+                }
+            }
+            proto::ReaderFormat::Ipc => {
+                let prefix = reader.buffered(|_| false).map_err(read_error)?;
+                IpcReader::new(Cursor::new(prefix.bytes.as_slice()))
+                    .with_n_rows(Some(0))
+                    .finish()?
+            }
+            #[cfg(feature = "parquet")]
+            proto::ReaderFormat::Parquet => {
+                let prefix = reader.buffered(|_| false).map_err(read_error)?;
+                ParquetReader::new(Cursor::new(prefix.bytes.as_slice()))
+                    .with_slice(Some((0, 0)))
+                    .finish()?
+            }
+            #[cfg(not(feature = "parquet"))]
+            proto::ReaderFormat::Parquet => unreachable!("rejected in reader_scan"),
+        };
+        Ok(df.schema().clone())
+    }
+
+    fn csv_options(&self, has_header: bool, schema: Option<SchemaRef>) -> CsvReadOptions {
+        CsvReadOptions::default()
+            .with_has_header(has_header)
+            .with_schema(schema)
+            .with_parse_options(self.parse_options.clone())
+    }
+
+    // 按记录分块读取，每块解析后立即投影；读到 n_rows 行后停止拉取
+    fn scan_records(
+        &self,
+        reader: &GoReader,
+        columns: Option<&[PlSmallStr]>,
+        n_rows: Option<usize>,
+    ) -> PolarsResult<DataFrame> {
+        let mut records = Records {
+            source: reader.stream(),
+            buffer: Vec::new(),
+            quote: self.quote_char(),
+            eof: false,
+        };
+        let mut frame = FrameBuilder::default();
+        let mut rows = 0usize;
+        let mut first = true;
+        while n_rows.is_none_or(|n| rows < n) {
+            let Some(chunk) = records.next_chunk().map_err(read_error)? else {
+                break;
+            };
+            let cursor = Cursor::new(chunk);
+            let df = match self.format {
+                proto::ReaderFormat::Csv => self
+                    .csv_options(first && self.has_header, Some(self.schema.clone()))
+                    .with_raise_if_empty(false)
+                    .into_reader_with_file_handle(cursor)
+                    .finish()?,
+                _ => JsonReader::new(cursor)
+                    .with_json_format(JsonFormat::JsonLines)
+                    .with_schema(self.schema.clone())
+                    .finish()?,
+            };
+            first = false;
+            let df = match columns {
+                Some(columns) => df.select(columns.iter().cloned())?,
+                None => df,
+            };
+            rows += df.height();
+            frame.append(df).map_err(to_polars)?;
+        }
+
+        let df = frame.finish();
+        if df.width() > 0 {
+            return Ok(df);
+        }
+        let empty = DataFrame::empty_with_schema(&self.schema);
+        match columns {
+            Some(columns) => empty.select(columns.iter().cloned()),
+            None => Ok(empty),
+        }
+    }
+}
+
+impl AnonymousScan for ReaderSource {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self, _infer_schema_length: Option<usize>) -> PolarsResult<SchemaRef> {
+        Ok(self.schema.clone())
+    }
+
+    fn allows_projection_pushdown(&self) -> bool {
+        true
+    }
+
+    fn allows_slice_pushdown(&self) -> bool {
+        true
+    }
+
+    fn scan(&self, scan_opts: AnonymousScanArgs) -> PolarsResult<DataFrame> {
+        let reader = handles::readers().remove(self.reader_id).ok_or_else(|| {
+            polars_err!(ComputeError: "Reader handle {} was already consumed by another scan", self.reader_id)
+        })?;
+        let columns = scan_opts.with_columns.as_deref();
+        let column_names = || columns.map(|c| c.iter().map(|c| c.to_string()).collect());
+
+        let df = match self.format {
+            proto::ReaderFormat::Csv | proto::ReaderFormat::Ndjson => {
+                self.scan_records(&reader, columns, scan_opts.n_rows)?
+            }
+            proto::ReaderFormat::Ipc => {
+                let mut bytes = Vec::new();
+                reader.stream().read_to_end(&mut bytes).map_err(read_error)?;
+                IpcReader::new(Cursor::new(bytes))
+                    .with_columns(column_names())
+                    .with_n_rows(scan_opts.n_rows)
+                    .finish()?
+            }
+            #[cfg(feature = "parquet")]
+            proto::ReaderFormat::Parquet => {
+                let mut bytes = Vec::new();
+                reader.stream().read_to_end(&mut bytes).map_err(read_error)?;
+                ParquetReader::new(Cursor::new(bytes))
+                    .with_columns(column_names())
+                    .with_slice(scan_opts.n_rows.map(|n| (0, n)))
+                    .finish()?
+            }
+            #[cfg(not(feature = "parquet"))]
+            proto::ReaderFormat::Parquet => unreachable!("rejected in reader_scan"),
+        };
+
+        Ok(match scan_opts.n_rows {
+            Some(n) => df.head(Some(n)),
+            None => df,
+        })
+    }
+}
+
+// 按完整记录切分的字节流（引号内的换行不作为记录边界）
+struct Records<R> {
+    source: R,
+    buffer: Vec<u8>,
+    quote: Option<u8>,
+    eof: bool,
+}
+
+impl<R: Read> Records<R> {
+    fn next_chunk(&mut self) -> std::io::Result<Option<Vec<u8>>> {
+        loop {
+            if self.eof {
+                return Ok(Some(std::mem::take(&mut self.buffer)).filter(|b| !b.is_empty()));
+            }
+
+            let start = self.buffer.len();
+            self.buffer.resize(start + CHUNK_SIZE, 0);
+            let read = match self.source.read(&mut self.buffer[start..]) {
+                Ok(read) => read,
+                Err(e) => {
+                    self.buffer.truncate(start);
+                    return Err(e);
+                }
+            };
+            self.buffer.truncate(start + read);
+            if read == 0 {
+                self.eof = true;
+                continue;
+            }
+
+            if let Some(end) = record_ends(&self.buffer, self.quote).last() {
+                let rest = self.buffer.split_off(end);
+                return Ok(Some(std::mem::replace(&mut self.buffer, rest)));
+            }
+        }
+    }
+}
+
+// 每条记录结束（换行之后）的偏移
+fn record_ends(bytes: &[u8], quote: Option<u8>) -> impl Iterator<Item = usize> + '_ {
+    let mut quoted = false;
+    bytes.iter().enumerate().filter_map(move |(i, &b)| {
+        if Some(b) == quote {
+            quoted = !quoted;
+        }
+        (b == b'\n' && !quoted).then_some(i + 1)
+    })
+}
+
+fn read_error(e: std::io::Error) -> PolarsError {
+    PolarsError::ComputeError(format!("ReaderScan failed to read input: {}", e).into())
+}
+
+fn to_polars(e: BridgeError) -> PolarsError {
+    PolarsError::ComputeError(e.to_string().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::executor;
+    use crate::test_plans::{col, node, plan, select};
+    use proto::node::Kind as N;
+
+    // 模拟 Go 读取器：每次最多返回 7 字节，使记录跨多次读取
+    struct Source {
+        bytes: Vec<u8>,
+        pos: AtomicUsize,
+    }
+
+    extern "C" fn read_source(ctx: *mut c_void, buf: *mut u8, len: usize, out_read: *mut usize) -> c_int {
+        let source = unsafe { &*(ctx as *const Source) };
+        let pos = source.pos.load(Ordering::SeqCst);
+        let n = len.min(7).min(source.bytes.len() - pos);
+        unsafe {
+            std::ptr::copy_nonoverlapping(source.bytes[pos..].as_ptr(), buf, n);
+            *out_read = n;
+        }
+        source.pos.store(pos + n, Ordering::SeqCst);
+        0
+    }
+
+    fn register(source: &Source) -> u64 {
+        handles::readers().insert(GoReader::new(read_source, source as *const Source as *mut c_void))
+    }
+
+    fn source(text: &str) -> Source {
+        Source { bytes: text.as_bytes().to_vec(), pos: AtomicUsize::new(0) }
+    }
+
+    fn reader_scan(reader_id: u64, format: proto::ReaderFormat) -> proto::Node {
+        node(1, N::ReaderScan(proto::ReaderScan {
+            reader_id,
+            format: format as i32,
+            ..Default::default()
+        }))
+    }
+
+    #[test]
+    fn csv_records_split_across_reads() {
+        // 引号内的换行不是记录边界
+        let source = source("id;note\n1;\"two\nlines\"\n2;plain\n3;\"a;b\"\n");
+        let id = register(&source);
+        let scan = node(1, N::ReaderScan(proto::ReaderScan {
+            reader_id: id,
+            format: proto::ReaderFormat::Csv as i32,
+            separator: ";".into(),
+            ..Default::default()
+        }));
+
+        let df = executor::execute_plan_df(&plan(scan), None).unwrap();
+        let expected = df!("id" => [1i64, 2, 3], "note" => ["two\nlines", "plain", "a;b"]).unwrap();
+        assert!(df.equals(&expected), "{df}");
+        assert!(handles::readers().get(id).is_err());
+    }
+
+    #[test]
+    fn ndjson_projection_and_limit_stop_reading_early() {
+        let text: String = (0..500).map(|i| format!("{{\"a\":{i},\"b\":\"row{i}\"}}\n")).collect();
+        let source = source(&text);
+        let id = register(&source);
+
+        let limited = node(2, N::Limit(Box::new(proto::Limit {
+            input: Some(Box::new(reader_scan(id, proto::ReaderFormat::Ndjson))),
+            n: 3,
+        })));
+        let df = executor::execute_plan_df(&select(limited, vec![col("b")]), None).unwrap();
+        assert!(df.equals(&df!("b" => ["row0", "row1", "row2"]).unwrap()), "{df}");
+        // 推断 schema 预读 100 条记录，执行时满 3 行即停止，不会读完 500 条
+        assert!(source.pos.load(Ordering::SeqCst) < source.bytes.len());
+    }
+
+    #[test]
+    fn reader_is_consumed_by_the_first_execution() {
+        let source = source("a\n1\n");
+        let id = register(&source);
+        let scan = plan(reader_scan(id, proto::ReaderFormat::Csv));
+
+        executor::execute_plan_df(&scan, None).unwrap();
+        let err = executor::execute_plan_df(&scan, None).unwrap_err();
+        assert!(matches!(err, BridgeError::InvalidArgument(_)));
+        assert!(err.to_string().contains("already consumed reader handle"), "{err}");
+    }
+}
@@ -0,0 +1,265 @@
+use std::fs::{self, File};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{OnceLock, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use polars::prelude::*;
+use prost::Message;
+use serde_json::json;
+
+use crate::error::BridgeError;
+use crate::nodes::node_inputs_mut;
+use crate::proto;
+
+const PLAN_FILE: &str = "plan.pb";
+const HINTS_FILE: &str = "hints.pb";
+const INPUT_FILE: &str = "input.ipc";
+const RECORD_FILE: &str = "record.json";
+const REDACTED: &str = "<redacted>";
+
+/// 查询录制：把执行过的 Plan、输入 schema 与耗时写入录制目录，之后可以用 load 回放
+///
+/// 每次录制是目录下的一个子目录（`<毫秒时间戳>-<pid>-<序号>`）：
+/// - `plan.pb`：Plan（默认抹掉凭据）；
+/// - `hints.pb`：ExecutionHints（不含会话句柄）；
+/// - `input.ipc`：输入 DataFrame（仅 record_inputs，redact_columns 中的列替换为 null）；
+/// - `record.json`：输入 schema、耗时、结果行列数或错误消息。
+///
+/// 录制失败不影响查询本身。
+struct Recorder {
+    options: proto::RecorderOptions,
+    seen: AtomicU64,
+    written: AtomicU64,
+}
+
+// 关闭时只读这个标志，不获取锁
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+fn state() -> &'static RwLock<Option<Recorder>> {
+    static STATE: OnceLock<RwLock<Option<Recorder>>> = OnceLock::new();
+    STATE.get_or_init(|| RwLock::new(None))
+}
+
+/// 开启或关闭录制（dir 为空表示关闭）
+pub fn configure(options: proto::RecorderOptions) -> Result<(), BridgeError> {
+    let mut state = state().write().unwrap_or_else(|e| e.into_inner());
+    if options.dir.is_empty() {
+        ENABLED.store(false, Ordering::Release);
+        *state = None;
+        return Ok(());
+    }
+
+    if let Some(rate) = options.sample_rate {
+        if !(rate > 0.0 && rate <= 1.0) {
+            return Err(BridgeError::InvalidArgument(format!(
+                "Recorder sample_rate must be in (0, 1], got {}",
+                rate
+            )));
+        }
+    }
+    fs::create_dir_all(&options.dir).map_err(|e| {
+        BridgeError::InvalidArgument(format!(
+            "Failed to create recorder directory '{}': {}",
+            options.dir, e
+        ))
+    })?;
+
+    *state = Some(Recorder {
+        options,
+        seen: AtomicU64::new(0),
+        written: AtomicU64::new(0),
+    });
+    ENABLED.store(true, Ordering::Release);
+    Ok(())
+}
+
+/// 记录一次执行（未开启录制时立即返回）
+pub fn record(
+    plan: &proto::Plan,
+    input_df: Option<&DataFrame>,
+    hints: &proto::ExecutionHints,
+    elapsed: Duration,
+    result: &Result<DataFrame, BridgeError>,
+) {
+    if !ENABLED.load(Ordering::Acquire) {
+        return;
+    }
+    let state = state().read().unwrap_or_else(|e| e.into_inner());
+    let Some(recorder) = state.as_ref() else {
+        return;
+    };
+
+    if let Some(threshold) = recorder.options.slow_query_us {
+        if (elapsed.as_micros() as u64) < threshold {
+            return;
+        }
+    }
+    if !recorder.sampled() {
+        return;
+    }
+    let _ = recorder.write(plan, input_df, hints, elapsed, result);
+}
+
+/// 读取一次录制：返回 Plan、录制的输入（如果有）与执行提示
+pub fn load(
+    record_dir: &str,
+) -> Result<(proto::Plan, Option<DataFrame>, proto::ExecutionHints), BridgeError> {
+    let dir = Path::new(record_dir);
+    let read = |name: &str| {
+        fs::read(dir.join(name)).map_err(|e| {
+            BridgeError::InvalidArgument(format!(
+                "Failed to read '{}' in recording '{}': {}",
+                name, record_dir, e
+            ))
+        })
+    };
+
+    let plan = crate::compile_plan(&read(PLAN_FILE)?)?;
+    let hints = if dir.join(HINTS_FILE).is_file() {
+        proto::ExecutionHints::decode(read(HINTS_FILE)?.as_slice())
+            .map_err(|e| BridgeError::PlanDecode(format!("Invalid {}: {}", HINTS_FILE, e)))?
+    } else {
+        proto::ExecutionHints::default()
+    };
+    let input = if dir.join(INPUT_FILE).is_file() {
+        let file = File::open(dir.join(INPUT_FILE)).map_err(|e| {
+            BridgeError::Execution(format!("Failed to open {}: {}", INPUT_FILE, e))
+        })?;
+        let df = IpcReader::new(file).finish().map_err(|e| {
+            BridgeError::Execution(format!("Failed to read {}", INPUT_FILE)).with_source(e)
+        })?;
+        Some(df)
+    } else {
+        None
+    };
+    Ok((plan, input, hints))
+}
+
+impl Recorder {
+    // 按比例均匀抽样：第 n 次执行在 floor(n * rate) 增加时被录制
+    fn sampled(&self) -> bool {
+        let rate = self.options.sample_rate.unwrap_or(1.0);
+        let n = self.seen.fetch_add(1, Ordering::Relaxed);
+        ((n + 1) as f64 * rate).floor() > (n as f64 * rate).floor()
+    }
+
+    fn write(
+        &self,
+        plan: &proto::Plan,
+        input_df: Option<&DataFrame>,
+        hints: &proto::ExecutionHints,
+        elapsed: Duration,
+        result: &Result<DataFrame, BridgeError>,
+    ) -> std::io::Result<()> {
+        let recorded_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default();
+        let seq = self.written.fetch_add(1, Ordering::Relaxed);
+        let dir = Path::new(&self.options.dir)
+            .join(format!("{}-{}-{:06}", recorded_at, std::process::id(), seq));
+        fs::create_dir_all(&dir)?;
+
+        let mut plan = plan.clone();
+        if !self.options.keep_secrets {
+            if let Some(root) = plan.root.as_mut() {
+                redact_secrets(root);
+            }
+        }
+        fs::write(dir.join(PLAN_FILE), plan.encode_to_vec())?;
+
+        // 会话句柄只在当前进程内有效
+        let hints = proto::ExecutionHints {
+            session_handle: 0,
+            ..hints.clone()
+        };
+        fs::write(dir.join(HINTS_FILE), hints.encode_to_vec())?;
+
+        if let Some(df) = input_df.filter(|_| self.options.record_inputs) {
+            let mut df = self.redact_columns(df);
+            IpcWriter::new(File::create(dir.join(INPUT_FILE))?)
+                .finish(&mut df)
+                .map_err(std::io::Error::other)?;
+        }
+
+        let input_schema: Vec<_> = input_df
+            .map(|df| {
+                df.schema()
+                    .iter()
+                    .map(|(name, dtype)| json!({ "name": name.as_str(), "dtype": dtype.to_string() }))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let (outcome, error) = match result {
+            Ok(df) => (json!({ "rows": df.height(), "columns": df.width() }), None),
+            Err(e) => (serde_json::Value::Null, Some(e.to_string())),
+        };
+        let record = json!({
+            "recorded_at_ms": recorded_at,
+            "crate_version": env!("CARGO_PKG_VERSION"),
+            "query_id": hints.query_id,
+            "wall_time_us": elapsed.as_micros() as u64,
+            "input_schema": input_schema,
+            "input_rows": input_df.map(|df| df.height()),
+            "result": outcome,
+            "error": error,
+            "secrets_redacted": !self.options.keep_secrets,
+        });
+        let bytes = serde_json::to_vec_pretty(&record).map_err(std::io::Error::other)?;
+        fs::write(dir.join(RECORD_FILE), bytes)
+    }
+
+    fn redact_columns(&self, df: &DataFrame) -> DataFrame {
+        let mut df = df.clone();
+        for name in &self.options.redact_columns {
+            let Ok(column) = df.column(name) else {
+                continue;
+            };
+            let nulls = Series::full_null(name.as_str().into(), df.height(), column.dtype());
+            let _ = df.with_column(nulls);
+        }
+        df
+    }
+}
+
+// 抹掉凭据，保留其余配置以便判断数据源
+fn redact_secrets(node: &mut proto::Node) {
+    use proto::node::Kind;
+
+    match node.kind.as_mut() {
+        Some(Kind::CsvScan(scan)) => redact_cloud(scan.cloud.as_mut()),
+        Some(Kind::ParquetScan(scan)) => redact_cloud(scan.cloud.as_mut()),
+        Some(Kind::IpcScan(scan)) => redact_cloud(scan.cloud.as_mut()),
+        Some(Kind::IcebergScan(scan)) => redact_cloud(scan.cloud.as_mut()),
+        Some(Kind::DeltaScan(scan)) => redact_cloud(scan.cloud.as_mut()),
+        Some(Kind::DatabaseScan(scan)) => {
+            scan.uri = REDACTED.into();
+            scan.driver_options.values_mut().for_each(|v| *v = REDACTED.into());
+        }
+        Some(Kind::FlightScan(scan)) if !scan.auth_token.is_empty() => {
+            scan.auth_token = REDACTED.into();
+        }
+        _ => {}
+    }
+
+    for input in node_inputs_mut(node) {
+        redact_secrets(input);
+    }
+}
+
+fn redact_cloud(cloud: Option<&mut proto::CloudOptions>) {
+    let Some(cloud) = cloud else {
+        return;
+    };
+    for secret in [
+        &mut cloud.access_key_id,
+        &mut cloud.secret_access_key,
+        &mut cloud.session_token,
+    ] {
+        if !secret.is_empty() {
+            *secret = REDACTED.into();
+        }
+    }
+    cloud.extra.values_mut().for_each(|v| *v = REDACTED.into());
+}
@@ -0,0 +1,50 @@
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use regex::Regex;
+
+use crate::error::BridgeError;
+use crate::metadata;
+
+// 缓存上限：请求级 Plan 通常只复用少量固定的模式，超出后整体清空即可
+const CAPACITY: usize = 256;
+
+fn cache() -> &'static RwLock<HashMap<String, Arc<Regex>>> {
+    static CACHE: OnceLock<RwLock<HashMap<String, Arc<Regex>>>> = OnceLock::new();
+    CACHE.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// 编译正则（进程内按模式缓存）
+///
+/// 字符串表达式在构建时从这里取得已编译的正则并在执行中直接使用，
+/// 同一模式在后续 Plan 执行中不再重新编译；命中计入本次执行的 cache_hits。
+pub fn compile(pattern: &str) -> Result<Arc<Regex>, BridgeError> {
+    if let Some(regex) = cache().read().unwrap_or_else(|e| e.into_inner()).get(pattern) {
+        metadata::count_cache_hit();
+        return Ok(regex.clone());
+    }
+
+    let regex = Arc::new(Regex::new(pattern).map_err(|e| {
+        BridgeError::InvalidArgument(format!("Invalid regex pattern '{}': {}", pattern, e))
+    })?);
+
+    let mut cache = cache().write().unwrap_or_else(|e| e.into_inner());
+    if cache.len() >= CAPACITY {
+        cache.clear();
+    }
+    cache.insert(pattern.to_string(), regex.clone());
+    Ok(regex)
+}
+
+/// 模式中没有正则元字符时可以按字面字符串匹配，完全跳过正则编译
+pub fn is_plain(pattern: &str) -> bool {
+    !pattern.chars().any(regex_syntax::is_meta_character)
+}
+
+pub fn len() -> usize {
+    cache().read().unwrap_or_else(|e| e.into_inner()).len()
+}
+
+pub fn clear() {
+    cache().write().unwrap_or_else(|e| e.into_inner()).clear();
+}
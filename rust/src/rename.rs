@@ -0,0 +1,229 @@
+use std::collections::HashSet;
+
+use polars::prelude::*;
+
+use crate::error::BridgeError;
+use crate::proto;
+
+/// 批量改名：pairs 中的列直接改名，其余列依次应用 rules；结果为新的 DataFrame（不修改输入）
+pub fn rename_columns(
+    df: &DataFrame,
+    options: &proto::RenameOptions,
+) -> Result<DataFrame, BridgeError> {
+    use proto::rename_rule::Rule;
+
+    if options.strict {
+        let schema = df.schema();
+        if let Some(missing) = options
+            .pairs
+            .keys()
+            .find(|name| !schema.contains(name.as_str()))
+        {
+            return Err(BridgeError::InvalidArgument(format!(
+                "Cannot rename missing column '{}'",
+                missing
+            )));
+        }
+    }
+
+    let rules = options
+        .rules
+        .iter()
+        .map(|rule| {
+            rule.rule
+                .as_ref()
+                .ok_or_else(|| BridgeError::InvalidArgument("RenameRule has no rule".into()))
+        })
+        .collect::<Result<Vec<&Rule>, _>>()?;
+
+    let mut names = Vec::with_capacity(df.width());
+    let mut seen = HashSet::with_capacity(df.width());
+    for name in df.get_column_names() {
+        let renamed = match options.pairs.get(name.as_str()) {
+            Some(renamed) => renamed.clone(),
+            None => apply_rules(name.as_str(), &rules)?,
+        };
+        if !seen.insert(renamed.clone()) {
+            return Err(BridgeError::InvalidArgument(format!(
+                "Renaming '{}' produces duplicate column name '{}'",
+                name, renamed
+            )));
+        }
+        names.push(renamed);
+    }
+
+    let mut df = df.clone();
+    df.set_column_names(names)
+        .map_err(|e| BridgeError::Execution("Failed to rename columns".into()).with_source(e))?;
+    Ok(df)
+}
+
+fn apply_rules(name: &str, rules: &[&proto::rename_rule::Rule]) -> Result<String, BridgeError> {
+    use proto::rename_rule::Rule;
+    use proto::RenameCase;
+
+    let mut name = name.to_string();
+    for rule in rules {
+        name = match rule {
+            Rule::AddPrefix(prefix) => format!("{}{}", prefix, name),
+            Rule::AddSuffix(suffix) => format!("{}{}", name, suffix),
+            Rule::StripPrefix(prefix) => name
+                .strip_prefix(prefix.as_str())
+                .unwrap_or(&name)
+                .to_string(),
+            Rule::StripSuffix(suffix) => name
+                .strip_suffix(suffix.as_str())
+                .unwrap_or(&name)
+                .to_string(),
+            Rule::ToCase(case) => match RenameCase::try_from(*case) {
+                Ok(RenameCase::Lower) => name.to_lowercase(),
+                Ok(RenameCase::Upper) => name.to_uppercase(),
+                Ok(RenameCase::Snake) => to_snake_case(&name),
+                Err(_) => {
+                    return Err(BridgeError::Unsupported(format!(
+                        "Unknown rename case: {}",
+                        case
+                    )))
+                }
+            },
+        };
+    }
+    Ok(name)
+}
+
+// 非字母数字字符视为分隔符；小写 / 数字后接大写、以及连续大写后接小写处断词（"HTTPServer" -> "http_server"）
+fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::with_capacity(name.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !out.is_empty() && !out.ends_with('_') {
+                out.push('_');
+            }
+            continue;
+        }
+        if c.is_uppercase() && i > 0 && !out.is_empty() && !out.ends_with('_') {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_lower) {
+                out.push('_');
+            }
+        }
+        out.extend(c.to_lowercase());
+    }
+
+    let out = out.trim_end_matches('_');
+    // 全部是分隔符的列名保持不变，避免出现空列名
+    if out.is_empty() {
+        name.to_string()
+    } else {
+        out.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proto::rename_rule::Rule;
+
+    fn rule(rule: Rule) -> proto::RenameRule {
+        proto::RenameRule { rule: Some(rule) }
+    }
+
+    fn renamed(df: &DataFrame, options: &proto::RenameOptions) -> Vec<String> {
+        rename_columns(df, options)
+            .unwrap()
+            .get_column_names()
+            .iter()
+            .map(|name| name.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn snake_case_table() {
+        let cases = [
+            ("HTTPServer", "http_server"),
+            ("orderId", "order_id"),
+            ("Order ID", "order_id"),
+            ("getHTTP2Response", "get_http2_response"),
+            ("col1Name", "col1_name"),
+            ("version2", "version2"),
+            ("ABC", "abc"),
+            ("already_snake", "already_snake"),
+            ("my--col  name", "my_col_name"),
+            ("__private__", "private"),
+            ("Ünïcode Wörd", "ünïcode_wörd"),
+            ("---", "---"),
+            ("", ""),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(to_snake_case(input), expected, "input {input:?}");
+        }
+    }
+
+    #[test]
+    fn rules_apply_in_order_after_pairs() {
+        let df = df!("raw_OrderId" => [1i64], "raw_Total" => [2i64], "keep" => [3i64]).unwrap();
+        let options = proto::RenameOptions {
+            pairs: [("keep".to_string(), "Kept".to_string())].into(),
+            rules: vec![
+                rule(Rule::StripPrefix("raw_".into())),
+                rule(Rule::ToCase(proto::RenameCase::Snake as i32)),
+                rule(Rule::AddPrefix("src_".into())),
+                rule(Rule::AddSuffix("_v1".into())),
+                rule(Rule::StripSuffix("_v1".into())),
+                rule(Rule::ToCase(proto::RenameCase::Upper as i32)),
+            ],
+            strict: false,
+        };
+        assert_eq!(renamed(&df, &options), ["SRC_ORDER_ID", "SRC_TOTAL", "Kept"]);
+    }
+
+    #[test]
+    fn strip_leaves_non_matching_names_unchanged() {
+        let df = df!("a_x" => [1i64], "b" => [2i64]).unwrap();
+        let options = proto::RenameOptions {
+            rules: vec![rule(Rule::StripPrefix("a_".into())), rule(Rule::StripSuffix("_x".into()))],
+            ..Default::default()
+        };
+        assert_eq!(renamed(&df, &options), ["x", "b"]);
+    }
+
+    #[test]
+    fn strict_rejects_missing_pair() {
+        let df = df!("a" => [1i64]).unwrap();
+        let mut options = proto::RenameOptions {
+            pairs: [("missing".to_string(), "b".to_string())].into(),
+            ..Default::default()
+        };
+        assert_eq!(renamed(&df, &options), ["a"]);
+
+        options.strict = true;
+        assert!(matches!(
+            rename_columns(&df, &options),
+            Err(BridgeError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_duplicate_and_invalid_renames() {
+        let df = df!("a" => [1i64], "A" => [2i64]).unwrap();
+        let lower = proto::RenameOptions {
+            rules: vec![rule(Rule::ToCase(proto::RenameCase::Lower as i32))],
+            ..Default::default()
+        };
+        assert!(matches!(rename_columns(&df, &lower), Err(BridgeError::InvalidArgument(_))));
+
+        let unknown = proto::RenameOptions {
+            rules: vec![rule(Rule::ToCase(99))],
+            ..Default::default()
+        };
+        assert!(matches!(rename_columns(&df, &unknown), Err(BridgeError::Unsupported(_))));
+
+        let empty = proto::RenameOptions {
+            rules: vec![proto::RenameRule { rule: None }],
+            ..Default::default()
+        };
+        assert!(matches!(rename_columns(&df, &empty), Err(BridgeError::InvalidArgument(_))));
+    }
+}
@@ -0,0 +1,71 @@
+use polars::prelude::*;
+
+use crate::columns::{dedup_column_names, DuplicatePolicy};
+use crate::error::BridgeError;
+use crate::proto;
+use crate::schema::to_polars_dtype;
+
+/// 按 schema 把行式数据转为 DataFrame（值先构造为 AnyValue，再严格转换为声明的类型）
+pub fn df_from_rows(batch: &proto::RowBatch) -> Result<DataFrame, BridgeError> {
+    let fields = batch
+        .schema
+        .as_ref()
+        .map(|s| s.fields.as_slice())
+        .ok_or_else(|| BridgeError::InvalidArgument("RowBatch has no schema".into()))?;
+
+    let names: Vec<PlSmallStr> = fields.iter().map(|f| f.name.as_str().into()).collect();
+    dedup_column_names(&names, DuplicatePolicy::Error)?;
+
+    for (idx, row) in batch.rows.iter().enumerate() {
+        if row.values.len() != fields.len() {
+            return Err(BridgeError::InvalidArgument(format!(
+                "Row {} has {} values, schema has {} fields",
+                idx,
+                row.values.len(),
+                fields.len()
+            )));
+        }
+    }
+
+    let columns = fields
+        .iter()
+        .enumerate()
+        .map(|(idx, field)| {
+            let values: Vec<AnyValue> = batch
+                .rows
+                .iter()
+                .map(|row| literal_to_any_value(&row.values[idx]))
+                .collect();
+            let to_error = |e: PolarsError| {
+                BridgeError::InvalidArgument(format!(
+                    "Invalid values for column '{}': {}",
+                    field.name, e
+                ))
+            };
+
+            let dtype = to_polars_dtype(field.data_type)?;
+            let series = Series::from_any_values(field.name.as_str().into(), &values, false)
+                .map_err(to_error)?;
+            let series = series.strict_cast(&dtype).map_err(to_error)?;
+            Ok(Column::from(series))
+        })
+        .collect::<Result<Vec<_>, BridgeError>>()?;
+
+    DataFrame::new(columns)
+        .map_err(|e| BridgeError::Execution(format!("Failed to create DataFrame: {}", e)))
+}
+
+pub fn literal_to_any_value(literal: &proto::Literal) -> AnyValue<'static> {
+    use proto::literal::Value;
+
+    match literal.value.as_ref() {
+        None | Some(Value::NullVal(_)) => AnyValue::Null,
+        Some(Value::IntVal(v)) => AnyValue::Int64(*v),
+        Some(Value::FloatVal(v)) => AnyValue::Float64(*v),
+        Some(Value::BoolVal(v)) => AnyValue::Boolean(*v),
+        // 日期/时间与 Decimal 以字符串传入，由 strict_cast 解析
+        Some(Value::StringVal(v)) | Some(Value::DecimalVal(v)) => {
+            AnyValue::StringOwned(v.as_str().into())
+        }
+    }
+}
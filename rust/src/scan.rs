@@ -0,0 +1,638 @@
+#[cfg(feature = "parquet")]
+use std::collections::BTreeMap;
+use std::fs::File;
+#[cfg(feature = "parquet")]
+use std::path::Path;
+use std::sync::Arc;
+#[cfg(feature = "parquet")]
+use std::time::UNIX_EPOCH;
+
+#[cfg(feature = "avro")]
+use polars::io::avro::AvroReader;
+#[cfg(feature = "parquet")]
+use polars::io::HiveOptions;
+use polars::prelude::*;
+
+use crate::cloud::cloud_options;
+use crate::error::BridgeError;
+use crate::proto;
+use crate::schema::to_polars_schema;
+use crate::sink::parse_ascii_char;
+
+/// CsvScan：按解析选项构建 LazyCsvReader
+pub fn csv_scan(scan: &proto::CsvScan) -> Result<LazyFrame, BridgeError> {
+    csv_reader(scan)?.finish().map_err(|e| {
+        BridgeError::Execution(format!(
+            "CsvScan failed for '{}': {}",
+            display_paths(&scan.path, &scan.paths),
+            e
+        ))
+    })
+}
+
+pub fn csv_reader(scan: &proto::CsvScan) -> Result<LazyCsvReader, BridgeError> {
+    let paths = scan_paths(&scan.path, &scan.paths, "CsvScan")?;
+    let cloud = cloud_options(paths[0].to_str(), scan.cloud.as_ref())?;
+    let mut reader = LazyCsvReader::new_paths(paths.into())
+        .with_glob(scan.glob.unwrap_or(true))
+        .with_cloud_options(cloud);
+
+    if !scan.separator.is_empty() {
+        reader = reader.with_separator(parse_ascii_char(&scan.separator, "CsvScan separator")?);
+    }
+    if let Some(quote_char) = &scan.quote_char {
+        let quote_char = if quote_char.is_empty() {
+            None
+        } else {
+            Some(parse_ascii_char(quote_char, "CsvScan quote_char")?)
+        };
+        reader = reader.with_quote_char(quote_char);
+    }
+    if let Some(has_header) = scan.has_header {
+        reader = reader.with_has_header(has_header);
+    }
+    if !scan.comment_prefix.is_empty() {
+        reader = reader.with_comment_prefix(Some(scan.comment_prefix.as_str().into()));
+    }
+    if !scan.null_values.is_empty() {
+        let null_values = scan.null_values.iter().map(|v| v.as_str().into()).collect();
+        reader = reader.with_null_values(Some(NullValues::AllColumns(null_values)));
+    }
+    if !scan.eol_char.is_empty() {
+        reader = reader.with_eol_char(parse_ascii_char(&scan.eol_char, "CsvScan eol_char")?);
+    }
+
+    let encoding = match proto::CsvEncoding::try_from(scan.encoding) {
+        Ok(proto::CsvEncoding::Utf8) => CsvEncoding::Utf8,
+        Ok(proto::CsvEncoding::LossyUtf8) => CsvEncoding::LossyUtf8,
+        Err(_) => {
+            return Err(BridgeError::Unsupported(format!(
+                "Unknown CSV encoding: {}",
+                scan.encoding
+            )))
+        }
+    };
+    reader = reader
+        .with_encoding(encoding)
+        .with_skip_rows(scan.skip_rows as usize);
+
+    // 类型推断控制
+    if !scan.schema_overrides.is_empty() {
+        let overrides = to_polars_schema(&scan.schema_overrides)?;
+        reader = reader.with_dtype_overwrite(Some(Arc::new(overrides)));
+    }
+    if let Some(length) = scan.infer_schema_length {
+        reader = reader.with_infer_schema_length(Some(length as usize));
+    }
+    reader = reader
+        .with_try_parse_dates(scan.try_parse_dates)
+        .with_truncate_ragged_lines(scan.truncate_ragged_lines)
+        .with_ignore_errors(scan.ignore_errors);
+
+    if let Some(chunk_size) = scan.chunk_size {
+        if chunk_size == 0 {
+            return Err(BridgeError::InvalidArgument(
+                "CsvScan chunk_size must be greater than 0".into(),
+            ));
+        }
+        reader = reader.with_chunk_size(chunk_size as usize);
+    }
+
+    if let Some(options) = scan.options.as_ref() {
+        reader = reader
+            .with_rechunk(options.rechunk)
+            .with_cache(options.cache.unwrap_or(true))
+            .with_low_memory(options.low_memory);
+    }
+
+    Ok(reader)
+}
+
+/// ParquetScan：懒加载一个或多个 Parquet 文件（支持 glob）
+#[cfg(feature = "parquet")]
+pub fn parquet_scan(scan: &proto::ParquetScan) -> Result<LazyFrame, BridgeError> {
+    let paths = match scan.as_of.as_ref() {
+        Some(as_of) => {
+            if !scan.paths.is_empty() {
+                return Err(BridgeError::InvalidArgument(
+                    "ParquetScan as_of cannot be combined with paths".into(),
+                ));
+            }
+            vec![PlPath::new(&snapshot_glob(&scan.path, as_of)?)]
+        }
+        None => scan_paths(&scan.path, &scan.paths, "ParquetScan")?,
+    };
+    let mut args = ScanArgsParquet {
+        glob: scan.as_of.is_some() || scan.glob.unwrap_or(true),
+        cloud_options: cloud_options(paths[0].to_str(), scan.cloud.as_ref())?,
+        hive_options: hive_options(scan.hive.as_ref())?,
+        ..Default::default()
+    };
+    if let Some(options) = scan.options.as_ref() {
+        args.rechunk = options.rechunk;
+        args.cache = options.cache.unwrap_or(true);
+        args.low_memory = options.low_memory;
+    }
+
+    LazyFrame::scan_parquet_files(paths.into(), args).map_err(|e| {
+        BridgeError::Execution(format!(
+            "ParquetScan failed for '{}': {}",
+            display_paths(&scan.path, &scan.paths),
+            e
+        ))
+    })
+}
+
+#[cfg(not(feature = "parquet"))]
+pub fn parquet_scan(_scan: &proto::ParquetScan) -> Result<LazyFrame, BridgeError> {
+    Err(crate::features::unavailable("ParquetScan", "parquet"))
+}
+
+// 快照目录：选中的子目录下的全部 Parquet 文件（glob 的基准目录是快照本身，快照名不会被当作 Hive 分区）
+#[cfg(feature = "parquet")]
+fn snapshot_glob(root: &str, as_of: &proto::AsOf) -> Result<String, BridgeError> {
+    if root.is_empty() {
+        return Err(BridgeError::InvalidArgument("ParquetScan has no path".into()));
+    }
+    if root.contains("://") {
+        return Err(BridgeError::Unsupported(format!(
+            "ParquetScan as_of requires a local snapshot directory: '{}'",
+            root
+        )));
+    }
+    let entries = std::fs::read_dir(root).map_err(|e| {
+        BridgeError::Execution(format!("Failed to read snapshot directory '{}': {}", root, e))
+    })?;
+
+    let mut snapshots = BTreeMap::new();
+    for entry in entries.flatten() {
+        if !entry.path().is_dir() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let version = name
+            .strip_prefix("version=")
+            .or_else(|| name.strip_prefix('v'))
+            .unwrap_or(&name);
+        if let Ok(version) = version.parse::<i64>() {
+            snapshots.insert(version, entry.path());
+        }
+    }
+
+    let versions: Vec<i64> = snapshots.keys().copied().collect();
+    let target = as_of_target(Some(as_of), "ParquetScan")?;
+    let version = select_version(&versions, target, |v| modified_ms(&snapshots[&v]), "ParquetScan")?;
+    Ok(format!("{}/**/*.parquet", snapshots[&version].display()))
+}
+
+/// 历史版本的选择方式（ParquetScan 快照目录与 DeltaScan 共用）
+#[cfg(feature = "parquet")]
+pub enum AsOfTarget {
+    Latest,
+    Version(i64),
+    Timestamp(i64),
+}
+
+#[cfg(feature = "parquet")]
+pub fn as_of_target(as_of: Option<&proto::AsOf>, name: &str) -> Result<AsOfTarget, BridgeError> {
+    match as_of.map(|as_of| (as_of.version, as_of.timestamp_ms)) {
+        None | Some((None, None)) => Ok(AsOfTarget::Latest),
+        Some((Some(version), None)) => Ok(AsOfTarget::Version(version)),
+        Some((None, Some(ts))) => Ok(AsOfTarget::Timestamp(ts)),
+        Some((Some(_), Some(_))) => Err(BridgeError::InvalidArgument(format!(
+            "{name} as_of cannot set both version and timestamp_ms"
+        ))),
+    }
+}
+
+/// 在升序的版本列表中选出目标版本；按时间点选择时从最新版本往前找第一个提交时间不晚于该时间点的版本
+#[cfg(feature = "parquet")]
+pub fn select_version<F>(
+    versions: &[i64],
+    target: AsOfTarget,
+    timestamp: F,
+    name: &str,
+) -> Result<i64, BridgeError>
+where
+    F: Fn(i64) -> Result<i64, BridgeError>,
+{
+    let latest = *versions
+        .last()
+        .ok_or_else(|| BridgeError::InvalidArgument(format!("{name} found no versions")))?;
+
+    match target {
+        AsOfTarget::Latest => Ok(latest),
+        AsOfTarget::Version(version) if versions.binary_search(&version).is_ok() => Ok(version),
+        AsOfTarget::Version(version) => Err(BridgeError::InvalidArgument(format!(
+            "{name} version {} not found (latest is {})",
+            version, latest
+        ))),
+        AsOfTarget::Timestamp(ts) => {
+            for &version in versions.iter().rev() {
+                if timestamp(version)? <= ts {
+                    return Ok(version);
+                }
+            }
+            Err(BridgeError::InvalidArgument(format!(
+                "{name} has no version committed at or before timestamp {}",
+                ts
+            )))
+        }
+    }
+}
+
+#[cfg(feature = "parquet")]
+pub fn modified_ms(path: &Path) -> Result<i64, BridgeError> {
+    let modified = std::fs::metadata(path).and_then(|m| m.modified()).map_err(|e| {
+        BridgeError::Execution(format!("Failed to read modification time of '{}': {}", path.display(), e))
+    })?;
+    Ok(modified
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or_default())
+}
+
+/// 去掉表元数据中本地路径常见的 file: 前缀（DeltaScan 与 IcebergScan 共用）
+#[cfg(feature = "parquet")]
+pub fn local_path(path: &str) -> &str {
+    path.strip_prefix("file://")
+        .or_else(|| path.strip_prefix("file:"))
+        .unwrap_or(path)
+}
+
+#[cfg(feature = "parquet")]
+fn hive_options(hive: Option<&proto::HiveOptions>) -> Result<HiveOptions, BridgeError> {
+    let Some(hive) = hive else {
+        return Ok(HiveOptions::default());
+    };
+
+    let schema = if hive.schema.is_empty() {
+        None
+    } else {
+        Some(Arc::new(to_polars_schema(&hive.schema)?))
+    };
+    Ok(HiveOptions {
+        enabled: hive.enabled,
+        schema,
+        try_parse_dates: hive.try_parse_dates.unwrap_or(true),
+        ..Default::default()
+    })
+}
+
+// 合并 path 与 paths 字段
+fn scan_paths(path: &str, paths: &[String], name: &str) -> Result<Vec<PlPath>, BridgeError> {
+    let all: Vec<PlPath> = std::iter::once(path)
+        .chain(paths.iter().map(String::as_str))
+        .filter(|p| !p.is_empty())
+        .map(PlPath::new)
+        .collect();
+
+    if all.is_empty() {
+        return Err(BridgeError::InvalidArgument(format!("{name} has no path")));
+    }
+    Ok(all)
+}
+
+pub fn display_paths(path: &str, paths: &[String]) -> String {
+    std::iter::once(path)
+        .chain(paths.iter().map(String::as_str))
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// IpcScan：懒加载 Arrow IPC 文件；关闭内存映射时整体读入内存
+///
+/// Polars 的 IPC 读取没有低内存模式，设置 low_memory 时报错。
+pub fn ipc_scan(scan: &proto::IpcScan) -> Result<LazyFrame, BridgeError> {
+    reject_low_memory(scan.options.as_ref(), "IpcScan")?;
+    let n_rows = scan.n_rows.map(|n| n as usize);
+    let to_error = |e: PolarsError| {
+        BridgeError::Execution(format!("IpcScan failed for '{}': {}", scan.path, e))
+    };
+
+    // 云存储路径无法内存映射，总是走懒加载扫描
+    if scan.memory_map.unwrap_or(true) || scan.cloud.is_some() {
+        let mut args = UnifiedScanArgs {
+            cloud_options: cloud_options(&scan.path, scan.cloud.as_ref())?,
+            cache: true,
+            ..Default::default()
+        };
+        if let Some(options) = scan.options.as_ref() {
+            args.rechunk = options.rechunk;
+            args.cache = options.cache.unwrap_or(true);
+        }
+        let lf = LazyFrame::scan_ipc(PlPath::new(scan.path.as_str()), IpcScanOptions, args)
+            .map_err(to_error)?;
+        // 行数限制以 slice 表示，优化器会把它下推到扫描
+        return Ok(match n_rows {
+            Some(n) => lf.slice(0, n.min(IdxSize::MAX as usize) as IdxSize),
+            None => lf,
+        });
+    }
+
+    let file = File::open(&scan.path).map_err(|e| {
+        BridgeError::Execution(format!("IpcScan failed to open '{}': {}", scan.path, e))
+    })?;
+    let df = IpcReader::new(file)
+        .with_n_rows(n_rows)
+        .memory_mapped(None)
+        .finish()
+        .map_err(to_error)?;
+    in_memory_scan(df, scan.options.as_ref(), "IpcScan")
+}
+
+/// 已在内存中的数据应用读取选项：rechunk 合并内存块，cache 为 true 时加缓存节点
+///
+/// 内存数据不会被重复读取，未设置 cache 时不加缓存节点；low_memory 没有对应的读取过程，设置时报错。
+pub fn in_memory_scan(
+    mut df: DataFrame,
+    options: Option<&proto::ScanOptions>,
+    node: &str,
+) -> Result<LazyFrame, BridgeError> {
+    let Some(options) = options else {
+        return Ok(df.lazy());
+    };
+    reject_low_memory(Some(options), node)?;
+
+    if options.rechunk {
+        df.rechunk_mut();
+    }
+    let lf = df.lazy();
+    Ok(if options.cache == Some(true) { lf.cache() } else { lf })
+}
+
+fn reject_low_memory(options: Option<&proto::ScanOptions>, node: &str) -> Result<(), BridgeError> {
+    match options {
+        Some(options) if options.low_memory => Err(BridgeError::Unsupported(format!(
+            "{node} does not support low_memory"
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// 应用通用扫描选项中的行范围与行号（Polars 优化器会把 slice 下推到扫描）
+pub fn apply_scan_options(
+    mut lf: LazyFrame,
+    options: Option<&proto::ScanOptions>,
+) -> Result<LazyFrame, BridgeError> {
+    let Some(options) = options else {
+        return Ok(lf);
+    };
+
+    if options.skip_rows > 0 || options.n_rows.is_some() {
+        let offset = i64::try_from(options.skip_rows).map_err(|_| {
+            BridgeError::InvalidArgument(format!("skip_rows is too large: {}", options.skip_rows))
+        })?;
+        let len = options
+            .n_rows
+            .map(|n| n.min(IdxSize::MAX as u64) as IdxSize)
+            .unwrap_or(IdxSize::MAX);
+        lf = lf.slice(offset, len);
+    }
+    if !options.row_index_name.is_empty() {
+        let offset = options.row_index_offset as IdxSize;
+        lf = lf.with_row_index(options.row_index_name.as_str(), Some(offset));
+    }
+    Ok(lf)
+}
+
+/// AvroScan：Avro 没有懒加载扫描，整体读入内存后转为 LazyFrame
+#[cfg(feature = "avro")]
+pub fn avro_scan(scan: &proto::AvroScan) -> Result<LazyFrame, BridgeError> {
+    let file = File::open(&scan.path).map_err(|e| {
+        BridgeError::Execution(format!("AvroScan failed to open '{}': {}", scan.path, e))
+    })?;
+
+    let mut reader = AvroReader::new(file);
+    if !scan.columns.is_empty() {
+        reader = reader.with_columns(Some(scan.columns.clone()));
+    }
+    // n_rows 可以直接交给读取器，skip_rows 仍由 apply_scan_options 处理
+    if let Some(options) = scan.options.as_ref().filter(|o| o.skip_rows == 0) {
+        reader = reader.with_n_rows(options.n_rows.map(|n| n as usize));
+    }
+
+    let df = reader.finish().map_err(|e| {
+        BridgeError::Execution(format!("AvroScan failed for '{}': {}", scan.path, e))
+    })?;
+    in_memory_scan(df, scan.options.as_ref(), "AvroScan")
+}
+
+#[cfg(not(feature = "avro"))]
+pub fn avro_scan(_scan: &proto::AvroScan) -> Result<LazyFrame, BridgeError> {
+    Err(crate::features::unavailable("AvroScan", "avro"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::executor;
+    use crate::test_plans::{node, plan};
+    use proto::node::Kind as N;
+
+    // id,value / 1,100 / 2,200 / 3,300
+    const SMALL_CSV: &str = "../testdata/small.csv";
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("polars_bridge_scan_{}_{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn path_str(path: &Path) -> String {
+        path.to_str().unwrap().to_string()
+    }
+
+    fn scan(kind: N) -> Result<DataFrame, BridgeError> {
+        executor::execute_plan_df(&plan(node(1, kind)), None)
+    }
+
+    fn small() -> DataFrame {
+        df!("id" => [1i64, 2, 3], "value" => [100i64, 200, 300]).unwrap()
+    }
+
+    #[cfg(feature = "parquet")]
+    fn write_parquet(path: &Path, mut df: DataFrame) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        ParquetWriter::new(File::create(path).unwrap()).finish(&mut df).unwrap();
+    }
+
+    #[test]
+    fn csv_parse_options() {
+        let path = temp_dir("parse").join("data.csv");
+        fs::write(&path, "# exported\nid|name|score\n1|'a|b'|NA\n2|bob|2.5\n").unwrap();
+
+        let df = scan(N::CsvScan(proto::CsvScan {
+            path: path_str(&path),
+            separator: "|".into(),
+            quote_char: Some("'".into()),
+            comment_prefix: "#".into(),
+            null_values: vec!["NA".into()],
+            ..Default::default()
+        }))
+        .unwrap();
+        let expected = df!("id" => [1i64, 2], "name" => ["a|b", "bob"], "score" => [None, Some(2.5f64)]).unwrap();
+        assert!(df.equals_missing(&expected), "{df}");
+    }
+
+    #[test]
+    fn csv_schema_overrides_and_inference_length() {
+        let df = scan(N::CsvScan(proto::CsvScan {
+            path: SMALL_CSV.into(),
+            schema_overrides: vec![proto::Field { name: "value".into(), data_type: proto::DataType::Float64 as i32 }],
+            ..Default::default()
+        }))
+        .unwrap();
+        assert_eq!(df.column("id").unwrap().dtype(), &DataType::Int64);
+        assert_eq!(df.column("value").unwrap().f64().unwrap().get(2), Some(300.0));
+
+        // 0 表示不推断：全部按字符串读取
+        let df = scan(N::CsvScan(proto::CsvScan {
+            path: SMALL_CSV.into(),
+            infer_schema_length: Some(0),
+            ..Default::default()
+        }))
+        .unwrap();
+        assert!(df.equals(&df!("id" => ["1", "2", "3"], "value" => ["100", "200", "300"]).unwrap()), "{df}");
+    }
+
+    #[test]
+    fn scan_options_slice_before_row_index() {
+        let df = scan(N::CsvScan(proto::CsvScan {
+            path: SMALL_CSV.into(),
+            options: Some(proto::ScanOptions {
+                skip_rows: 1,
+                n_rows: Some(1),
+                row_index_name: "row".into(),
+                row_index_offset: 10,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .unwrap();
+        let expected = df!("row" => [10 as IdxSize], "id" => [2i64], "value" => [200i64]).unwrap();
+        assert!(df.equals(&expected), "{df}");
+    }
+
+    #[test]
+    fn csv_globs_and_extra_paths_scan_as_one_frame() {
+        let dir = temp_dir("glob");
+        fs::write(dir.join("a.csv"), "id,value\n1,100\n").unwrap();
+        fs::write(dir.join("b.csv"), "id,value\n2,200\n").unwrap();
+        fs::write(dir.join("c.txt"), "id,value\n9,900\n").unwrap();
+
+        let df = scan(N::CsvScan(proto::CsvScan {
+            path: format!("{}/*.csv", dir.display()),
+            paths: vec![SMALL_CSV.into()],
+            ..Default::default()
+        }))
+        .unwrap();
+        let expected = df!("id" => [1i64, 2, 1, 2, 3], "value" => [100i64, 200, 100, 200, 300]).unwrap();
+        assert!(df.equals(&expected), "{df}");
+    }
+
+    #[test]
+    fn compressed_csv_is_decompressed() {
+        let df = scan(N::CsvScan(proto::CsvScan {
+            path: "../testdata/small.csv.gz".into(),
+            ..Default::default()
+        }))
+        .unwrap();
+        assert!(df.equals(&small()), "{df}");
+    }
+
+    #[test]
+    fn ipc_scan_with_and_without_memory_map() {
+        let path = temp_dir("ipc").join("data.ipc");
+        IpcWriter::new(File::create(&path).unwrap()).finish(&mut small()).unwrap();
+
+        for memory_map in [true, false] {
+            let df = scan(N::IpcScan(proto::IpcScan {
+                path: path_str(&path),
+                memory_map: Some(memory_map),
+                n_rows: Some(5),
+                options: Some(proto::ScanOptions { n_rows: Some(2), ..Default::default() }),
+                ..Default::default()
+            }))
+            .unwrap();
+            assert!(df.equals(&small().head(Some(2))), "memory_map={memory_map}\n{df}");
+        }
+
+        let err = scan(N::IpcScan(proto::IpcScan {
+            path: path_str(&path),
+            options: Some(proto::ScanOptions { low_memory: true, ..Default::default() }),
+            ..Default::default()
+        }))
+        .unwrap_err();
+        assert!(matches!(err, BridgeError::Unsupported(_)), "{err}");
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn parquet_scan_reads_hive_partitions() {
+        let dir = temp_dir("hive");
+        write_parquet(&dir.join("year=2023/part.parquet"), df!("id" => [1i64, 2]).unwrap());
+        write_parquet(&dir.join("year=2024/part.parquet"), df!("id" => [3i64]).unwrap());
+
+        let df = scan(N::ParquetScan(proto::ParquetScan {
+            path: format!("{}/**/*.parquet", dir.display()),
+            hive: Some(proto::HiveOptions {
+                enabled: Some(true),
+                schema: vec![proto::Field { name: "year".into(), data_type: proto::DataType::Int32 as i32 }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .unwrap();
+        let expected = df!("id" => [1i64, 2, 3], "year" => [2023i32, 2023, 2024]).unwrap();
+        assert!(df.equals(&expected), "{df}");
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn parquet_snapshot_directory_selects_a_version() {
+        let dir = temp_dir("snapshots");
+        write_parquet(&dir.join("v1/part.parquet"), df!("id" => [1i64]).unwrap());
+        write_parquet(&dir.join("v2/part-0.parquet"), df!("id" => [1i64, 2]).unwrap());
+        write_parquet(&dir.join("v2/sub/part-1.parquet"), df!("id" => [3i64]).unwrap());
+        fs::create_dir_all(dir.join("not_a_version")).unwrap();
+
+        let snapshot = |as_of| {
+            scan(N::ParquetScan(proto::ParquetScan {
+                path: path_str(&dir),
+                as_of: Some(as_of),
+                ..Default::default()
+            }))
+        };
+        let latest = snapshot(proto::AsOf::default()).unwrap();
+        assert!(latest.equals(&df!("id" => [1i64, 2, 3]).unwrap()), "{latest}");
+        let v1 = snapshot(proto::AsOf { version: Some(1), timestamp_ms: None }).unwrap();
+        assert!(v1.equals(&df!("id" => [1i64]).unwrap()), "{v1}");
+
+        let err = snapshot(proto::AsOf { version: Some(3), timestamp_ms: None }).unwrap_err();
+        assert!(err.to_string().contains("version 3 not found (latest is 2)"), "{err}");
+        let err = snapshot(proto::AsOf { version: Some(1), timestamp_ms: Some(0) }).unwrap_err();
+        assert!(matches!(err, BridgeError::InvalidArgument(_)), "{err}");
+    }
+
+    #[cfg(feature = "avro")]
+    #[test]
+    fn avro_scan_reads_selected_columns() {
+        let path = path_str(&temp_dir("avro").join("data.avro"));
+        let data = df!("id" => [1i64, 2, 3], "name" => ["ann", "bob", "cy"]).unwrap();
+        crate::writer::write_avro(&data, &path, &proto::AvroWriteOptions::default()).unwrap();
+
+        let df = scan(N::AvroScan(proto::AvroScan {
+            path: path.clone(),
+            columns: vec!["name".into()],
+            options: Some(proto::ScanOptions { skip_rows: 1, ..Default::default() }),
+        }))
+        .unwrap();
+        assert!(df.equals(&df!("name" => ["bob", "cy"]).unwrap()), "{df}");
+    }
+}
@@ -0,0 +1,111 @@
+use polars::prelude::*;
+
+use crate::decimal::MAX_PRECISION;
+use crate::error::BridgeError;
+use crate::proto;
+
+/// 将 proto DataType 转换为 Polars DataType
+pub fn to_polars_dtype(data_type: i32) -> Result<DataType, BridgeError> {
+    match proto::DataType::try_from(data_type) {
+        Ok(proto::DataType::Int64) => Ok(DataType::Int64),
+        Ok(proto::DataType::Int32) => Ok(DataType::Int32),
+        Ok(proto::DataType::Int16) => Ok(DataType::Int16),
+        Ok(proto::DataType::Int8) => Ok(DataType::Int8),
+        Ok(proto::DataType::Uint64) => Ok(DataType::UInt64),
+        Ok(proto::DataType::Uint32) => Ok(DataType::UInt32),
+        Ok(proto::DataType::Uint16) => Ok(DataType::UInt16),
+        Ok(proto::DataType::Uint8) => Ok(DataType::UInt8),
+        Ok(proto::DataType::Float64) => Ok(DataType::Float64),
+        Ok(proto::DataType::Float32) => Ok(DataType::Float32),
+        Ok(proto::DataType::Bool) => Ok(DataType::Boolean),
+        Ok(proto::DataType::Utf8) => Ok(DataType::String),
+        Ok(proto::DataType::Date) => Ok(DataType::Date),
+        Ok(proto::DataType::Datetime) => Ok(DataType::Datetime(TimeUnit::Microseconds, None)),
+        Ok(proto::DataType::Time) => Ok(DataType::Time),
+        Ok(proto::DataType::Decimal) => Ok(DataType::Decimal(MAX_PRECISION, 0)),
+        Err(_) => Err(BridgeError::Unsupported(format!(
+            "Unknown data type: {}",
+            data_type
+        ))),
+    }
+}
+
+/// 能转换为 Polars 类型的 proto DataType 名称（用于能力声明）
+pub fn supported_dtypes() -> Vec<String> {
+    (0..)
+        .map_while(|value| proto::DataType::try_from(value).ok())
+        .filter(|dtype| to_polars_dtype(*dtype as i32).is_ok())
+        .map(|dtype| format!("{:?}", dtype))
+        .collect()
+}
+
+/// 将 proto Field 列表转换为 Polars Schema
+pub fn to_polars_schema(fields: &[proto::Field]) -> Result<Schema, BridgeError> {
+    fields
+        .iter()
+        .map(|f| Ok(Field::new(f.name.as_str().into(), to_polars_dtype(f.data_type)?)))
+        .collect()
+}
+
+/// 校验实际 schema 是否满足期望的 schema 契约（列名与类型一致，不要求顺序）
+pub fn validate_schema(expected: &proto::Schema, actual: &Schema) -> Result<(), BridgeError> {
+    let mut differences = Vec::new();
+
+    for field in &expected.fields {
+        let expected_dtype = to_polars_dtype(field.data_type)?;
+        match actual.get(field.name.as_str()) {
+            None => differences.push(format!("missing column '{}'", field.name)),
+            Some(dtype) if !dtype_matches(&expected_dtype, dtype) => differences.push(format!(
+                "column '{}': expected {}, got {}",
+                field.name, expected_dtype, dtype
+            )),
+            Some(_) => {}
+        }
+    }
+
+    for name in actual.iter_names() {
+        if !expected.fields.iter().any(|f| f.name == name.as_str()) {
+            differences.push(format!("unexpected column '{}'", name));
+        }
+    }
+
+    if differences.is_empty() {
+        Ok(())
+    } else {
+        Err(BridgeError::SchemaMismatch(differences.join("; ")))
+    }
+}
+
+/// 将 proto Cast 的目标类型转换为 Polars DataType（DECIMAL 时带上精度与小数位数）
+pub fn cast_dtype(cast: &proto::Cast) -> Result<DataType, BridgeError> {
+    match to_polars_dtype(cast.data_type)? {
+        DataType::Decimal(_, _) => Ok(DataType::Decimal(
+            cast.precision.map_or(MAX_PRECISION, |p| p as usize),
+            cast.scale.map_or(0, |s| s as usize),
+        )),
+        dtype => Ok(dtype),
+    }
+}
+
+// Datetime 不区分时间单位与时区，Decimal 不区分精度与小数位数
+fn dtype_matches(expected: &DataType, actual: &DataType) -> bool {
+    match (expected, actual) {
+        (DataType::Datetime(_, _), DataType::Datetime(_, _)) => true,
+        (DataType::Decimal(_, _), DataType::Decimal(_, _)) => true,
+        _ => expected == actual,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn supported_dtypes_cover_every_proto_type() {
+        let dtypes = supported_dtypes();
+        assert_eq!(dtypes.len(), proto::DataType::Decimal as usize + 1);
+        for name in ["Int64", "Uint8", "Float32", "Bool", "Utf8", "Datetime", "Decimal"] {
+            assert!(dtypes.iter().any(|dtype| dtype == name), "missing {name}: {dtypes:?}");
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use polars::prelude::*;
+
+use crate::error::BridgeError;
+use crate::executor::{build_lazy_frame, BuildContext};
+use crate::nodes::node_inputs;
+use crate::proto;
+
+/// 注册在会话中的表：DataFrame 快照或在执行时才构建的节点（通常是扫描）
+#[derive(Clone)]
+pub enum TableSource {
+    DataFrame(Arc<DataFrame>),
+    Node(Box<proto::Node>),
+}
+
+/// 会话：逻辑表名 -> 物理数据源，TableScan 在执行时按名称解析
+#[derive(Default)]
+pub struct Session {
+    tables: RwLock<HashMap<String, TableSource>>,
+}
+
+impl Session {
+    /// 注册（或替换）一张表
+    pub fn register(&self, name: &str, source: TableSource) -> Result<(), BridgeError> {
+        if name.is_empty() {
+            return Err(BridgeError::InvalidArgument("Table name cannot be empty".into()));
+        }
+        if let TableSource::Node(node) = &source {
+            validate_table_node(node)?;
+        }
+        self.tables
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(name.to_string(), source);
+        Ok(())
+    }
+
+    pub fn unregister(&self, name: &str) -> bool {
+        self.tables
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(name)
+            .is_some()
+    }
+
+    fn resolve(&self, name: &str) -> Result<TableSource, BridgeError> {
+        self.tables
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(name)
+            .cloned()
+            .ok_or_else(|| BridgeError::PlanSemantic(format!("Unknown table: '{}'", name)))
+    }
+}
+
+/// TableScan：按名称从执行会话中解析表
+pub fn table_scan(scan: &proto::TableScan, ctx: &BuildContext) -> Result<LazyFrame, BridgeError> {
+    let session = ctx.session.as_ref().ok_or_else(|| {
+        BridgeError::PlanSemantic(format!(
+            "TableScan '{}' requires a session (ExecutionHints.session_handle)",
+            scan.name
+        ))
+    })?;
+
+    match session.resolve(&scan.name)? {
+        TableSource::DataFrame(df) => Ok(df.as_ref().clone().lazy()),
+        TableSource::Node(node) => build_lazy_frame(&node, ctx),
+    }
+}
+
+// 注册的节点不能依赖执行时的输入或其他表（避免循环引用）
+fn validate_table_node(node: &proto::Node) -> Result<(), BridgeError> {
+    use proto::node::Kind;
+
+    match node.kind.as_ref() {
+        None => Err(BridgeError::PlanSemantic("Table node has no kind".into())),
+        Some(Kind::MemoryScan(_)) | Some(Kind::TableScan(_)) => Err(BridgeError::PlanSemantic(
+            "Registered table nodes cannot contain MemoryScan or TableScan".into(),
+        )),
+        Some(_) => node_inputs(node).into_iter().try_for_each(validate_table_node),
+    }
+}
@@ -0,0 +1,348 @@
+use polars::prelude::*;
+
+use crate::error::BridgeError;
+#[cfg(feature = "parquet")]
+use crate::parquet_columns::sink_parquet_columns;
+use crate::proto;
+
+/// SinkParquet：将 LazyFrame 流式写入 Parquet 文件（collect 时执行）
+#[cfg(feature = "parquet")]
+pub fn sink_parquet(lf: LazyFrame, sink: &proto::SinkParquet) -> Result<LazyFrame, BridgeError> {
+    let target = sink_target(&sink.path, "SinkParquet")?;
+    if !sink.columns.is_empty() {
+        return sink_parquet_columns(lf, sink);
+    }
+
+    let statistics = match sink.statistics {
+        Some(false) => StatisticsOptions::empty(),
+        _ => StatisticsOptions::default(),
+    };
+    let options = ParquetWriteOptions {
+        compression: parquet_compression(sink.compression, sink.compression_level)?,
+        statistics,
+        row_group_size: sink.row_group_size.map(|n| n as usize),
+        data_page_size: sink.data_page_size.map(|n| n as usize),
+        ..Default::default()
+    };
+
+    lf.sink_parquet(target, options, None, SinkOptions::default())
+        .map_err(|e| BridgeError::Execution(format!("SinkParquet failed for '{}': {}", sink.path, e)))
+}
+
+#[cfg(not(feature = "parquet"))]
+pub fn sink_parquet(_lf: LazyFrame, _sink: &proto::SinkParquet) -> Result<LazyFrame, BridgeError> {
+    Err(crate::features::unavailable("SinkParquet", "parquet"))
+}
+
+/// SinkCsv：将 LazyFrame 流式写入 CSV 文件（collect 时执行）
+pub fn sink_csv(lf: LazyFrame, sink: &proto::SinkCsv) -> Result<LazyFrame, BridgeError> {
+    let target = sink_target(&sink.path, "SinkCsv")?;
+
+    let mut serialize_options = SerializeOptions::default();
+    if !sink.separator.is_empty() {
+        serialize_options.separator = parse_ascii_char(&sink.separator, "SinkCsv separator")?;
+    }
+    if !sink.quote_char.is_empty() {
+        serialize_options.quote_char = parse_ascii_char(&sink.quote_char, "SinkCsv quote_char")?;
+    }
+    if !sink.line_terminator.is_empty() {
+        serialize_options.line_terminator = sink.line_terminator.clone();
+    }
+    serialize_options.null = sink.null_value.clone();
+    serialize_options.quote_style = quote_style(sink.quote_style)?;
+    serialize_options.datetime_format = sink.datetime_format.clone();
+    serialize_options.date_format = sink.date_format.clone();
+    serialize_options.time_format = sink.time_format.clone();
+    serialize_options.float_precision = sink.float_precision.map(|p| p as usize);
+
+    let options = CsvWriterOptions {
+        include_bom: sink.include_bom,
+        include_header: sink.include_header.unwrap_or(true),
+        serialize_options,
+        ..Default::default()
+    };
+
+    lf.sink_csv(target, options, None, SinkOptions::default())
+        .map_err(|e| BridgeError::Execution(format!("SinkCsv failed for '{}': {}", sink.path, e)))
+}
+
+/// SinkNdjson：将 LazyFrame 流式写入 NDJSON 文件（collect 时执行）
+pub fn sink_ndjson(lf: LazyFrame, sink: &proto::SinkNdjson) -> Result<LazyFrame, BridgeError> {
+    let target = sink_target(&sink.path, "SinkNdjson")?;
+
+    lf.sink_json(target, JsonWriterOptions::default(), None, SinkOptions::default())
+        .map_err(|e| BridgeError::Execution(format!("SinkNdjson failed for '{}': {}", sink.path, e)))
+}
+
+/// SinkIpc：将 LazyFrame 流式写入 Arrow IPC 文件（collect 时执行）
+pub fn sink_ipc(lf: LazyFrame, sink: &proto::SinkIpc) -> Result<LazyFrame, BridgeError> {
+    let target = sink_target(&sink.path, "SinkIpc")?;
+
+    let options = IpcWriterOptions {
+        compression: ipc_compression(sink.compression)?,
+        ..Default::default()
+    };
+
+    lf.sink_ipc(target, options, None, SinkOptions::default())
+        .map_err(|e| BridgeError::Execution(format!("SinkIpc failed for '{}': {}", sink.path, e)))
+}
+
+pub fn ipc_compression(compression: i32) -> Result<Option<IpcCompression>, BridgeError> {
+    use proto::IpcCompression as Compression;
+
+    match Compression::try_from(compression) {
+        Ok(Compression::Uncompressed) => Ok(None),
+        Ok(Compression::Lz4) => Ok(Some(IpcCompression::LZ4)),
+        Ok(Compression::Zstd) => Ok(Some(IpcCompression::ZSTD(Default::default()))),
+        Err(_) => Err(BridgeError::Unsupported(format!(
+            "Unknown IPC compression: {}",
+            compression
+        ))),
+    }
+}
+
+fn quote_style(style: i32) -> Result<QuoteStyle, BridgeError> {
+    use proto::CsvQuoteStyle as Style;
+
+    match Style::try_from(style) {
+        Ok(Style::Necessary) => Ok(QuoteStyle::Necessary),
+        Ok(Style::Always) => Ok(QuoteStyle::Always),
+        Ok(Style::NonNumeric) => Ok(QuoteStyle::NonNumeric),
+        Ok(Style::Never) => Ok(QuoteStyle::Never),
+        Err(_) => Err(BridgeError::Unsupported(format!("Unknown CSV quote style: {}", style))),
+    }
+}
+
+pub fn parse_ascii_char(value: &str, name: &str) -> Result<u8, BridgeError> {
+    match value.as_bytes() {
+        [b] if b.is_ascii() => Ok(*b),
+        _ => Err(BridgeError::InvalidArgument(format!(
+            "{name} must be a single ASCII character"
+        ))),
+    }
+}
+
+fn sink_target(path: &str, name: &str) -> Result<SinkTarget, BridgeError> {
+    if path.is_empty() {
+        return Err(BridgeError::InvalidArgument(format!("{name} path cannot be empty")));
+    }
+    Ok(SinkTarget::Path(PlPath::new(path)))
+}
+
+#[cfg(feature = "parquet")]
+pub fn parquet_compression(
+    compression: i32,
+    level: Option<i32>,
+) -> Result<ParquetCompression, BridgeError> {
+    use proto::ParquetCompression as Compression;
+
+    let invalid_level = |e: PolarsError| {
+        BridgeError::InvalidArgument(format!("Invalid parquet compression level: {}", e))
+    };
+
+    match Compression::try_from(compression) {
+        Ok(Compression::Zstd) => Ok(ParquetCompression::Zstd(
+            level.map(ZstdLevel::try_new).transpose().map_err(invalid_level)?,
+        )),
+        Ok(Compression::Uncompressed) => Ok(ParquetCompression::Uncompressed),
+        Ok(Compression::Snappy) => Ok(ParquetCompression::Snappy),
+        Ok(Compression::Gzip) => Ok(ParquetCompression::Gzip(
+            level
+                .map(|l| GzipLevel::try_new(l as u8))
+                .transpose()
+                .map_err(invalid_level)?,
+        )),
+        Ok(Compression::Lz4Raw) => Ok(ParquetCompression::Lz4Raw),
+        Ok(Compression::Brotli) => Ok(ParquetCompression::Brotli(
+            level
+                .map(|l| BrotliLevel::try_new(l as u32))
+                .transpose()
+                .map_err(invalid_level)?,
+        )),
+        Err(_) => Err(BridgeError::Unsupported(format!(
+            "Unknown parquet compression: {}",
+            compression
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use polars::prelude::{df, DataFrame, JsonLineReader, SerReader};
+
+    use super::*;
+    use crate::executor;
+    use crate::test_plans::{input, memory_scan, node, plan};
+    use proto::node::Kind as N;
+
+    // 名字里带分隔符与引号，score 含 null
+    fn data() -> DataFrame {
+        df!(
+            "id" => [1i64, 2, 3],
+            "name" => ["ann", "b,ob", "c\"y"],
+            "score" => [Some(1.5f64), None, Some(-2.25)],
+        )
+        .unwrap()
+    }
+
+    fn temp_path(file: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("polars_bridge_sink_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir.join(file)
+    }
+
+    fn run_sink(sink: N) {
+        let result = executor::execute_plan_df(&plan(node(2, sink)), Some(&data())).unwrap();
+        assert_eq!(result.height(), 0);
+    }
+
+    fn scan(kind: N) -> DataFrame {
+        executor::execute_plan_df(&plan(node(1, kind)), None).unwrap()
+    }
+
+    #[test]
+    fn csv_sink_round_trips_through_csv_scan() {
+        let path = temp_path("round_trip.csv");
+        let path_str = path.to_str().unwrap().to_string();
+        run_sink(N::SinkCsv(Box::new(proto::SinkCsv {
+            input: input(memory_scan()),
+            path: path_str.clone(),
+            separator: ";".into(),
+            null_value: "NA".into(),
+            ..Default::default()
+        })));
+
+        let text = fs::read_to_string(&path).unwrap();
+        assert_eq!(text, "id;name;score\n1;ann;1.5\n2;b,ob;NA\n3;\"c\"\"y\";-2.25\n");
+
+        let df = scan(N::CsvScan(proto::CsvScan {
+            path: path_str,
+            separator: ";".into(),
+            null_values: vec!["NA".into()],
+            ..Default::default()
+        }));
+        assert!(df.equals_missing(&data()), "{df}");
+    }
+
+    #[test]
+    fn ndjson_sink_writes_one_object_per_row() {
+        let path = temp_path("round_trip.ndjson");
+        run_sink(N::SinkNdjson(Box::new(proto::SinkNdjson {
+            input: input(memory_scan()),
+            path: path.to_str().unwrap().into(),
+        })));
+
+        let text = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            text.lines().collect::<Vec<_>>(),
+            [
+                r#"{"id":1,"name":"ann","score":1.5}"#,
+                r#"{"id":2,"name":"b,ob","score":null}"#,
+                r#"{"id":3,"name":"c\"y","score":-2.25}"#,
+            ]
+        );
+
+        let df = JsonLineReader::new(fs::File::open(&path).unwrap()).finish().unwrap();
+        assert!(df.equals_missing(&data()), "{df}");
+    }
+
+    #[test]
+    fn ipc_sink_round_trips_with_every_compression() {
+        for (compression, file) in [
+            (proto::IpcCompression::Uncompressed, "plain.ipc"),
+            (proto::IpcCompression::Lz4, "lz4.ipc"),
+            (proto::IpcCompression::Zstd, "zstd.ipc"),
+        ] {
+            let path = temp_path(file).to_str().unwrap().to_string();
+            run_sink(N::SinkIpc(Box::new(proto::SinkIpc {
+                input: input(memory_scan()),
+                path: path.clone(),
+                compression: compression as i32,
+            })));
+
+            let df = scan(N::IpcScan(proto::IpcScan { path, ..Default::default() }));
+            assert!(df.equals_missing(&data()), "{compression:?}\n{df}");
+        }
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn parquet_sink_round_trips_through_parquet_scan() {
+        use polars_parquet::parquet::compression::Compression;
+
+        let path = temp_path("round_trip.parquet").to_str().unwrap().to_string();
+        run_sink(N::SinkParquet(Box::new(proto::SinkParquet {
+            input: input(memory_scan()),
+            path: path.clone(),
+            compression: proto::ParquetCompression::Snappy as i32,
+            statistics: Some(false),
+            ..Default::default()
+        })));
+
+        let df = scan(N::ParquetScan(proto::ParquetScan { path: path.clone(), ..Default::default() }));
+        assert!(df.equals_missing(&data()), "{df}");
+
+        let mut reader = ParquetReader::new(fs::File::open(&path).unwrap());
+        let metadata = reader.get_metadata().unwrap();
+        for column in metadata.row_groups[0].parquet_columns() {
+            assert_eq!(column.compression(), Compression::Snappy);
+            assert!(column.statistics().is_none());
+        }
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn parquet_sink_applies_column_options() {
+        use polars_parquet::parquet::compression::Compression;
+
+        let path = temp_path("columns.parquet").to_str().unwrap().to_string();
+        run_sink(N::SinkParquet(Box::new(proto::SinkParquet {
+            input: input(memory_scan()),
+            path: path.clone(),
+            compression: proto::ParquetCompression::Zstd as i32,
+            columns: vec![
+                proto::ParquetColumnOptions {
+                    name: "name".into(),
+                    dictionary: Some(false),
+                    compression: Some(proto::ParquetCompression::Uncompressed as i32),
+                    ..Default::default()
+                },
+                proto::ParquetColumnOptions {
+                    name: "score".into(),
+                    encoding: proto::ParquetColumnEncoding::ByteStreamSplit as i32,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        })));
+
+        let df = scan(N::ParquetScan(proto::ParquetScan { path: path.clone(), ..Default::default() }));
+        assert!(df.equals_missing(&data()), "{df}");
+
+        let mut reader = ParquetReader::new(fs::File::open(&path).unwrap());
+        let metadata = reader.get_metadata().unwrap();
+        let columns = metadata.row_groups[0].parquet_columns();
+        // 未设置选项的列沿用文件级压缩
+        assert_eq!(columns[0].compression(), Compression::Zstd);
+        assert!(columns[1].dictionary_page_offset().is_none());
+        assert_eq!(columns[1].compression(), Compression::Uncompressed);
+        assert!(columns[2].dictionary_page_offset().is_none());
+        assert_eq!(columns[2].compression(), Compression::Zstd);
+    }
+
+    #[test]
+    fn sinks_reject_invalid_options() {
+        let err = sink_csv(data().lazy(), &proto::SinkCsv::default()).err().unwrap();
+        assert!(matches!(err, BridgeError::InvalidArgument(_)), "{err}");
+
+        let sink = proto::SinkCsv { path: "out.csv".into(), separator: "::".into(), ..Default::default() };
+        let err = sink_csv(data().lazy(), &sink).err().unwrap();
+        assert!(err.to_string().contains("SinkCsv separator must be a single ASCII character"), "{err}");
+
+        let sink = proto::SinkIpc { path: "out.ipc".into(), compression: 99, ..Default::default() };
+        assert!(matches!(sink_ipc(data().lazy(), &sink), Err(BridgeError::Unsupported(_))));
+    }
+}
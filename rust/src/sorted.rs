@@ -0,0 +1,42 @@
+use polars::prelude::*;
+use polars::series::IsSorted;
+
+use crate::error::BridgeError;
+
+pub fn sorted_flag(descending: bool) -> IsSorted {
+    if descending {
+        IsSorted::Descending
+    } else {
+        IsSorted::Ascending
+    }
+}
+
+/// 检查列是否有序（已有排序标记时直接返回，否则扫描数据）
+pub fn is_sorted(df: &DataFrame, name: &str, descending: bool) -> Result<bool, BridgeError> {
+    let series = df
+        .column(name)
+        .map_err(|e| BridgeError::InvalidArgument(format!("Column '{}' not found: {}", name, e)))?
+        .as_materialized_series();
+    if series.is_sorted_flag() == sorted_flag(descending) {
+        return Ok(true);
+    }
+
+    let options = SortOptions::default()
+        .with_order_descending(descending)
+        .with_nulls_last(descending);
+    series
+        .is_sorted(options)
+        .map_err(|e| BridgeError::Execution(format!("Failed to check sortedness of '{}': {}", name, e)))
+}
+
+/// 返回设置了排序标记的 DataFrame 副本（列数据共享，不复制）
+pub fn set_sorted(df: &DataFrame, name: &str, descending: bool) -> Result<DataFrame, BridgeError> {
+    let idx = df
+        .get_column_index(name)
+        .ok_or_else(|| BridgeError::InvalidArgument(format!("Column '{}' not found", name)))?;
+
+    let mut columns = df.get_columns().to_vec();
+    columns[idx].set_sorted_flag(sorted_flag(descending));
+    DataFrame::new(columns)
+        .map_err(|e| BridgeError::Execution(format!("Failed to set sorted flag: {}", e)))
+}
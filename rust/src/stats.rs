@@ -0,0 +1,95 @@
+use polars::prelude::*;
+
+use crate::error::BridgeError;
+use crate::proto;
+
+/// 计算 DataFrame 中某一列的统计信息
+pub fn column_stats(df: &DataFrame, name: &str) -> Result<proto::ColumnStats, BridgeError> {
+    let column = df
+        .column(name)
+        .map_err(|e| BridgeError::InvalidArgument(format!("Column '{}' not found: {}", name, e)))?;
+    let series = column.as_materialized_series();
+
+    let to_execution_error =
+        |e: PolarsError| BridgeError::Execution(format!("Failed to compute stats for '{}': {}", name, e));
+
+    let min = series.min_reduce().map_err(to_execution_error)?;
+    let max = series.max_reduce().map_err(to_execution_error)?;
+    let n_unique = series.n_unique().map_err(to_execution_error)?;
+
+    Ok(proto::ColumnStats {
+        name: name.to_string(),
+        dtype: series.dtype().to_string(),
+        len: series.len() as u64,
+        null_count: series.null_count() as u64,
+        n_unique: n_unique as u64,
+        min: Some(any_value_to_literal(min.value())),
+        max: Some(any_value_to_literal(max.value())),
+        estimated_size: series.estimated_size() as u64,
+    })
+}
+
+/// 读取 DataFrame 中某一列某一行的值
+pub fn scalar(df: &DataFrame, name: &str, row: usize) -> Result<proto::Scalar, BridgeError> {
+    let column = df
+        .column(name)
+        .map_err(|e| BridgeError::InvalidArgument(format!("Column '{}' not found: {}", name, e)))?;
+    if row >= column.len() {
+        return Err(BridgeError::InvalidArgument(format!(
+            "Row {} out of bounds for column '{}' of length {}",
+            row,
+            name,
+            column.len()
+        )));
+    }
+
+    let value = column
+        .get(row)
+        .map_err(|e| BridgeError::Execution(format!("Failed to read '{}'[{}]: {}", name, row, e)))?;
+    Ok(proto::Scalar {
+        column: name.to_string(),
+        dtype: column.dtype().to_string(),
+        value: Some(any_value_to_literal(&value)),
+    })
+}
+
+/// 读取 1x1 DataFrame 的唯一值（常见于全表聚合的结果）
+pub fn item(df: &DataFrame) -> Result<proto::Scalar, BridgeError> {
+    if df.shape() != (1, 1) {
+        return Err(BridgeError::InvalidArgument(format!(
+            "Expected a 1x1 DataFrame, got shape {:?}",
+            df.shape()
+        )));
+    }
+    let name = df.get_column_names()[0].clone();
+    scalar(df, name.as_str(), 0)
+}
+
+/// 将 AnyValue 转换为 proto Literal（无法直接表示的类型转为字符串）
+pub fn any_value_to_literal(value: &AnyValue) -> proto::Literal {
+    use proto::literal::Value;
+
+    let value = match value {
+        AnyValue::Null => Value::NullVal(proto::NullValue {}),
+        AnyValue::Boolean(v) => Value::BoolVal(*v),
+        AnyValue::Int8(v) => Value::IntVal(*v as i64),
+        AnyValue::Int16(v) => Value::IntVal(*v as i64),
+        AnyValue::Int32(v) => Value::IntVal(*v as i64),
+        AnyValue::Int64(v) => Value::IntVal(*v),
+        AnyValue::UInt8(v) => Value::IntVal(*v as i64),
+        AnyValue::UInt16(v) => Value::IntVal(*v as i64),
+        AnyValue::UInt32(v) => Value::IntVal(*v as i64),
+        AnyValue::UInt64(v) => match i64::try_from(*v) {
+            Ok(v) => Value::IntVal(v),
+            Err(_) => Value::StringVal(v.to_string()),
+        },
+        AnyValue::Float32(v) => Value::FloatVal(*v as f64),
+        AnyValue::Float64(v) => Value::FloatVal(*v),
+        AnyValue::String(v) => Value::StringVal(v.to_string()),
+        AnyValue::StringOwned(v) => Value::StringVal(v.to_string()),
+        AnyValue::Decimal(..) => Value::DecimalVal(value.to_string()),
+        other => Value::StringVal(other.to_string()),
+    };
+
+    proto::Literal { value: Some(value) }
+}
@@ -0,0 +1,673 @@
+use std::ffi::c_void;
+use std::io::{BufRead, BufReader, Cursor, ErrorKind};
+use std::net::TcpStream;
+use std::os::raw::c_int;
+use std::sync::Arc;
+use std::time::Duration;
+
+use polars::prelude::*;
+use polars_arrow::ffi::{ArrowArray, ArrowSchema};
+
+use crate::arrow_bridge::export_dataframe_to_arrow;
+use crate::batch_provider::GoBatchProvider;
+use crate::dynamic_group;
+use crate::error::BridgeError;
+use crate::executor;
+use crate::handles;
+use crate::incremental::IncrementalAgg;
+use crate::proto;
+use crate::schema::to_polars_schema;
+
+const DEFAULT_BATCH_ROWS: u32 = 1024;
+const DEFAULT_READ_TIMEOUT_MS: u32 = 30_000;
+
+/// Go 侧的结果回调（推送 sink）：每个微批的结果通过 Arrow C Data Interface 交给 Go，
+/// Go 按 move 语义导入（接管所有权）；未被导入的数组在回调返回后由 bridge 释放。返回非 0 表示出错并停止
+pub type ResultCallback = extern "C" fn(
+    ctx: *mut c_void,
+    batch_index: u64,
+    schema: *mut ArrowSchema,
+    array: *mut ArrowArray,
+) -> c_int;
+
+/// 微批流：从数据源逐批拉取数据，每批执行一次 Plan，结果推送给 Go
+///
+/// 设置 event_time_column 时按水位线丢弃迟到的行：水位线只会前进，
+/// 由之前批次中的最大事件时间减去 allowed_lateness 得到（事件时间为 null 的行保留）。
+/// incremental 模式下根节点必须是 GroupBy，状态跨批次累积。
+/// 根节点为 GroupByDynamic 时按事件时间开窗：窗口在水位线越过其终点后才推送（每个窗口只推送一次），
+/// 未结束窗口的行跨批次保留，数据源结束时推送剩余的窗口。
+pub struct MicroBatchStream {
+    plan: proto::Plan,
+    options: proto::StreamOptions,
+    source: BatchSource,
+    sink: ResultSink,
+    incremental: Option<IncrementalAgg>,
+    windows: Option<EventWindows>,
+    stats: proto::StreamStats,
+}
+
+// 事件时间窗口的跨批次状态
+struct EventWindows {
+    node: proto::GroupByDynamic,
+    period: Option<i64>,  // 窗口长度（event_time_column 的物理值单位，见到第一批时换算）
+    pending: Option<DataFrame>,  // 尚未结束的窗口可能用到的行
+    emitted_until: Option<i64>,  // 已推送窗口的最大起点
+}
+
+enum BatchSource {
+    Provider {
+        provider: Arc<GoBatchProvider>,
+        batch_index: u64,
+    },
+    Ndjson {
+        reader: BufReader<TcpStream>,
+        schema: SchemaRef,
+        batch_rows: u32,
+        partial: Vec<u8>,  // 读取超时时尚未读完的一行，下次继续
+    },
+}
+
+struct ResultSink {
+    callback: ResultCallback,
+    ctx: *mut c_void,
+}
+
+// ctx 是 Go 侧的不透明句柄，不会在 Rust 侧解引用；回调自身负责线程安全
+unsafe impl Send for ResultSink {}
+unsafe impl Sync for ResultSink {}
+
+impl MicroBatchStream {
+    pub fn new(
+        plan: proto::Plan,
+        options: proto::StreamOptions,
+        callback: ResultCallback,
+        ctx: *mut c_void,
+    ) -> Result<Self, BridgeError> {
+        let source = BatchSource::open(&options)?;
+        let incremental = if options.incremental {
+            Some(IncrementalAgg::new(plan.clone())?)
+        } else {
+            None
+        };
+        let windows = EventWindows::new(&plan, &options)?;
+
+        Ok(MicroBatchStream {
+            plan,
+            options,
+            source,
+            sink: ResultSink { callback, ctx },
+            incremental,
+            windows,
+            stats: proto::StreamStats::default(),
+        })
+    }
+
+    /// 处理至多 max_batches 个批次（0 表示直到数据源结束），返回累计统计
+    pub fn run(&mut self, max_batches: u64) -> Result<proto::StreamStats, BridgeError> {
+        let mut processed = 0;
+        while !self.stats.finished && (max_batches == 0 || processed < max_batches) {
+            let Some(batch) = self.source.next()? else {
+                self.stats.finished = true;
+                if let Some(result) = self.windows.as_mut().map(|w| w.flush(&self.plan)).transpose()? {
+                    self.emit(result)?;
+                }
+                break;
+            };
+            self.process(batch)?;
+            processed += 1;
+        }
+        Ok(self.stats)
+    }
+
+    fn process(&mut self, batch: DataFrame) -> Result<(), BridgeError> {
+        self.stats.rows_in += batch.height() as u64;
+        let batch = self.apply_watermark(batch)?;
+
+        let result = if let Some(agg) = self.incremental.as_mut() {
+            agg.update(&batch)?;
+            agg.finalize()?
+        } else if let Some(windows) = self.windows.as_mut() {
+            windows.update(&self.plan, batch, self.stats.watermark)?
+        } else {
+            executor::execute_plan_unrecorded(
+                &self.plan,
+                Some(&batch),
+                &proto::ExecutionHints::default(),
+            )?
+        };
+        self.emit(result)
+    }
+
+    fn emit(&mut self, result: DataFrame) -> Result<(), BridgeError> {
+        self.stats.rows_out += result.height() as u64;
+        self.sink.push(self.stats.batches, &result)?;
+        self.stats.batches += 1;
+        Ok(())
+    }
+
+    // 先按当前水位线丢弃迟到的行，再用本批的事件时间推进水位线
+    fn apply_watermark(&mut self, batch: DataFrame) -> Result<DataFrame, BridgeError> {
+        let name = self.options.event_time_column.as_str();
+        if name.is_empty() {
+            return Ok(batch);
+        }
+
+        let times = event_times(&batch, name)?;
+        let batch = match self.stats.watermark {
+            Some(watermark) => {
+                let keep = &times.gt_eq(watermark) | &times.is_null();
+                let kept = batch.filter(&keep).map_err(|e| {
+                    BridgeError::Execution("Failed to drop late rows".into()).with_source(e)
+                })?;
+                self.stats.late_rows += (batch.height() - kept.height()) as u64;
+                kept
+            }
+            None => batch,
+        };
+
+        if let Some(max) = times.max() {
+            let candidate = max.saturating_sub(self.options.allowed_lateness);
+            self.stats.watermark = Some(self.stats.watermark.map_or(candidate, |w| w.max(candidate)));
+        }
+        Ok(batch)
+    }
+}
+
+impl EventWindows {
+    fn new(plan: &proto::Plan, options: &proto::StreamOptions) -> Result<Option<Self>, BridgeError> {
+        let Some(proto::node::Kind::GroupByDynamic(node)) = executor::plan_root(plan)?.kind.as_ref() else {
+            return Ok(None);
+        };
+        if options.event_time_column != node.index_column {
+            return Err(BridgeError::PlanSemantic(format!(
+                "Streaming GroupByDynamic requires event_time_column to be its index_column '{}'",
+                node.index_column
+            )));
+        }
+        dynamic_group::options(node)?;
+
+        Ok(Some(EventWindows {
+            node: node.as_ref().clone(),
+            period: None,
+            pending: None,
+            emitted_until: None,
+        }))
+    }
+
+    // 合并保留的行与本批，推送水位线已越过其终点的窗口，并只保留仍未结束的窗口可能用到的行
+    fn update(
+        &mut self,
+        plan: &proto::Plan,
+        batch: DataFrame,
+        watermark: Option<i64>,
+    ) -> Result<DataFrame, BridgeError> {
+        let name = self.node.index_column.clone();
+        let period = match self.period {
+            Some(period) => period,
+            None => {
+                let dtype = batch.column(&name).map_err(|e| invalid_event_time(&name, e))?.dtype();
+                *self.period.insert(dynamic_group::physical_period(&self.node, dtype)?)
+            }
+        };
+
+        let rows = match self.pending.take() {
+            Some(mut pending) => {
+                pending.vstack_mut(&batch).map_err(|e| {
+                    BridgeError::Execution("Failed to buffer stream window rows".into()).with_source(e)
+                })?;
+                pending
+            }
+            None => batch,
+        };
+        let rows = rows.sort([name.as_str()], SortMultipleOptions::default()).map_err(|e| {
+            BridgeError::Execution("Failed to sort stream window rows".into()).with_source(e)
+        })?;
+
+        // 窗口 [start, start + period] 的终点早于水位线后，不会再有属于它的行
+        let result = self.closed_windows(plan, &rows, |start| {
+            watermark.is_some_and(|w| start.saturating_add(period) < w)
+        })?;
+
+        self.pending = Some(match watermark {
+            Some(w) => {
+                let times = event_times(&rows, &name)?;
+                rows.filter(&times.gt_eq(w.saturating_sub(period))).map_err(|e| {
+                    BridgeError::Execution("Failed to drop closed window rows".into()).with_source(e)
+                })?
+            }
+            None => rows,
+        });
+        Ok(result)
+    }
+
+    // 数据源结束：推送剩余的全部窗口
+    fn flush(&mut self, plan: &proto::Plan) -> Result<DataFrame, BridgeError> {
+        match self.pending.take() {
+            Some(rows) => self.closed_windows(plan, &rows, |_| true),
+            None => Ok(DataFrame::empty()),
+        }
+    }
+
+    // 执行 Plan，返回满足 closed 且尚未推送过的窗口（窗口按起点标识）
+    fn closed_windows(
+        &mut self,
+        plan: &proto::Plan,
+        rows: &DataFrame,
+        closed: impl Fn(i64) -> bool,
+    ) -> Result<DataFrame, BridgeError> {
+        let windows =
+            executor::execute_plan_unrecorded(plan, Some(rows), &proto::ExecutionHints::default())?;
+        let starts = event_times(&windows, &self.node.index_column)?;
+        let emitted_until = self.emitted_until;
+        let mask: BooleanChunked = starts
+            .iter()
+            .map(|start| {
+                start.is_some_and(|start| closed(start) && emitted_until.is_none_or(|e| start > e))
+            })
+            .collect();
+
+        let result = windows.filter(&mask).map_err(|e| {
+            BridgeError::Execution("Failed to select closed windows".into()).with_source(e)
+        })?;
+        if let Some(max) = starts.filter(&mask).ok().and_then(|emitted| emitted.max()) {
+            self.emitted_until = Some(max);
+        }
+        Ok(result)
+    }
+}
+
+// 事件时间列的物理值（Date 为天数，Datetime 为所在时间单位）
+fn event_times(df: &DataFrame, name: &str) -> Result<Int64Chunked, BridgeError> {
+    let times = df
+        .column(name)
+        .map_err(|e| invalid_event_time(name, e))?
+        .to_physical_repr()
+        .cast(&DataType::Int64)
+        .map_err(|e| invalid_event_time(name, e))?;
+    Ok(times.i64().map_err(|e| invalid_event_time(name, e))?.clone())
+}
+
+fn invalid_event_time(name: &str, e: PolarsError) -> BridgeError {
+    BridgeError::PlanSemantic(format!("Invalid stream event_time_column '{}': {}", name, e))
+}
+
+impl BatchSource {
+    fn open(options: &proto::StreamOptions) -> Result<Self, BridgeError> {
+        use proto::stream_options::Source;
+
+        match options.source.as_ref() {
+            Some(Source::ProviderId(id)) => Ok(BatchSource::Provider {
+                provider: handles::batch_providers().get(*id)?,
+                batch_index: 0,
+            }),
+            Some(Source::NdjsonAddr(addr)) => {
+                let fields = options
+                    .schema
+                    .as_ref()
+                    .map(|s| s.fields.as_slice())
+                    .filter(|fields| !fields.is_empty())
+                    .ok_or_else(|| {
+                        BridgeError::PlanSemantic("NDJSON stream source requires a schema".into())
+                    })?;
+                let schema: SchemaRef = Arc::new(to_polars_schema(fields)?);
+                let socket = TcpStream::connect(addr.as_str()).map_err(|e| {
+                    BridgeError::Execution(format!("Failed to connect to NDJSON source '{}': {}", addr, e))
+                })?;
+                let batch_rows = options.batch_rows.unwrap_or(DEFAULT_BATCH_ROWS);
+                if batch_rows == 0 {
+                    return Err(BridgeError::InvalidArgument(
+                        "Stream batch_rows must be greater than 0".into(),
+                    ));
+                }
+                // 对端既不发送数据也不关闭连接时，读取不能无限阻塞（bridge_stream_run 持有流的锁）
+                let timeout = match options.read_timeout_ms.unwrap_or(DEFAULT_READ_TIMEOUT_MS) {
+                    0 => None,
+                    ms => Some(Duration::from_millis(ms.into())),
+                };
+                socket.set_read_timeout(timeout).map_err(|e| {
+                    BridgeError::Execution(format!("Failed to set NDJSON source read timeout: {}", e))
+                })?;
+                Ok(BatchSource::Ndjson {
+                    reader: BufReader::new(socket),
+                    schema,
+                    batch_rows,
+                    partial: Vec::new(),
+                })
+            }
+            None => Err(BridgeError::InvalidArgument("StreamOptions has no source".into())),
+        }
+    }
+
+    // 拉取下一批；返回 None 表示数据源结束
+    fn next(&mut self) -> Result<Option<DataFrame>, BridgeError> {
+        match self {
+            BatchSource::Provider { provider, batch_index } => {
+                let request = proto::BatchRequest {
+                    batch_index: *batch_index,
+                    ..Default::default()
+                };
+                let batch = provider.next(&request)?;
+                *batch_index += 1;
+                Ok(batch)
+            }
+            BatchSource::Ndjson { reader, schema, batch_rows, partial } => {
+                // 上次超时时读到一半的行在 buf 开头，从它继续读
+                let mut buf = std::mem::take(partial);
+                let mut start = 0;
+                let mut rows = 0;
+                while rows < *batch_rows {
+                    match reader.read_until(b'\n', &mut buf) {
+                        Ok(0) if start == buf.len() => break,
+                        Ok(_) => {}
+                        Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                        // 超时：先处理已读到的完整行；一行都没有时报错，保留的半行留到下次
+                        Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                            *partial = buf.split_off(start);
+                            if rows > 0 {
+                                break;
+                            }
+                            return Err(BridgeError::Execution(
+                                "Timed out waiting for NDJSON source records".into(),
+                            ));
+                        }
+                        Err(e) => {
+                            return Err(BridgeError::Execution(format!(
+                                "Failed to read NDJSON source: {}",
+                                e
+                            )))
+                        }
+                    }
+                    if buf[start..].iter().all(u8::is_ascii_whitespace) {
+                        buf.truncate(start);
+                    } else {
+                        rows += 1;
+                        start = buf.len();
+                    }
+                }
+                if rows == 0 {
+                    return Ok(None);
+                }
+
+                JsonReader::new(Cursor::new(buf))
+                    .with_json_format(JsonFormat::JsonLines)
+                    .with_schema(schema.clone())
+                    .finish()
+                    .map(Some)
+                    .map_err(|e| BridgeError::Execution(format!("Invalid NDJSON batch: {}", e)))
+            }
+        }
+    }
+}
+
+impl ResultSink {
+    fn push(&self, batch_index: u64, df: &DataFrame) -> Result<(), BridgeError> {
+        let mut schema = ArrowSchema::empty();
+        let mut array = ArrowArray::empty();
+        export_dataframe_to_arrow(df, &mut schema, &mut array)?;
+
+        let status = (self.callback)(self.ctx, batch_index, &mut schema, &mut array);
+        if status != 0 {
+            return Err(BridgeError::Execution(format!(
+                "Stream result callback failed with status {} at batch {}",
+                status, batch_index
+            )));
+        }
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+    use std::net::TcpListener;
+    use std::sync::{mpsc, Mutex};
+    use std::thread;
+
+    use prost::Message;
+
+    use super::*;
+    use crate::arrow_bridge::{import_dataframe_from_arrow, ImportOptions};
+    use crate::test_plans::{self as tp, agg, alias, expr, input, memory_scan, node};
+    use proto::expr::Kind as E;
+    use proto::node::Kind as N;
+
+    // 批次提供者：ctx 指向 Vec<DataFrame>，按 batch_index 依次导出
+    extern "C" fn provide(
+        ctx: *mut c_void,
+        request_ptr: *const u8,
+        request_len: usize,
+        out_schema: *mut ArrowSchema,
+        out_array: *mut ArrowArray,
+        out_done: *mut c_int,
+    ) -> c_int {
+        let batches = unsafe { &*(ctx as *const Vec<DataFrame>) };
+        let bytes = unsafe { std::slice::from_raw_parts(request_ptr, request_len) };
+        let request = proto::BatchRequest::decode(bytes).unwrap();
+        match batches.get(request.batch_index as usize) {
+            Some(batch) => export_dataframe_to_arrow(batch, out_schema, out_array).map_or(1, |_| 0),
+            None => {
+                unsafe { *out_done = 1 };
+                0
+            }
+        }
+    }
+
+    // 结果回调：ctx 指向 Mutex<Vec<DataFrame>>，按 move 语义导入后把原结构置空
+    extern "C" fn collect(
+        ctx: *mut c_void,
+        _batch_index: u64,
+        schema: *mut ArrowSchema,
+        array: *mut ArrowArray,
+    ) -> c_int {
+        let results = unsafe { &*(ctx as *const Mutex<Vec<DataFrame>>) };
+        let df = import_dataframe_from_arrow(schema, array, &ImportOptions::from_options(None));
+        unsafe {
+            std::ptr::write(schema, ArrowSchema::empty());
+            std::ptr::write(array, ArrowArray::empty());
+        }
+        match df {
+            Ok(df) => {
+                results.lock().unwrap().push(df);
+                0
+            }
+            Err(_) => 1,
+        }
+    }
+
+    struct Harness {
+        stream: MicroBatchStream,
+        results: Box<Mutex<Vec<DataFrame>>>,
+        provider: Option<(u64, Arc<Vec<DataFrame>>)>,
+    }
+
+    // 注册的提供者引用着批次，随 Harness 一起释放
+    impl Drop for Harness {
+        fn drop(&mut self) {
+            if let Some((id, _)) = self.provider.take() {
+                handles::batch_providers().release(id);
+            }
+        }
+    }
+
+    impl Harness {
+        fn provider(plan: proto::Plan, mut options: proto::StreamOptions, batches: Vec<DataFrame>) -> Self {
+            let batches = Arc::new(batches);
+            let ctx = Arc::as_ptr(&batches) as *mut c_void;
+            let id = handles::batch_providers().insert(GoBatchProvider::new(provide, ctx));
+            options.source = Some(proto::stream_options::Source::ProviderId(id));
+            let mut harness = Self::open(plan, options);
+            harness.provider = Some((id, batches));
+            harness
+        }
+
+        fn open(plan: proto::Plan, options: proto::StreamOptions) -> Self {
+            let results = Box::new(Mutex::new(Vec::new()));
+            let ctx = &*results as *const Mutex<Vec<DataFrame>> as *mut c_void;
+            let stream = MicroBatchStream::new(plan, options, collect, ctx).unwrap();
+            Harness { stream, results, provider: None }
+        }
+
+        fn results(&self) -> Vec<DataFrame> {
+            self.results.lock().unwrap().clone()
+        }
+    }
+
+    fn batch(t: &[Option<i64>]) -> DataFrame {
+        let v: Vec<Option<i64>> = t.iter().map(|t| t.map(|t| t * 10)).collect();
+        df!("t" => t, "v" => v).unwrap()
+    }
+
+    fn column(df: &DataFrame, name: &str) -> Vec<Option<i64>> {
+        df.column(name).unwrap().i64().unwrap().into_iter().collect()
+    }
+
+    fn event_time_options(allowed_lateness: i64) -> proto::StreamOptions {
+        proto::StreamOptions {
+            event_time_column: "t".into(),
+            allowed_lateness,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn drops_rows_behind_the_watermark() {
+        let plan = tp::select(memory_scan(), vec![tp::col("t"), tp::col("v")]);
+        let batches = vec![
+            batch(&[Some(1), Some(5)]),
+            // 水位线 5 - 2 = 3：2 迟到
+            batch(&[Some(2), Some(4), Some(6)]),
+            // 水位线 6 - 2 = 4：3 迟到，null 保留
+            batch(&[Some(3), None, Some(7)]),
+        ];
+        let mut harness = Harness::provider(plan, event_time_options(2), batches);
+
+        let stats = harness.stream.run(0).unwrap();
+        assert!(stats.finished);
+        assert_eq!(stats.batches, 3);
+        assert_eq!(stats.rows_in, 8);
+        assert_eq!(stats.late_rows, 2);
+        assert_eq!(stats.rows_out, 6);
+        assert_eq!(stats.watermark, Some(5));
+
+        let results = harness.results();
+        assert_eq!(column(&results[1], "t"), [Some(4), Some(6)]);
+        assert_eq!(column(&results[2], "t"), [None, Some(7)]);
+    }
+
+    fn window_plan() -> proto::Plan {
+        tp::plan(node(2, N::GroupByDynamic(Box::new(proto::GroupByDynamic {
+            input: input(memory_scan()),
+            index_column: "t".into(),
+            every: "10i".into(),
+            aggs: vec![alias(expr(E::Sum(agg(tp::col("v")))), "total")],
+            ..Default::default()
+        }))))
+    }
+
+    #[test]
+    fn emits_each_event_time_window_once() {
+        let batches = vec![
+            // 水位线 12 - 5 = 7：窗口 [0, 10) 尚未结束
+            batch(&[Some(1), Some(2), Some(12)]),
+            // 8、9 乱序到达但未迟到，3 迟到；水位线 19：推送 [0, 10)，9 仍保留在缓冲中
+            batch(&[Some(8), Some(3), Some(24), Some(9)]),
+            // 17 迟到；水位线 26：推送 [10, 20)，[0, 10) 不会重复推送
+            batch(&[Some(17), Some(31)]),
+        ];
+        let mut harness = Harness::provider(window_plan(), event_time_options(5), batches);
+
+        let stats = harness.stream.run(2).unwrap();
+        assert!(!stats.finished);
+        assert_eq!(stats.late_rows, 1);
+        assert_eq!(stats.watermark, Some(19));
+
+        // 数据源结束时推送剩余的 [20, 30) 与 [30, 40)
+        let stats = harness.stream.run(0).unwrap();
+        assert!(stats.finished);
+        assert_eq!(stats.batches, 4);
+        assert_eq!(stats.late_rows, 2);
+        assert_eq!(stats.rows_out, 4);
+
+        let results = harness.results();
+        let emitted: Vec<_> = results
+            .iter()
+            .map(|df| (column(df, "t"), column(df, "total")))
+            .collect();
+        assert_eq!(
+            emitted,
+            [
+                (vec![], vec![]),
+                (vec![Some(0)], vec![Some(200)]),
+                (vec![Some(10)], vec![Some(120)]),
+                (vec![Some(20), Some(30)], vec![Some(240), Some(310)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn window_stream_requires_index_as_event_time() {
+        let batches: Vec<DataFrame> = Vec::new();
+        let provider_ctx = &batches as *const Vec<DataFrame> as *mut c_void;
+        let id = handles::batch_providers().insert(GoBatchProvider::new(provide, provider_ctx));
+        let options = proto::StreamOptions {
+            source: Some(proto::stream_options::Source::ProviderId(id)),
+            ..Default::default()
+        };
+        let results = Mutex::new(Vec::<DataFrame>::new());
+        let ctx = &results as *const _ as *mut c_void;
+        let result = MicroBatchStream::new(window_plan(), options, collect, ctx);
+        handles::batch_providers().release(id);
+        assert!(matches!(result, Err(BridgeError::PlanSemantic(_))));
+    }
+
+    #[test]
+    fn ndjson_line_split_across_read_timeout() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let (written, wait_written) = mpsc::channel();
+        let (resume, wait_resume) = mpsc::channel::<()>();
+        let writer = thread::spawn(move || {
+            let (mut socket, _) = listener.accept().unwrap();
+            socket.write_all(b"{\"t\": 1, \"v\": 10}\n{\"t\": 2, ").unwrap();
+            written.send(()).unwrap();
+            wait_resume.recv().unwrap();
+            socket.write_all(b"\"v\": 20}\n\n{\"t\": 3, \"v\": 30}\n").unwrap();
+        });
+
+        let field = |name: &str| proto::Field {
+            name: name.into(),
+            data_type: proto::DataType::Int64 as i32,
+        };
+        let options = proto::StreamOptions {
+            source: Some(proto::stream_options::Source::NdjsonAddr(addr)),
+            schema: Some(proto::Schema { fields: vec![field("t"), field("v")] }),
+            read_timeout_ms: Some(100),
+            ..Default::default()
+        };
+        let plan = tp::select(memory_scan(), vec![tp::col("t"), tp::col("v")]);
+        let mut harness = Harness::open(plan, options);
+        wait_written.recv().unwrap();
+
+        // 第一行完整，第二行读到一半时超时：先推送已读到的一行
+        let stats = harness.stream.run(1).unwrap();
+        assert_eq!((stats.batches, stats.rows_in), (1, 1));
+
+        // 没有新数据：超时报错，读到一半的行保留
+        let err = harness.stream.run(1).unwrap_err();
+        assert!(err.to_string().contains("Timed out"), "{err}");
+
+        // 对端补全该行后关闭连接：空行被跳过，两行作为一批，随后数据源结束
+        resume.send(()).unwrap();
+        writer.join().unwrap();
+        let stats = harness.stream.run(0).unwrap();
+        assert!(stats.finished);
+        assert_eq!((stats.batches, stats.rows_in), (2, 3));
+
+        let results = harness.results();
+        assert_eq!(column(&results[0], "t"), [Some(1)]);
+        assert_eq!(column(&results[1], "t"), [Some(2), Some(3)]);
+        assert_eq!(column(&results[1], "v"), [Some(20), Some(30)]);
+    }
+}
@@ -0,0 +1,57 @@
+//! 单元测试用的 Plan 构造函数
+
+use crate::proto;
+use proto::expr::Kind as E;
+use proto::node::Kind as N;
+
+pub fn expr(kind: E) -> proto::Expr {
+    proto::Expr { kind: Some(kind) }
+}
+
+pub fn boxed(e: proto::Expr) -> Option<Box<proto::Expr>> {
+    Some(Box::new(e))
+}
+
+pub fn col(name: &str) -> proto::Expr {
+    expr(E::Col(proto::Column { name: name.into() }))
+}
+
+pub fn alias(e: proto::Expr, name: &str) -> proto::Expr {
+    expr(E::Alias(Box::new(proto::Alias { expr: boxed(e), name: name.into() })))
+}
+
+pub fn agg(e: proto::Expr) -> Box<proto::AggFunction> {
+    Box::new(proto::AggFunction { expr: boxed(e) })
+}
+
+pub fn node(id: u32, kind: N) -> proto::Node {
+    proto::Node { id, kind: Some(kind) }
+}
+
+pub fn input(n: proto::Node) -> Option<Box<proto::Node>> {
+    Some(Box::new(n))
+}
+
+pub fn memory_scan() -> proto::Node {
+    node(1, N::MemoryScan(proto::MemoryScan::default()))
+}
+
+pub fn plan(root: proto::Node) -> proto::Plan {
+    proto::Plan { plan_version: 1, root: Some(root), ..Default::default() }
+}
+
+pub fn select(source: proto::Node, expressions: Vec<proto::Expr>) -> proto::Plan {
+    plan(node(2, N::Project(Box::new(proto::Project { input: input(source), expressions }))))
+}
+
+pub fn lit(value: proto::literal::Value) -> proto::Expr {
+    expr(E::Lit(proto::Literal { value: Some(value) }))
+}
+
+pub fn binary(left: proto::Expr, op: proto::BinaryOperator, right: proto::Expr) -> proto::Expr {
+    expr(E::Binary(Box::new(proto::BinaryExpr {
+        left: boxed(left),
+        op: op as i32,
+        right: boxed(right),
+    })))
+}
@@ -0,0 +1,17 @@
+use std::cell::RefCell;
+
+// 当前 FFI 调用关联的查询 ID（由 ffi_guard 在调用结束时取出并清空）
+thread_local! {
+    static QUERY_ID: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// 为当前调用设置查询 ID（空字符串表示不设置）
+pub fn set_query_id(query_id: &str) {
+    QUERY_ID.with(|id| {
+        *id.borrow_mut() = (!query_id.is_empty()).then(|| query_id.to_string());
+    });
+}
+
+pub fn take_query_id() -> Option<String> {
+    QUERY_ID.with(|id| id.borrow_mut().take())
+}
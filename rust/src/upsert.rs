@@ -0,0 +1,195 @@
+use polars::prelude::*;
+
+use crate::error::BridgeError;
+use crate::proto;
+
+// 内部辅助列（不会出现在结果中）
+const MATCHED: &str = "__upsert_matched";
+const SUFFIX: &str = "__upsert";
+
+/// Upsert：left join 更新匹配行，anti join 找出新行，再按 target 的列顺序拼接
+pub fn upsert(
+    mut target: LazyFrame,
+    mut source: LazyFrame,
+    node: &proto::Upsert,
+) -> Result<LazyFrame, BridgeError> {
+    if node.keys.is_empty() {
+        return Err(BridgeError::PlanSemantic("Upsert has no keys".into()));
+    }
+
+    let target_schema = target.collect_schema()
+        .map_err(|e| BridgeError::Execution(format!("Failed to resolve upsert target schema: {}", e)))?;
+    let source_schema = source.collect_schema()
+        .map_err(|e| BridgeError::Execution(format!("Failed to resolve upsert source schema: {}", e)))?;
+
+    for key in &node.keys {
+        if !target_schema.contains(key) || !source_schema.contains(key) {
+            return Err(BridgeError::PlanSemantic(format!(
+                "Upsert key '{}' must exist in both target and source",
+                key
+            )));
+        }
+    }
+
+    // 默认更新两侧都有的非键列：只在 source 中出现的列不会写入 target
+    let update_columns: Vec<PlSmallStr> = if node.update_columns.is_empty() {
+        source_schema
+            .iter_names()
+            .filter(|name| !node.keys.iter().any(|k| k == name.as_str()))
+            .filter(|name| target_schema.contains(name))
+            .cloned()
+            .collect()
+    } else {
+        node.update_columns.iter().map(|c| c.as_str().into()).collect()
+    };
+    for name in &update_columns {
+        if !target_schema.contains(name) || !source_schema.contains(name) {
+            return Err(BridgeError::PlanSemantic(format!(
+                "Upsert column '{}' must exist in both target and source",
+                name
+            )));
+        }
+    }
+
+    let keys: Vec<Expr> = node.keys.iter().map(|k| col(k.as_str())).collect();
+    let target_dtype = |name: &str| target_schema.get(name).cloned().unwrap_or(DataType::Null);
+
+    // 1. 更新匹配行：source 的每个键最多一行，避免 target 行被放大
+    let updates = source.clone().select(
+        keys.iter()
+            .cloned()
+            .chain(update_columns.iter().map(|name| {
+                col(name.clone())
+                    .cast(target_dtype(name))
+                    .alias(format!("{}{}", name, SUFFIX))
+            }))
+            .chain(std::iter::once(lit(true).alias(MATCHED)))
+            .collect::<Vec<_>>(),
+    );
+    let mut args = JoinArgs::new(JoinType::Left);
+    args.nulls_equal = node.nulls_equal;
+    args.validation = JoinValidation::ManyToOne;
+
+    let projection: Vec<Expr> = target_schema
+        .iter_names()
+        .map(|name| {
+            if update_columns.contains(name) {
+                when(col(MATCHED).is_not_null())
+                    .then(col(format!("{}{}", name, SUFFIX)))
+                    .otherwise(col(name.clone()))
+                    .alias(name.clone())
+            } else {
+                col(name.clone())
+            }
+        })
+        .collect();
+    let updated = target
+        .clone()
+        .join(updates, keys.clone(), keys.clone(), args)
+        .select(projection);
+
+    if !node.insert_unmatched.unwrap_or(true) {
+        return Ok(updated);
+    }
+
+    // 2. 追加未匹配的行：source 中没有的 target 列补 null
+    let mut anti = JoinArgs::new(JoinType::Anti);
+    anti.nulls_equal = node.nulls_equal;
+    let inserted_columns: Vec<Expr> = target_schema
+        .iter()
+        .filter(|(name, _)| source_schema.contains(name))
+        .map(|(name, dtype)| col(name.clone()).cast(dtype.clone()))
+        .collect();
+    let inserts = source
+        .join(target.select(keys.clone()), keys.clone(), keys, anti)
+        .select(inserted_columns);
+
+    concat_lf_diagonal([updated, inserts], UnionArgs::default())
+        .map_err(|e| BridgeError::Execution(format!("Upsert failed: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target() -> DataFrame {
+        df!(
+            "id" => [Some(1i64), Some(2), None],
+            "name" => ["a", "b", "n"],
+            "score" => [10i32, 20, 30],
+            "note" => ["x", "y", "z"],
+        )
+        .unwrap()
+    }
+
+    // score 为 Int64，合并时按 target 的 Int32 转换；extra 不在 target 中
+    fn source() -> DataFrame {
+        df!(
+            "id" => [Some(2i64), Some(4), None],
+            "score" => [21i64, 41, 31],
+            "extra" => [true, false, true],
+        )
+        .unwrap()
+    }
+
+    fn run(node: proto::Upsert, target: DataFrame, source: DataFrame) -> Result<DataFrame, BridgeError> {
+        upsert(target.lazy(), source.lazy(), &node)?
+            .collect()
+            .map_err(|e| BridgeError::Execution(e.to_string()))
+    }
+
+    fn by_id() -> proto::Upsert {
+        proto::Upsert { keys: vec!["id".into()], ..Default::default() }
+    }
+
+    #[test]
+    fn updates_matches_and_inserts_new_rows() {
+        let result = run(by_id(), target(), source()).unwrap();
+        let expected = df!(
+            "id" => [Some(1i64), Some(2), None, Some(4), None],
+            "name" => [Some("a"), Some("b"), Some("n"), None, None],
+            "score" => [10i32, 21, 30, 41, 31],
+            "note" => [Some("x"), Some("y"), Some("z"), None, None],
+        )
+        .unwrap();
+        assert!(result.equals_missing(&expected), "{result}");
+    }
+
+    #[test]
+    fn skips_inserts_when_disabled() {
+        let node = proto::Upsert { insert_unmatched: Some(false), ..by_id() };
+        let result = run(node, target(), source()).unwrap();
+        let scores: Vec<_> = result.column("score").unwrap().i32().unwrap().into_no_null_iter().collect();
+        assert_eq!(scores, [10, 21, 30]);
+    }
+
+    #[test]
+    fn null_keys_match_when_nulls_equal() {
+        let node = proto::Upsert { nulls_equal: true, ..by_id() };
+        let result = run(node, target(), source()).unwrap();
+        let expected = df!(
+            "id" => [Some(1i64), Some(2), None, Some(4)],
+            "name" => [Some("a"), Some("b"), Some("n"), None],
+            "score" => [10i32, 21, 31, 41],
+            "note" => [Some("x"), Some("y"), Some("z"), None],
+        )
+        .unwrap();
+        assert!(result.equals_missing(&expected), "{result}");
+    }
+
+    #[test]
+    fn rejects_duplicate_source_keys() {
+        let source = df!("id" => [2i64, 2], "score" => [1i64, 2]).unwrap();
+        let err = run(by_id(), target(), source).unwrap_err();
+        assert!(err.to_string().contains("m:1 validation"), "{err}");
+    }
+
+    #[test]
+    fn explicit_update_columns_must_exist_on_both_sides() {
+        let node = proto::Upsert { update_columns: vec!["extra".into()], ..by_id() };
+        assert!(matches!(run(node, target(), source()), Err(BridgeError::PlanSemantic(_))));
+
+        let node = proto::Upsert { keys: vec!["name".into()], ..Default::default() };
+        assert!(matches!(run(node, target(), source()), Err(BridgeError::PlanSemantic(_))));
+    }
+}
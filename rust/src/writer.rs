@@ -0,0 +1,95 @@
+use std::fs::File;
+
+#[cfg(feature = "avro")]
+use polars::io::avro::{AvroCompression, AvroWriter};
+use polars::prelude::*;
+
+use crate::error::BridgeError;
+use crate::proto;
+use crate::sink::ipc_compression;
+
+/// 将 DataFrame 写入 Arrow IPC/Feather 文件
+pub fn write_ipc(
+    df: &DataFrame,
+    path: &str,
+    options: &proto::IpcWriteOptions,
+) -> Result<(), BridgeError> {
+    let file = create_file(path)?;
+    let mut df = df.clone();
+    IpcWriter::new(file)
+        .with_compression(ipc_compression(options.compression)?)
+        .finish(&mut df)
+        .map_err(|e| BridgeError::Execution(format!("Failed to write IPC to '{}': {}", path, e)))
+}
+
+/// 将 DataFrame 写入 NDJSON（或 JSON 数组）文件
+pub fn write_ndjson(
+    df: &DataFrame,
+    path: &str,
+    options: &proto::NdjsonWriteOptions,
+) -> Result<(), BridgeError> {
+    let format = match proto::JsonFormat::try_from(options.format) {
+        Ok(proto::JsonFormat::JsonLines) => JsonFormat::JsonLines,
+        Ok(proto::JsonFormat::Json) => JsonFormat::Json,
+        Err(_) => {
+            return Err(BridgeError::Unsupported(format!(
+                "Unknown JSON format: {}",
+                options.format
+            )))
+        }
+    };
+
+    let file = create_file(path)?;
+    let mut df = df.clone();
+    JsonWriter::new(file)
+        .with_json_format(format)
+        .finish(&mut df)
+        .map_err(|e| BridgeError::Execution(format!("Failed to write NDJSON to '{}': {}", path, e)))
+}
+
+/// 将 DataFrame 写入 Avro 文件
+#[cfg(feature = "avro")]
+pub fn write_avro(
+    df: &DataFrame,
+    path: &str,
+    options: &proto::AvroWriteOptions,
+) -> Result<(), BridgeError> {
+    let compression = match proto::AvroCompression::try_from(options.compression) {
+        Ok(proto::AvroCompression::Uncompressed) => None,
+        Ok(proto::AvroCompression::Deflate) => Some(AvroCompression::Deflate),
+        Ok(proto::AvroCompression::Snappy) => Some(AvroCompression::Snappy),
+        Err(_) => {
+            return Err(BridgeError::Unsupported(format!(
+                "Unknown Avro compression: {}",
+                options.compression
+            )))
+        }
+    };
+
+    let file = create_file(path)?;
+    let mut df = df.clone();
+    let mut writer = AvroWriter::new(file).with_compression(compression);
+    if !options.record_name.is_empty() {
+        writer = writer.with_name(options.record_name.clone());
+    }
+    writer
+        .finish(&mut df)
+        .map_err(|e| BridgeError::Execution(format!("Failed to write Avro to '{}': {}", path, e)))
+}
+
+#[cfg(not(feature = "avro"))]
+pub fn write_avro(
+    _df: &DataFrame,
+    _path: &str,
+    _options: &proto::AvroWriteOptions,
+) -> Result<(), BridgeError> {
+    Err(crate::features::unavailable("Avro output", "avro"))
+}
+
+fn create_file(path: &str) -> Result<File, BridgeError> {
+    if path.is_empty() {
+        return Err(BridgeError::InvalidArgument("Output path cannot be empty".into()));
+    }
+    File::create(path)
+        .map_err(|e| BridgeError::Execution(format!("Failed to create '{}': {}", path, e)))
+}
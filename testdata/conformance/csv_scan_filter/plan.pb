@@ -0,0 +1,6 @@
+9r5
+Z
+../testdata/small.csv
+	
+
+value
\ No newline at end of file